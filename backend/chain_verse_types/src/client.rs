@@ -0,0 +1,143 @@
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::keyword::LinkedKeyword;
+use crate::poem::{EngagedPoem, TodayStatus};
+use crate::provenance::AnnotatedProvenance;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The server answered with an error status and its `error` message
+    #[error("{status}: {message}")]
+    Api { status: StatusCode, message: String },
+}
+
+/// Body of the API's error responses
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Typed client for a Chain Verse instance's read API
+///
+/// ```no_run
+/// # async fn demo() -> chain_verse_types::client::Result<()> {
+/// let client = chain_verse_types::Client::new("http://localhost:3000");
+/// let today = client.today().await?;
+/// println!("{} of {} keywords", today.keywords_collected, today.keywords_needed);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl Client {
+    /// Client for the instance at `base_url`, e.g. `https://chainverse.example`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+        }
+    }
+
+    /// Send `key` as a bearer token, for instances that give key holders a
+    /// higher rate limit
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Use a preconfigured reqwest client, e.g. one with a timeout or proxy
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// GET /api/poems/today - Progress toward today's poem
+    pub async fn today(&self) -> Result<TodayStatus> {
+        self.get("/api/poems/today").await
+    }
+
+    /// GET /api/poems - Every poem, latest first
+    pub async fn poems(&self) -> Result<Vec<EngagedPoem>> {
+        self.get("/api/poems").await
+    }
+
+    /// GET /api/poems/{date} - A day's poem; None if it hasn't been written
+    pub async fn poem(&self, date: &str) -> Result<Option<EngagedPoem>> {
+        self.get_optional(&format!("/api/poems/{}", date)).await
+    }
+
+    /// GET /api/keywords/{date} - The keywords collected on a day
+    pub async fn keywords(&self, date: &str) -> Result<Vec<LinkedKeyword>> {
+        self.get(&format!("/api/keywords/{}", date)).await
+    }
+
+    /// GET /api/poems/{date}/provenance - How a day's poem was made; None for
+    /// poems generated before manifests were recorded
+    pub async fn provenance(&self, date: &str) -> Result<Option<AnnotatedProvenance>> {
+        self.get_optional(&format!("/api/poems/{}/provenance", date))
+            .await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let mut request = self.http.get(self.url(path));
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = match serde_json::from_str::<ErrorResponse>(&body) {
+                Ok(error) => error.error,
+                Err(_) => body,
+            };
+            return Err(Error::Api { status, message });
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn get_optional<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path).await {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::Api {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let client = Client::new("http://localhost:3000/");
+        assert_eq!(client.url("/api/poems"), "http://localhost:3000/api/poems");
+        let client = Client::new("https://chainverse.example");
+        assert_eq!(
+            client.url("/api/poems/2026-01-05"),
+            "https://chainverse.example/api/poems/2026-01-05"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// A keyword derived from a block, as stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredKeyword {
+    pub id: i64,
+    pub word: String,
+    /// Negative for words a curator added by hand
+    pub slot: i64,
+    pub blockhash: String,
+    /// Parent blockhash; None for keywords stored before it was recorded
+    pub previous_blockhash: Option<String>,
+    pub block_time: Option<i64>,
+    pub word_index: i64,
+    /// Entropy the word was derived from, or `manual`
+    pub source: String,
+    /// Dictionary theme the word was drawn from; None for the base dictionary
+    pub theme: Option<String>,
+    /// Re-hashes it took to skip blocked or recently used words
+    pub rerolls: i64,
+    /// Why the keyword was collected off-schedule (`event`); None for scheduled collection
+    pub tag: Option<String>,
+    /// Solana epoch of the slot; None for keywords collected before it was recorded
+    pub epoch: Option<i64>,
+    /// The slot's block was abandoned by a reorg; left out of the day's poem
+    #[serde(default)]
+    pub orphaned: bool,
+    pub created_at: String,
+}
+
+/// A keyword as /api/keywords/{date} and /api/poems/today return it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkedKeyword {
+    #[serde(flatten)]
+    pub keyword: StoredKeyword,
+    /// None for manual keywords
+    pub explorer: Option<ExplorerLinks>,
+    /// What the word means; None until it has been looked up, or when the
+    /// dictionary has no entry for it
+    pub definition: Option<WordDefinition>,
+    /// How rare the word was when collected; None where it isn't looked up
+    pub rarity: Option<KeywordRarity>,
+}
+
+/// Block explorer pages for a keyword's slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorerLinks {
+    pub solscan: String,
+    pub solana_explorer: String,
+}
+
+/// A short explanation of a derived word
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordDefinition {
+    pub definition: String,
+    pub part_of_speech: Option<String>,
+    /// Etymology, when the dictionary has one
+    pub origin: Option<String>,
+    pub source: String,
+}
+
+/// How unusual a keyword's word is, judged by its earlier appearances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordRarity {
+    /// Keywords with the same word collected before this one
+    pub previous_occurrences: i64,
+    /// Day the word last appeared before this keyword; None on its first appearance
+    pub last_seen: Option<String>,
+    /// Days between `last_seen` and this keyword's day
+    pub days_since_last: Option<i64>,
+    /// 1.0 for a first appearance, otherwise rising from 0 with the gap since the last one
+    pub score: f64,
+    /// A first appearance or a return after a long gap
+    pub rare: bool,
+    /// The rarity in words, e.g. "First appearance in 212 days"
+    pub summary: String,
+}
@@ -0,0 +1,20 @@
+//! The JSON the Chain Verse API serves, as serde types, and a client for it.
+//!
+//! The server's own tests deserialize its responses into these types, so they
+//! stay in step with `/api/openapi.json`. Build without default features for
+//! the types alone.
+
+#[cfg(feature = "client")]
+pub mod client;
+pub mod keyword;
+pub mod poem;
+pub mod provenance;
+
+#[cfg(feature = "client")]
+pub use client::{Client, Error};
+pub use keyword::{ExplorerLinks, KeywordRarity, LinkedKeyword, StoredKeyword, WordDefinition};
+pub use poem::{EngagedPoem, PoemEngagement, StoredPoem, TodayStatus};
+pub use provenance::{
+    AnnotatedProvenance, DerivationProvenance, KeywordAnnotation, KeywordProvenance, LlmProvenance,
+    Mood, PoemLength, PoemProvenance, SamplingOptions,
+};
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::keyword::LinkedKeyword;
+
+/// A day's poem, as stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredPoem {
+    pub id: i64,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub title: Option<String>,
+    pub content: String,
+    pub keyword_ids: Vec<i64>,
+    /// SHA-256 of the poem and its keywords, when anchored on-chain
+    pub anchor_hash: Option<String>,
+    /// Memo transaction that recorded `anchor_hash`
+    pub anchor_signature: Option<String>,
+    /// SHA-256 of the date, keyword slots and text; stable across retitling
+    #[serde(default)]
+    pub poem_hash: Option<String>,
+    pub created_at: String,
+}
+
+/// How often a day's poem was read and how readers reacted to it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoemEngagement {
+    pub views: i64,
+    /// Count per emoji; emoji nobody picked are left out
+    pub reactions: BTreeMap<String, i64>,
+}
+
+/// A poem as the /api/poems endpoints return it, with its views and reactions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngagedPoem {
+    #[serde(flatten)]
+    pub poem: StoredPoem,
+    #[serde(flatten)]
+    pub engagement: PoemEngagement,
+}
+
+/// Progress toward today's poem, from /api/poems/today
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodayStatus {
+    pub date: String,
+    /// Timezone `date` is counted in: UTC or an offset like +09:00
+    pub timezone: String,
+    pub keywords_collected: usize,
+    pub keywords_needed: usize,
+    pub poem_ready: bool,
+    /// Minutes between the collector's keyword collections
+    pub interval_minutes: u64,
+    /// Estimated seconds until the next collection; None once today's keywords
+    /// are complete or before the first one sets the cadence
+    pub next_collection_secs: Option<i64>,
+    /// Estimated seconds until the poem is generated; None once it is ready
+    pub poem_expected_secs: Option<i64>,
+    /// Keywords collected toward the poem, 0-100; 100 only once it is stored
+    pub progress_percent: u8,
+    pub keywords: Vec<LinkedKeyword>,
+    pub poem: Option<EngagedPoem>,
+}
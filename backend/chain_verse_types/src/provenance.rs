@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to reproduce a poem, recorded when it was generated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoemProvenance {
+    pub date: String,
+    pub derivation: DerivationProvenance,
+    pub keywords: Vec<KeywordProvenance>,
+    pub llm: LlmProvenance,
+}
+
+/// A poem's provenance as /api/poems/{date}/provenance serves it, with
+/// curators' notes on its keywords
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotatedProvenance {
+    #[serde(flatten)]
+    pub provenance: PoemProvenance,
+    /// Notes on the day's keywords, oldest first; match them to `keywords`
+    /// by word and slot
+    #[serde(default)]
+    pub annotations: Vec<KeywordAnnotation>,
+}
+
+/// The derivation algorithm and word lists in effect when the poem was generated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivationProvenance {
+    pub version: u32,
+    /// SHA-256 of the active dictionary's words, one per line, in index order
+    pub dictionary_sha256: String,
+    /// Active dictionary theme; None for the base dictionary
+    pub theme: Option<String>,
+    /// Language tag of the active dictionary; None for manifests recorded
+    /// before dictionaries declared one
+    #[serde(default)]
+    pub language: Option<String>,
+    /// SHA-256 of the sorted blocklist, one word per line; None without a blocklist
+    pub blocklist_sha256: Option<String>,
+}
+
+/// Where one of the poem's keywords came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordProvenance {
+    pub word: String,
+    /// Negative for words a curator added by hand
+    pub slot: i64,
+    pub blockhash: String,
+    pub word_index: i64,
+    /// Entropy the word was derived from, or `manual`
+    pub source: String,
+    pub theme: Option<String>,
+    /// Re-hashes past blocked or recently used words
+    #[serde(default)]
+    pub rerolls: i64,
+}
+
+/// The model and exact prompts the poem was generated from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProvenance {
+    pub model: String,
+    /// Only the parameters that were set; the rest were the model's defaults
+    #[serde(default)]
+    pub sampling: SamplingOptions,
+    pub form: String,
+    /// Day's mood from its block activity; None when it wasn't recorded
+    #[serde(default)]
+    pub mood: Option<Mood>,
+    /// Free verse's line bounds and the count scaled from the day's activity;
+    /// None for the fixed forms
+    #[serde(default)]
+    pub length: Option<PoemLength>,
+    pub prompt_sha256: String,
+    pub system_prompt_sha256: Option<String>,
+}
+
+/// Sampling parameters sent with the completion; unset ones used the model's defaults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// A day's character, from how busy its blocks were and how heavy their fees
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mood {
+    Frenetic,
+    Bustling,
+    Heavy,
+    Steady,
+    Brooding,
+    Tranquil,
+}
+
+/// Line count asked of free verse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoemLength {
+    pub min_lines: usize,
+    pub max_lines: usize,
+    /// Non-vote transactions per block, averaged over the day's keyword blocks
+    pub transactions_per_block: Option<u64>,
+    /// Lines scaled from `transactions_per_block` between the bounds
+    pub lines: Option<usize>,
+}
+
+/// A curator's note on one of the day's keywords
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordAnnotation {
+    pub id: i64,
+    pub keyword_id: i64,
+    pub word: String,
+    pub slot: i64,
+    pub note: String,
+    pub curator: String,
+    pub created_at: String,
+}
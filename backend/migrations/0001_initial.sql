@@ -0,0 +1,58 @@
+-- Baseline schema, formerly schema.sql.
+-- Tables use IF NOT EXISTS so databases created before migrations adopt it cleanly.
+-- Never edit an applied migration: add a new numbered file instead.
+
+-- Keywords table: stores words derived from blockchain
+CREATE TABLE IF NOT EXISTS keywords (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL UNIQUE,
+    blockhash TEXT NOT NULL,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_keywords_created_at ON keywords(created_at);
+CREATE INDEX IF NOT EXISTS idx_keywords_slot ON keywords(slot);
+
+-- Poems table: stores daily generated poems
+CREATE TABLE IF NOT EXISTS poems (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL UNIQUE,  -- Format: YYYY-MM-DD
+    title TEXT,
+    content TEXT NOT NULL,
+    keyword_ids TEXT NOT NULL,  -- JSON array of keyword IDs
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_poems_date ON poems(date);
+
+-- Milestones table: achievements such as the 100th poem or a 30-day streak
+CREATE TABLE IF NOT EXISTS milestones (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,         -- poems | keywords | streak
+    value INTEGER NOT NULL,     -- Threshold reached
+    date TEXT NOT NULL,         -- Day the milestone was reached (YYYY-MM-DD)
+    achieved_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(kind, value)
+);
+
+-- Poem mints table: compressed NFT mints of daily poems
+CREATE TABLE IF NOT EXISTS poem_mints (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL UNIQUE,  -- Poem date (YYYY-MM-DD)
+    signature TEXT NOT NULL,    -- Mint transaction signature
+    merkle_tree TEXT NOT NULL,
+    minted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Custom poems table: on-demand poems for a user-chosen slot range, kept when requested
+CREATE TABLE IF NOT EXISTS custom_poems (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    start_slot INTEGER NOT NULL,
+    end_slot INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    keywords TEXT NOT NULL,     -- JSON array of words
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
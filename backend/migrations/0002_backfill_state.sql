@@ -0,0 +1,11 @@
+-- Progress of backfill runs, one row per requested date range, so an
+-- interrupted run resumes instead of re-scanning from the start
+CREATE TABLE backfill_state (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    start_date TEXT NOT NULL,   -- YYYY-MM-DD, inclusive
+    end_date TEXT NOT NULL,     -- YYYY-MM-DD, inclusive
+    last_completed_date TEXT,   -- Last day fully processed
+    last_slot_tried INTEGER,    -- Last sample slot fetched in the day after last_completed_date
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(start_date, end_date)
+);
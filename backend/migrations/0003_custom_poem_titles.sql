@@ -0,0 +1,2 @@
+-- Custom poems carry the title generated alongside them
+ALTER TABLE custom_poems ADD COLUMN title TEXT;
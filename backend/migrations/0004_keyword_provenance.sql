@@ -0,0 +1,4 @@
+-- Record which entropy produced each word and the parent blockhash.
+-- Every keyword stored before this migration was derived from its blockhash.
+ALTER TABLE keywords ADD COLUMN source TEXT NOT NULL DEFAULT 'blockhash';
+ALTER TABLE keywords ADD COLUMN previous_blockhash TEXT;
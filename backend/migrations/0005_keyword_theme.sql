@@ -0,0 +1,2 @@
+-- Record which dictionary theme each word was drawn from (NULL = base dictionary)
+ALTER TABLE keywords ADD COLUMN theme TEXT;
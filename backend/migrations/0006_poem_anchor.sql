@@ -0,0 +1,3 @@
+-- Hash of each poem anchored on-chain in a memo transaction, and that transaction's signature
+ALTER TABLE poems ADD COLUMN anchor_hash TEXT;
+ALTER TABLE poems ADD COLUMN anchor_signature TEXT;
@@ -0,0 +1,8 @@
+-- Days whose poem generation failed, retried with exponential backoff until one succeeds
+CREATE TABLE pending_poems (
+    date TEXT PRIMARY KEY,          -- YYYY-MM-DD
+    attempts INTEGER NOT NULL,      -- Failed attempts so far
+    next_attempt_at TEXT NOT NULL,  -- UTC, YYYY-MM-DD HH:MM:SS
+    last_error TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,13 @@
+-- Curator edits to keywords made through the admin API
+CREATE TABLE keyword_audit (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL,           -- add | remove
+    keyword_id INTEGER NOT NULL,    -- Keyword added or removed (removed rows no longer exist)
+    date TEXT NOT NULL,             -- Day the keyword belongs to (YYYY-MM-DD)
+    word TEXT NOT NULL,
+    curator TEXT NOT NULL,          -- Who made the change (X-Curator header)
+    reason TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_keyword_audit_date ON keyword_audit(date);
@@ -0,0 +1,6 @@
+-- Reproducibility manifest recorded when each poem is generated (see PoemProvenance)
+CREATE TABLE poem_provenance (
+    date TEXT PRIMARY KEY,          -- Poem date (YYYY-MM-DD)
+    manifest TEXT NOT NULL,         -- JSON: derivation version, word list hashes, keywords, LLM
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
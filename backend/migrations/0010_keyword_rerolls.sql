@@ -0,0 +1,2 @@
+-- Re-hashes it took to skip blocked or recently used words (0 = the block's own word)
+ALTER TABLE keywords ADD COLUMN rerolls INTEGER NOT NULL DEFAULT 0;
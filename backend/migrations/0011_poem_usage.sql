@@ -0,0 +1,4 @@
+-- What generating each poem cost, as reported by OpenRouter (NULL when unknown)
+ALTER TABLE poems ADD COLUMN prompt_tokens INTEGER;
+ALTER TABLE poems ADD COLUMN completion_tokens INTEGER;
+ALTER TABLE poems ADD COLUMN cost_usd REAL;
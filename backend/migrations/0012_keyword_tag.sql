@@ -0,0 +1,2 @@
+-- Why a keyword was collected outside the regular schedule (`event`); NULL for scheduled ones
+ALTER TABLE keywords ADD COLUMN tag TEXT;
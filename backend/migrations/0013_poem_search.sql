@@ -0,0 +1,27 @@
+-- Full-text index over poem titles and text, kept in sync with `poems` by triggers
+CREATE VIRTUAL TABLE poems_fts USING fts5(
+    title,
+    content,
+    content = 'poems',
+    content_rowid = 'id',
+    tokenize = 'porter unicode61'
+);
+
+INSERT INTO poems_fts (rowid, title, content)
+SELECT id, title, content FROM poems;
+
+CREATE TRIGGER poems_fts_insert AFTER INSERT ON poems BEGIN
+    INSERT INTO poems_fts (rowid, title, content) VALUES (new.id, new.title, new.content);
+END;
+
+CREATE TRIGGER poems_fts_delete AFTER DELETE ON poems BEGIN
+    INSERT INTO poems_fts (poems_fts, rowid, title, content)
+    VALUES ('delete', old.id, old.title, old.content);
+END;
+
+-- Regenerating a poem upserts it; anchoring only touches other columns
+CREATE TRIGGER poems_fts_update AFTER UPDATE OF title, content ON poems BEGIN
+    INSERT INTO poems_fts (poems_fts, rowid, title, content)
+    VALUES ('delete', old.id, old.title, old.content);
+    INSERT INTO poems_fts (rowid, title, content) VALUES (new.id, new.title, new.content);
+END;
@@ -0,0 +1,18 @@
+-- Derived keywords whose insert into `keywords` failed, replayed by the collector
+-- until one attempt succeeds so the RPC work behind them isn't lost
+CREATE TABLE failed_keywords (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL UNIQUE,
+    blockhash TEXT NOT NULL,
+    previous_blockhash TEXT,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    theme TEXT,
+    rerolls INTEGER NOT NULL DEFAULT 0,
+    tag TEXT,
+    collected_at TEXT NOT NULL,     -- UTC, YYYY-MM-DD HH:MM:SS; becomes keywords.created_at
+    attempts INTEGER NOT NULL DEFAULT 0,  -- Failed replays so far
+    last_error TEXT
+);
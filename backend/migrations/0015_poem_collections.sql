@@ -0,0 +1,12 @@
+-- Weekly and monthly meta-poems synthesized from the period's daily poems
+CREATE TABLE poem_collections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    period TEXT NOT NULL UNIQUE,    -- 2026-W02 for a week, 2026-01 for a month
+    kind TEXT NOT NULL,             -- week or month
+    start_date TEXT NOT NULL,       -- First day of the period (YYYY-MM-DD)
+    end_date TEXT NOT NULL,         -- Last day of the period, inclusive
+    title TEXT,
+    content TEXT NOT NULL,
+    poem_dates TEXT NOT NULL,       -- JSON array of the daily poems it drew on
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
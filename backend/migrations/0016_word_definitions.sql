@@ -0,0 +1,9 @@
+-- Dictionary definitions of collected words, looked up once per word for keyword tooltips
+CREATE TABLE word_definitions (
+    word TEXT PRIMARY KEY,          -- Lowercased
+    definition TEXT,                -- NULL when the dictionary has no entry for the word
+    part_of_speech TEXT,
+    origin TEXT,                    -- Etymology, when the dictionary has one
+    source TEXT,
+    fetched_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
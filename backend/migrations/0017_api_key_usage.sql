@@ -0,0 +1,12 @@
+-- Requests made with an admin API key, for auditing who used which key
+CREATE TABLE api_key_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    key_name TEXT NOT NULL,         -- `name` of the configured key
+    role TEXT NOT NULL,             -- reader | curator | admin, as configured at the time
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    status INTEGER NOT NULL,        -- HTTP status of the response (403 when the role fell short)
+    used_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_api_key_usage_key ON api_key_usage(key_name, used_at);
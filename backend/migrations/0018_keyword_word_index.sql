@@ -0,0 +1,2 @@
+-- Earlier occurrences of a word are looked up for every keyword served (rarity)
+CREATE INDEX idx_keywords_word ON keywords(word COLLATE NOCASE);
@@ -0,0 +1,60 @@
+-- A block can now give several keywords (collector.keywords_per_block), so a
+-- keyword is unique per slot and word rather than per slot.
+-- SQLite can't drop a column constraint, so both tables are rebuilt.
+CREATE TABLE keywords_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    blockhash TEXT NOT NULL,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    source TEXT NOT NULL DEFAULT 'blockhash',
+    previous_blockhash TEXT,
+    theme TEXT,
+    rerolls INTEGER NOT NULL DEFAULT 0,
+    tag TEXT,
+    UNIQUE(slot, word)
+);
+
+INSERT INTO keywords_new
+    (id, word, slot, blockhash, block_time, word_index, created_at, source, previous_blockhash,
+     theme, rerolls, tag)
+SELECT id, word, slot, blockhash, block_time, word_index, created_at, source, previous_blockhash,
+       theme, rerolls, tag
+FROM keywords;
+
+-- Keep ids of removed keywords (still named in keyword_audit) from being reused
+UPDATE sqlite_sequence
+SET seq = MAX(seq, (SELECT seq FROM sqlite_sequence WHERE name = 'keywords'))
+WHERE name = 'keywords_new'
+  AND EXISTS (SELECT 1 FROM sqlite_sequence WHERE name = 'keywords');
+
+DROP TABLE keywords;
+ALTER TABLE keywords_new RENAME TO keywords;
+
+-- The (slot, word) constraint's index serves lookups by slot
+CREATE INDEX idx_keywords_created_at ON keywords(created_at);
+CREATE INDEX idx_keywords_word ON keywords(word COLLATE NOCASE);
+
+CREATE TABLE failed_keywords_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    blockhash TEXT NOT NULL,
+    previous_blockhash TEXT,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    theme TEXT,
+    rerolls INTEGER NOT NULL DEFAULT 0,
+    tag TEXT,
+    collected_at TEXT NOT NULL,     -- UTC, YYYY-MM-DD HH:MM:SS; becomes keywords.created_at
+    attempts INTEGER NOT NULL DEFAULT 0,  -- Failed replays so far
+    last_error TEXT,
+    UNIQUE(slot, word)
+);
+
+INSERT INTO failed_keywords_new SELECT * FROM failed_keywords;
+DROP TABLE failed_keywords;
+ALTER TABLE failed_keywords_new RENAME TO failed_keywords;
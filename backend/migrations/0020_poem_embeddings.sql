@@ -0,0 +1,15 @@
+-- One embedding per poem for /api/poems/{date}/similar; vectors are compared
+-- only with others from the same model
+CREATE TABLE poem_embeddings (
+    poem_id INTEGER PRIMARY KEY REFERENCES poems(id) ON DELETE CASCADE,
+    model TEXT NOT NULL,
+    vector BLOB NOT NULL,           -- Little-endian f32s
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_poem_embeddings_model ON poem_embeddings(model);
+
+-- A regenerated poem is embedded again
+CREATE TRIGGER poem_embeddings_stale AFTER UPDATE OF title, content ON poems BEGIN
+    DELETE FROM poem_embeddings WHERE poem_id = new.id;
+END;
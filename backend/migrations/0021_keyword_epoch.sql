@@ -0,0 +1,7 @@
+-- Solana epoch of the keyword's slot, looked up when it was collected (getEpochInfo);
+-- NULL for keywords collected before it was recorded or while the lookup failed
+ALTER TABLE keywords ADD COLUMN epoch INTEGER;
+ALTER TABLE failed_keywords ADD COLUMN epoch INTEGER;
+
+-- Poems are browsed by epoch (/api/poems/epoch/{epoch})
+CREATE INDEX idx_keywords_epoch ON keywords(epoch);
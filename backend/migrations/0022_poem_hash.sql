@@ -0,0 +1,25 @@
+-- Content address of a poem (see database::poem_hash), filled in by the
+-- writer at startup for poems stored before it existed
+ALTER TABLE poems ADD COLUMN poem_hash TEXT;
+
+CREATE UNIQUE INDEX idx_poems_poem_hash ON poems(poem_hash);
+
+-- Earlier versions of regenerated poems, so a cited hash keeps resolving
+CREATE TABLE poem_revisions (
+    poem_hash TEXT PRIMARY KEY,
+    poem_id INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    title TEXT,
+    content TEXT NOT NULL,
+    keyword_ids TEXT NOT NULL,
+    created_at TIMESTAMP,
+    replaced_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER poem_revisions_keep BEFORE UPDATE OF poem_hash ON poems
+WHEN old.poem_hash IS NOT NULL AND old.poem_hash IS NOT new.poem_hash BEGIN
+    INSERT OR IGNORE INTO poem_revisions
+        (poem_hash, poem_id, date, title, content, keyword_ids, created_at)
+    VALUES
+        (old.poem_hash, old.id, old.date, old.title, old.content, old.keyword_ids, old.created_at);
+END;
@@ -0,0 +1,11 @@
+-- Totals over the blocks each day's keywords were derived from, and the mood
+-- (see mood::Mood) they give the day's poem
+CREATE TABLE day_activity (
+    date TEXT PRIMARY KEY,
+    blocks INTEGER NOT NULL DEFAULT 0,
+    transactions INTEGER NOT NULL DEFAULT 0,
+    non_vote_transactions INTEGER NOT NULL DEFAULT 0,
+    fees INTEGER NOT NULL DEFAULT 0,     -- Lamports
+    mood TEXT,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
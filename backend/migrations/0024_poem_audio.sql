@@ -0,0 +1,14 @@
+-- A spoken recording of each poem for /api/poems/{date}/audio; poems are read
+-- again when the configured voice or format changes
+CREATE TABLE poem_audio (
+    poem_id INTEGER PRIMARY KEY REFERENCES poems(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,             -- Audio file, relative to the working directory unless absolute
+    format TEXT NOT NULL,           -- mp3 or ogg
+    voice TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- A regenerated poem is read again
+CREATE TRIGGER poem_audio_stale AFTER UPDATE OF title, content ON poems BEGIN
+    DELETE FROM poem_audio WHERE poem_id = new.id;
+END;
@@ -0,0 +1,69 @@
+-- A keyword is identified by the block, the entropy source it was derived
+-- from and the word it landed on, rather than by slot and word: each source
+-- of a block (collector.keywords_per_block) is its own keyword.
+-- SQLite can't drop a table constraint, so both tables are rebuilt.
+CREATE TABLE keywords_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    blockhash TEXT NOT NULL,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    source TEXT NOT NULL DEFAULT 'blockhash',
+    previous_blockhash TEXT,
+    theme TEXT,
+    rerolls INTEGER NOT NULL DEFAULT 0,
+    tag TEXT,
+    epoch INTEGER,
+    UNIQUE(slot, source, word_index)
+);
+
+INSERT INTO keywords_new
+    (id, word, slot, blockhash, block_time, word_index, created_at, source, previous_blockhash,
+     theme, rerolls, tag, epoch)
+SELECT id, word, slot, blockhash, block_time, word_index, created_at, source, previous_blockhash,
+       theme, rerolls, tag, epoch
+FROM keywords;
+
+-- Keep ids of removed keywords (still named in keyword_audit) from being reused
+UPDATE sqlite_sequence
+SET seq = MAX(seq, (SELECT seq FROM sqlite_sequence WHERE name = 'keywords'))
+WHERE name = 'keywords_new'
+  AND EXISTS (SELECT 1 FROM sqlite_sequence WHERE name = 'keywords');
+
+DROP TABLE keywords;
+ALTER TABLE keywords_new RENAME TO keywords;
+
+-- The (slot, source, word_index) constraint's index serves lookups by slot
+CREATE INDEX idx_keywords_created_at ON keywords(created_at);
+CREATE INDEX idx_keywords_word ON keywords(word COLLATE NOCASE);
+CREATE INDEX idx_keywords_epoch ON keywords(epoch);
+
+CREATE TABLE failed_keywords_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    blockhash TEXT NOT NULL,
+    previous_blockhash TEXT,
+    block_time INTEGER,
+    word_index INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    theme TEXT,
+    rerolls INTEGER NOT NULL DEFAULT 0,
+    tag TEXT,
+    collected_at TEXT NOT NULL,     -- UTC, YYYY-MM-DD HH:MM:SS; becomes keywords.created_at
+    attempts INTEGER NOT NULL DEFAULT 0,  -- Failed replays so far
+    last_error TEXT,
+    epoch INTEGER,
+    UNIQUE(slot, source, word_index)
+);
+
+INSERT INTO failed_keywords_new
+    (id, word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+     rerolls, tag, collected_at, attempts, last_error, epoch)
+SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+       rerolls, tag, collected_at, attempts, last_error, epoch
+FROM failed_keywords;
+DROP TABLE failed_keywords;
+ALTER TABLE failed_keywords_new RENAME TO failed_keywords;
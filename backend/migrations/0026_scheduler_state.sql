@@ -0,0 +1,6 @@
+-- When the collector last finished each task, so a restarted daemon knows
+-- which ticks it missed while it was down and can catch up on them
+CREATE TABLE scheduler_state (
+    task TEXT PRIMARY KEY,          -- collection, poem
+    last_success_at TEXT NOT NULL   -- UTC, YYYY-MM-DD HH:MM:SS
+);
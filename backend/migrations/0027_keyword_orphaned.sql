@@ -0,0 +1,5 @@
+-- Confirmed blocks can still be abandoned by a reorg. An hour after it is
+-- collected, each keyword's slot is re-read at finalized commitment; a
+-- keyword whose block didn't survive is flagged and kept out of poems.
+ALTER TABLE keywords ADD COLUMN orphaned INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE keywords ADD COLUMN verified_at TEXT;  -- UTC, YYYY-MM-DD HH:MM:SS; NULL until checked
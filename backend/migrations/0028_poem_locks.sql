@@ -0,0 +1,7 @@
+-- Leases on generating a day's poem, so when several collectors share the
+-- database only one of them writes it; an expired lease can be taken over
+CREATE TABLE poem_locks (
+    date TEXT PRIMARY KEY,          -- YYYY-MM-DD
+    holder TEXT NOT NULL,           -- instance id of the collector holding it
+    expires_at TEXT NOT NULL        -- UTC, YYYY-MM-DD HH:MM:SS
+);
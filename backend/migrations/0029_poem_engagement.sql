@@ -0,0 +1,13 @@
+-- How often each day's poem was read and the emoji reactions readers left,
+-- keyed by date so the counts carry over to a regenerated poem
+CREATE TABLE poem_views (
+    date TEXT PRIMARY KEY,              -- YYYY-MM-DD
+    views INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE poem_reactions (
+    date TEXT NOT NULL,                 -- YYYY-MM-DD
+    emoji TEXT NOT NULL,                -- one of consts::POEM_REACTIONS
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (date, emoji)
+);
@@ -0,0 +1,10 @@
+-- Curator notes on keywords, shown with the poem's provenance
+CREATE TABLE keyword_annotations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    keyword_id INTEGER NOT NULL REFERENCES keywords(id) ON DELETE CASCADE,
+    note TEXT NOT NULL,
+    curator TEXT NOT NULL,          -- Who wrote the note (X-Curator header)
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_keyword_annotations_keyword ON keyword_annotations(keyword_id);
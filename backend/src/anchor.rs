@@ -0,0 +1,99 @@
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::blockchain::SolanaClient;
+use crate::database::{StoredKeyword, StoredPoem};
+
+/// SPL Memo program (v2)
+const MEMO_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Hex SHA-256 of the poem text followed by each keyword on its own line
+///
+/// Keywords are taken in collection order, matching `/api/keywords/{date}`
+/// filtered to the poem's `keyword_ids`.
+pub fn poem_anchor_hash(content: &str, words: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    for word in words {
+        hasher.update(b"\n");
+        hasher.update(word.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Memo text written on-chain for a poem
+fn anchor_memo(date: &str, hash: &str) -> String {
+    format!("chain_verse {} sha256:{}", date, hash)
+}
+
+/// Records a hash of each poem on Solana with a Memo-program transaction
+pub struct PoemAnchor {
+    solana_client: SolanaClient,
+    payer: Arc<Keypair>,
+}
+
+impl PoemAnchor {
+    /// `keypair_path` (`anchor.keypair_path`) pays the fees and signs the memos
+    pub fn new(solana_client: SolanaClient, keypair_path: &Path) -> Result<Self> {
+        let payer = read_keypair_file(keypair_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", keypair_path.display(), e))?;
+
+        Ok(Self {
+            solana_client,
+            payer: Arc::new(payer),
+        })
+    }
+
+    /// Account that signs the memos; verifiers check transactions came from it
+    pub fn signer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Send the memo for a poem; returns the hash and the transaction signature
+    pub async fn anchor_poem(
+        &self,
+        poem: &StoredPoem,
+        keywords: &[StoredKeyword],
+    ) -> Result<(String, String)> {
+        let words: Vec<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+        let hash = poem_anchor_hash(&poem.content, &words);
+        let instruction = Instruction::new_with_bytes(
+            MEMO_PROGRAM_ID,
+            anchor_memo(&poem.date, &hash).as_bytes(),
+            vec![AccountMeta::new_readonly(self.payer.pubkey(), true)],
+        );
+
+        let signature = self
+            .solana_client
+            .send_instructions(vec![instruction], Arc::clone(&self.payer))
+            .await?;
+
+        info!(date = %poem.date, %hash, %signature, "Anchored poem on-chain");
+        Ok((hash, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poem_anchor_hash() {
+        let hash = poem_anchor_hash("moon over water", &["moon", "water"]);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, poem_anchor_hash("moon over water", &["moon", "water"]));
+        assert_ne!(hash, poem_anchor_hash("moon over water", &["water", "moon"]));
+        assert_ne!(hash, poem_anchor_hash("moon over the water", &["moon", "water"]));
+
+        assert_eq!(
+            anchor_memo("2026-01-05", &hash),
+            format!("chain_verse 2026-01-05 sha256:{}", hash)
+        );
+    }
+}
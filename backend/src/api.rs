@@ -1,28 +1,274 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::get,
+    body::Body,
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
+    response::sse::{Event, KeepAlive, Sse},
+    response::{Html, IntoResponse, Response},
+    routing::{delete, get, post},
     Json, Router,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 use tower_http::cors::{Any, CorsLayer};
+use tracing::{info, warn};
 
-use crate::database::{Database, StoredKeyword, StoredPoem};
+use crate::auth::{self, ApiKeys, Caller, Guard, Role};
+use crate::blockchain::{BlockInfo, SolanaClient};
+use crate::collections::{CollectionKind, Period};
+use crate::consts::{
+    DEFAULT_API_USAGE_LIMIT, DEFAULT_COOCCURRENCE_LIMIT, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SIMILAR_LIMIT, DEFAULT_STATS_TOP_WORDS, HISTORICAL_POEM_MAX_AGE_SECS,
+    MAX_ANNOTATION_CHARS, MAX_API_USAGE_LIMIT, MAX_COOCCURRENCE_LIMIT, MAX_DERIVE_INPUT_LEN,
+    MAX_SEARCH_LIMIT, MAX_SIMILAR_LIMIT, MAX_STATS_TOP_WORDS, POEM_REACTIONS,
+    RECENT_CACHE_MAX_AGE_SECS,
+};
+use crate::control::{CollectorCommand, CollectorControl};
+use crate::database::{
+    ApiKeyUsage, Database, KeywordAnnotation, KeywordAuditEntry, KeywordOccurrence, PoemEngagement,
+    PoemSearchHit, SimilarPoem, StoredCollection, StoredCustomPoem, StoredKeyword, StoredPoem,
+    WordCooccurrence,
+};
+use crate::dataset;
+use crate::definitions::WordDefinition;
+use crate::derivation::{DerivationTrace, KeywordDerivation};
+use crate::engagement::{self, EngagedPoem, ViewCounter};
+use crate::error::ChainVerseError;
+use crate::events::EventBus;
+use crate::explorer::{self, ExplorerLinks, LinkedKeyword};
+use crate::export::{self, ExportFormat};
+use crate::health::{HealthChecker, HealthReport, HealthStatus};
+use crate::http_cache;
+use crate::metrics;
+use crate::milestones::{self, MilestoneSummary};
+use crate::minting;
+use crate::openapi;
+use crate::preview::{CustomPoem, CustomRangeRequest, PreviewError, PreviewPoem, PreviewService};
+use crate::provenance::AnnotatedProvenance;
+use crate::rarity::KeywordRarity;
+use crate::rate_limit::{self, RateLimiter};
+use crate::scheduler::CollectionSchedule;
+use crate::stats::{self, CorpusStats};
+use crate::tts::AudioFormat;
+use crate::words::DictionaryHandle;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Arc<Database>,
+    /// Time-travel previews; None when the server has no LLM credentials
+    pub preview: Option<Arc<PreviewService>>,
+    /// Dependency probes for /health/deep; None when the server has no LLM credentials
+    pub health: Option<Arc<HealthChecker>>,
+    /// Refetches blocks for /api/blocks/{slot}; None serves only stored keyword data
+    pub blockchain: Option<Arc<SolanaClient>>,
+    /// Dictionary shared with the collector, reloadable via the admin API
+    pub dictionary: Option<DictionaryHandle>,
+    /// The collector's dictionary and blocklist, for /api/derive
+    pub derivation: Option<Arc<KeywordDerivation>>,
+    /// Keys accepted by the admin routes, each with a role; admin routes are disabled when empty
+    pub api_keys: Arc<ApiKeys>,
+    /// Per-client quotas for the /api routes; None disables rate limiting
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// Per-IP quota for POST /api/poems/{date}/react, applied even without `rate_limiter`
+    pub reaction_limiter: Arc<RateLimiter>,
+    /// Views of /api/poems/{date} waiting to be written; `serve` flushes them
+    pub views: Arc<ViewCounter>,
+    /// Collector lifecycle events for /api/events; None when no collector runs in-process
+    pub events: Option<EventBus>,
+    /// Commands for the collector from /api/admin/collect-now, pause, and
+    /// resume; None when no collector runs in-process
+    pub control: Option<CollectorControl>,
+    /// The collector's cadence and keyword targets, for /api/poems/today's estimates
+    pub schedule: CollectionSchedule,
+    /// Compiled frontend served for paths no route matches; None serves only the API
+    pub frontend_dir: Option<PathBuf>,
+    /// Flips to true when the server shuts down, ending /api/events streams;
+    /// set by `serve`
+    pub shutdown: Option<tokio::sync::watch::Receiver<bool>>,
+}
+
+impl AppState {
+    pub fn new(db: Database) -> Self {
+        let db = Arc::new(db);
+        Self {
+            views: Arc::new(ViewCounter::new(db.clone())),
+            db,
+            preview: None,
+            health: None,
+            blockchain: None,
+            dictionary: None,
+            derivation: None,
+            api_keys: Arc::default(),
+            rate_limiter: None,
+            reaction_limiter: Arc::new(engagement::reaction_limiter(false)),
+            events: None,
+            control: None,
+            schedule: CollectionSchedule::default(),
+            frontend_dir: None,
+            shutdown: None,
+        }
+    }
+}
+
+/// Body of POST /api/derive
+#[derive(Deserialize)]
+struct DeriveRequest {
+    input: String,
+}
+
+/// Body of POST /api/admin/keywords
+#[derive(Deserialize)]
+struct AddKeywordRequest {
+    date: String,
+    word: String,
+    reason: Option<String>,
+}
+
+/// Body of POST /api/admin/keywords/{id}/annotations
+#[derive(Deserialize)]
+struct AnnotationRequest {
+    note: String,
+}
+
+/// Body of POST /api/poems/{date}/react
+#[derive(Deserialize)]
+struct ReactionRequest {
+    emoji: String,
+}
+
+#[derive(Deserialize)]
+struct RemoveKeywordQuery {
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiUsageQuery {
+    key: Option<String>,
+    limit: Option<i64>,
 }
 
 #[derive(Serialize)]
 struct TodayStatus {
     date: String,
+    /// Timezone `date` is counted in: UTC or an offset like +09:00
+    timezone: String,
     keywords_collected: usize,
     keywords_needed: usize,
     poem_ready: bool,
+    /// Minutes between the collector's keyword collections
+    interval_minutes: u64,
+    /// Estimated seconds until the next collection; None once today's keywords
+    /// are complete or before the first one sets the cadence
+    next_collection_secs: Option<i64>,
+    /// Estimated seconds until the poem is generated; None once it is ready
+    poem_expected_secs: Option<i64>,
+    /// Keywords collected toward the poem, 0-100; 100 only once it is stored
+    progress_percent: u8,
+    keywords: Vec<LinkedKeyword>,
+    poem: Option<EngagedPoem>,
+}
+
+#[derive(Deserialize)]
+struct OnThisDayQuery {
+    date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CooccurrenceQuery {
+    min_count: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct StatsQuery {
+    top: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct KeywordSearchQuery {
+    word: String,
+}
+
+#[derive(Deserialize)]
+struct PoemSearchQuery {
+    q: String,
+    limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct SimilarPoemsQuery {
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct PoemSearchResult {
+    query: String,
+    matches: usize,
+    poems: Vec<PoemSearchHit>,
+}
+
+/// The daily poems of an ISO week
+#[derive(Serialize)]
+struct WeekPoems {
+    week: String,
+    start_date: String,
+    end_date: String,
+    poems: Vec<EngagedPoem>,
+}
+
+/// The daily poems drawing on keywords from a Solana epoch
+#[derive(Serialize)]
+struct EpochPoems {
+    epoch: u64,
+    poems: Vec<EngagedPoem>,
+}
+
+#[derive(Serialize)]
+struct KeywordSearchResult {
+    word: String,
+    occurrences: usize,
+    keywords: Vec<LinkedOccurrence>,
+}
+
+/// A keyword search hit with links to its block and the word's definition
+#[derive(Serialize)]
+struct LinkedOccurrence {
+    #[serde(flatten)]
+    keyword: LinkedKeyword,
+    poems: Vec<String>,
+}
+
+impl LinkedOccurrence {
+    fn new(
+        occurrence: KeywordOccurrence,
+        definitions: &HashMap<String, WordDefinition>,
+        rarity: &HashMap<i64, KeywordRarity>,
+    ) -> Self {
+        let keyword_rarity = rarity.get(&occurrence.keyword.id).cloned();
+        Self {
+            keyword: LinkedKeyword::new(occurrence.keyword, definitions)
+                .with_rarity(keyword_rarity),
+            poems: occurrence.poems,
+        }
+    }
+}
+
+/// Response for GET /api/blocks/{slot}
+#[derive(Serialize)]
+struct BlockDetail {
+    slot: u64,
+    /// Refetched from Solana RPC; None when RPC is unavailable but keywords came from the slot
+    block: Option<BlockInfo>,
+    /// The keywords derived from this slot, as stored when they were collected,
+    /// by source and word index
     keywords: Vec<StoredKeyword>,
-    poem: Option<StoredPoem>,
+    explorer: ExplorerLinks,
 }
 
 #[derive(Serialize)]
@@ -30,22 +276,136 @@ struct ErrorResponse {
     error: String,
 }
 
-pub fn create_router(db: Database) -> Router {
-    let state = AppState { db: Arc::new(db) };
-
+pub fn create_router(state: AppState) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    Router::new()
-        .route("/health", get(health_check))
+    let mut api = Router::new()
         .route("/api/poems", get(get_all_poems))
+        .route("/api/archive", get(get_archive))
         .route("/api/poems/today", get(get_today))
-        .route("/api/poems/{date}", get(get_poem_by_date))
+        .route("/api/poems/random", get(get_random_poem))
+        .route("/api/poems/on-this-day", get(get_poems_on_this_day))
+        .route("/api/poems/search", get(search_poems))
+        .route("/api/poems/custom", post(create_custom_poem))
+        .route("/api/poems/custom/{id}", get(get_custom_poem))
+        .route("/api/poems/week/{week}", get(get_poems_for_week))
+        .route("/api/poems/epoch/{epoch}", get(get_poems_for_epoch))
+        .route("/api/poems/hash/{hash}", get(get_poem_by_hash))
+        .route(
+            "/api/poems/{date}",
+            get(get_poem_by_date).layer(middleware::from_fn_with_state(
+                state.views.clone(),
+                engagement::count_views,
+            )),
+        )
+        .route("/api/poems/{date}/metadata.json", get(get_poem_metadata))
+        .route("/api/poems/{date}/provenance", get(get_poem_provenance))
+        .route("/api/poems/{date}/similar", get(get_similar_poems))
+        .route("/api/poems/{date}/audio", get(get_poem_audio))
+        .route(
+            "/api/poems/{date}/react",
+            post(react_to_poem).layer(middleware::from_fn_with_state(
+                state.reaction_limiter.clone(),
+                rate_limit::limit,
+            )),
+        )
         .route("/api/keywords/today", get(get_today_keywords))
+        .route("/api/keywords/search", get(search_keywords))
+        .route("/api/keywords/{date}", get(get_keywords_by_date))
+        .route("/api/blocks/{slot}", get(get_block))
+        .route("/api/derive", post(derive_word))
+        .route("/api/export/keywords.csv", get(export_keywords_csv))
+        .route("/api/collections", get(get_collections))
+        .route("/api/collections/{period}", get(get_collection))
+        .route("/api/events", get(stream_events))
+        .route("/api/stats", get(get_stats))
+        .route("/api/stats/cooccurrence", get(get_cooccurrence))
+        .route("/api/stats/milestones", get(get_milestones))
+        .route("/api/preview/{date}", post(create_preview))
+        .route("/api/openapi.json", get(get_openapi))
+        .route("/api/docs", get(get_docs))
+        .merge(admin_routes(&state));
+
+    #[cfg(feature = "graphql")]
+    {
+        api = api.merge(crate::graphql::router(state.db.clone()));
+    }
+
+    // Health checks and Prometheus scrapes are exempt from rate limiting
+    if let Some(limiter) = state.rate_limiter.clone() {
+        api = api.layer(middleware::from_fn_with_state(limiter, rate_limit::limit));
+    }
+
+    #[cfg(feature = "frontend")]
+    let frontend_dir = state.frontend_dir.clone();
+
+    let router = Router::new()
+        .route("/health", get(health_check))
+        .route("/health/deep", get(deep_health_check))
+        .route("/metrics", get(get_metrics))
+        .merge(api)
         .with_state(state)
-        .layer(cors)
+        .layer(cors);
+
+    #[cfg(feature = "frontend")]
+    let router = match frontend_dir {
+        Some(dir) => serve_frontend(router, &dir),
+        None => router,
+    };
+
+    router
+}
+
+/// Serve the files in `dir` for every path no route matches, falling back to
+/// its index.html so the frontend can route client-side; unknown /api paths
+/// still get a 404
+#[cfg(feature = "frontend")]
+fn serve_frontend(router: Router, dir: &std::path::Path) -> Router {
+    use tower_http::services::{ServeDir, ServeFile};
+
+    let files = ServeDir::new(dir).fallback(ServeFile::new(dir.join("index.html")));
+    router
+        .route(
+            "/api/{*path}",
+            axum::routing::any(|| async { StatusCode::NOT_FOUND }),
+        )
+        .fallback_service(files)
+}
+
+/// Admin routes, each behind the API key role it needs
+fn admin_routes(state: &AppState) -> Router<AppState> {
+    let guard = |role| {
+        middleware::from_fn_with_state(
+            Guard {
+                keys: state.api_keys.clone(),
+                db: state.db.clone(),
+                role,
+            },
+            auth::require,
+        )
+    };
+
+    let reader = Router::new()
+        .route("/api/admin/audit/{date}", get(get_keyword_audit))
+        .route_layer(guard(Role::Reader));
+    let curator = Router::new()
+        .route("/api/admin/keywords", post(add_keyword))
+        .route("/api/admin/keywords/{id}", delete(remove_keyword))
+        .route("/api/admin/keywords/{id}/annotations", post(add_annotation))
+        .route("/api/admin/annotations/{id}", delete(remove_annotation))
+        .route_layer(guard(Role::Curator));
+    let admin = Router::new()
+        .route("/api/admin/dictionary/reload", post(reload_dictionary))
+        .route("/api/admin/collect-now", post(collect_now))
+        .route("/api/admin/pause", post(pause_collector))
+        .route("/api/admin/resume", post(resume_collector))
+        .route("/api/admin/usage", get(get_api_usage))
+        .route_layer(guard(Role::Admin));
+
+    reader.merge(curator).merge(admin)
 }
 
 /// GET /health - Health check endpoint
@@ -57,18 +417,107 @@ async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
+/// GET /health/deep - Collector liveness and dependency health; 503 when unhealthy
+async fn deep_health_check(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<HealthReport>), (StatusCode, Json<ErrorResponse>)> {
+    let Some(health) = state.health.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Deep health checks are not enabled on this server".to_string(),
+            }),
+        ));
+    };
+
+    let report = health.check().await;
+    let status = match report.status {
+        HealthStatus::Ok | HealthStatus::Degraded => StatusCode::OK,
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    Ok((status, Json(report)))
+}
+
+/// GET /metrics - Prometheus metrics
+async fn get_metrics() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics::render())
+}
+
 /// GET /api/poems - Get all poems
 async fn get_all_poems(
     State(state): State<AppState>,
-) -> Result<Json<Vec<StoredPoem>>, (StatusCode, Json<ErrorResponse>)> {
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
     match state.db.get_all_poems().await {
-        Ok(poems) => Ok(Json(poems)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )),
+        Ok(poems) => {
+            let engaged = engage_poems(&state, poems.clone()).await?;
+            poems_json(&headers, &poems, &engaged, RECENT_CACHE_MAX_AGE_SECS)
+        }
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/random - One poem from the archive, chosen at random
+async fn get_random_poem(
+    State(state): State<AppState>,
+) -> Result<Json<EngagedPoem>, (StatusCode, Json<ErrorResponse>)> {
+    match state.db.get_random_poem().await {
+        Ok(Some(poem)) => Ok(Json(engage_poem(&state, poem).await?)),
+        Ok(None) => Err(error_response(StatusCode::NOT_FOUND, "No poems yet")),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/on-this-day?date= - Poems from earlier years on the same
+/// month and day (default today)
+async fn get_poems_on_this_day(
+    State(state): State<AppState>,
+    Query(query): Query<OnThisDayQuery>,
+) -> Result<Json<Vec<EngagedPoem>>, (StatusCode, Json<ErrorResponse>)> {
+    let date = match query.date {
+        Some(date) => {
+            if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+                return Err(error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid date (expected YYYY-MM-DD): {}", date),
+                ));
+            }
+            date
+        }
+        None => state.db.today(),
+    };
+
+    match state.db.get_poems_on_this_day(&date).await {
+        Ok(poems) => Ok(Json(engage_poems(&state, poems).await?)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/search?q=rain - Poems mentioning every word of the query, with
+/// highlighted snippets
+async fn search_poems(
+    State(state): State<AppState>,
+    Query(query): Query<PoemSearchQuery>,
+) -> Result<Json<PoemSearchResult>, (StatusCode, Json<ErrorResponse>)> {
+    let q = query.q.trim().to_string();
+    if q.is_empty() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "Query parameter `q` must not be empty",
+        ));
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    match state.db.search_poems(&q, limit).await {
+        Ok(poems) => Ok(Json(PoemSearchResult {
+            matches: poems.len(),
+            query: q,
+            poems,
+        })),
+        Err(e) => Err(failure(e)),
     }
 }
 
@@ -76,89 +525,1241 @@ async fn get_all_poems(
 async fn get_today(
     State(state): State<AppState>,
 ) -> Result<Json<TodayStatus>, (StatusCode, Json<ErrorResponse>)> {
-    let today = Database::today();
+    let today = state.db.today();
 
     let keywords = match state.db.get_keywords_for_date(&today).await {
         Ok(kw) => kw,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: e.to_string(),
-                }),
-            ))
-        }
+        Err(e) => return Err(failure(e)),
     };
 
     let poem = match state.db.get_poem_by_date(&today).await {
         Ok(p) => p,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: e.to_string(),
-                }),
-            ))
-        }
+        Err(e) => return Err(failure(e)),
     };
+    let poem_ready = poem.is_some();
+
+    let now = chrono::Utc::now();
+    let estimate = state.schedule.estimate(&keywords, poem_ready, now);
+    let secs_until = |at: chrono::DateTime<chrono::Utc>| (at - now).num_seconds().max(0);
 
     Ok(Json(TodayStatus {
         date: today,
+        timezone: state.db.timezone().to_string(),
         keywords_collected: keywords.len(),
-        keywords_needed: 15, // Target number
-        poem_ready: poem.is_some(),
-        keywords,
-        poem,
+        keywords_needed: state.schedule.keywords_per_day,
+        poem_ready,
+        interval_minutes: state.schedule.interval_minutes,
+        next_collection_secs: estimate.next_collection.map(secs_until),
+        poem_expected_secs: estimate.poem_expected.map(secs_until),
+        progress_percent: estimate.progress_percent,
+        keywords: link_keywords(&state.db, keywords).await?,
+        poem: match poem {
+            Some(poem) => Some(engage_poem(&state, poem).await?),
+            None => None,
+        },
     }))
 }
 
 /// GET /api/poems/:date - Get a specific poem by date
+///
+/// `{date}.txt`, `{date}.md`, and `{date}.png` export it for sharing instead.
 async fn get_poem_by_date(
     State(state): State<AppState>,
     Path(date): Path<String>,
-) -> Result<Json<StoredPoem>, (StatusCode, Json<ErrorResponse>)> {
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if let Some((date, format)) = export::split_export_path(&date) {
+        return export_poem(&state, &headers, date, format).await;
+    }
+
     match state.db.get_poem_by_date(&date).await {
-        Ok(Some(poem)) => Ok(Json(poem)),
+        Ok(Some(poem)) => {
+            let max_age = poem_max_age(&state.db, &poem.date);
+            let engaged = engage_poem(&state, poem.clone()).await?;
+            poems_json(&headers, &poem, &engaged, max_age)
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(ErrorResponse {
                 error: format!("No poem found for date: {}", date),
             }),
         )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/:date.{txt,md,png} - The poem with its keywords, ready to share
+async fn export_poem(
+    state: &AppState,
+    headers: &HeaderMap,
+    date: &str,
+    format: ExportFormat,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let poem = state.db.get_poem_by_date(date).await.map_err(failure)?.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
             Json(ErrorResponse {
-                error: e.to_string(),
+                error: format!("No poem found for date: {}", date),
             }),
+        )
+    })?;
+
+    // The poem row (keyword ids included) decides the rendering, so a client
+    // with a current copy is answered before the PNG is drawn
+    let row = serde_json::to_vec(&poem).map_err(failure)?;
+    let etag = http_cache::etag(&[&row, format.content_type().as_bytes()]);
+    let mut keywords = state.db.get_keywords_for_date(date).await.map_err(failure)?;
+    keywords.retain(|k| poem.keyword_ids.contains(&k.id));
+
+    let max_age = poem_max_age(&state.db, &poem.date);
+    let response = http_cache::respond_async(headers, &etag, max_age, || async move {
+        format
+            .render_blocking(poem, keywords)
+            .await
+            .map(|body| ([(header::CONTENT_TYPE, format.content_type())], body))
+            .map_err(failure)
+    })
+    .await;
+    Ok(response)
+}
+
+/// GET /api/poems/:date/metadata.json - Metaplex NFT metadata for a poem
+async fn get_poem_metadata(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let poem = state.db.get_poem_by_date(&date).await.map_err(failure)?.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("No poem found for date: {}", date),
+            }),
+        )
+    })?;
+
+    let mut keywords = state.db.get_keywords_for_date(&date).await.map_err(failure)?;
+    keywords.retain(|k| poem.keyword_ids.contains(&k.id));
+
+    let base_url = std::env::var("PUBLIC_BASE_URL").unwrap_or_else(|_| {
+        let host = headers
+            .get(header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("localhost");
+        format!("https://{}", host)
+    });
+
+    Ok(Json(minting::poem_metadata(&poem, &keywords, &base_url)))
+}
+
+/// GET /api/poems/:date/provenance - Reproducibility manifest recorded with the
+/// poem, plus curators' notes on its keywords
+async fn get_poem_provenance(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+) -> Result<Json<AnnotatedProvenance>, (StatusCode, Json<ErrorResponse>)> {
+    let provenance = match state.db.get_poem_provenance(&date).await {
+        Ok(Some(provenance)) => provenance,
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("No provenance recorded for {}", date),
+            ));
+        }
+        Err(e) => return Err(failure(e)),
+    };
+
+    match state.db.get_keyword_annotations(&date).await {
+        Ok(annotations) => Ok(Json(AnnotatedProvenance {
+            provenance,
+            annotations,
+        })),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/:date/similar - Poems closest in theme to the day's poem, by
+/// embedding similarity
+async fn get_similar_poems(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    Query(query): Query<SimilarPoemsQuery>,
+) -> Result<Json<Vec<SimilarPoem>>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT);
+
+    match state.db.get_poem_by_date(&date).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("No poem found for date: {}", date),
+            ))
+        }
+        Err(e) => return Err(failure(e)),
+    }
+
+    match state.db.get_similar_poems(&date, limit as usize).await {
+        Ok(Some(similar)) => Ok(Json(similar)),
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("The poem for {} has not been embedded yet", date),
         )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/:date/audio - The poem read aloud, as MP3 or Ogg Opus
+async fn get_poem_audio(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let audio = match state.db.get_poem_audio(&date).await.map_err(failure)? {
+        Some(audio) => audio,
+        None => {
+            let message = match state.db.get_poem_by_date(&date).await.map_err(failure)? {
+                Some(_) => format!("The poem for {} has not been read aloud yet", date),
+                None => format!("No poem found for date: {}", date),
+            };
+            return Err(error_response(StatusCode::NOT_FOUND, message));
+        }
+    };
+    let content_type = audio
+        .format
+        .parse::<AudioFormat>()
+        .map_err(failure)?
+        .content_type();
+
+    let body = tokio::fs::read(&audio.path).await.map_err(|e| {
+        warn!(date = %date, path = %audio.path, error = %e, "Poem audio file is missing");
+        error_response(
+            StatusCode::NOT_FOUND,
+            format!("The recording of the poem for {} is missing", date),
+        )
+    })?;
+
+    // A new recording gets a new row, so the row identifies the file's contents
+    let etag = http_cache::etag(&[audio.path.as_bytes(), audio.created_at.as_bytes()]);
+    Ok(http_cache::respond(
+        &headers,
+        &etag,
+        poem_max_age(&state.db, &date),
+        || ([(header::CONTENT_TYPE, content_type)], body),
+    ))
+}
+
+/// GET /api/poems/week/:week - The daily poems of an ISO week (`2026-W02`), oldest first
+async fn get_poems_for_week(
+    State(state): State<AppState>,
+    Path(week): Path<String>,
+) -> Result<Json<WeekPoems>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(period) = Period::parse(&week).filter(|p| p.kind == CollectionKind::Week) else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid week (expected YYYY-Www): {}", week),
+        ));
+    };
+
+    let (start_date, end_date) = (period.start_date(), period.end_date());
+    match state.db.get_poems_between(&start_date, &end_date).await {
+        Ok(poems) => Ok(Json(WeekPoems {
+            week: period.key(),
+            start_date,
+            end_date,
+            poems: engage_poems(&state, poems).await?,
+        })),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/epoch/:epoch - The daily poems using keywords from a Solana epoch,
+/// oldest first
+async fn get_poems_for_epoch(
+    State(state): State<AppState>,
+    Path(epoch): Path<String>,
+) -> Result<Json<EpochPoems>, (StatusCode, Json<ErrorResponse>)> {
+    let Ok(epoch) = epoch.parse::<u64>() else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid epoch: {}", epoch),
+        ));
+    };
+
+    match state.db.get_poems_for_epoch(epoch).await {
+        Ok(poems) => Ok(Json(EpochPoems {
+            epoch,
+            poems: engage_poems(&state, poems).await?,
+        })),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/poems/hash/:hash - The poem with a content hash, including an
+/// earlier version of one since regenerated
+async fn get_poem_by_hash(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid poem hash: {}", hash),
+        ));
+    }
+
+    match state.db.get_poem_by_hash(&hash.to_ascii_lowercase()).await {
+        Ok(Some(poem)) => {
+            let max_age = poem_max_age(&state.db, &poem.date);
+            let engaged = engage_poem(&state, poem.clone()).await?;
+            poems_json(&headers, &poem, &engaged, max_age)
+        }
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No poem found with hash: {}", hash),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// POST /api/poems/:date/react - Leave an emoji reaction on a poem, answered
+/// with its updated counts
+async fn react_to_poem(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    Json(request): Json<ReactionRequest>,
+) -> Result<Json<PoemEngagement>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_writable(&state)?;
+    let Some(emoji) = engagement::reaction(&request.emoji) else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Unknown reaction `{}`; use one of {}",
+                request.emoji,
+                POEM_REACTIONS.join(" ")
+            ),
+        ));
+    };
+    match state.db.get_poem_by_date(&date).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("No poem found for date: {}", date),
+            ))
+        }
+        Err(e) => return Err(failure(e)),
+    }
+
+    if let Err(e) = state.db.add_poem_reaction(&date, emoji).await {
+        return Err(failure(e));
+    }
+    match state.db.get_poem_engagement(&date).await {
+        Ok(engagement) => Ok(Json(engagement)),
+        Err(e) => Err(failure(e)),
     }
 }
 
 /// GET /api/keywords/today - Get today's keywords
 async fn get_today_keywords(
     State(state): State<AppState>,
-) -> Result<Json<Vec<StoredKeyword>>, (StatusCode, Json<ErrorResponse>)> {
-    let today = Database::today();
+) -> Result<Json<Vec<LinkedKeyword>>, (StatusCode, Json<ErrorResponse>)> {
+    let today = state.db.today();
 
     match state.db.get_keywords_for_date(&today).await {
-        Ok(keywords) => Ok(Json(keywords)),
+        Ok(keywords) => Ok(Json(link_keywords(&state.db, keywords).await?)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/keywords/:date - Get the keywords collected on a specific date
+async fn get_keywords_by_date(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+) -> Result<Json<Vec<LinkedKeyword>>, (StatusCode, Json<ErrorResponse>)> {
+    if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Invalid date (expected YYYY-MM-DD): {}", date),
+            }),
+        ));
+    }
+
+    match state.db.get_keywords_for_date(&date).await {
+        Ok(keywords) => Ok(Json(link_keywords(&state.db, keywords).await?)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// Attach explorer links, stored definitions, and rarity to keywords for a response
+async fn link_keywords(
+    db: &Database,
+    keywords: Vec<StoredKeyword>,
+) -> Result<Vec<LinkedKeyword>, (StatusCode, Json<ErrorResponse>)> {
+    let words: Vec<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+    let definitions = db.get_definitions(&words).await.map_err(failure)?;
+    let rarity = db.get_keyword_rarity(&keywords).await.map_err(failure)?;
+    Ok(explorer::link_keywords(keywords, &definitions, &rarity))
+}
+
+/// Attach each poem's views (including those not written yet) and reactions
+/// for a response
+async fn engage_poems(
+    state: &AppState,
+    poems: Vec<StoredPoem>,
+) -> Result<Vec<EngagedPoem>, (StatusCode, Json<ErrorResponse>)> {
+    let dates: Vec<&str> = poems.iter().map(|poem| poem.date.as_str()).collect();
+    let engagement = state
+        .db
+        .get_poems_engagement(&dates)
+        .await
+        .map_err(failure)?;
+    let mut poems = engagement::engage_poems(poems, &engagement);
+    for poem in &mut poems {
+        poem.engagement.views += state.views.pending(&poem.poem.date);
+    }
+    Ok(poems)
+}
+
+/// Attach a poem's views (including those not written yet) and reactions for
+/// a response
+async fn engage_poem(
+    state: &AppState,
+    poem: StoredPoem,
+) -> Result<EngagedPoem, (StatusCode, Json<ErrorResponse>)> {
+    let mut engagement = state
+        .db
+        .get_poem_engagement(&poem.date)
+        .await
+        .map_err(failure)?;
+    engagement.views += state.views.pending(&poem.date);
+    Ok(EngagedPoem { poem, engagement })
+}
+
+/// GET /api/blocks/:slot - A block refetched from RPC, the keyword it yielded, and explorer links
+///
+/// When RPC fails, a slot with stored keywords is still answered from those
+/// keywords; `block` is then null.
+async fn get_block(
+    State(state): State<AppState>,
+    Path(slot): Path<String>,
+) -> Result<Json<BlockDetail>, (StatusCode, Json<ErrorResponse>)> {
+    let Ok(slot) = slot.parse::<u64>() else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid slot: {}", slot),
+        ));
+    };
+
+    let keywords = state
+        .db
+        .get_keywords_by_slot(slot as i64)
+        .await
+        .map_err(failure)?;
+
+    let block = match state.blockchain.as_ref() {
+        Some(client) => match client.get_block(slot).await {
+            Ok(block) => Some(block),
+            Err(e) if !keywords.is_empty() => {
+                warn!(slot, error = %e, "Failed to refetch block; serving the stored keywords");
+                None
+            }
+            Err(e) => return Err(failure(e)),
+        },
+        None => None,
+    };
+
+    if block.is_none() && keywords.is_empty() {
+        return Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No block data for slot {}", slot),
+        ));
+    }
+
+    Ok(Json(BlockDetail {
+        slot,
+        block,
+        keywords,
+        explorer: ExplorerLinks::for_slot(slot),
+    }))
+}
+
+/// GET /api/export/keywords.csv - Every keyword with its slot, blockhash, source,
+/// and date, streamed as CSV
+async fn export_keywords_csv(State(state): State<AppState>) -> Response {
+    let rows = dataset::csv_stream(Database::clone(&state.db)).map(|page| {
+        if let Err(e) = &page {
+            warn!(error = %e, "Keyword export failed partway");
+        }
+        page
+    });
+    (
+        [
+            (header::CONTENT_TYPE, dataset::CSV_CONTENT_TYPE),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"keywords.csv\"",
+            ),
+        ],
+        Body::from_stream(rows),
+    )
+        .into_response()
+}
+
+/// GET /api/collections - Every weekly and monthly meta-poem, latest period first
+async fn get_collections(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<StoredCollection>>, (StatusCode, Json<ErrorResponse>)> {
+    match state.db.get_poem_collections().await {
+        Ok(collections) => Ok(Json(collections)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/collections/:period - The meta-poem for a week (`2026-W02`) or month (`2026-01`)
+async fn get_collection(
+    State(state): State<AppState>,
+    Path(period): Path<String>,
+) -> Result<Json<StoredCollection>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(parsed) = Period::parse(&period) else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid period (expected YYYY-Www or YYYY-MM): {}", period),
+        ));
+    };
+
+    match state.db.get_poem_collection(&parsed.key()).await {
+        Ok(Some(collection)) => Ok(Json(collection)),
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No meta-poem for {}", period),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/keywords/search?word=moon - Every occurrence of a word and the poems it appeared in
+async fn search_keywords(
+    State(state): State<AppState>,
+    Query(query): Query<KeywordSearchQuery>,
+) -> Result<Json<KeywordSearchResult>, (StatusCode, Json<ErrorResponse>)> {
+    let word = query.word.trim().to_string();
+    if word.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Query parameter `word` must not be empty".to_string(),
+            }),
+        ));
+    }
+
+    let definitions = state
+        .db
+        .get_definitions(&[word.as_str()])
+        .await
+        .map_err(failure)?;
+    let occurrences = state
+        .db
+        .search_keyword_occurrences(&word)
+        .await
+        .map_err(failure)?;
+    let found: Vec<StoredKeyword> = occurrences.iter().map(|o| o.keyword.clone()).collect();
+    let rarity = state.db.get_keyword_rarity(&found).await.map_err(failure)?;
+    Ok(Json(KeywordSearchResult {
+        occurrences: occurrences.len(),
+        word,
+        keywords: occurrences
+            .into_iter()
+            .map(|occurrence| LinkedOccurrence::new(occurrence, &definitions, &rarity))
+            .collect(),
+    }))
+}
+
+/// GET /api/events - Server-Sent Events stream of collector progress
+///
+/// Each event's name is its `type`; a `lagged` event carries how many events a
+/// slow client missed. The stream ends when the server shuts down, so it
+/// doesn't hold up graceful shutdown.
+async fn stream_events(
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    let Some(events) = state.events.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Collector events are not enabled on this server".to_string(),
+            }),
+        ));
+    };
+
+    let stream = BroadcastStream::new(events.subscribe()).map(|event| match event {
+        Ok(event) => Event::default().event(event.name()).json_data(&event),
+        Err(BroadcastStreamRecvError::Lagged(missed)) => {
+            Ok(Event::default().event("lagged").data(missed.to_string()))
+        }
+    });
+    let mut shutdown = state.shutdown.clone();
+    let stream = futures::StreamExt::take_until(stream, async move {
+        match shutdown.as_mut() {
+            Some(shutdown) => {
+                let _ = shutdown.wait_for(|&stop| stop).await;
+            }
+            None => std::future::pending().await,
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// GET /api/archive - Poem dates grouped by month, for calendar navigation
+async fn get_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    match stats::archive(&state.db).await {
+        Ok(archive) => cached_json(&headers, &archive, RECENT_CACHE_MAX_AGE_SECS),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/openapi.json - OpenAPI 3 description of this API
+async fn get_openapi(headers: HeaderMap) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    cached_json(&headers, &openapi::document(), RECENT_CACHE_MAX_AGE_SECS)
+}
+
+/// GET /api/docs - Swagger UI over /api/openapi.json
+async fn get_docs() -> Html<String> {
+    Html(openapi::swagger_ui())
+}
+
+/// GET /api/stats - Corpus analytics for the about page
+async fn get_stats(
+    State(state): State<AppState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<CorpusStats>, (StatusCode, Json<ErrorResponse>)> {
+    let top = query
+        .top
+        .unwrap_or(DEFAULT_STATS_TOP_WORDS)
+        .clamp(1, MAX_STATS_TOP_WORDS);
+
+    match stats::corpus_stats(&state.db, top).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/stats/cooccurrence - Word pairs written into the same poem, with counts
+async fn get_cooccurrence(
+    State(state): State<AppState>,
+    Query(query): Query<CooccurrenceQuery>,
+) -> Result<Json<Vec<WordCooccurrence>>, (StatusCode, Json<ErrorResponse>)> {
+    let min_count = query.min_count.unwrap_or(1).max(1);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_COOCCURRENCE_LIMIT)
+        .clamp(1, MAX_COOCCURRENCE_LIMIT);
+
+    match state.db.get_word_cooccurrences(min_count, limit).await {
+        Ok(pairs) => Ok(Json(pairs)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// GET /api/stats/milestones - Streaks, totals, and achieved/upcoming milestones
+async fn get_milestones(
+    State(state): State<AppState>,
+) -> Result<Json<MilestoneSummary>, (StatusCode, Json<ErrorResponse>)> {
+    match milestones::summarize(&state.db, &state.db.today()).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// POST /api/preview/:date - Unofficial poem for a date before the project started
+async fn create_preview(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+) -> Result<Json<PreviewPoem>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(preview) = state.preview.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Previews are not enabled on this server".to_string(),
+            }),
+        ));
+    };
+
+    match preview.preview(&date).await {
+        Ok(poem) => Ok(Json(poem)),
+        Err(e) => Err(preview_error(e)),
+    }
+}
+
+/// POST /api/derive - The word an arbitrary blockhash or signature derives,
+/// with every step of the derivation
+async fn derive_word(
+    State(state): State<AppState>,
+    Json(request): Json<DeriveRequest>,
+) -> Result<Json<DerivationTrace>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(derivation) = state.derivation.as_ref() else {
+        return Err(error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Derivation is not enabled on this server",
+        ));
+    };
+    let input = request.input.trim();
+    if input.is_empty() || input.len() > MAX_DERIVE_INPUT_LEN {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("input must be 1 to {} bytes", MAX_DERIVE_INPUT_LEN),
+        ));
+    }
+
+    derivation.trace(input).map(Json).map_err(failure)
+}
+
+/// POST /api/poems/custom - Unofficial poem for a slot or timestamp range
+async fn create_custom_poem(
+    State(state): State<AppState>,
+    Json(request): Json<CustomRangeRequest>,
+) -> Result<Json<CustomPoem>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(preview) = state.preview.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "On-demand poems are not enabled on this server".to_string(),
+            }),
+        ));
+    };
+    if request.persist {
+        ensure_writable(&state)?;
+    }
+
+    match preview.custom(&request).await {
+        Ok(poem) => Ok(Json(poem)),
+        Err(e) => Err(preview_error(e)),
+    }
+}
+
+/// GET /api/poems/custom/:id - A previously persisted custom range poem
+async fn get_custom_poem(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<StoredCustomPoem>, (StatusCode, Json<ErrorResponse>)> {
+    match state.db.get_custom_poem(id).await {
+        Ok(Some(poem)) => Ok(Json(poem)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("No custom poem found with id: {}", id),
+            }),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// Map a preview failure to the HTTP status that describes it
+fn preview_error(e: PreviewError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        PreviewError::InvalidDate(_) | PreviewError::InvalidRange(_) => StatusCode::BAD_REQUEST,
+        PreviewError::OfficialRange(_) => StatusCode::CONFLICT,
+        PreviewError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        PreviewError::Failed(ref error) => error_status(error),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+/// POST /api/admin/dictionary/reload - Re-read the word dictionary from disk
+async fn reload_dictionary(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(dictionary) = state.dictionary.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "No dictionary is attached to this server".to_string(),
+            }),
+        ));
+    };
+
+    match dictionary.reload() {
+        Ok(words) => {
+            info!(words, "Reloaded word dictionary via admin API");
+            Ok(Json(serde_json::json!({
+                "status": "reloaded",
+                "words": words,
+                "path": dictionary.path().map(|p| p.display().to_string()),
+            })))
+        }
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::UNPROCESSABLE_ENTITY,
             Json(ErrorResponse {
-                error: e.to_string(),
+                error: format!("{:#}", e),
             }),
         )),
     }
 }
 
-pub async fn serve(db: Database, port: u16) -> anyhow::Result<()> {
-    let app = create_router(db);
+/// POST /api/admin/collect-now - Run a keyword collection now, off the schedule
+async fn collect_now(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    send_command(&state, CollectorCommand::CollectNow)
+}
+
+/// POST /api/admin/pause - Skip collections until resumed
+async fn pause_collector(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    send_command(&state, CollectorCommand::Pause)
+}
+
+/// POST /api/admin/resume - Collect on schedule again after a pause
+async fn resume_collector(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    send_command(&state, CollectorCommand::Resume)
+}
+
+/// Queue `command` for the in-process collector; 202, since it runs after
+/// whatever the collector is doing now
+fn send_command(
+    state: &AppState,
+    command: CollectorCommand,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    let Some(control) = state.control.as_ref() else {
+        return Err(error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "No collector runs in this process",
+        ));
+    };
+    control
+        .send(command)
+        .map_err(|e| error_response(StatusCode::SERVICE_UNAVAILABLE, e.to_string()))?;
+    info!(
+        command = command.name(),
+        "Sent collector command via admin API"
+    );
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({ "status": "accepted", "command": command })),
+    ))
+}
+
+/// POST /api/admin/keywords - Add a curator-chosen word to a date before its poem exists
+async fn add_keyword(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+    Json(request): Json<AddKeywordRequest>,
+) -> Result<(StatusCode, Json<StoredKeyword>), (StatusCode, Json<ErrorResponse>)> {
+    ensure_writable(&state)?;
+    if chrono::NaiveDate::parse_from_str(&request.date, "%Y-%m-%d").is_err() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid date (expected YYYY-MM-DD): {}", request.date),
+        ));
+    }
+    let word = request.word.trim().to_lowercase();
+    if word.is_empty() || word.contains(char::is_whitespace) {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "word must be a single non-empty word",
+        ));
+    }
+    ensure_no_poem(&state, &request.date).await?;
+
+    let curator = curator(&headers, &caller);
+    match state
+        .db
+        .add_manual_keyword(&request.date, &word, &curator, request.reason.as_deref())
+        .await
+    {
+        Ok(keyword) => {
+            info!(date = %request.date, %word, %curator, "Curator added keyword");
+            Ok((StatusCode::CREATED, Json(keyword)))
+        }
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// DELETE /api/admin/keywords/{id} - Pull a keyword before its day's poem is generated
+async fn remove_keyword(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    Query(query): Query<RemoveKeywordQuery>,
+) -> Result<Json<StoredKeyword>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_writable(&state)?;
+    let keyword = match state.db.get_keyword(id).await {
+        Ok(Some(keyword)) => keyword,
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                format!("No keyword with id {}", id),
+            ));
+        }
+        Err(e) => return Err(failure(e)),
+    };
+    ensure_no_poem(&state, &state.db.keyword_date(&keyword)).await?;
+
+    let curator = curator(&headers, &caller);
+    match state.db.remove_keyword(id, &curator, query.reason.as_deref()).await {
+        Ok(Some(keyword)) => {
+            info!(id, word = %keyword.word, %curator, "Curator removed keyword");
+            Ok(Json(keyword))
+        }
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No keyword with id {}", id),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// POST /api/admin/keywords/{id}/annotations - Attach a note to a keyword, shown
+/// with its poem's provenance; allowed after the poem is generated
+async fn add_annotation(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    Json(request): Json<AnnotationRequest>,
+) -> Result<(StatusCode, Json<KeywordAnnotation>), (StatusCode, Json<ErrorResponse>)> {
+    ensure_writable(&state)?;
+    let note = request.note.trim();
+    if note.is_empty() || note.chars().count() > MAX_ANNOTATION_CHARS {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("note must be 1 to {} characters", MAX_ANNOTATION_CHARS),
+        ));
+    }
+
+    let curator = curator(&headers, &caller);
+    match state.db.add_keyword_annotation(id, note, &curator).await {
+        Ok(Some(annotation)) => {
+            info!(keyword_id = id, word = %annotation.word, %curator, "Curator annotated keyword");
+            Ok((StatusCode::CREATED, Json(annotation)))
+        }
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No keyword with id {}", id),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// DELETE /api/admin/annotations/{id} - Remove a note from a keyword
+async fn remove_annotation(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Result<Json<KeywordAnnotation>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_writable(&state)?;
+    match state.db.remove_keyword_annotation(id).await {
+        Ok(Some(annotation)) => {
+            let curator = curator(&headers, &caller);
+            info!(id, word = %annotation.word, %curator, "Curator removed annotation");
+            Ok(Json(annotation))
+        }
+        Ok(None) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            format!("No annotation with id {}", id),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// Writes go to the instance running the collector, not a read-only replica
+fn ensure_writable(state: &AppState) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if state.db.is_read_only() {
+        return Err(error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "This server is a read-only replica; send writes to the instance running the collector",
+        ));
+    }
+    Ok(())
+}
+
+/// Keyword edits only make sense before the day's poem is written from them
+async fn ensure_no_poem(
+    state: &AppState,
+    date: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match state.db.get_poem_by_date(date).await {
+        Ok(None) => Ok(()),
+        Ok(Some(_)) => Err(error_response(
+            StatusCode::CONFLICT,
+            format!("The poem for {} has already been generated", date),
+        )),
+        Err(e) => Err(failure(e)),
+    }
+}
+
+/// Who is making an admin change, from the `X-Curator` header or else the
+/// API key's name (recorded in the audit log)
+fn curator(headers: &HeaderMap, caller: &Caller) -> String {
+    headers
+        .get("x-curator")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(&caller.name)
+        .to_string()
+}
+
+/// GET /api/admin/audit/{date} - Curator keyword edits for a date, oldest first
+async fn get_keyword_audit(
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+) -> Result<Json<Vec<KeywordAuditEntry>>, (StatusCode, Json<ErrorResponse>)> {
+    if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid date (expected YYYY-MM-DD): {}", date),
+        ));
+    }
+    let entries = state.db.get_keyword_audit(&date).await.map_err(failure)?;
+    Ok(Json(entries))
+}
+
+/// GET /api/admin/usage - Recent requests made with API keys, newest first
+async fn get_api_usage(
+    State(state): State<AppState>,
+    Query(query): Query<ApiUsageQuery>,
+) -> Result<Json<Vec<ApiKeyUsage>>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_API_USAGE_LIMIT)
+        .clamp(1, MAX_API_USAGE_LIMIT);
+    let usage = state
+        .db
+        .get_api_key_usage(query.key.as_deref(), limit)
+        .await
+        .map_err(failure)?;
+    Ok(Json(usage))
+}
+
+/// Serve `value` as JSON with an ETag over its serialized form, answering 304
+/// when the client's copy is current (see `http_cache::respond`)
+fn cached_json<T: Serialize>(
+    headers: &HeaderMap,
+    value: &T,
+    max_age_secs: u64,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let body = serde_json::to_vec(value)
+        .map_err(failure)?;
+    let etag = http_cache::etag(&[&body]);
+    Ok(http_cache::respond(headers, &etag, max_age_secs, || {
+        ([(header::CONTENT_TYPE, "application/json")], body)
+    }))
+}
+
+/// Serve `value` as JSON like `cached_json`, but with the ETag over `rows`,
+/// the stored poems it was built from
+///
+/// Views and reactions change with every read, so an ETag over them would
+/// never let a client revalidate; they're only as fresh as max-age.
+fn poems_json<R: Serialize, T: Serialize>(
+    headers: &HeaderMap,
+    rows: &R,
+    value: &T,
+    max_age_secs: u64,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let etag = http_cache::etag(&[&serde_json::to_vec(rows).map_err(failure)?]);
+    let body = serde_json::to_vec(value).map_err(failure)?;
+    Ok(http_cache::respond(headers, &etag, max_age_secs, || {
+        ([(header::CONTENT_TYPE, "application/json")], body)
+    }))
+}
+
+/// Past days' poems rarely change (an anchor or regeneration still updates the
+/// ETag); today's may still be anchored or regenerated within minutes
+fn poem_max_age(db: &Database, date: &str) -> u64 {
+    if date < db.today().as_str() {
+        HISTORICAL_POEM_MAX_AGE_SECS
+    } else {
+        RECENT_CACHE_MAX_AGE_SECS
+    }
+}
+
+fn error_response(
+    status: StatusCode,
+    error: impl Into<String>,
+) -> (StatusCode, Json<ErrorResponse>) {
+    (status, Json(ErrorResponse { error: error.into() }))
+}
+
+/// Error response for a failed operation, with the status its error class calls for
+fn failure(error: impl Into<anyhow::Error>) -> (StatusCode, Json<ErrorResponse>) {
+    let error = error.into();
+    error_response(error_status(&error), error.to_string())
+}
+
+/// Status for a failure, by the `ChainVerseError` behind it
+///
+/// Solana RPC and OpenRouter failures are 502; while their circuit breaker is
+/// open, or while the database is too busy to answer, 503 tells clients to
+/// come back later. Anything else is our own fault: 500.
+fn error_status(error: &anyhow::Error) -> StatusCode {
+    let Some(error) = error.downcast_ref::<ChainVerseError>() else {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    };
+    if error.is_circuit_open() || error.is_database_busy() {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+    match error {
+        ChainVerseError::Rpc(_) | ChainVerseError::LlmProvider(_) => StatusCode::BAD_GATEWAY,
+        ChainVerseError::Database(_)
+        | ChainVerseError::Derivation(_)
+        | ChainVerseError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Serve the API until `shutdown` flips to true, then drain connections
+///
+/// The database pool is left open: the caller may share it with the collector.
+pub async fn serve(
+    mut state: AppState,
+    port: u16,
+    mut shutdown: tokio::sync::watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    state.shutdown = Some(shutdown.clone());
+    if let Some(dir) = &state.frontend_dir {
+        info!(dir = %dir.display(), "Serving the frontend at /");
+    }
+    let views = state.views.clone();
+    let view_shutdown = shutdown.clone();
+    let flush_views = tokio::spawn(async move { views.run(view_shutdown).await });
+    let app = create_router(state);
 
     let addr = format!("0.0.0.0:{}", port);
-    println!("🌐 API server listening on http://{}", addr);
+    info!("API server listening on http://{}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.wait_for(|&stop| stop).await;
+            info!("API server shutting down");
+        })
+        .await?;
 
+    // The last views counted are written once requests have drained
+    flush_views.await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::CircuitOpen;
+    use std::time::Duration;
+
+    #[test]
+    fn test_failure_status() {
+        let status = |error: ChainVerseError| failure(error).0;
+        let open = || {
+            anyhow::Error::from(CircuitOpen {
+                name: "openrouter",
+                retry_in: Duration::from_secs(30),
+            })
+        };
+
+        assert_eq!(
+            status(ChainVerseError::Rpc(anyhow::anyhow!("connection refused"))),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            status(ChainVerseError::LlmProvider(anyhow::anyhow!("timed out"))),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            status(ChainVerseError::Rpc(open())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(ChainVerseError::LlmProvider(open())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(sqlx::Error::PoolTimedOut.into()),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(sqlx::Error::RowNotFound.into()),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status(ChainVerseError::Derivation("no keyword".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        // Context added on the way up doesn't hide the class
+        let wrapped =
+            anyhow::Error::from(ChainVerseError::LlmProvider(anyhow::anyhow!("timed out")))
+                .context("Failed to generate poem");
+        assert_eq!(failure(wrapped).0, StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            failure(anyhow::anyhow!("unclassified")).0,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[cfg(feature = "frontend")]
+    #[tokio::test]
+    async fn test_serve_frontend() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let dir = std::env::temp_dir().join(format!("chain_verse_dist_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("index.html"), "<div id=\"root\"></div>").unwrap();
+        std::fs::write(dir.join("assets/app.js"), "render()").unwrap();
+
+        let (path, url) = crate::database::temp_database_url("frontend");
+        let mut state = AppState::new(Database::new(&url).await.unwrap());
+        state.frontend_dir = Some(dir.clone());
+        let router = create_router(state);
+
+        let get = |uri: &'static str| {
+            let router = router.clone();
+            async move {
+                let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+                let response = router.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        // Client-side routes get index.html; files are served as they are
+        assert_eq!(
+            get("/archive/2026-01-05").await,
+            (StatusCode::OK, "<div id=\"root\"></div>".to_string())
+        );
+        assert_eq!(
+            get("/assets/app.js").await,
+            (StatusCode::OK, "render()".to_string())
+        );
+        assert_eq!(get("/api/nope").await.0, StatusCode::NOT_FOUND);
+        assert_eq!(get("/api/poems").await.0, StatusCode::OK);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(&path);
+    }
+}
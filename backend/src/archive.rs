@@ -0,0 +1,297 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::database::{Database, StoredCollection, StoredKeyword, StoredPoem};
+use crate::provenance::PoemProvenance;
+
+/// Bumped when the JSON archive layout changes incompatibly
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// File format for `chain-verse export` and `import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// One JSON document with every keyword, poem, manifest, and meta-poem
+    Json,
+    /// A copy of the SQLite database, schema and all; importing one restores
+    /// every table, not just the JSON archive's
+    Sqlite,
+}
+
+/// The whole poem archive, as written by `chain-verse export --format json`
+///
+/// Keyword ids are kept, so poems' `keyword_ids` stay valid after an import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Archive {
+    pub version: u32,
+    pub exported_at: String,
+    pub keywords: Vec<StoredKeyword>,
+    pub poems: Vec<StoredPoem>,
+    pub provenance: Vec<PoemProvenance>,
+    #[serde(default)]
+    pub collections: Vec<StoredCollection>,
+}
+
+/// Rows written by an export or restored by an import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub keywords: usize,
+    pub poems: usize,
+    pub provenance: usize,
+    pub collections: usize,
+}
+
+impl Archive {
+    /// Read every keyword, poem, provenance manifest, and meta-poem, oldest first
+    pub async fn read(db: &Database) -> Result<Self> {
+        let mut poems = db.get_all_poems().await?;
+        poems.reverse();
+        let mut collections = db.get_poem_collections().await?;
+        collections.reverse();
+
+        Ok(Self {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now().to_rfc3339(),
+            keywords: db.get_all_keywords().await?,
+            poems,
+            provenance: db.get_all_poem_provenance().await?,
+            collections,
+        })
+    }
+
+    pub fn summary(&self) -> ArchiveSummary {
+        ArchiveSummary {
+            keywords: self.keywords.len(),
+            poems: self.poems.len(),
+            provenance: self.provenance.len(),
+            collections: self.collections.len(),
+        }
+    }
+}
+
+/// Write the archive to `out`, replacing the file if it exists
+pub async fn export(db: &Database, format: ArchiveFormat, out: &Path) -> Result<ArchiveSummary> {
+    let archive = Archive::read(db).await?;
+
+    match format {
+        ArchiveFormat::Json => {
+            let json = serde_json::to_vec_pretty(&archive)?;
+            fs::write(out, json).with_context(|| format!("Failed to write {}", out.display()))?;
+        }
+        ArchiveFormat::Sqlite => {
+            // VACUUM INTO refuses to overwrite a non-empty file
+            if out.exists() {
+                fs::remove_file(out)
+                    .with_context(|| format!("Failed to replace {}", out.display()))?;
+            }
+            db.vacuum_into(out).await?;
+        }
+    }
+
+    Ok(archive.summary())
+}
+
+/// Restore an archive written by `export` into an empty database
+///
+/// A SQLite dump is copied aside and migrated to the current schema before
+/// its tables are copied in, so dumps from older versions import too and the
+/// file itself is left untouched.
+pub async fn import(db: &Database, format: ArchiveFormat, path: &Path) -> Result<ArchiveSummary> {
+    let archive = match format {
+        ArchiveFormat::Json => {
+            let json =
+                fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let archive: Archive = serde_json::from_slice(&json)
+                .with_context(|| format!("Invalid archive {}", path.display()))?;
+            if archive.version > ARCHIVE_VERSION {
+                anyhow::bail!(
+                    "Archive version {} is newer than this binary supports ({})",
+                    archive.version,
+                    ARCHIVE_VERSION
+                );
+            }
+            archive
+        }
+        ArchiveFormat::Sqlite => {
+            let copy =
+                std::env::temp_dir().join(format!("chain_verse_import_{}.db", std::process::id()));
+            fs::copy(path, &copy).with_context(|| format!("Failed to read {}", path.display()))?;
+            let result = import_dump(db, &copy).await;
+            let _ = fs::remove_file(&copy);
+            return result;
+        }
+    };
+
+    db.import_archive(&archive).await?;
+    Ok(archive.summary())
+}
+
+/// Migrate the dump at `path`, then copy all of its tables into `db`
+async fn import_dump(db: &Database, path: &Path) -> Result<ArchiveSummary> {
+    let dump = Database::new(&format!("sqlite:{}", path.display())).await?;
+    let summary = Archive::read(&dump).await.map(|archive| archive.summary());
+    dump.close().await;
+    let summary = summary?;
+
+    db.import_database(path).await?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::Period;
+    use crate::consts::BlockDataSource;
+    use crate::derivation::DerivedKeyword;
+    use crate::provenance::{DerivationProvenance, LlmProvenance};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "chain_verse_archive_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    async fn temp_database(name: &str) -> (PathBuf, Database) {
+        let path = temp_path(&format!("{}.db", name));
+        let db = Database::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        (path, db)
+    }
+
+    async fn seed(db: &Database) {
+        let keyword = DerivedKeyword {
+            word: "ember".to_string(),
+            slot: 250_000_000,
+            blockhash: "hash".to_string(),
+            previous_blockhash: "parent".to_string(),
+            block_time: Some(1_700_000_000),
+            word_index: 7,
+            source: BlockDataSource::Blockhash,
+            theme: None,
+            rerolls: 1,
+            epoch: None,
+        };
+        let id = db
+            .insert_keyword_with_date(&keyword, "2026-01-05")
+            .await
+            .unwrap()
+            .unwrap();
+        db.add_manual_keyword("2026-01-05", "river", "alice", None)
+            .await
+            .unwrap();
+        db.insert_poem("2026-01-05", Some("Ember"), "A poem", &[id])
+            .await
+            .unwrap();
+
+        let keywords = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        db.insert_poem_provenance(&PoemProvenance::new(
+            "2026-01-05",
+            DerivationProvenance {
+                version: 1,
+                dictionary_sha256: "dict".to_string(),
+                theme: None,
+                language: None,
+                blocklist_sha256: None,
+            },
+            &keywords,
+            LlmProvenance {
+                model: "model".to_string(),
+                sampling: Default::default(),
+                form: "haiku".to_string(),
+                prompt_sha256: "prompt".to_string(),
+                system_prompt_sha256: None,
+                mood: None,
+                length: None,
+            },
+        ))
+        .await
+        .unwrap();
+
+        let week = Period::parse("2026-W02").unwrap();
+        db.insert_poem_collection(&week, None, "A week", &["2026-01-05".to_string()])
+            .await
+            .unwrap();
+
+        // Only a SQLite dump carries these
+        db.add_keyword_annotation(id, "A bright one", "alice")
+            .await
+            .unwrap();
+        db.add_poem_views("2026-01-05", 3).await.unwrap();
+        db.add_poem_reaction("2026-01-05", "🔥").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let (source_path, source) = temp_database("source").await;
+        seed(&source).await;
+        let original = Archive::read(&source).await.unwrap();
+        assert_eq!(
+            original.summary(),
+            ArchiveSummary {
+                keywords: 2,
+                poems: 1,
+                provenance: 1,
+                collections: 1,
+            }
+        );
+
+        for (format, extension) in [
+            (ArchiveFormat::Json, "json"),
+            (ArchiveFormat::Sqlite, "sqlite"),
+        ] {
+            let out = temp_path(&format!("export.{}", extension));
+            export(&source, format, &out).await.unwrap();
+
+            let (target_path, target) = temp_database(extension).await;
+            assert_eq!(
+                import(&target, format, &out).await.unwrap(),
+                original.summary()
+            );
+            let restored = Archive::read(&target).await.unwrap();
+            assert_eq!(restored.keywords, original.keywords, "{:?}", format);
+            assert_eq!(restored.poems, original.poems, "{:?}", format);
+            assert_eq!(restored.provenance, original.provenance, "{:?}", format);
+            assert_eq!(
+                restored.collections[0].poem_dates,
+                original.collections[0].poem_dates
+            );
+
+            if format == ArchiveFormat::Sqlite {
+                let date = "2026-01-05";
+                assert_eq!(
+                    target.get_keyword_audit(date).await.unwrap().len(),
+                    source.get_keyword_audit(date).await.unwrap().len()
+                );
+                assert_eq!(
+                    target.get_keyword_annotations(date).await.unwrap(),
+                    source.get_keyword_annotations(date).await.unwrap()
+                );
+                assert_eq!(
+                    target.get_poem_engagement(date).await.unwrap(),
+                    source.get_poem_engagement(date).await.unwrap()
+                );
+                // The search index is rebuilt from the copied poems
+                assert_eq!(target.search_poems("poem", 10).await.unwrap().len(), 1);
+            }
+
+            // Imports only fill an empty database
+            assert!(import(&target, format, &out).await.is_err());
+
+            target.close().await;
+            let _ = fs::remove_file(&target_path);
+            let _ = fs::remove_file(&out);
+        }
+
+        source.close().await;
+        let _ = fs::remove_file(&source_path);
+    }
+}
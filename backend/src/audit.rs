@@ -0,0 +1,345 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::{Keypair, Signer};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::blockchain::{BlockDetail, BlockInfo, SolanaClient};
+use crate::consts::{BlockDataSource, MANUAL_KEYWORD_SOURCE};
+use crate::database::{Database, StoredKeyword};
+use crate::derivation::KeywordDerivation;
+use crate::provenance::DerivationProvenance;
+
+/// Result of `chain-verse verify-archive`: every stored keyword re-derived
+/// from its block with the current algorithm and dictionaries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub generated_at: String,
+    /// The algorithm and word lists the keywords were checked against
+    pub derivation: DerivationProvenance,
+    /// True when blocks weren't fetched and only the stored blockhashes were used
+    pub offline: bool,
+    pub keywords: usize,
+    /// Keywords that re-derive to the stored word and index
+    pub verified: usize,
+    /// Keywords a curator added by hand, which no block derives
+    pub manual: usize,
+    /// Keywords that couldn't be checked (block unavailable, theme not loaded)
+    pub skipped: Vec<AuditFinding>,
+    /// Keywords that don't match their block or their derivation
+    pub mismatches: Vec<AuditFinding>,
+}
+
+/// One keyword the audit couldn't verify, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub keyword_id: i64,
+    pub slot: i64,
+    pub word: String,
+    pub source: String,
+    pub reason: String,
+}
+
+impl AuditFinding {
+    fn new(keyword: &StoredKeyword, reason: impl Into<String>) -> Self {
+        Self {
+            keyword_id: keyword.id,
+            slot: keyword.slot,
+            word: keyword.word.clone(),
+            source: keyword.source.clone(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Detached signature over the report file's exact bytes, written next to it
+/// as `<report>.sig`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportSignature {
+    /// Base58 public key of the signing keypair
+    pub signer: String,
+    /// Base58 ed25519 signature
+    pub signature: String,
+}
+
+/// Re-derives stored keywords to check the archive against the chain
+pub struct ArchiveAuditor {
+    derivation: KeywordDerivation,
+    solana_client: Option<SolanaClient>,
+}
+
+impl ArchiveAuditor {
+    /// Audit against the stored blockhashes only; keywords from other
+    /// sources are skipped
+    pub fn new(derivation: KeywordDerivation) -> Self {
+        Self {
+            derivation,
+            solana_client: None,
+        }
+    }
+
+    /// Re-fetch each keyword's block (once per slot) and check the stored
+    /// blockhashes against it too
+    pub fn with_client(mut self, solana_client: SolanaClient) -> Self {
+        self.solana_client = Some(solana_client);
+        self
+    }
+
+    /// Check every stored keyword, oldest first
+    pub async fn audit(&self, db: &Database) -> Result<AuditReport> {
+        let keywords = db.get_all_keywords().await?;
+        let mut report = AuditReport {
+            generated_at: Utc::now().to_rfc3339(),
+            derivation: self.derivation.provenance(),
+            offline: self.solana_client.is_none(),
+            keywords: keywords.len(),
+            verified: 0,
+            manual: 0,
+            skipped: Vec::new(),
+            mismatches: Vec::new(),
+        };
+        let mut blocks: HashMap<i64, std::result::Result<BlockInfo, String>> = HashMap::new();
+        // Each block is fetched with just the detail its keywords' sources need
+        let mut sources: HashMap<i64, Vec<BlockDataSource>> = HashMap::new();
+        for keyword in &keywords {
+            if let Some(source) = BlockDataSource::from_name(&keyword.source) {
+                sources.entry(keyword.slot).or_default().push(source);
+            }
+        }
+
+        for keyword in &keywords {
+            if keyword.source == MANUAL_KEYWORD_SOURCE {
+                report.manual += 1;
+                continue;
+            }
+            let Some(source) = BlockDataSource::from_name(&keyword.source) else {
+                report
+                    .mismatches
+                    .push(AuditFinding::new(keyword, "Unknown entropy source"));
+                continue;
+            };
+
+            let block = match &self.solana_client {
+                Some(client) => {
+                    if let Entry::Vacant(entry) = blocks.entry(keyword.slot) {
+                        let detail = BlockDetail::for_sources(&sources[&keyword.slot]);
+                        let block = client
+                            .get_block_with_detail(keyword.slot as u64, detail)
+                            .await;
+                        entry.insert(block.map_err(|e| e.to_string()));
+                    }
+                    match &blocks[&keyword.slot] {
+                        Ok(block) => block.clone(),
+                        Err(e) => {
+                            report.skipped.push(AuditFinding::new(
+                                keyword,
+                                format!("Block unavailable: {}", e),
+                            ));
+                            continue;
+                        }
+                    }
+                }
+                None => match stored_block(keyword, source) {
+                    Some(block) => block,
+                    None => {
+                        report.skipped.push(AuditFinding::new(
+                            keyword,
+                            "Derived from block contents; run without --offline to check it",
+                        ));
+                        continue;
+                    }
+                },
+            };
+
+            match self.check(keyword, source, &block) {
+                Check::Verified => report.verified += 1,
+                Check::Skipped(reason) => report.skipped.push(AuditFinding::new(keyword, reason)),
+                Check::Mismatch(reason) => {
+                    report.mismatches.push(AuditFinding::new(keyword, reason))
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn check(&self, keyword: &StoredKeyword, source: BlockDataSource, block: &BlockInfo) -> Check {
+        if keyword.blockhash != block.blockhash {
+            return Check::Mismatch(format!(
+                "Stored blockhash {} but the chain has {}",
+                keyword.blockhash, block.blockhash
+            ));
+        }
+        if let Some(previous) = &keyword.previous_blockhash {
+            if *previous != block.previous_blockhash {
+                return Check::Mismatch(format!(
+                    "Stored previous blockhash {} but the chain has {}",
+                    previous, block.previous_blockhash
+                ));
+            }
+        }
+
+        let theme = keyword.theme.as_deref();
+        let Some((index, word)) =
+            self.derivation
+                .rederive(block, source, theme, keyword.rerolls.max(0) as u32)
+        else {
+            return Check::Skipped(format!(
+                "Dictionary theme `{}` is not loaded",
+                theme.unwrap_or_default()
+            ));
+        };
+        if word != keyword.word || index as i64 != keyword.word_index {
+            return Check::Mismatch(format!(
+                "Derives `{}` (index {}) after {} rerolls, but `{}` (index {}) is stored",
+                word, index, keyword.rerolls, keyword.word, keyword.word_index
+            ));
+        }
+        Check::Verified
+    }
+}
+
+enum Check {
+    Verified,
+    Skipped(String),
+    Mismatch(String),
+}
+
+/// The block as far as the keyword records it, when that's all its source
+/// reads: blockhash keywords always, previous-blockhash ones once recorded
+fn stored_block(keyword: &StoredKeyword, source: BlockDataSource) -> Option<BlockInfo> {
+    let previous_blockhash = match source {
+        BlockDataSource::Blockhash => keyword.previous_blockhash.clone().unwrap_or_default(),
+        BlockDataSource::PreviousBlockhash => keyword.previous_blockhash.clone()?,
+        _ => return None,
+    };
+    Some(BlockInfo {
+        slot: keyword.slot as u64,
+        blockhash: keyword.blockhash.clone(),
+        previous_blockhash,
+        block_time: keyword.block_time,
+        block_height: None,
+        parent_slot: 0,
+        transaction_count: 0,
+        sample_signatures: Vec::new(),
+        total_fees: 0,
+        vote_transaction_count: 0,
+        non_vote_transaction_count: 0,
+        leader: None,
+        enhanced: None,
+    })
+}
+
+/// Write the report as JSON to `out` and its signature to `<out>.sig`;
+/// returns the signature's path
+pub fn write_signed(report: &AuditReport, keypair: &Keypair, out: &Path) -> Result<PathBuf> {
+    let json = serde_json::to_vec_pretty(report)?;
+    let signature = sign(&json, keypair);
+    fs::write(out, &json).with_context(|| format!("Failed to write {}", out.display()))?;
+
+    let mut sig_path = out.as_os_str().to_owned();
+    sig_path.push(".sig");
+    let sig_path = PathBuf::from(sig_path);
+    fs::write(&sig_path, serde_json::to_vec_pretty(&signature)?)
+        .with_context(|| format!("Failed to write {}", sig_path.display()))?;
+    Ok(sig_path)
+}
+
+fn sign(bytes: &[u8], keypair: &Keypair) -> ReportSignature {
+    ReportSignature {
+        signer: keypair.pubkey().to_string(),
+        signature: keypair.sign_message(bytes).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivation::DerivedKeyword;
+    use crate::words::WordDictionary;
+    use solana_sdk::signature::Signature;
+    use std::str::FromStr;
+
+    fn block(slot: u64) -> BlockInfo {
+        BlockInfo {
+            slot,
+            blockhash: format!("hash{}", slot),
+            previous_blockhash: format!("hash{}", slot - 1),
+            block_time: None,
+            block_height: None,
+            parent_slot: slot - 1,
+            transaction_count: 12,
+            sample_signatures: Vec::new(),
+            total_fees: 0,
+            vote_transaction_count: 0,
+            non_vote_transaction_count: 0,
+            leader: None,
+            enhanced: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_audit() {
+        let path =
+            std::env::temp_dir().join(format!("chain_verse_audit_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = Database::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+
+        let good = derivation.derive_keyword(&block(100)).unwrap();
+        let previous = derivation
+            .derive_keyword_from_source(&block(200), BlockDataSource::PreviousBlockhash)
+            .unwrap();
+        let tampered = DerivedKeyword {
+            word: "forged".to_string(),
+            ..derivation.derive_keyword(&block(300)).unwrap()
+        };
+        let themed = DerivedKeyword {
+            theme: Some("ocean".to_string()),
+            ..derivation.derive_keyword(&block(400)).unwrap()
+        };
+        let counted = derivation
+            .derive_keyword_from_source(&block(500), BlockDataSource::TransactionCount)
+            .unwrap();
+        for keyword in [&good, &previous, &tampered, &themed, &counted] {
+            db.insert_keyword_with_date(keyword, "2026-01-05")
+                .await
+                .unwrap();
+        }
+        db.add_manual_keyword("2026-01-05", "wander", "curator", None)
+            .await
+            .unwrap();
+
+        let report = ArchiveAuditor::new(derivation).audit(&db).await.unwrap();
+        assert!(report.offline);
+        assert_eq!(report.keywords, 6);
+        assert_eq!(report.verified, 2);
+        assert_eq!(report.manual, 1);
+        let slots = |findings: &[AuditFinding]| -> Vec<i64> {
+            findings.iter().map(|finding| finding.slot).collect()
+        };
+        assert_eq!(slots(&report.mismatches), vec![300]);
+        assert_eq!(slots(&report.skipped), vec![400, 500]);
+
+        // The signature covers the file exactly as written
+        let out =
+            std::env::temp_dir().join(format!("chain_verse_audit_{}.json", std::process::id()));
+        let keypair = Keypair::new();
+        let sig_path = write_signed(&report, &keypair, &out).unwrap();
+        let signature: ReportSignature =
+            serde_json::from_slice(&fs::read(&sig_path).unwrap()).unwrap();
+        assert_eq!(signature.signer, keypair.pubkey().to_string());
+        let signature = Signature::from_str(&signature.signature).unwrap();
+        assert!(signature.verify(keypair.pubkey().as_ref(), &fs::read(&out).unwrap()));
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&out);
+        let _ = std::fs::remove_file(&sig_path);
+    }
+}
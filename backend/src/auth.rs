@@ -0,0 +1,320 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::database::Database;
+
+/// What an API key may do; each role includes the ones below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Read the admin audit logs
+    Reader,
+    /// Also add and remove keywords
+    Curator,
+    /// Also reload the dictionary and read API key usage
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Curator => "curator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "reader" => Ok(Role::Reader),
+            "curator" => Ok(Role::Curator),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!(
+                "unknown role `{}` (expected reader, curator, or admin)",
+                other
+            )),
+        }
+    }
+}
+
+/// A bearer key for the admin API, from `[[api.keys]]` or API_KEYS
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+    /// Who holds the key; recorded in the usage log and as the default curator
+    pub name: String,
+    pub key: String,
+    pub role: Role,
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("name", &self.name)
+            .field("key", &"<redacted>")
+            .field("role", &self.role)
+            .finish()
+    }
+}
+
+/// Parses `name:role:key`, the API_KEYS entry format
+impl FromStr for ApiKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(name), Some(role), Some(key)) => Ok(ApiKey {
+                name: name.trim().to_string(),
+                role: role.parse()?,
+                key: key.trim().to_string(),
+            }),
+            _ => Err("expected name:role:key".to_string()),
+        }
+    }
+}
+
+/// The key a request was authenticated with, added to the request's extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caller {
+    pub name: String,
+    pub role: Role,
+}
+
+/// Why a request was turned away
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// No keys are configured, so the admin API is off
+    Disabled,
+    /// No bearer key, or one that matches no configured key
+    Unauthenticated,
+    /// A valid key whose role is below the route's
+    Forbidden { caller: Caller, required: Role },
+}
+
+impl AuthError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AuthError::Unauthenticated => StatusCode::UNAUTHORIZED,
+            AuthError::Disabled | AuthError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Disabled => f.write_str("Admin API is disabled (no API keys configured)"),
+            AuthError::Unauthenticated => f.write_str("Missing or invalid API key"),
+            AuthError::Forbidden { caller, required } => write!(
+                f,
+                "API key `{}` has the {} role; this route requires {}",
+                caller.name, caller.role, required
+            ),
+        }
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let mut response = (
+            self.status(),
+            Json(serde_json::json!({ "error": self.to_string() })),
+        )
+            .into_response();
+        if self == AuthError::Unauthenticated {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+/// The configured API keys
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    keys: Vec<ApiKey>,
+}
+
+impl ApiKeys {
+    pub fn new(keys: Vec<ApiKey>) -> Self {
+        Self { keys }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The key sent as `Authorization: Bearer <key>`, if it is one of ours
+    pub fn authenticate(&self, headers: &HeaderMap) -> Option<&ApiKey> {
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))?;
+        self.keys.iter().find(|key| key.key == provided)
+    }
+
+    /// Check that the request's key holds at least `required`
+    pub fn authorize(&self, headers: &HeaderMap, required: Role) -> Result<Caller, AuthError> {
+        if self.is_empty() {
+            return Err(AuthError::Disabled);
+        }
+        let key = self
+            .authenticate(headers)
+            .ok_or(AuthError::Unauthenticated)?;
+        let caller = Caller {
+            name: key.name.clone(),
+            role: key.role,
+        };
+        if caller.role < required {
+            return Err(AuthError::Forbidden { caller, required });
+        }
+        Ok(caller)
+    }
+}
+
+/// State for `require`: the keys, where usage is recorded, and the route's role
+#[derive(Clone)]
+pub struct Guard {
+    pub keys: Arc<ApiKeys>,
+    pub db: Arc<Database>,
+    pub role: Role,
+}
+
+/// Middleware: answer 401/403 unless the request carries a key with the
+/// guard's role, and record every request made with a known key (except on a
+/// read-only replica, which can't)
+///
+/// The caller is added to the request's extensions for handlers to read.
+pub async fn require(State(guard): State<Guard>, mut request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    let (caller, response) = match guard.keys.authorize(request.headers(), guard.role) {
+        Ok(caller) => {
+            request.extensions_mut().insert(caller.clone());
+            (Some(caller), next.run(request).await)
+        }
+        Err(e) => {
+            debug!(%path, error = %e, "Rejected API request");
+            let caller = match &e {
+                AuthError::Forbidden { caller, .. } => Some(caller.clone()),
+                _ => None,
+            };
+            (caller, e.into_response())
+        }
+    };
+
+    if let Some(caller) = caller.filter(|_| !guard.db.is_read_only()) {
+        let status = response.status().as_u16();
+        if let Err(e) = guard
+            .db
+            .record_api_key_usage(&caller.name, caller.role, &method, &path, status)
+            .await
+        {
+            warn!(key = %caller.name, error = %e, "Failed to record API key usage");
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> ApiKeys {
+        ApiKeys::new(vec![
+            "ops:admin:admin-secret".parse().unwrap(),
+            "alice:curator:curator-secret".parse().unwrap(),
+            "dash:reader:reader-secret".parse().unwrap(),
+        ])
+    }
+
+    fn bearer(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", key)).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_parse_api_key() {
+        let key: ApiKey = "alice : Curator : a:b".parse().unwrap();
+        assert_eq!(key.name, "alice");
+        assert_eq!(key.role, Role::Curator);
+        // Keys may contain colons; only the first two separate fields
+        assert_eq!(key.key, "a:b");
+        assert!(!format!("{:?}", key).contains("a:b"));
+
+        assert!("alice:owner:secret".parse::<ApiKey>().is_err());
+        assert!("alice:secret".parse::<ApiKey>().is_err());
+    }
+
+    #[test]
+    fn test_authorize_by_role() {
+        let keys = keys();
+
+        let caller = keys
+            .authorize(&bearer("curator-secret"), Role::Curator)
+            .unwrap();
+        assert_eq!(
+            caller,
+            Caller {
+                name: "alice".to_string(),
+                role: Role::Curator,
+            }
+        );
+        // Higher roles include lower ones
+        assert!(keys
+            .authorize(&bearer("admin-secret"), Role::Reader)
+            .is_ok());
+
+        let denied = keys
+            .authorize(&bearer("reader-secret"), Role::Curator)
+            .unwrap_err();
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+        assert!(matches!(
+            denied,
+            AuthError::Forbidden {
+                required: Role::Curator,
+                ..
+            }
+        ));
+
+        assert_eq!(
+            keys.authorize(&bearer("wrong"), Role::Reader),
+            Err(AuthError::Unauthenticated)
+        );
+        assert_eq!(
+            keys.authorize(&HeaderMap::new(), Role::Reader),
+            Err(AuthError::Unauthenticated)
+        );
+        assert_eq!(
+            ApiKeys::default().authorize(&bearer("admin-secret"), Role::Reader),
+            Err(AuthError::Disabled)
+        );
+    }
+}
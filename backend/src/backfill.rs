@@ -0,0 +1,448 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use tokio::sync::watch;
+use tracing::{error, info, instrument, warn};
+
+use crate::blockchain::{SlotLocator, SolanaClient};
+use crate::consts::{DEFAULT_KEYWORDS_PER_DAY, MIN_KEYWORDS_FOR_POEM, POEM_LOCK_TTL_SECS};
+use crate::database::{Database, StoredKeyword};
+use crate::derivation::{KeywordDerivation, SourceWeights};
+use crate::mood::{BlockActivity, Mood};
+use crate::poem_form::{FormSelection, PoemLength};
+use crate::poem_generator::PoemGenerator;
+use crate::provenance::PoemProvenance;
+use crate::scheduler::{
+    apply_weekly_theme, assign_epochs, day_activity, lease_holder, recent_words, renew_poem_lock,
+    scheduled_keywords, source_counts,
+};
+use crate::words::{DictionaryHandle, ThemeSelection};
+
+/// Following slots tried when a target slot was skipped
+const NEARBY_SLOT_ATTEMPTS: u64 = 50;
+
+/// Pause between days so the LLM provider isn't hammered (RPC calls are paced
+/// by the client's `RpcThrottle`)
+const DAY_DELAY_SECS: u64 = 2;
+
+/// Fills in keywords and poems for past days
+///
+/// Each day's slot range is found from block times with a `SlotLocator`, and
+/// keywords are sampled evenly across it, fetching several blocks at once.
+///
+/// Each day's keywords are stored with one batch insert, after which progress
+/// is saved to `backfill_state`, so an interrupted run over the same range
+/// picks up where it stopped. The state row is
+/// removed once the range completes.
+pub struct Backfill {
+    solana_client: SolanaClient,
+    derivation: KeywordDerivation,
+    database: Database,
+    poem_generator: PoemGenerator,
+    min_keywords_for_poem: usize,
+    keywords_per_day: usize,
+    dedup_days: u32,
+    source_weights: SourceWeights,
+    form_selection: FormSelection,
+    poem_length: PoemLength,
+    themes: Option<(DictionaryHandle, ThemeSelection)>,
+    /// Names this backfill's leases on poem generation (see `Database::try_acquire_poem_lock`)
+    lease_holder: String,
+}
+
+/// What a backfill run accomplished
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BackfillSummary {
+    pub days_processed: usize,
+    pub keywords_collected: usize,
+    pub poems_generated: usize,
+}
+
+impl Backfill {
+    pub fn new(
+        solana_client: SolanaClient,
+        derivation: KeywordDerivation,
+        database: Database,
+        poem_generator: PoemGenerator,
+    ) -> Self {
+        Self {
+            solana_client,
+            derivation,
+            database,
+            poem_generator,
+            min_keywords_for_poem: MIN_KEYWORDS_FOR_POEM,
+            keywords_per_day: DEFAULT_KEYWORDS_PER_DAY,
+            dedup_days: 0,
+            source_weights: SourceWeights::default(),
+            form_selection: FormSelection::default(),
+            poem_length: PoemLength::DEFAULT,
+            themes: None,
+            lease_holder: lease_holder(),
+        }
+    }
+
+    /// Switch `dictionary` (the one behind `derivation`) to each week's theme
+    pub fn with_theme_selection(
+        mut self,
+        dictionary: DictionaryHandle,
+        theme_selection: ThemeSelection,
+    ) -> Self {
+        self.themes = Some((dictionary, theme_selection));
+        self
+    }
+
+    /// Override how many keywords a day needs before its poem is generated
+    pub fn with_min_keywords_for_poem(mut self, min_keywords: usize) -> Self {
+        self.min_keywords_for_poem = min_keywords;
+        self
+    }
+
+    /// Override how many keywords are collected for each backfilled day
+    pub fn with_keywords_per_day(mut self, keywords_per_day: usize) -> Self {
+        self.keywords_per_day = keywords_per_day;
+        self
+    }
+
+    /// Re-roll words already collected on the day or the `days` days before it (0 disables)
+    pub fn with_dedup_days(mut self, days: u32) -> Self {
+        self.dedup_days = days;
+        self
+    }
+
+    /// Choose which source each block's word comes from by their weights, as
+    /// the collector does (see `SourceWeights::compose`)
+    pub fn with_source_weights(mut self, source_weights: SourceWeights) -> Self {
+        self.source_weights = source_weights;
+        self
+    }
+
+    /// Choose how each day's poem form is picked
+    pub fn with_form_selection(mut self, form_selection: FormSelection) -> Self {
+        self.form_selection = form_selection;
+        self
+    }
+
+    /// Bound free verse's line count, which scales with each day's activity
+    pub fn with_poem_length(mut self, poem_length: PoemLength) -> Self {
+        self.poem_length = poem_length;
+        self
+    }
+
+    /// Backfill every day from `start` to `end` inclusive, stopping early if
+    /// `shutdown` flips to true (progress is kept for the next run)
+    #[instrument(skip(self, shutdown))]
+    pub async fn run(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        shutdown: watch::Receiver<bool>,
+    ) -> Result<BackfillSummary> {
+        if start > end {
+            anyhow::bail!("Backfill start {} is after end {}", start, end);
+        }
+
+        let start_date = start.format("%Y-%m-%d").to_string();
+        let end_date = end.format("%Y-%m-%d").to_string();
+
+        let state = self.database.get_backfill_state(&start_date, &end_date).await?;
+        let (mut day, mut resume_slot) = match &state {
+            Some(state) => {
+                let day = match &state.last_completed_date {
+                    Some(done) => NaiveDate::parse_from_str(done, "%Y-%m-%d")? + Duration::days(1),
+                    None => start,
+                };
+                info!(
+                    resume_from = %day,
+                    last_slot_tried = ?state.last_slot_tried,
+                    "Resuming interrupted backfill"
+                );
+                (day, state.last_slot_tried.map(|slot| slot as u64))
+            }
+            None => (start, None),
+        };
+
+        let locator = SlotLocator::new(&self.solana_client).with_timezone(self.database.timezone());
+        let mut summary = BackfillSummary::default();
+
+        while day <= end {
+            if *shutdown.borrow() {
+                info!(%day, "Backfill interrupted; progress saved");
+                return Ok(summary);
+            }
+
+            let date = day.format("%Y-%m-%d").to_string();
+
+            let finished = self
+                .backfill_day(
+                    &start_date,
+                    &end_date,
+                    day,
+                    &locator,
+                    resume_slot,
+                    &shutdown,
+                    &mut summary,
+                )
+                .await?;
+            if !finished {
+                info!(%date, "Backfill interrupted; progress saved");
+                return Ok(summary);
+            }
+
+            self.database
+                .complete_backfill_day(&start_date, &end_date, &date)
+                .await?;
+            summary.days_processed += 1;
+            resume_slot = None;
+            day += Duration::days(1);
+
+            if day <= end {
+                tokio::time::sleep(tokio::time::Duration::from_secs(DAY_DELAY_SECS)).await;
+            }
+        }
+
+        self.database.clear_backfill_state(&start_date, &end_date).await?;
+        info!(
+            days = summary.days_processed,
+            keywords = summary.keywords_collected,
+            poems = summary.poems_generated,
+            "Backfill complete"
+        );
+
+        Ok(summary)
+    }
+
+    /// Collect missing keywords and the poem for one day; returns false if interrupted
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(%day))]
+    async fn backfill_day(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        day: NaiveDate,
+        locator: &SlotLocator<'_>,
+        resume_slot: Option<u64>,
+        shutdown: &watch::Receiver<bool>,
+        summary: &mut BackfillSummary,
+    ) -> Result<bool> {
+        let date = &day.format("%Y-%m-%d").to_string();
+
+        if self.database.get_poem_by_date(date).await?.is_some() {
+            info!("Poem already exists, skipping");
+            return Ok(true);
+        }
+
+        let stored = self.database.get_poem_keywords_for_date(date).await?;
+        let mut collected = stored.len();
+        if collected >= self.keywords_per_day {
+            return self.generate_poem(date, summary).await.map(|()| true);
+        }
+
+        // Exact day boundaries from block times; today ends at the chain tip
+        let first_slot = locator.first_slot_of_day(day).await?;
+        let next_day = day + Duration::days(1);
+        let end_slot = if next_day <= self.database.timezone().today() {
+            locator.first_slot_of_day(next_day).await?.saturating_sub(1)
+        } else {
+            self.solana_client.get_current_slot().await?
+        };
+        let spacing = (end_slot.saturating_sub(first_slot) / self.keywords_per_day as u64).max(1);
+        info!(first_slot, end_slot, "Located day's slot range");
+
+        // Keywords are written in one transaction per day; progress is only
+        // saved after they are, so a crash re-fetches the unsaved slots
+        let mut pending = Vec::new();
+        let mut activity = BlockActivity::default();
+        let mut recent = recent_words(&self.database, day, self.dedup_days).await?;
+        let mut taken = source_counts(scheduled_keywords(&stored));
+        let mut last_target = None;
+        let mut interrupted = false;
+
+        // Targets an interrupted run hasn't tried yet
+        let mut targets = (0..self.keywords_per_day as u64)
+            .map(|i| first_slot + i * spacing)
+            .filter(|&target| resume_slot.is_none_or(|tried| target > tried))
+            .peekable();
+
+        // Each round fetches just enough targets to fill the day, several at
+        // once; targets whose nearby slots were all skipped leave room for another round
+        while collected + pending.len() < self.keywords_per_day && targets.peek().is_some() {
+            if *shutdown.borrow() {
+                interrupted = true;
+                break;
+            }
+
+            let needed = self.keywords_per_day - collected - pending.len();
+            let round: Vec<u64> = targets.by_ref().take(needed).collect();
+            let blocks = self
+                .solana_client
+                .get_first_blocks(round.iter().map(|&target| {
+                    (target..target + NEARBY_SLOT_ATTEMPTS)
+                        .take_while(move |&slot| slot <= end_slot)
+                }))
+                .await;
+
+            // Derive in slot order, as a sequential run would, so the weekly
+            // theme and dedup see the same blocks first
+            for block in blocks.into_iter().flatten() {
+                if pending.is_empty() {
+                    if let Some((dictionary, selection)) = &self.themes {
+                        apply_weekly_theme(
+                            &self.database,
+                            dictionary,
+                            selection,
+                            day,
+                            &block.blockhash,
+                        )
+                        .await?;
+                    }
+                }
+                let keywords = self.derivation.derive_weighted(
+                    &block,
+                    1,
+                    &recent,
+                    &self.source_weights,
+                    &mut taken,
+                )?;
+                for keyword in keywords {
+                    info!(
+                        word = %keyword.word,
+                        slot = keyword.slot,
+                        source = keyword.source.as_str(),
+                        rerolls = keyword.rerolls,
+                        "Backfilled keyword"
+                    );
+                    if self.dedup_days > 0 {
+                        // Not stored yet, so later slots today must avoid it explicitly
+                        recent.insert(keyword.word.clone());
+                    }
+                    pending.push(keyword);
+                }
+                activity.add(&block);
+            }
+
+            last_target = round.last().copied();
+        }
+
+        if !pending.is_empty() {
+            assign_epochs(&self.solana_client, &mut pending).await;
+            let inserted = self.database.insert_keywords_with_date(&pending, date).await?;
+            info!(
+                inserted = inserted.inserted,
+                skipped = inserted.skipped,
+                "Stored backfilled keywords"
+            );
+            collected += inserted.inserted;
+            summary.keywords_collected += inserted.inserted;
+            self.database.record_day_activity(date, &activity).await?;
+        }
+        if let Some(slot) = last_target {
+            self.database
+                .save_backfill_slot(start_date, end_date, slot)
+                .await?;
+        }
+        if interrupted {
+            return Ok(false);
+        }
+
+        info!(collected, "Day's keywords collected");
+        self.generate_poem(date, summary).await?;
+        Ok(true)
+    }
+
+    /// Generate and store the day's poem if it has enough keywords
+    ///
+    /// A running collector (or another backfill) sharing the database may hold
+    /// the day's lease; the poem is then left to it.
+    async fn generate_poem(&self, date: &str, summary: &mut BackfillSummary) -> Result<()> {
+        let keywords = self.database.get_poem_keywords_for_date(date).await?;
+        if keywords.len() < self.min_keywords_for_poem {
+            warn!(
+                keywords = keywords.len(),
+                needed = self.min_keywords_for_poem,
+                "Not enough keywords for a poem"
+            );
+            return Ok(());
+        }
+
+        if !self
+            .database
+            .try_acquire_poem_lock(date, &self.lease_holder, POEM_LOCK_TTL_SECS)
+            .await?
+        {
+            info!("Another instance is generating this poem, leaving it to them");
+            return Ok(());
+        }
+        let result = async {
+            // The holder we waited on may have just finished and released the lease
+            if self.database.get_poem_by_date(date).await?.is_some() {
+                return Ok(());
+            }
+            self.generate_poem_from(date, keywords, summary).await
+        }
+        .await;
+        if let Err(e) = self
+            .database
+            .release_poem_lock(date, &self.lease_holder)
+            .await
+        {
+            warn!(error = %e, "Failed to release poem lock");
+        }
+        result
+    }
+
+    /// Generate and store the day's poem from `keywords`; the caller holds the
+    /// day's lease
+    async fn generate_poem_from(
+        &self,
+        date: &str,
+        keywords: Vec<StoredKeyword>,
+        summary: &mut BackfillSummary,
+    ) -> Result<()> {
+        let activity = day_activity(&self.database, date).await;
+        let mood = Mood::of(&activity);
+        let form = self
+            .form_selection
+            .resolve(keywords.first().map(|k| k.blockhash.as_str()))
+            .with_length(self.poem_length.scaled(&activity));
+        let words: Vec<String> = keywords.iter().map(|k| k.word.clone()).collect();
+
+        match self
+            .poem_generator
+            .generate_poem_in_form(&words, form, date, mood)
+            .await
+        {
+            Ok(poem) => {
+                if let Err(e) = renew_poem_lock(&self.database, date, &self.lease_holder).await {
+                    warn!(error = %e, "Discarding backfilled poem");
+                    return Ok(());
+                }
+                let keyword_ids: Vec<i64> = keywords.iter().map(|k| k.id).collect();
+                self.database
+                    .insert_poem(date, poem.title.as_deref(), &poem.content, &keyword_ids)
+                    .await?;
+                if let Some(usage) = &poem.usage {
+                    if let Err(e) = self.database.set_poem_usage(date, usage).await {
+                        warn!(error = %e, "Failed to store poem token usage");
+                    }
+                }
+                let provenance = PoemProvenance::new(
+                    date,
+                    self.derivation.provenance(),
+                    &keywords,
+                    self.poem_generator
+                        .provenance(&poem.model, &words, form, date, mood),
+                );
+                if let Err(e) = self.database.insert_poem_provenance(&provenance).await {
+                    warn!(error = %e, "Failed to store poem provenance");
+                }
+                info!(%form, title = poem.title.as_deref().unwrap_or_default(), "Backfilled poem");
+                summary.poems_generated += 1;
+            }
+            Err(e) => {
+                // Keep going; rerunning the range after it finishes will retry this day
+                error!(error = %e, "Failed to generate poem");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::consts::{DEFAULT_BACKUP_INTERVAL_HOURS, DEFAULT_BACKUP_KEEP, ONE_HOUR};
+use crate::database::Database;
+
+/// Backups are named `chain_verse-<UTC timestamp>.db`, so names sort oldest first
+const BACKUP_PREFIX: &str = "chain_verse-";
+const BACKUP_EXTENSION: &str = ".db";
+
+/// Suffix of a backup still being written
+const PARTIAL_EXTENSION: &str = ".partial";
+
+/// Periodic copies of the SQLite database in a directory, keeping the newest few
+///
+/// Each copy is written with VACUUM INTO under a `.partial` name and renamed
+/// once complete, so a crash mid-backup never leaves a truncated file that
+/// passes for a good one. The directory can be a mounted bucket or volume;
+/// nothing is uploaded anywhere else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseBackup {
+    dir: PathBuf,
+    keep: usize,
+    interval: Duration,
+}
+
+impl DatabaseBackup {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            keep: DEFAULT_BACKUP_KEEP,
+            interval: Duration::from_secs(DEFAULT_BACKUP_INTERVAL_HOURS * ONE_HOUR),
+        }
+    }
+
+    /// Backups to keep, newest first (at least one)
+    pub fn with_keep(mut self, keep: usize) -> Self {
+        self.keep = keep.max(1);
+        self
+    }
+
+    /// Time between backups
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn keep(&self) -> usize {
+        self.keep
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Write a new backup, then delete all but the newest `keep`; returns the new file
+    pub async fn run(&self, db: &Database) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+
+        let name = format!(
+            "{}{}{}",
+            BACKUP_PREFIX,
+            Utc::now().format("%Y%m%dT%H%M%SZ"),
+            BACKUP_EXTENSION
+        );
+        let path = self.dir.join(&name);
+        let partial = self.dir.join(format!("{}{}", name, PARTIAL_EXTENSION));
+        db.vacuum_into(&partial).await?;
+        fs::rename(&partial, &path)
+            .with_context(|| format!("Failed to move backup to {}", path.display()))?;
+
+        self.prune()?;
+        Ok(path)
+    }
+
+    /// Delete all but the newest `keep` backups, and any partial ones an
+    /// interrupted run left behind
+    fn prune(&self) -> Result<()> {
+        let mut backups = Vec::new();
+        let mut stale = Vec::new();
+        let entries = fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to list {}", self.dir.display()))?;
+        for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !name.starts_with(BACKUP_PREFIX) {
+                continue;
+            }
+            if name.ends_with(BACKUP_EXTENSION) {
+                backups.push(path);
+            } else if name.ends_with(PARTIAL_EXTENSION) {
+                stale.push(path);
+            }
+        }
+        backups.sort();
+
+        let excess = backups.len().saturating_sub(self.keep);
+        stale.extend(backups.drain(..excess));
+        for path in &stale {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to delete old backup {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backup_and_retention() {
+        let root = std::env::temp_dir().join(format!("chain_verse_backup_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let dir = root.join("backups");
+        let db = Database::new(&format!("sqlite:{}", root.join("live.db").display()))
+            .await
+            .unwrap();
+        db.insert_poem("2026-01-05", None, "A poem", &[])
+            .await
+            .unwrap();
+
+        // Older backups, one a crash left unfinished, and a file that isn't a backup
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "chain_verse-20260101T000000Z.db",
+            "chain_verse-20260102T000000Z.db",
+            "chain_verse-20260103T000000Z.db.partial",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(name), "old").unwrap();
+        }
+
+        let backup = DatabaseBackup::new(dir.clone()).with_keep(2);
+        let path = backup.run(&db).await.unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "chain_verse-20260102T000000Z.db".to_string(),
+                path.file_name().unwrap().to_string_lossy().into_owned(),
+                "notes.txt".to_string(),
+            ]
+        );
+
+        // The backup is a complete database
+        let copy = Database::open_read_only(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        assert_eq!(copy.count_poems().await.unwrap(), 1);
+
+        copy.close().await;
+        db.close().await;
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,406 @@
+use anyhow::{Context, Result};
+use chain_verse::config::Config;
+use chain_verse::database::Database;
+use chain_verse_types::{Client, StoredKeyword, StoredPoem};
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How long to wait for a key before checking on a running collection
+const INPUT_POLL: Duration = Duration::from_millis(200);
+
+/// Browse Chain Verse poems and the keywords they were written from
+#[derive(Debug, Parser)]
+#[command(name = "chain-verse-tui", version)]
+struct Args {
+    /// Read from a running instance instead of the local database, e.g.
+    /// http://localhost:3000
+    #[arg(long, value_name = "URL")]
+    api: Option<String>,
+
+    /// Config file naming the local database (default: config.toml if
+    /// present, or CHAIN_VERSE_CONFIG)
+    #[arg(long, value_name = "PATH", conflicts_with = "api")]
+    config: Option<PathBuf>,
+
+    /// chain_verse binary that `c` runs as `collect-once` (default: the one
+    /// next to this binary)
+    #[arg(long, value_name = "PATH", conflicts_with = "api")]
+    collector: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    let args = Args::parse();
+
+    let (source, collector) = match args.api {
+        Some(url) => (Source::Api(Client::new(url)), None),
+        None => {
+            let config = Config::load_without_api_key(args.config.as_deref())?;
+            let db = Database::new(&config.database.url)
+                .await
+                .with_context(|| format!("Failed to open {}", config.database.url))?
+                .with_timezone(config.timezone());
+            let bin = match args.collector {
+                Some(bin) => bin,
+                None => sibling_collector()?,
+            };
+            let collector = Collector {
+                bin,
+                config: args.config,
+            };
+            (Source::Local(db), Some(collector))
+        }
+    };
+
+    let mut app = App::new(source, collector);
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app).await;
+    ratatui::restore();
+    result
+}
+
+/// The main binary, built alongside this one
+fn sibling_collector() -> Result<PathBuf> {
+    let exe = std::env::current_exe().context("Failed to find this binary's path")?;
+    Ok(exe.with_file_name(format!("chain_verse{}", std::env::consts::EXE_SUFFIX)))
+}
+
+async fn run(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    app.refresh().await;
+    while !app.quit {
+        terminal.draw(|frame| draw(frame, app))?;
+        app.check_collection().await;
+
+        if event::poll(INPUT_POLL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.on_key(key.code).await;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where poems and keywords are read from
+enum Source {
+    Api(Client),
+    Local(Database),
+}
+
+impl Source {
+    fn describe(&self) -> &'static str {
+        match self {
+            Source::Api(_) => "API",
+            Source::Local(_) => "local database",
+        }
+    }
+
+    /// Today's date in the instance timezone, and every poem, latest first
+    async fn poems(&self) -> Result<(String, Vec<StoredPoem>)> {
+        match self {
+            Source::Api(client) => {
+                let today = client.today().await?.date;
+                let poems = client.poems().await?;
+                Ok((today, poems.into_iter().map(|p| p.poem).collect()))
+            }
+            Source::Local(db) => Ok((db.today(), convert(db.get_all_poems().await?)?)),
+        }
+    }
+
+    async fn keywords(&self, date: &str) -> Result<Vec<StoredKeyword>> {
+        match self {
+            Source::Api(client) => {
+                let keywords = client.keywords(date).await?;
+                Ok(keywords.into_iter().map(|k| k.keyword).collect())
+            }
+            Source::Local(db) => convert(db.get_keywords_for_date(date).await?),
+        }
+    }
+}
+
+/// Database rows as the API's types; openapi's tests keep the two in step
+fn convert<T: DeserializeOwned>(value: impl Serialize) -> Result<T> {
+    Ok(serde_json::from_value(serde_json::to_value(value)?)?)
+}
+
+/// Runs the main binary's `collect-once`, so a collection from the reader is
+/// configured exactly like the daemon's
+#[derive(Clone)]
+struct Collector {
+    bin: PathBuf,
+    config: Option<PathBuf>,
+}
+
+impl Collector {
+    /// Collect a keyword (and today's poem, if due); the last line it logged
+    async fn collect_once(self) -> Result<String> {
+        let mut command = tokio::process::Command::new(&self.bin);
+        if let Some(config) = &self.config {
+            command.arg("--config").arg(config);
+        }
+        let output = command
+            .arg("collect-once")
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {}", self.bin.display()))?;
+
+        let logged = [output.stdout, output.stderr].concat();
+        let last_line = String::from_utf8_lossy(&logged)
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .to_string();
+        if !output.status.success() {
+            anyhow::bail!("collect-once failed: {}", last_line);
+        }
+        Ok(last_line)
+    }
+}
+
+/// One date in the list: a poem, or today while its keywords are still coming in
+struct Entry {
+    date: String,
+    poem: Option<StoredPoem>,
+}
+
+struct App {
+    source: Source,
+    /// Only in local mode
+    collector: Option<Collector>,
+    entries: Vec<Entry>,
+    list: ListState,
+    /// The selected date's keywords
+    keywords: Vec<StoredKeyword>,
+    status: String,
+    collecting: Option<JoinHandle<Result<String>>>,
+    quit: bool,
+}
+
+impl App {
+    fn new(source: Source, collector: Option<Collector>) -> Self {
+        Self {
+            source,
+            collector,
+            entries: Vec::new(),
+            list: ListState::default(),
+            keywords: Vec::new(),
+            status: String::new(),
+            collecting: None,
+            quit: false,
+        }
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.list.selected().and_then(|i| self.entries.get(i))
+    }
+
+    /// Reload the poem list, keeping the selected date when it is still there
+    async fn refresh(&mut self) {
+        let (today, poems) = match self.source.poems().await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.status = format!("Failed to load poems: {:#}", e);
+                return;
+            }
+        };
+        let selected = self.selected().map(|entry| entry.date.clone());
+
+        self.entries.clear();
+        if !poems.iter().any(|poem| poem.date == today) {
+            self.entries.push(Entry {
+                date: today,
+                poem: None,
+            });
+        }
+        self.entries.extend(poems.into_iter().map(|poem| Entry {
+            date: poem.date.clone(),
+            poem: Some(poem),
+        }));
+
+        let index = selected
+            .and_then(|date| self.entries.iter().position(|entry| entry.date == date))
+            .unwrap_or(0);
+        self.list.select(Some(index));
+        self.status = self.help();
+        self.load_keywords().await;
+    }
+
+    async fn load_keywords(&mut self) {
+        let Some(date) = self.selected().map(|entry| entry.date.clone()) else {
+            self.keywords.clear();
+            return;
+        };
+        match self.source.keywords(&date).await {
+            Ok(keywords) => self.keywords = keywords,
+            Err(e) => {
+                self.keywords.clear();
+                self.status = format!("Failed to load keywords for {}: {:#}", date, e);
+            }
+        }
+    }
+
+    fn help(&self) -> String {
+        let collect = if self.collector.is_some() {
+            "  c collect"
+        } else {
+            ""
+        };
+        format!(
+            "{} poems from the {}  ↑↓ select  r refresh{}  q quit",
+            self.entries
+                .iter()
+                .filter(|entry| entry.poem.is_some())
+                .count(),
+            self.source.describe(),
+            collect
+        )
+    }
+
+    async fn on_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.select_next();
+                self.load_keywords().await;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.select_previous();
+                self.load_keywords().await;
+            }
+            KeyCode::Char('r') => self.refresh().await,
+            KeyCode::Char('c') => self.start_collection(),
+            _ => {}
+        }
+    }
+
+    fn start_collection(&mut self) {
+        let Some(collector) = &self.collector else {
+            self.status = "Collecting needs local mode; run without --api".to_string();
+            return;
+        };
+        if self.collecting.is_some() {
+            return;
+        }
+        self.status = "Collecting a keyword...".to_string();
+        self.collecting = Some(tokio::spawn(collector.clone().collect_once()));
+    }
+
+    /// Report a finished collection and show what it stored
+    async fn check_collection(&mut self) {
+        if !self
+            .collecting
+            .as_ref()
+            .is_some_and(JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(handle) = self.collecting.take() else {
+            return;
+        };
+        let outcome = match handle.await {
+            Ok(outcome) => outcome,
+            Err(e) => Err(e.into()),
+        };
+        self.refresh().await;
+        self.status = match outcome {
+            Ok(last_line) => format!("Collected: {}", last_line),
+            Err(e) => format!("{:#}", e),
+        };
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, status] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+    let [dates, detail] =
+        Layout::horizontal([Constraint::Length(36), Constraint::Fill(1)]).areas(main);
+    let [poem, keywords] =
+        Layout::vertical([Constraint::Fill(3), Constraint::Fill(2)]).areas(detail);
+
+    let items: Vec<ListItem> = app
+        .entries
+        .iter()
+        .map(|entry| {
+            let title = match &entry.poem {
+                Some(poem) => poem.title.as_deref().unwrap_or("Untitled"),
+                None => "(collecting)",
+            };
+            ListItem::new(format!("{}  {}", entry.date, title))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(" Poems "))
+        .highlight_style(Style::new().reversed());
+    frame.render_stateful_widget(list, dates, &mut app.list);
+
+    let (title, text) = match app.selected() {
+        Some(Entry {
+            date,
+            poem: Some(poem),
+        }) => (
+            format!(
+                " {} — {} ",
+                date,
+                poem.title.as_deref().unwrap_or("Untitled")
+            ),
+            poem.content.clone(),
+        ),
+        Some(Entry { date, poem: None }) => (
+            format!(" {} ", date),
+            "No poem yet; the keywords collected so far are below.".to_string(),
+        ),
+        None => (" Poem ".to_string(), "No poems yet.".to_string()),
+    };
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .block(Block::bordered().title(title));
+    frame.render_widget(paragraph, poem);
+
+    let rows = app.keywords.iter().map(|keyword| {
+        let source = match &keyword.theme {
+            Some(theme) => format!("{} ({})", keyword.source, theme),
+            None => keyword.source.clone(),
+        };
+        // Orphaned keywords were left out of the poem (see StoredKeyword::orphaned)
+        let style = if keyword.orphaned {
+            Style::new().dim().crossed_out()
+        } else {
+            Style::new()
+        };
+        Row::new(vec![
+            keyword.word.clone(),
+            source,
+            keyword.slot.to_string(),
+            keyword.created_at.clone(),
+        ])
+        .style(style)
+    });
+    let widths = [
+        Constraint::Length(14),
+        Constraint::Length(28),
+        Constraint::Length(12),
+        Constraint::Fill(1),
+    ];
+    let table = Table::new(rows, widths)
+        .header(Row::new(["Word", "Source", "Slot", "Collected (UTC)"]).style(Style::new().bold()))
+        .block(Block::bordered().title(format!(" Keywords ({}) ", app.keywords.len())));
+    frame.render_widget(table, keywords);
+
+    frame.render_widget(Line::from(app.status.as_str()).dim(), status);
+}
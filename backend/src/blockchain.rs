@@ -1,12 +1,43 @@
-use anyhow::{Context, Result};
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
 use solana_client::rpc_config::RpcBlockConfig;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
-use std::sync::Arc;
+use solana_sdk::epoch_info::EpochInfo;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, Reward, RewardType, TransactionDetails,
+    UiMessage, UiTransaction, UiTransactionEncoding,
+};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::RpcError;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
 
-use crate::consts::{CONFIRMATION_SLOTS, MAINNET_RPC_URL};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::consts::{
+    BlockDataSource, BLOCK_FETCH_CONCURRENCY, CONFIRMATION_SLOTS, DEFAULT_SAMPLE_SIGNATURES,
+    ESTIMATED_SLOTS_PER_DAY, EVENT_BASELINE_BLOCKS, EVENT_COOLDOWN_SLOTS, EVENT_MIN_SLOT_GAP,
+    EVENT_SURGE_FACTOR, MAINNET_RPC_URL, ONE_DAY, RPC_FAILURE_COOLDOWN_SECS, RPC_MAX_COOLDOWN_SECS,
+    VOTE_PROGRAM_ID,
+};
+use crate::enhanced_rpc::{self, EnhancedBlockData};
+use crate::error::{ChainVerseError, Result};
+use crate::metrics;
+use crate::retry::RetryPolicy;
+use crate::rpc_throttle::RpcThrottle;
+use crate::timezone::Timezone;
 
 /// Rich block information from Solana
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,30 +51,271 @@ pub struct BlockInfo {
     pub transaction_count: usize,
     /// Sample transaction signatures for additional entropy
     pub sample_signatures: Vec<String>,
+    /// Lamports paid in fees by every transaction in the block
+    pub total_fees: u64,
+    /// Transactions calling the vote program, and all the others; these
+    /// cover the whole block even with a program filter
+    pub vote_transaction_count: usize,
+    pub non_vote_transaction_count: usize,
+    /// Identity of the validator that produced the block, from its fee
+    /// reward or else the leader schedule
+    pub leader: Option<String>,
+    /// Read from the block's parsed transactions; None unless fetched
+    /// through an enhanced RPC (`SolanaClient::with_enhanced_rpc`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enhanced: Option<EnhancedBlockData>,
 }
 
-impl BlockInfo {
-    /// Get multiple entropy sources from the block
-    pub fn entropy_sources(&self) -> Vec<String> {
-        let mut sources = vec![
-            self.blockhash.clone(),
-            self.previous_blockhash.clone(),
-            format!("{}", self.slot),
-            format!("{}", self.transaction_count),
-        ];
+/// Program signatures requested per block when a program filter is set (the RPC maximum)
+const PROGRAM_SIGNATURE_LIMIT: usize = 1000;
 
-        // Add sample signatures
-        sources.extend(self.sample_signatures.iter().cloned());
+/// Sample signatures and same-slot transaction count from a program's signature history
+///
+/// `history` is (signature, slot) newest first, as getSignaturesForAddress
+/// returns it. Quiet slots fall back to the program's most recent activity
+/// before `slot`, so the sample is only empty if the program never ran.
+fn program_sample(slot: u64, history: &[(String, u64)], size: usize) -> (Vec<String>, usize) {
+    let recent: Vec<&(String, u64)> = history.iter().filter(|(_, s)| *s <= slot).collect();
+    let in_slot = recent.iter().filter(|(_, s)| *s == slot).count();
+    let sample = recent
+        .iter()
+        .take(size)
+        .map(|(signature, _)| signature.clone())
+        .collect();
+    (sample, in_slot)
+}
+
+/// How much of a block to ask the RPC for: the least that the entropy
+/// sources derived from it need
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockDetail {
+    transactions: TransactionDetails,
+    /// Rewards name the leader (the fee reward's recipient)
+    rewards: bool,
+}
+
+impl BlockDetail {
+    /// The detail covering every source in `sources`
+    ///
+    /// Hashes and the block height come with any block; signatures cover the
+    /// sample and count, account keys and fees cover fees and vote counts,
+    /// and the enhanced sources need every transaction parsed.
+    pub fn for_sources(sources: &[BlockDataSource]) -> Self {
+        let rank = |details: TransactionDetails| match details {
+            TransactionDetails::None => 0,
+            TransactionDetails::Signatures => 1,
+            TransactionDetails::Accounts => 2,
+            TransactionDetails::Full => 3,
+        };
+        let mut detail = Self {
+            transactions: TransactionDetails::None,
+            rewards: false,
+        };
+        for source in sources {
+            let transactions = match source {
+                BlockDataSource::Blockhash
+                | BlockDataSource::PreviousBlockhash
+                | BlockDataSource::Rewards
+                | BlockDataSource::Leader => TransactionDetails::None,
+                BlockDataSource::TransactionRoot | BlockDataSource::TransactionCount => {
+                    TransactionDetails::Signatures
+                }
+                BlockDataSource::TotalFees | BlockDataSource::VoteCounts => {
+                    TransactionDetails::Accounts
+                }
+                BlockDataSource::ProgramInvocations
+                | BlockDataSource::NftMints
+                | BlockDataSource::TokenTransfers => TransactionDetails::Full,
+            };
+            if rank(transactions) > rank(detail.transactions) {
+                detail.transactions = transactions;
+            }
+            detail.rewards |= *source == BlockDataSource::Leader;
+        }
+        detail
+    }
+
+    /// Whether transactions come back parsed, for the enhanced sources
+    fn parsed(&self) -> bool {
+        self.transactions == TransactionDetails::Full
+    }
+}
+
+/// What a block's transactions add up to
+#[derive(Debug, Default, PartialEq)]
+struct TransactionTally {
+    /// Each transaction's first signature, in block order
+    signatures: Vec<String>,
+    total_fees: u64,
+    votes: usize,
+    non_votes: usize,
+}
+
+/// Tally a block fetched with `TransactionDetails::Accounts`, or with full
+/// details in `jsonParsed` form (enhanced RPC)
+fn tally_transactions(transactions: &[EncodedTransactionWithStatusMeta]) -> TransactionTally {
+    let mut tally = TransactionTally::default();
+    for transaction in transactions {
+        tally.total_fees += transaction.meta.as_ref().map_or(0, |meta| meta.fee);
+        let (signatures, vote) = match &transaction.transaction {
+            EncodedTransaction::Accounts(accounts) => (
+                &accounts.signatures,
+                accounts
+                    .account_keys
+                    .iter()
+                    .any(|account| account.pubkey == VOTE_PROGRAM_ID),
+            ),
+            EncodedTransaction::Json(UiTransaction {
+                signatures,
+                message: UiMessage::Parsed(message),
+            }) => (
+                signatures,
+                message
+                    .account_keys
+                    .iter()
+                    .any(|account| account.pubkey == VOTE_PROGRAM_ID),
+            ),
+            _ => continue,
+        };
+        if let Some(signature) = signatures.first() {
+            tally.signatures.push(signature.clone());
+        }
+        if vote {
+            tally.votes += 1;
+        } else {
+            tally.non_votes += 1;
+        }
+    }
+    tally
+}
+
+/// The validator credited with the block's fees, i.e. its leader
+fn block_leader(rewards: &[Reward]) -> Option<String> {
+    rewards
+        .iter()
+        .find(|reward| reward.reward_type == Some(RewardType::Fee))
+        .map(|reward| reward.pubkey.clone())
+}
+
+/// The epoch `slot` falls in, counted from the current epoch in `info`
+///
+/// Epochs past the warmup all hold `slots_in_epoch` slots, so earlier ones are
+/// whole epochs back from the current one's first slot.
+pub fn epoch_of_slot(info: &EpochInfo, slot: u64) -> u64 {
+    let slots_in_epoch = info.slots_in_epoch.max(1);
+    let first_slot = info.absolute_slot.saturating_sub(info.slot_index);
+    if slot >= first_slot {
+        info.epoch + (slot - first_slot) / slots_in_epoch
+    } else {
+        info.epoch
+            .saturating_sub((first_slot - slot).div_ceil(slots_in_epoch))
+    }
+}
+
+/// Error codes the RPC uses when a slot simply has no block; the endpoint itself is fine
+const MISSING_BLOCK_ERROR_CODES: [i64; 3] = [
+    -32004, // Block not available for slot
+    -32007, // Slot was skipped or missing due to ledger jump
+    -32009, // Slot missing in long-term storage
+];
+
+/// Error code for a slot the ledger skipped; at finalized commitment it means
+/// no block there survived
+const SKIPPED_SLOT_ERROR_CODE: i64 = -32007;
+
+/// Error code (invalid params) for a slot outside the epochs whose leader
+/// schedule the RPC still has
+const LEADER_SCHEDULE_UNAVAILABLE_ERROR_CODE: i64 = -32602;
+
+/// A single RPC endpoint with its health bookkeeping
+struct RpcEndpoint {
+    url: String,
+    client: RpcClient,
+    consecutive_failures: AtomicU32,
+    cooldown_until: Mutex<Option<Instant>>,
+}
+
+impl RpcEndpoint {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            client: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            consecutive_failures: AtomicU32::new(0),
+            cooldown_until: Mutex::new(None),
+        }
+    }
+
+    /// Whether the endpoint is currently benched after failures
+    fn in_cooldown(&self) -> bool {
+        let cooldown_until = self.cooldown_until.lock().unwrap_or_else(|e| e.into_inner());
+        cooldown_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.cooldown_until.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
 
-        sources
+    /// Bench the endpoint, doubling the cooldown with each consecutive failure
+    fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let cooldown_secs = RPC_FAILURE_COOLDOWN_SECS
+            .saturating_mul(1u64 << (failures - 1).min(16))
+            .min(RPC_MAX_COOLDOWN_SECS);
+        *self.cooldown_until.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(Instant::now() + Duration::from_secs(cooldown_secs));
     }
+
+    fn health(&self) -> EndpointHealth {
+        EndpointHealth {
+            url: self.url.clone(),
+            consecutive_failures: self.consecutive_failures.load(Ordering::Relaxed),
+            healthy: !self.in_cooldown(),
+        }
+    }
+}
+
+/// Health snapshot of one configured RPC endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub consecutive_failures: u32,
+    pub healthy: bool,
 }
 
 /// Solana blockchain client using official SDK
-/// Uses Arc to allow sharing across async tasks
+/// Rotates across the configured RPC endpoints, failing over when one errors
 pub struct SolanaClient {
-    client: Arc<RpcClient>,
-    rpc_url: String,
+    endpoints: Vec<Arc<RpcEndpoint>>,
+    current: AtomicUsize,
+    program_filter: Option<Pubkey>,
+    sample_size: usize,
+    detail: BlockDetail,
+    breaker: CircuitBreaker,
+    throttle: RpcThrottle,
+    retry: RetryPolicy,
+}
+
+/// Why a pass over the RPC endpoints failed
+enum PassFailure {
+    /// An endpoint answered without the data, or the call itself broke;
+    /// another pass wouldn't help
+    Final(ChainVerseError),
+    /// Every endpoint failed or was rate limited; the last error, if any
+    Unavailable(Option<anyhow::Error>),
+    /// Every endpoint was up but rate limited us; the last 429
+    RateLimited(anyhow::Error),
+}
+
+impl fmt::Display for PassFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PassFailure::Final(e) => write!(f, "{}", e),
+            PassFailure::Unavailable(Some(e)) => write!(f, "{:#}", e),
+            PassFailure::Unavailable(None) => write!(f, "No RPC endpoints configured"),
+            PassFailure::RateLimited(e) => write!(f, "{:#}", e),
+        }
+    }
 }
 
 impl SolanaClient {
@@ -54,54 +326,371 @@ impl SolanaClient {
 
     /// Create a new client with custom RPC URL
     pub fn with_url(url: &str) -> Self {
-        let client = RpcClient::new_with_commitment(
-            url.to_string(),
-            CommitmentConfig::confirmed(),
-        );
+        Self::with_urls(&[url.to_string()])
+    }
+
+    /// Create a client that fails over across several RPC URLs, in priority order
+    pub fn with_urls(urls: &[String]) -> Self {
+        let mut endpoints: Vec<Arc<RpcEndpoint>> =
+            urls.iter().map(|url| Arc::new(RpcEndpoint::new(url))).collect();
+        if endpoints.is_empty() {
+            endpoints.push(Arc::new(RpcEndpoint::new(MAINNET_RPC_URL)));
+        }
         Self {
-            client: Arc::new(client),
-            rpc_url: url.to_string(),
+            endpoints,
+            current: AtomicUsize::new(0),
+            program_filter: None,
+            sample_size: DEFAULT_SAMPLE_SIGNATURES,
+            detail: BlockDetail::for_sources(BlockDataSource::all()),
+            breaker: CircuitBreaker::new("solana_rpc"),
+            throttle: RpcThrottle::default(),
+            retry: RetryPolicy::rpc(),
+        }
+    }
+
+    /// Share a circuit breaker with other clients of the same RPC endpoints
+    ///
+    /// The breaker counts calls that failed on every endpoint; a missing block
+    /// is an answer, not a failure.
+    pub fn with_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    pub fn breaker(&self) -> &CircuitBreaker {
+        &self.breaker
+    }
+
+    /// Share RPC pacing with other clients of the same endpoints, so together
+    /// they stay under each endpoint's rate limit
+    pub fn with_throttle(mut self, throttle: RpcThrottle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// How many passes over the endpoints a call gets, and the backoff between them
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sample block signatures only from transactions that touch `program`
+    ///
+    /// Blocks keep their blockhash, but `sample_signatures` and
+    /// `transaction_count` then describe that program's activity, so derived
+    /// keywords follow one on-chain community (a DEX, an NFT marketplace).
+    pub fn with_program_filter(mut self, program: Pubkey) -> Self {
+        self.program_filter = Some(program);
+        self
+    }
+
+    /// Keep `size` transaction signatures per block in `sample_signatures`
+    pub fn with_sample_size(mut self, size: usize) -> Self {
+        self.sample_size = size;
+        self
+    }
+
+    /// Fetch blocks with every transaction parsed and fill in
+    /// `BlockInfo::enhanced`; needs the `enhanced-rpc` feature and an
+    /// endpoint that serves full parsed blocks (see `EnhancedBlockData`)
+    pub fn with_enhanced_rpc(mut self) -> Self {
+        let sources: Vec<BlockDataSource> = BlockDataSource::all()
+            .iter()
+            .chain(BlockDataSource::enhanced())
+            .copied()
+            .collect();
+        self.detail = BlockDetail::for_sources(&sources);
+        self
+    }
+
+    /// Health of every configured endpoint
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.endpoints.iter().map(|endpoint| endpoint.health()).collect()
+    }
+
+    /// Endpoint indices to try, starting from the active one; benched endpoints go last
+    fn endpoint_order(&self) -> Vec<usize> {
+        let count = self.endpoints.len();
+        let start = self.current.load(Ordering::Relaxed);
+        let (ready, benched): (Vec<usize>, Vec<usize>) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .partition(|&index| !self.endpoints[index].in_cooldown());
+        ready.into_iter().chain(benched).collect()
+    }
+
+    /// Run a blocking RPC call, failing over to the next endpoint on transport errors
+    ///
+    /// Calls wait their turn in the shared `RpcThrottle`. The RPC sender
+    /// itself retries a 429 a few times, sleeping for the Retry-After the
+    /// endpoint sent; only a 429 that outlasts those retries pauses the
+    /// endpoint for every caller, with a backoff, and fails over without
+    /// benching it.
+    ///
+    /// When every endpoint fails, the client's `RetryPolicy` decides whether
+    /// to wait and make another pass. Giving up then counts against the
+    /// breaker, unless every endpoint was merely rate limiting us.
+    async fn call<T, F>(&self, op: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: Fn(&RpcClient) -> anyhow::Result<T> + Send + Sync + 'static,
+    {
+        self.breaker
+            .acquire()
+            .map_err(|open| ChainVerseError::Rpc(open.into()))?;
+        let op = Arc::new(op);
+
+        let result = self
+            .retry
+            .run(
+                "Solana RPC call",
+                |failure| {
+                    matches!(
+                        failure,
+                        PassFailure::Unavailable(_) | PassFailure::RateLimited(_)
+                    )
+                },
+                |_| self.call_endpoints(Arc::clone(&op)),
+            )
+            .await;
+        match result {
+            Ok(value) => Ok(value),
+            Err(PassFailure::Final(e)) => Err(e),
+            Err(PassFailure::Unavailable(last_error)) => {
+                self.breaker.record_failure();
+                Err(last_error.map_or_else(
+                    || ChainVerseError::Config("No RPC endpoints configured".to_string()),
+                    ChainVerseError::Rpc,
+                ))
+            }
+            // The endpoints are up, so the breaker has nothing to protect
+            Err(PassFailure::RateLimited(e)) => Err(ChainVerseError::Rpc(e)),
         }
     }
 
-    /// Get the RPC URL being used
-    pub fn rpc_url(&self) -> &str {
-        &self.rpc_url
+    /// One pass of `call` over the endpoints, starting from the active one
+    async fn call_endpoints<T, F>(&self, op: Arc<F>) -> std::result::Result<T, PassFailure>
+    where
+        T: Send + 'static,
+        F: Fn(&RpcClient) -> anyhow::Result<T> + Send + Sync + 'static,
+    {
+        let mut last_error = None;
+        let mut all_rate_limited = true;
+
+        for index in self.endpoint_order() {
+            let endpoint = Arc::clone(&self.endpoints[index]);
+            self.throttle.acquire(&endpoint.url).await;
+
+            let attempt = Arc::clone(&op);
+            let result = tokio::task::spawn_blocking(move || attempt(&endpoint.client))
+                .await
+                .map_err(|e| PassFailure::Final(ChainVerseError::Rpc(e.into())))?;
+            let endpoint = &self.endpoints[index];
+
+            match result {
+                Ok(value) => {
+                    self.throttle.record_success(&endpoint.url);
+                    endpoint.record_success();
+                    self.breaker.record_success();
+                    self.current.store(index, Ordering::Relaxed);
+                    return Ok(value);
+                }
+                Err(e) if is_rate_limited(&e) => {
+                    // The endpoint works, it just wants fewer calls
+                    metrics::RPC_FAILURES.inc();
+                    self.throttle.record_throttled(&endpoint.url, None);
+                    last_error = Some(e);
+                }
+                Err(e) if !is_endpoint_failure(&e) => {
+                    // The endpoint answered; the data just isn't there
+                    metrics::RPC_FAILURES.inc();
+                    endpoint.record_success();
+                    self.breaker.record_success();
+                    return Err(PassFailure::Final(ChainVerseError::Rpc(e)));
+                }
+                Err(e) => {
+                    metrics::RPC_FAILURES.inc();
+                    endpoint.record_failure();
+                    all_rate_limited = false;
+                    if self.endpoints.len() > 1 {
+                        warn!(url = %endpoint.url, error = %e, "RPC endpoint failed, failing over");
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if all_rate_limited => Err(PassFailure::RateLimited(e)),
+            last_error => Err(PassFailure::Unavailable(last_error)),
+        }
     }
 
     /// Get the current slot number (async wrapper)
     pub async fn get_current_slot(&self) -> Result<u64> {
-        let client = Arc::clone(&self.client);
-        tokio::task::spawn_blocking(move || {
-            client.get_slot().context("Failed to get current slot")
-        })
-        .await?
+        self.call(|client| client.get_slot().context("Failed to get current slot"))
+            .await
     }
 
     /// Get the current epoch info (async wrapper)
-    pub async fn get_epoch_info(&self) -> Result<solana_sdk::epoch_info::EpochInfo> {
-        let client = Arc::clone(&self.client);
-        tokio::task::spawn_blocking(move || {
-            client.get_epoch_info().context("Failed to get epoch info")
-        })
-        .await?
+    pub async fn get_epoch_info(&self) -> Result<EpochInfo> {
+        self.call(|client| client.get_epoch_info().context("Failed to get epoch info"))
+            .await
     }
 
     /// Get rich block information for a specific slot (async wrapper)
+    ///
+    /// Asks for enough of the block to derive from every source the client
+    /// serves (see `with_enhanced_rpc`).
     pub async fn get_block(&self, slot: u64) -> Result<BlockInfo> {
-        let client = Arc::clone(&self.client);
-        tokio::task::spawn_blocking(move || {
-            Self::get_block_sync(&client, slot)
+        self.get_block_with_detail(slot, self.detail).await
+    }
+
+    /// Get a block with only the `detail` its caller needs; fields it doesn't
+    /// cover are left empty
+    pub async fn get_block_with_detail(&self, slot: u64, detail: BlockDetail) -> Result<BlockInfo> {
+        let started = Instant::now();
+        let program = self.program_filter;
+        let sample_size = self.sample_size;
+        let result = self
+            .call(move |client| {
+                Self::get_block_sync(client, slot, program.as_ref(), sample_size, detail)
+            })
+            .await;
+        let elapsed = started.elapsed();
+        metrics::BLOCK_FETCH_SECONDS.observe(elapsed.as_secs_f64());
+        debug!(
+            slot,
+            ok = result.is_ok(),
+            duration_ms = elapsed.as_millis() as u64,
+            "Fetched block"
+        );
+        result
+    }
+
+    /// Get the unix timestamp at which a slot was produced, or None if the slot was skipped
+    pub async fn get_block_time(&self, slot: u64) -> Result<Option<i64>> {
+        let result = self
+            .call(move |client| {
+                client
+                    .get_block_time(slot)
+                    .map_err(anyhow::Error::from)
+                    .context(format!("Failed to get block time for slot {}", slot))
+            })
+            .await;
+
+        match result {
+            Ok(time) => Ok(Some(time)),
+            Err(ChainVerseError::Rpc(e)) if !is_endpoint_failure(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The blockhash the cluster finalized at `slot`, or None when the finalized
+    /// ledger skipped the slot
+    ///
+    /// Blocks are otherwise fetched at confirmed commitment, which a reorg can
+    /// still undo. Fails while the block isn't available at finalized
+    /// commitment, so a slot that can't be checked yet isn't taken as abandoned.
+    pub async fn get_finalized_blockhash(&self, slot: u64) -> Result<Option<String>> {
+        let result = self
+            .call(move |client| {
+                let config = RpcBlockConfig {
+                    encoding: None,
+                    transaction_details: Some(TransactionDetails::None),
+                    rewards: Some(false),
+                    commitment: Some(CommitmentConfig::finalized()),
+                    max_supported_transaction_version: Some(0),
+                };
+                client
+                    .get_block_with_config(slot, config)
+                    .map_err(anyhow::Error::from)
+                    .context(format!("Failed to get finalized block for slot {}", slot))
+            })
+            .await;
+
+        match result {
+            Ok(block) => Ok(Some(block.blockhash)),
+            Err(ChainVerseError::Rpc(e)) if rpc_error_code(&e) == Some(SKIPPED_SLOT_ERROR_CODE) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sign and send a transaction built from `instructions`, waiting for confirmation
+    ///
+    /// The transaction is signed once, before any failover. A send that timed
+    /// out may still land, so each attempt first looks the signature up and
+    /// waits on it instead of sending again; resending the same signed
+    /// transaction can't execute it twice either way.
+    pub async fn send_instructions(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: Arc<Keypair>,
+    ) -> Result<String> {
+        let blockhash = self
+            .call(|client| {
+                client
+                    .get_latest_blockhash()
+                    .context("Failed to get latest blockhash")
+            })
+            .await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer.as_ref()],
+            blockhash,
+        );
+        let signature = transaction.signatures[0];
+
+        self.call(move |client| {
+            let landed = client
+                .get_signature_statuses(&[signature])
+                .context("Failed to get signature status")?
+                .value
+                .into_iter()
+                .next()
+                .flatten();
+            match landed {
+                Some(status) => {
+                    if let Some(err) = status.err {
+                        anyhow::bail!("Transaction {} failed: {}", signature, err);
+                    }
+                    client
+                        .poll_for_signature(&signature)
+                        .context("Failed to confirm transaction")?;
+                }
+                None => {
+                    client
+                        .send_and_confirm_transaction(&transaction)
+                        .context("Failed to send transaction")?;
+                }
+            }
+            Ok(signature.to_string())
         })
-        .await?
+        .await
     }
 
     /// Synchronous block fetch (internal)
-    fn get_block_sync(client: &RpcClient, slot: u64) -> Result<BlockInfo> {
+    fn get_block_sync(
+        client: &RpcClient,
+        slot: u64,
+        program: Option<&Pubkey>,
+        sample_size: usize,
+        detail: BlockDetail,
+    ) -> anyhow::Result<BlockInfo> {
+        // Accounts details carry each transaction's signatures, fee, and
+        // account keys (enough to spot votes) without the instructions; the
+        // enhanced sources need the parsed instructions as well
+        let encoding = if detail.parsed() {
+            UiTransactionEncoding::JsonParsed
+        } else {
+            UiTransactionEncoding::Base64
+        };
         let config = RpcBlockConfig {
-            encoding: Some(UiTransactionEncoding::Base64),
-            transaction_details: Some(TransactionDetails::Signatures),
-            rewards: Some(false),
+            encoding: Some(encoding),
+            transaction_details: Some(detail.transactions),
+            rewards: Some(detail.rewards),
             commitment: Some(CommitmentConfig::confirmed()),
             max_supported_transaction_version: Some(0),
         };
@@ -110,16 +699,34 @@ impl SolanaClient {
             .get_block_with_config(slot, config)
             .context(format!("Failed to get block for slot {}", slot))?;
 
-        // Extract sample signatures (up to 5 for entropy)
-        let sample_signatures: Vec<String> = block
-            .signatures
-            .clone()
-            .unwrap_or_default()
-            .into_iter()
-            .take(5)
-            .collect();
-
-        let transaction_count = block.signatures.as_ref().map(|s| s.len()).unwrap_or(0);
+        let transactions = block.transactions.as_deref().unwrap_or_default();
+        let mut tally = tally_transactions(transactions);
+        if let Some(signatures) = block.signatures {
+            tally.signatures = signatures;
+        }
+        let enhanced = if detail.parsed() {
+            Some(enhanced_rpc::analyze(transactions)?)
+        } else {
+            None
+        };
+        let leader = match block_leader(block.rewards.as_deref().unwrap_or_default()) {
+            Some(leader) => Some(leader),
+            None if detail.rewards => Self::get_slot_leader_sync(client, slot)?,
+            None => None,
+        };
+        let (sample_signatures, transaction_count) = match program {
+            // Without the block's signatures there's nowhere to search back from
+            Some(_) if detail.transactions == TransactionDetails::None => (Vec::new(), 0),
+            Some(program) => {
+                let history = Self::get_program_history_sync(client, program, &tally.signatures)?;
+                program_sample(slot, &history, sample_size)
+            }
+            None => {
+                let sample_signatures: Vec<String> =
+                    tally.signatures.iter().take(sample_size).cloned().collect();
+                (sample_signatures, tally.signatures.len())
+            }
+        };
 
         Ok(BlockInfo {
             slot,
@@ -130,9 +737,64 @@ impl SolanaClient {
             parent_slot: block.parent_slot,
             transaction_count,
             sample_signatures,
+            total_fees: tally.total_fees,
+            vote_transaction_count: tally.votes,
+            non_vote_transaction_count: tally.non_votes,
+            leader,
+            enhanced,
         })
     }
 
+    /// The slot's leader according to the leader schedule, for blocks whose
+    /// rewards don't name it (no fee reward, or rewards not returned)
+    ///
+    /// The schedule only reaches back a few epochs, so a slot before it has no
+    /// leader rather than an error; any other failure fails the block fetch.
+    fn get_slot_leader_sync(client: &RpcClient, slot: u64) -> anyhow::Result<Option<String>> {
+        match client.get_slot_leaders(slot, 1) {
+            Ok(leaders) => Ok(leaders.first().map(|leader| leader.to_string())),
+            Err(e) => {
+                let e = anyhow::Error::from(e);
+                if rpc_error_code(&e) == Some(LEADER_SCHEDULE_UNAVAILABLE_ERROR_CODE) {
+                    return Ok(None);
+                }
+                Err(e.context(format!("Failed to get leader for slot {}", slot)))
+            }
+        }
+    }
+
+    /// Successful signatures touching `program`, newest first, as (signature, slot)
+    ///
+    /// Searches back from the block's last signature, so the results start
+    /// inside the block itself (that last transaction is the one blind spot).
+    fn get_program_history_sync(
+        client: &RpcClient,
+        program: &Pubkey,
+        block_signatures: &[String],
+    ) -> anyhow::Result<Vec<(String, u64)>> {
+        let before = block_signatures
+            .last()
+            .map(|signature| Signature::from_str(signature))
+            .transpose()
+            .context("Block returned a malformed signature")?;
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: Some(PROGRAM_SIGNATURE_LIMIT),
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+
+        let statuses = client
+            .get_signatures_for_address_with_config(program, config)
+            .context(format!("Failed to get signatures for program {}", program))?;
+
+        Ok(statuses
+            .into_iter()
+            .filter(|status| status.err.is_none())
+            .map(|status| (status.signature, status.slot))
+            .collect())
+    }
+
     /// Get the most recent confirmed block (async wrapper)
     pub async fn get_latest_block(&self) -> Result<BlockInfo> {
         let slot = self.get_current_slot().await?;
@@ -141,67 +803,362 @@ impl SolanaClient {
         self.get_block(confirmed_slot).await
     }
 
-    /// Get multiple blocks for richer data (async wrapper)
+    /// Get multiple blocks for richer data, fetched concurrently (async wrapper)
     pub async fn get_recent_blocks(&self, count: usize) -> Result<Vec<BlockInfo>> {
         let current_slot = self.get_current_slot().await?;
-        let client = Arc::clone(&self.client);
-
-        tokio::task::spawn_blocking(move || {
-            let mut blocks = Vec::with_capacity(count);
-            let interval = 100; // ~40 seconds apart
-
-            for i in 0..count {
-                let target_slot = current_slot.saturating_sub(CONFIRMATION_SLOTS + (i as u64 * interval));
-                match Self::get_block_sync(&client, target_slot) {
-                    Ok(block) => blocks.push(block),
-                    Err(e) => {
-                        eprintln!("Slot {} unavailable: {}, trying nearby", target_slot, e);
-                        for offset in 1..=5 {
-                            if let Ok(block) = Self::get_block_sync(&client, target_slot.saturating_sub(offset)) {
-                                blocks.push(block);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+        let interval = 100; // ~40 seconds apart
+
+        // A skipped target falls back to the few slots before it
+        let candidates = (0..count as u64).map(|i| {
+            let target_slot = current_slot.saturating_sub(CONFIRMATION_SLOTS + i * interval);
+            (0..=5).map(move |offset| target_slot.saturating_sub(offset))
+        });
 
-            Ok(blocks)
+        Ok(self
+            .get_first_blocks(candidates)
+            .await
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Fetch the first available block from each list of candidate slots
+    ///
+    /// Up to BLOCK_FETCH_CONCURRENCY lists are worked on at once; within a
+    /// list, slots are tried in order. Results line up with `candidates`,
+    /// with None where no slot in the list could be fetched.
+    pub async fn get_first_blocks<I>(
+        &self,
+        candidates: impl IntoIterator<Item = I>,
+    ) -> Vec<Option<BlockInfo>>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        first_available(candidates, BLOCK_FETCH_CONCURRENCY, |slot| {
+            self.get_block(slot)
         })
-        .await?
+        .await
     }
 
     /// Check if the RPC connection is healthy (async wrapper)
+    /// Healthy when any configured endpoint reports healthy
     pub async fn health_check(&self) -> Result<bool> {
-        let client = Arc::clone(&self.client);
-        tokio::task::spawn_blocking(move || {
-            match client.get_health() {
-                Ok(_) => Ok(true),
-                Err(e) => {
-                    eprintln!("RPC health check failed: {}", e);
-                    Ok(false)
-                }
+        match self
+            .call(|client| client.get_health().context("RPC health check failed"))
+            .await
+        {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                warn!(error = %e, "RPC health check failed");
+                Ok(false)
             }
+        }
+    }
+}
+
+/// Consecutive skipped slots stepped over when probing for a produced block
+const SLOT_PROBE_LIMIT: u64 = 32;
+
+/// Times the search bracket may double before giving up
+const MAX_BRACKET_EXPANSIONS: u32 = 12;
+
+/// Maps timestamps to slots by binary-searching block times
+///
+/// Slot production rate drifts over time, so the linear estimate from
+/// ESTIMATED_SLOTS_PER_DAY is only used to seed the search. Day boundaries
+/// are cached, so locating consecutive days costs one search each.
+pub struct SlotLocator<'a> {
+    client: &'a SolanaClient,
+    timezone: Timezone,
+    days: Mutex<HashMap<NaiveDate, u64>>,
+}
+
+impl<'a> SlotLocator<'a> {
+    pub fn new(client: &'a SolanaClient) -> Self {
+        Self {
+            client,
+            timezone: Timezone::default(),
+            days: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count days from midnight in `timezone` instead of UTC
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// First slot produced at or after the start of `day` in the locator's timezone
+    pub async fn first_slot_of_day(&self, day: NaiveDate) -> Result<u64> {
+        if let Some(slot) = self.days.lock().unwrap_or_else(|e| e.into_inner()).get(&day) {
+            return Ok(*slot);
+        }
+
+        let midnight = self.timezone.midnight(day).timestamp();
+        let slot = self.first_slot_at(midnight).await?;
+
+        self.days
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(day, slot);
+        Ok(slot)
+    }
+
+    /// First slot produced at or after the unix timestamp `target`
+    pub async fn first_slot_at(&self, target: i64) -> Result<u64> {
+        let current_slot = self.client.get_current_slot().await?;
+        let now = Utc::now().timestamp();
+        if target > now {
+            return Err(ChainVerseError::Rpc(anyhow::anyhow!(
+                "Timestamp {} is in the future",
+                target
+            )));
+        }
+
+        let seconds_ago = (now - target) as u64;
+        let estimate = current_slot.saturating_sub(seconds_ago * ESTIMATED_SLOTS_PER_DAY / ONE_DAY);
+
+        let slot = locate_slot(target, estimate, current_slot, move |slot, limit| {
+            self.produced_slot_at_or_after(slot, limit)
         })
-        .await?
-    }
-
-    /// Get the current block production rate (slots per second) (async wrapper)
-    pub async fn get_block_production_rate(&self) -> Result<f64> {
-        let client = Arc::clone(&self.client);
-        tokio::task::spawn_blocking(move || {
-            let samples = client
-                .get_recent_performance_samples(Some(1))
-                .context("Failed to get performance samples")?;
-
-            if let Some(sample) = samples.first() {
-                let slots_per_second = sample.num_slots as f64 / sample.sample_period_secs as f64;
-                Ok(slots_per_second)
-            } else {
-                Ok(2.0)
+        .await?;
+        debug!(target, estimate, slot, "Located slot for timestamp");
+        Ok(slot)
+    }
+
+    /// The first slot in `slot..=limit` (within SLOT_PROBE_LIMIT) that has a block, with its time
+    async fn produced_slot_at_or_after(&self, slot: u64, limit: u64) -> Result<Option<(u64, i64)>> {
+        for candidate in slot..=limit.min(slot + SLOT_PROBE_LIMIT) {
+            if let Some(time) = self.client.get_block_time(candidate).await? {
+                return Ok(Some((candidate, time)));
             }
-        })
-        .await?
+        }
+        Ok(None)
+    }
+}
+
+/// Run `fetch` over each list of candidate slots, at most `concurrency` lists at once
+///
+/// Each list stops at its first successful fetch. Results keep the order of
+/// `candidates`, whatever order the fetches finish in.
+async fn first_available<C, I, T, F, Fut>(
+    candidates: C,
+    concurrency: usize,
+    fetch: F,
+) -> Vec<Option<T>>
+where
+    C: IntoIterator<Item = I>,
+    I: IntoIterator<Item = u64>,
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let permits = Semaphore::new(concurrency.max(1));
+    let fetch = &fetch;
+    let permits = &permits;
+
+    futures::future::join_all(candidates.into_iter().map(|slots| async move {
+        let Ok(_permit) = permits.acquire().await else {
+            return None;
+        };
+        for slot in slots {
+            match fetch(slot).await {
+                Ok(value) => return Some(value),
+                Err(e) => debug!(slot, error = %e, "Slot unavailable, trying the next candidate"),
+            }
+        }
+        None
+    }))
+    .await
+}
+
+/// Find the first produced slot whose block time is at or after `target`
+///
+/// `time_at(slot, limit)` returns the first produced slot in `slot..=limit`
+/// and its block time, looking at most SLOT_PROBE_LIMIT slots ahead. The
+/// search brackets `estimate`, widening until the bracket straddles `target`,
+/// then bisects.
+async fn locate_slot<F, Fut>(target: i64, estimate: u64, current_slot: u64, time_at: F) -> Result<u64>
+where
+    F: Fn(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Option<(u64, i64)>>>,
+{
+    let estimate = estimate.min(current_slot);
+    let mut margin = (ESTIMATED_SLOTS_PER_DAY / 24).max(1);
+
+    // Lower bound: a slot produced before the target (or slot 0)
+    let mut lo = estimate.saturating_sub(margin);
+    for _ in 0..MAX_BRACKET_EXPANSIONS {
+        match first_produced(&time_at, lo, current_slot).await? {
+            Some((_, time)) if time >= target && lo > 0 => {
+                margin *= 2;
+                lo = estimate.saturating_sub(margin);
+            }
+            _ => break,
+        }
+    }
+
+    // Upper bound: a slot produced at or after the target
+    let mut margin = (ESTIMATED_SLOTS_PER_DAY / 24).max(1);
+    let mut hi = (estimate + margin).min(current_slot);
+    for _ in 0..MAX_BRACKET_EXPANSIONS {
+        match first_produced(&time_at, hi, current_slot).await? {
+            Some((_, time)) if time < target => {
+                if hi == current_slot {
+                    return Err(ChainVerseError::Rpc(anyhow::anyhow!(
+                        "No block at or after timestamp {} yet",
+                        target
+                    )));
+                }
+                margin *= 2;
+                hi = (estimate + margin).min(current_slot);
+            }
+            _ => break,
+        }
+    }
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match first_produced(&time_at, mid, hi).await? {
+            Some((_, time)) if time >= target => hi = mid,
+            Some((produced, _)) => lo = produced + 1,
+            // Nothing produced between mid and hi, so the answer is at or before mid
+            None => hi = mid,
+        }
+    }
+
+    match first_produced(&time_at, lo, current_slot).await? {
+        Some((produced, _)) => Ok(produced),
+        None => Err(ChainVerseError::Rpc(anyhow::anyhow!(
+            "No produced block found near slot {}",
+            lo
+        ))),
+    }
+}
+
+/// `time_at` over all of `slot..=limit`, stepping past runs of skipped slots
+/// longer than one call looks ahead
+async fn first_produced<F, Fut>(time_at: &F, slot: u64, limit: u64) -> Result<Option<(u64, i64)>>
+where
+    F: Fn(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Option<(u64, i64)>>>,
+{
+    let mut probe = slot;
+    loop {
+        match time_at(probe, limit).await? {
+            Some(found) => return Ok(Some(found)),
+            None if probe + SLOT_PROBE_LIMIT >= limit => return Ok(None),
+            None => probe += SLOT_PROBE_LIMIT + 1,
+        }
+    }
+}
+
+/// Why a block stood out from the ones before it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainEvent {
+    /// Far more transactions than the recent average
+    TransactionSurge {
+        slot: u64,
+        transactions: usize,
+        average: f64,
+    },
+    /// A long run of skipped slots right before the block
+    SlotGap { slot: u64, skipped: u64 },
+}
+
+/// Spots unusual blocks in a stream of recent ones
+///
+/// A block is notable when it carries EVENT_SURGE_FACTOR times the average
+/// transaction count of the last EVENT_BASELINE_BLOCKS observed blocks, or
+/// when at least EVENT_MIN_SLOT_GAP slots before it were skipped. After an
+/// event, blocks within EVENT_COOLDOWN_SLOTS are still measured but not reported,
+/// so one episode triggers once.
+#[derive(Debug, Default)]
+pub struct EventWatcher {
+    recent_counts: VecDeque<usize>,
+    last_slot: Option<u64>,
+    last_event_slot: Option<u64>,
+}
+
+impl EventWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `block`, returning what made it notable, if anything
+    pub fn observe(&mut self, block: &BlockInfo) -> Option<ChainEvent> {
+        if self.last_slot.is_some_and(|last| block.slot <= last) {
+            return None;
+        }
+        self.last_slot = Some(block.slot);
+
+        // The average needs a few blocks behind it before it means anything
+        let baseline = (self.recent_counts.len() >= EVENT_BASELINE_BLOCKS / 2).then(|| {
+            self.recent_counts.iter().sum::<usize>() as f64 / self.recent_counts.len() as f64
+        });
+        if self.recent_counts.len() == EVENT_BASELINE_BLOCKS {
+            self.recent_counts.pop_front();
+        }
+        self.recent_counts.push_back(block.transaction_count);
+
+        let skipped = block.slot.saturating_sub(block.parent_slot + 1);
+        let event = if skipped >= EVENT_MIN_SLOT_GAP {
+            Some(ChainEvent::SlotGap {
+                slot: block.slot,
+                skipped,
+            })
+        } else {
+            baseline
+                .filter(|&average| {
+                    average > 0.0 && block.transaction_count as f64 >= average * EVENT_SURGE_FACTOR
+                })
+                .map(|average| ChainEvent::TransactionSurge {
+                    slot: block.slot,
+                    transactions: block.transaction_count,
+                    average,
+                })
+        }?;
+
+        if self
+            .last_event_slot
+            .is_some_and(|last| block.slot < last + EVENT_COOLDOWN_SLOTS)
+        {
+            return None;
+        }
+        self.last_event_slot = Some(block.slot);
+        Some(event)
+    }
+}
+
+/// Parse a comma-separated list of RPC URLs, ignoring blanks
+pub fn parse_rpc_urls(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether an error means the endpoint itself misbehaved (so another one should be tried)
+fn is_endpoint_failure(error: &anyhow::Error) -> bool {
+    rpc_error_code(error).is_none_or(|code| !MISSING_BLOCK_ERROR_CODES.contains(&code))
+}
+
+/// The JSON-RPC error code the endpoint answered with, if it answered with one
+fn rpc_error_code(error: &anyhow::Error) -> Option<i64> {
+    match error.downcast_ref::<ClientError>().map(|e| e.kind()) {
+        Some(ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })) => Some(*code),
+        _ => None,
+    }
+}
+
+/// Whether the endpoint answered 429 Too Many Requests
+fn is_rate_limited(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<ClientError>().map(|e| e.kind()) {
+        Some(ClientErrorKind::Reqwest(e)) => {
+            e.status().is_some_and(|status| status.as_u16() == 429)
+        }
+        _ => false,
     }
 }
 
@@ -233,22 +1190,359 @@ mod tests {
     }
 
     #[test]
-    fn test_entropy_sources() {
-        let block = BlockInfo {
-            slot: 12345,
-            blockhash: "abc123".to_string(),
-            previous_blockhash: "xyz789".to_string(),
-            block_time: Some(1234567890),
-            block_height: Some(1000),
-            parent_slot: 12344,
-            transaction_count: 50,
-            sample_signatures: vec!["sig1".to_string(), "sig2".to_string()],
+    fn test_program_sample() {
+        let history: Vec<(String, u64)> = [("e", 12), ("d", 10), ("c", 10), ("b", 9), ("a", 7)]
+            .iter()
+            .map(|(sig, slot)| (sig.to_string(), *slot))
+            .collect();
+
+        let (sample, in_slot) = program_sample(10, &history, 5);
+        assert_eq!(sample, vec!["d", "c", "b", "a"]);
+        assert_eq!(in_slot, 2);
+        let (sample, _) = program_sample(10, &history, 2);
+        assert_eq!(sample, vec!["d", "c"]);
+
+        // A quiet slot still samples the program's latest earlier activity
+        let (sample, in_slot) = program_sample(8, &history, 5);
+        assert_eq!(sample, vec!["a"]);
+        assert_eq!(in_slot, 0);
+
+        assert_eq!(program_sample(5, &history, 5), (vec![], 0));
+    }
+
+    #[test]
+    fn test_tally_transactions() {
+        let transaction = |signature: &str, program: &str, fee: u64| {
+            serde_json::json!({
+                "transaction": {
+                    "signatures": [signature],
+                    "accountKeys": [
+                        {"pubkey": "payer", "writable": true, "signer": true, "source": "transaction"},
+                        {"pubkey": program, "writable": false, "signer": false, "source": "transaction"}
+                    ]
+                },
+                "meta": {
+                    "err": null,
+                    "status": {"Ok": null},
+                    "fee": fee,
+                    "preBalances": [],
+                    "postBalances": []
+                }
+            })
+        };
+        let transactions = serde_json::json!([
+            transaction("vote1", VOTE_PROGRAM_ID, 5000),
+            transaction("swap1", "SwapProgram", 12000),
+            transaction("vote2", VOTE_PROGRAM_ID, 5000),
+        ]);
+        let transactions: Vec<EncodedTransactionWithStatusMeta> =
+            serde_json::from_value(transactions).unwrap();
+
+        assert_eq!(
+            tally_transactions(&transactions),
+            TransactionTally {
+                signatures: vec!["vote1".into(), "swap1".into(), "vote2".into()],
+                total_fees: 22000,
+                votes: 2,
+                non_votes: 1,
+            }
+        );
+
+        let rewards: Vec<Reward> = serde_json::from_value(serde_json::json!([
+            {"pubkey": "staker", "lamports": 10, "postBalance": 10, "rewardType": "Staking", "commission": null},
+            {"pubkey": "leader1", "lamports": 11000, "postBalance": 99, "rewardType": "Fee", "commission": null}
+        ]))
+        .unwrap();
+        assert_eq!(block_leader(&rewards), Some("leader1".to_string()));
+        assert_eq!(block_leader(&[]), None);
+    }
+
+    #[test]
+    fn test_block_detail_for_sources() {
+        let detail = BlockDetail::for_sources(&[BlockDataSource::Blockhash]);
+        assert_eq!(detail.transactions, TransactionDetails::None);
+        assert!(!detail.rewards);
+
+        let detail =
+            BlockDetail::for_sources(&[BlockDataSource::Leader, BlockDataSource::TransactionCount]);
+        assert_eq!(detail.transactions, TransactionDetails::Signatures);
+        assert!(detail.rewards);
+
+        // The default client covers every built-in source
+        let detail = BlockDetail::for_sources(BlockDataSource::all());
+        assert_eq!(detail.transactions, TransactionDetails::Accounts);
+        assert!(detail.rewards);
+        assert!(!detail.parsed());
+
+        let detail = BlockDetail::for_sources(BlockDataSource::enhanced());
+        assert!(detail.parsed());
+        assert!(!detail.rewards);
+    }
+
+    #[test]
+    fn test_epoch_of_slot() {
+        let info = EpochInfo {
+            epoch: 800,
+            slot_index: 1_000,
+            slots_in_epoch: 432_000,
+            absolute_slot: 345_601_000,
+            block_height: 0,
+            transaction_count: None,
+        };
+        assert_eq!(epoch_of_slot(&info, 345_601_000), 800);
+        assert_eq!(epoch_of_slot(&info, 345_600_000), 800);
+        assert_eq!(epoch_of_slot(&info, 345_599_999), 799);
+        assert_eq!(epoch_of_slot(&info, 345_168_000), 799);
+        assert_eq!(epoch_of_slot(&info, 345_167_999), 798);
+        assert_eq!(epoch_of_slot(&info, 346_032_000), 801);
+        assert_eq!(epoch_of_slot(&info, 0), 0);
+    }
+
+    #[test]
+    fn test_event_watcher() {
+        let block = |slot: u64, parent_slot: u64, transaction_count: usize| BlockInfo {
+            slot,
+            blockhash: format!("hash{}", slot),
+            previous_blockhash: format!("hash{}", parent_slot),
+            block_time: None,
+            block_height: None,
+            parent_slot,
+            transaction_count,
+            sample_signatures: Vec::new(),
+            total_fees: 0,
+            vote_transaction_count: 0,
+            non_vote_transaction_count: transaction_count,
+            leader: None,
+            enhanced: None,
+        };
+        let mut watcher = EventWatcher::new();
+
+        // No surge is reported before there is a baseline
+        assert_eq!(watcher.observe(&block(100, 99, 5000)), None);
+        for slot in 101..100 + EVENT_BASELINE_BLOCKS as u64 {
+            assert_eq!(watcher.observe(&block(slot, slot - 1, 1000)), None);
+        }
+
+        let slot = 200;
+        match watcher.observe(&block(slot, slot - 1, 4000)) {
+            Some(ChainEvent::TransactionSurge { transactions, .. }) => {
+                assert_eq!(transactions, 4000)
+            }
+            other => panic!("expected a surge, got {:?}", other),
+        }
+        // The same block again, and anything during the cooldown, is quiet
+        assert_eq!(watcher.observe(&block(slot, slot - 1, 4000)), None);
+        assert_eq!(watcher.observe(&block(slot + 10, slot - 40, 1000)), None);
+
+        let slot = 200 + EVENT_COOLDOWN_SLOTS;
+        assert_eq!(
+            watcher.observe(&block(slot, slot - 1 - EVENT_MIN_SLOT_GAP, 1000)),
+            Some(ChainEvent::SlotGap {
+                slot,
+                skipped: EVENT_MIN_SLOT_GAP
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rpc_urls() {
+        let urls = parse_rpc_urls(" https://a.example , ,https://b.example,");
+        assert_eq!(urls, vec!["https://a.example", "https://b.example"]);
+        assert!(parse_rpc_urls("").is_empty());
+    }
+
+    #[test]
+    fn test_failed_endpoint_is_tried_last() {
+        let client = SolanaClient::with_urls(&[
+            "https://a.example".to_string(),
+            "https://b.example".to_string(),
+        ]);
+        assert_eq!(client.endpoint_order(), vec![0, 1]);
+
+        client.endpoints[0].record_failure();
+        assert_eq!(client.endpoint_order(), vec![1, 0]);
+        assert!(!client.endpoint_health()[0].healthy);
+
+        client.endpoints[0].record_success();
+        assert_eq!(client.endpoint_order(), vec![0, 1]);
+    }
+
+    /// Synthetic chain: one slot every 400ms from t=1_000_000, every 7th slot skipped
+    async fn synthetic_time_at(slot: u64, limit: u64) -> Result<Option<(u64, i64)>> {
+        Ok((slot..=limit.min(slot + SLOT_PROBE_LIMIT))
+            .find(|candidate| candidate % 7 != 3)
+            .map(|candidate| (candidate, 1_000_000 + (candidate * 2 / 5) as i64)))
+    }
+
+    #[tokio::test]
+    async fn test_locate_slot() {
+        let current_slot = 2_000_000;
+
+        // Slot 1_400_000 is the first produced 560_000s in
+        let target = 1_000_000 + 560_000;
+
+        // Good, low, and very high estimates all converge on the same slot
+        for estimate in [1_400_000, 1_350_000, 1_800_000] {
+            let slot = locate_slot(target, estimate, current_slot, synthetic_time_at)
+                .await
+                .unwrap();
+            assert_eq!(slot, 1_400_000, "estimate {}", estimate);
+        }
+
+        // A target landing on a skipped slot resolves to the next produced one
+        let skipped = 1_400_003; // 1_400_003 % 7 == 3
+        let target = 1_000_000 + (skipped * 2 / 5) as i64;
+        let slot = locate_slot(target, skipped, current_slot, synthetic_time_at)
+            .await
+            .unwrap();
+        assert_eq!(slot, 1_400_004);
+
+        // Targets past the chain tip are rejected
+        assert!(locate_slot(2_000_000, current_slot, current_slot, synthetic_time_at)
+            .await
+            .is_err());
+
+        // A run of skipped slots longer than one probe doesn't hide the block after it
+        let gap = 1_399_800..1_400_200;
+        let gapped_time_at = |slot: u64, limit: u64| {
+            let gap = gap.clone();
+            async move {
+                Ok((slot..=limit.min(slot + SLOT_PROBE_LIMIT))
+                    .find(|candidate| !gap.contains(candidate))
+                    .map(|candidate| (candidate, 1_000_000 + (candidate * 2 / 5) as i64)))
+            }
+        };
+        let target = 1_000_000 + (1_400_000 * 2 / 5) as i64;
+        for estimate in [1_400_000, 1_350_000, 1_800_000] {
+            let slot = locate_slot(target, estimate, current_slot, gapped_time_at)
+                .await
+                .unwrap();
+            assert_eq!(slot, 1_400_200, "estimate {}", estimate);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_available_bounds_concurrency() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        // Slots divisible by 10 were skipped; later lists finish first
+        let candidates = (0..12u64).map(|i| [i * 10, i * 10 + 1]);
+        let results = first_available(candidates, 4, |slot| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20 - slot / 10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if slot % 10 == 0 {
+                    Err(ChainVerseError::Rpc(anyhow::anyhow!("slot {} skipped", slot)))
+                } else {
+                    Ok(slot)
+                }
+            }
+        })
+        .await;
+
+        let expected: Vec<Option<u64>> = (0..12).map(|i| Some(i * 10 + 1)).collect();
+        assert_eq!(results, expected);
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+
+        // A list with no fetchable slot yields None
+        let results = first_available([vec![10, 20]], 4, |slot| async move {
+            Err::<u64, _>(ChainVerseError::Rpc(anyhow::anyhow!(
+                "slot {} skipped",
+                slot
+            )))
+        })
+        .await;
+        assert_eq!(results, vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_leave_breaker_closed() {
+        use crate::circuit_breaker::BreakerState;
+        use std::io::{Read, Write};
+
+        // Answers every request with 429
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                let _ = stream.write_all(
+                    b"HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\n\
+                      Content-Length: 0\r\nConnection: close\r\n\r\n",
+                );
+            }
+        });
+
+        let breaker = || CircuitBreaker::with_limits("solana", 1, Duration::from_secs(60));
+        let client = SolanaClient::with_url(&url)
+            .with_breaker(breaker())
+            .with_retry(RetryPolicy::new(1, Duration::ZERO));
+        assert!(client.get_current_slot().await.is_err());
+        assert_eq!(client.breaker().status().state, BreakerState::Closed);
+
+        // An endpoint that can't be reached still trips it
+        let client = SolanaClient::with_url("http://127.0.0.1:1")
+            .with_breaker(breaker())
+            .with_retry(RetryPolicy::new(1, Duration::ZERO));
+        assert!(client.get_current_slot().await.is_err());
+        assert_eq!(client.breaker().status().state, BreakerState::Open);
+    }
+
+    #[test]
+    fn test_slot_leader_errors() {
+        use std::io::{Read, Write};
+
+        // Answers every JSON-RPC request with `body`
+        let serve = |body: &'static str| {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            std::thread::spawn(move || {
+                for mut stream in listener.incoming().flatten() {
+                    let mut request = [0u8; 4096];
+                    let _ = stream.read(&mut request);
+                    let _ = stream.write_all(
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                        .as_bytes(),
+                    );
+                }
+            });
+            RpcClient::new(url)
         };
 
-        let sources = block.entropy_sources();
-        assert!(sources.len() >= 4);
-        assert!(sources.contains(&"abc123".to_string()));
-        assert!(sources.contains(&"xyz789".to_string()));
+        // A slot before the schedule has no leader
+        let client = serve(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Invalid slot range: leader schedule for epoch 1 is unavailable"}}"#,
+        );
+        assert_eq!(
+            SolanaClient::get_slot_leader_sync(&client, 1).unwrap(),
+            None
+        );
+
+        // Anything else is the endpoint failing, not a missing leader
+        let client = serve(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is unhealthy"}}"#,
+        );
+        let error = SolanaClient::get_slot_leader_sync(&client, 1).unwrap_err();
+        assert_eq!(rpc_error_code(&error), Some(-32005));
+
+        let client =
+            serve(r#"{"jsonrpc":"2.0","id":1,"result":["11111111111111111111111111111111"]}"#);
+        assert_eq!(
+            SolanaClient::get_slot_leader_sync(&client, 1)
+                .unwrap()
+                .as_deref(),
+            Some("11111111111111111111111111111111")
+        );
     }
 
     #[tokio::test]
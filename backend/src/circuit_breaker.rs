@@ -0,0 +1,273 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::consts::{CIRCUIT_BREAKER_FAILURE_THRESHOLD, CIRCUIT_BREAKER_OPEN_SECS};
+
+/// Returned instead of calling the provider while its breaker is open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitOpen {
+    pub name: &'static str,
+    pub retry_in: Duration,
+}
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} circuit breaker is open; next probe in {}s",
+            self.name,
+            self.retry_in.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+/// Whether `error` is a call the breaker refused rather than a provider failure
+pub fn is_circuit_open(error: &anyhow::Error) -> bool {
+    error.is::<CircuitOpen>()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through
+    Closed,
+    /// Calls fail fast until the open period ends
+    Open,
+    /// One probe call is deciding whether to close again
+    HalfOpen,
+}
+
+/// Breaker snapshot, reported by /health/deep
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BreakerStatus {
+    pub name: &'static str,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    /// Seconds until an open breaker lets a probe through
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+enum State {
+    Closed,
+    Open {
+        until: Instant,
+    },
+    /// A probe abandoned mid-call (its future dropped) would wedge the breaker,
+    /// so another probe is allowed once `open_for` has passed since this one
+    HalfOpen {
+        probe_started: Instant,
+    },
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    consecutive_failures: u32,
+}
+
+/// Stops calling a provider after repeated consecutive failures
+///
+/// After `failure_threshold` failures in a row the breaker opens and calls
+/// fail fast with `CircuitOpen` for `open_for`. Then a single probe call is
+/// let through: success closes the breaker, failure opens it again. Clones
+/// share state, so every client of one provider trips together.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    failure_threshold: u32,
+    open_for: Duration,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CircuitBreaker {
+    /// Breaker with CIRCUIT_BREAKER_FAILURE_THRESHOLD and CIRCUIT_BREAKER_OPEN_SECS
+    pub fn new(name: &'static str) -> Self {
+        Self::with_limits(
+            name,
+            CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            Duration::from_secs(CIRCUIT_BREAKER_OPEN_SECS),
+        )
+    }
+
+    pub fn with_limits(name: &'static str, failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            name,
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            inner: Arc::new(Mutex::new(Inner {
+                state: State::Closed,
+                consecutive_failures: 0,
+            })),
+        }
+    }
+
+    /// Run `call` unless the breaker is open, recording whether it succeeded
+    ///
+    /// Errors `is_failure` rejects say nothing about the provider's health
+    /// and are recorded as neither.
+    pub async fn call<T>(
+        &self,
+        call: impl Future<Output = Result<T>>,
+        is_failure: impl Fn(&anyhow::Error) -> bool,
+    ) -> Result<T> {
+        self.acquire()?;
+        let result = call.await;
+        match &result {
+            Ok(_) => self.record_success(),
+            Err(e) if is_failure(e) => self.record_failure(),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Check whether a call may go ahead; callers must then record its outcome
+    pub fn acquire(&self) -> Result<(), CircuitOpen> {
+        self.acquire_at(Instant::now())
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if !matches!(inner.state, State::Closed) {
+            info!(breaker = self.name, "Circuit breaker closed");
+        }
+        inner.state = State::Closed;
+        inner.consecutive_failures = 0;
+    }
+
+    pub fn record_failure(&self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    pub fn status(&self) -> BreakerStatus {
+        self.status_at(Instant::now())
+    }
+
+    fn acquire_at(&self, now: Instant) -> Result<(), CircuitOpen> {
+        let mut inner = self.lock();
+        let waiting_until = match inner.state {
+            State::Closed => return Ok(()),
+            State::Open { until } => until,
+            State::HalfOpen { probe_started } => probe_started + self.open_for,
+        };
+        if now < waiting_until {
+            return Err(CircuitOpen {
+                name: self.name,
+                retry_in: waiting_until - now,
+            });
+        }
+        inner.state = State::HalfOpen { probe_started: now };
+        Ok(())
+    }
+
+    fn record_failure_at(&self, now: Instant) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let trip = match inner.state {
+            State::HalfOpen { .. } => true,
+            State::Closed => inner.consecutive_failures >= self.failure_threshold,
+            // A call that started before the breaker opened
+            State::Open { .. } => false,
+        };
+        if trip {
+            warn!(
+                breaker = self.name,
+                consecutive_failures = inner.consecutive_failures,
+                open_secs = self.open_for.as_secs(),
+                "Circuit breaker open"
+            );
+            inner.state = State::Open {
+                until: now + self.open_for,
+            };
+        }
+    }
+
+    fn status_at(&self, now: Instant) -> BreakerStatus {
+        let inner = self.lock();
+        let (state, retry_in) = match inner.state {
+            State::Closed => (BreakerState::Closed, None),
+            State::Open { until } if now < until => (BreakerState::Open, Some(until - now)),
+            State::Open { .. } | State::HalfOpen { .. } => (BreakerState::HalfOpen, None),
+        };
+        BreakerStatus {
+            name: self.name,
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            retry_in_secs: retry_in.map(|wait| wait.as_secs()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_and_probes() {
+        let breaker = CircuitBreaker::with_limits("test", 3, Duration::from_secs(60));
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert!(breaker.acquire_at(start).is_ok());
+            breaker.record_failure_at(start);
+        }
+        assert_eq!(breaker.status_at(start).state, BreakerState::Closed);
+
+        // The third failure in a row opens it
+        breaker.record_failure_at(start);
+        let refused = breaker.acquire_at(start).unwrap_err();
+        assert_eq!(refused.retry_in, Duration::from_secs(60));
+        assert_eq!(breaker.status_at(start).state, BreakerState::Open);
+
+        // One probe after the open period; others wait for its outcome
+        let later = start + Duration::from_secs(61);
+        assert!(breaker.acquire_at(later).is_ok());
+        assert!(breaker.acquire_at(later).is_err());
+        assert_eq!(breaker.status_at(later).state, BreakerState::HalfOpen);
+
+        // A failed probe reopens it immediately
+        breaker.record_failure_at(later);
+        assert!(breaker.acquire_at(later).is_err());
+
+        let probe = later + Duration::from_secs(61);
+        assert!(breaker.acquire_at(probe).is_ok());
+        breaker.record_success();
+        assert!(breaker.acquire_at(probe).is_ok());
+        assert_eq!(breaker.status_at(probe).consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_call() {
+        let breaker = CircuitBreaker::with_limits("test", 1, Duration::from_secs(60));
+        let shared = breaker.clone();
+
+        // Errors that aren't the provider's failure don't count
+        let result: Result<()> = breaker
+            .call(async { anyhow::bail!("busy") }, |_| false)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(breaker.status().state, BreakerState::Closed);
+
+        let result: Result<()> = breaker
+            .call(async { anyhow::bail!("down") }, |_| true)
+            .await;
+        assert!(!is_circuit_open(&result.unwrap_err()));
+
+        // Clones share state, and refused calls never run
+        let error = shared.call(async { Ok(()) }, |_| true).await.unwrap_err();
+        assert!(is_circuit_open(&error));
+        assert!(error.to_string().contains("test circuit breaker is open"));
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::archive::ArchiveFormat;
+use crate::dataset::KeywordFormat;
+
+/// Poems written from Solana blocks
+#[derive(Debug, Parser)]
+#[command(name = "chain-verse", version, arg_required_else_help = true)]
+pub struct Cli {
+    /// Config file (default: config.toml if present, or CHAIN_VERSE_CONFIG)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the keyword collector and the API server
+    #[command(alias = "full")]
+    Serve {
+        /// Serve the API without running the collector
+        #[arg(long)]
+        api_only: bool,
+        /// Open the database read-only, for API replicas alongside one writer;
+        /// admin writes are refused and API key usage isn't recorded
+        #[arg(long, requires = "api_only")]
+        read_only: bool,
+    },
+    /// Run the keyword collector without the API server
+    Daemon,
+    /// Collect one keyword (and today's poem, if it is due), then exit
+    #[command(alias = "test")]
+    CollectOnce,
+    /// Fill in keywords and poems for past days; interrupted runs resume
+    Backfill {
+        /// First day, YYYY-MM-DD
+        #[arg(value_parser = parse_date)]
+        start: NaiveDate,
+        /// Last day, YYYY-MM-DD (default: start)
+        #[arg(value_parser = parse_date)]
+        end: Option<NaiveDate>,
+    },
+    /// Generate a day's poem from its stored keywords
+    Regen {
+        /// Day, YYYY-MM-DD
+        #[arg(value_parser = parse_date)]
+        date: NaiveDate,
+        /// Replace the poem if the day already has one
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the derived keyword and LLM prompt without storing or sending anything
+    DryRun,
+    /// Write every keyword, poem, and provenance manifest to a file
+    Export {
+        #[arg(long, value_enum, default_value = "json")]
+        format: ArchiveFormat,
+        /// File to write; replaced if it exists
+        #[arg(long, value_name = "PATH")]
+        out: PathBuf,
+    },
+    /// Restore an exported archive into an empty database
+    Import {
+        #[arg(long, value_enum, default_value = "json")]
+        format: ArchiveFormat,
+        /// File written by `export`
+        #[arg(value_name = "PATH")]
+        file: PathBuf,
+    },
+    /// Write every keyword with its slot, blockhash, source, and date, for analysis
+    ExportKeywords {
+        #[arg(long, value_enum, default_value = "csv")]
+        format: KeywordFormat,
+        /// File to write; replaced if it exists
+        #[arg(long, value_name = "PATH")]
+        out: PathBuf,
+    },
+    /// Re-derive every stored keyword from its block and write a signed report
+    /// of any that don't match
+    VerifyArchive {
+        /// Report to write; its signature goes to `<PATH>.sig`
+        #[arg(long, value_name = "PATH")]
+        out: PathBuf,
+        /// Keypair that signs the report (default: anchor.keypair_path)
+        #[arg(long, value_name = "PATH")]
+        keypair: Option<PathBuf>,
+        /// Use the stored blockhashes instead of fetching blocks; keywords
+        /// derived from other block data are skipped
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
+impl Command {
+    /// Dry runs, archive transfers and audits work without an OpenRouter key
+    pub fn needs_api_key(&self) -> bool {
+        !matches!(
+            self,
+            Command::DryRun
+                | Command::Export { .. }
+                | Command::Import { .. }
+                | Command::ExportKeywords { .. }
+                | Command::VerifyArchive { .. }
+        )
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date `{}` (expected YYYY-MM-DD)", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("chain-verse").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = parse(&[
+            "backfill",
+            "2026-01-01",
+            "2026-01-07",
+            "--config",
+            "prod.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("prod.toml")));
+        match cli.command {
+            Command::Backfill { start, end } => {
+                assert_eq!(start, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+                assert_eq!(end, NaiveDate::from_ymd_opt(2026, 1, 7));
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+
+        assert!(matches!(
+            parse(&["full"]).unwrap().command,
+            Command::Serve {
+                api_only: false,
+                read_only: false
+            }
+        ));
+        assert!(matches!(
+            parse(&["serve", "--api-only", "--read-only"])
+                .unwrap()
+                .command,
+            Command::Serve {
+                api_only: true,
+                read_only: true
+            }
+        ));
+        assert!(parse(&["serve", "--read-only"]).is_err());
+        assert!(matches!(
+            parse(&["regen", "2026-01-05", "--force"]).unwrap().command,
+            Command::Regen { force: true, .. }
+        ));
+        assert!(!parse(&["dry-run"]).unwrap().command.needs_api_key());
+        match parse(&["export", "--format", "sqlite", "--out", "archive.db"])
+            .unwrap()
+            .command
+        {
+            Command::Export { format, out } => {
+                assert_eq!(format, ArchiveFormat::Sqlite);
+                assert_eq!(out, PathBuf::from("archive.db"));
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        assert!(matches!(
+            parse(&["import", "archive.json"]).unwrap().command,
+            Command::Import {
+                format: ArchiveFormat::Json,
+                ..
+            }
+        ));
+        assert!(parse(&["export", "--format", "csv", "--out", "a"]).is_err());
+        match parse(&[
+            "export-keywords",
+            "--format",
+            "parquet",
+            "--out",
+            "k.parquet",
+        ])
+        .unwrap()
+        .command
+        {
+            command @ Command::ExportKeywords { format, .. } => {
+                assert_eq!(format, KeywordFormat::Parquet);
+                assert!(!command.needs_api_key());
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        assert!(matches!(
+            parse(&["export-keywords", "--out", "k.csv"])
+                .unwrap()
+                .command,
+            Command::ExportKeywords {
+                format: KeywordFormat::Csv,
+                ..
+            }
+        ));
+        match parse(&["verify-archive", "--out", "audit.json", "--offline"])
+            .unwrap()
+            .command
+        {
+            command @ Command::VerifyArchive { offline, .. } => {
+                assert!(offline);
+                assert!(!command.needs_api_key());
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+
+        assert!(parse(&["backfill", "January 1st"]).is_err());
+        assert!(parse(&["bogus"]).is_err());
+    }
+}
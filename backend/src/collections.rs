@@ -0,0 +1,244 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::database::StoredPoem;
+
+/// Prompt compiled into the binary for weekly and monthly meta-poems
+const COLLECTION_TEMPLATE: &str = include_str!("../prompts/collection.txt");
+
+/// Lines of each daily poem quoted in a meta-poem's prompt
+const EXCERPT_LINES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionKind {
+    Week,
+    Month,
+}
+
+impl fmt::Display for CollectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionKind::Week => write!(f, "week"),
+            CollectionKind::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// A week (ISO, Monday to Sunday) or calendar month of daily poems
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub kind: CollectionKind,
+    pub start: NaiveDate,
+    /// Last day of the period, inclusive
+    pub end: NaiveDate,
+}
+
+impl Period {
+    /// The ISO week containing `date`
+    pub fn week_of(date: NaiveDate) -> Self {
+        let week = date.iso_week();
+        let start =
+            NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon).unwrap_or(date);
+        Self {
+            kind: CollectionKind::Week,
+            start,
+            end: start + Days::new(6),
+        }
+    }
+
+    /// The calendar month containing `date`
+    pub fn month_of(date: NaiveDate) -> Self {
+        let start = date.with_day(1).unwrap_or(date);
+        let end = start
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .unwrap_or(start);
+        Self {
+            kind: CollectionKind::Month,
+            start,
+            end,
+        }
+    }
+
+    /// The period of the same kind just before this one
+    pub fn previous(&self) -> Self {
+        let day_before = self.start.pred_opt().unwrap_or(self.start);
+        match self.kind {
+            CollectionKind::Week => Self::week_of(day_before),
+            CollectionKind::Month => Self::month_of(day_before),
+        }
+    }
+
+    /// Every week and month to have ended before `today` that has days on or
+    /// after `first`; weeks then months, each oldest first
+    pub fn ended_since(first: NaiveDate, today: NaiveDate) -> Vec<Self> {
+        let mut periods = Vec::new();
+        for latest in [
+            Self::week_of(today).previous(),
+            Self::month_of(today).previous(),
+        ] {
+            let mut ended = Vec::new();
+            let mut period = latest;
+            while period.end >= first && !ended.contains(&period) {
+                ended.push(period);
+                period = period.previous();
+            }
+            periods.extend(ended.into_iter().rev());
+        }
+        periods
+    }
+
+    /// Parse a period key: `2026-W02` for a week, `2026-01` for a month
+    pub fn parse(key: &str) -> Option<Self> {
+        if let Some((year, week)) = key.split_once("-W") {
+            if week.len() != 2 {
+                return None;
+            }
+            let start =
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)?;
+            return Some(Self::week_of(start));
+        }
+        if key.len() != 7 {
+            return None;
+        }
+        NaiveDate::parse_from_str(&format!("{}-01", key), "%Y-%m-%d")
+            .ok()
+            .map(Self::month_of)
+    }
+
+    /// Key the period is stored and served under (see `parse`)
+    pub fn key(&self) -> String {
+        match self.kind {
+            CollectionKind::Week => {
+                let week = self.start.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            CollectionKind::Month => self.start.format("%Y-%m").to_string(),
+        }
+    }
+
+    /// How the prompt names the period, e.g. "the week of 5 January 2026"
+    pub fn label(&self) -> String {
+        match self.kind {
+            CollectionKind::Week => format!("the week of {}", self.start.format("%-d %B %Y")),
+            CollectionKind::Month => self.start.format("%B %Y").to_string(),
+        }
+    }
+
+    pub fn start_date(&self) -> String {
+        self.start.format("%Y-%m-%d").to_string()
+    }
+
+    pub fn end_date(&self) -> String {
+        self.end.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Render the meta-poem prompt from the period's daily poems and keywords
+///
+/// Each poem contributes its date, title, and opening lines; the full texts
+/// of a month would crowd out the model's own writing.
+pub fn prompt(period: &Period, poems: &[StoredPoem], keywords: &[String]) -> String {
+    let excerpts: Vec<String> = poems
+        .iter()
+        .map(|poem| {
+            let opening: Vec<&str> = poem
+                .content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .take(EXCERPT_LINES)
+                .collect();
+            format!(
+                "{} - {}: {}",
+                poem.date,
+                poem.title.as_deref().unwrap_or("Untitled"),
+                opening.join(" / ")
+            )
+        })
+        .collect();
+
+    COLLECTION_TEMPLATE
+        .replace("{period}", &period.label())
+        .replace("{kind}", &period.kind.to_string())
+        .replace("{keywords}", &keywords.join(", "))
+        .replace("{excerpts}", &excerpts.join("\n"))
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_periods() {
+        // 2026-01-07 is a Wednesday in ISO week 2
+        let week = Period::week_of(date("2026-01-07"));
+        assert_eq!(week.key(), "2026-W02");
+        assert_eq!(
+            (week.start_date(), week.end_date()),
+            ("2026-01-05".to_string(), "2026-01-11".to_string())
+        );
+        assert_eq!(Period::parse("2026-W02"), Some(week));
+        assert_eq!(week.label(), "the week of 5 January 2026");
+
+        // ISO weeks can start in the previous year
+        let first = Period::week_of(date("2026-01-01"));
+        assert_eq!(first.key(), "2026-W01");
+        assert_eq!(first.start_date(), "2025-12-29");
+        assert_eq!(first.previous().key(), "2025-W52");
+
+        let february = Period::month_of(date("2028-02-14"));
+        assert_eq!(february.key(), "2028-02");
+        assert_eq!(february.end_date(), "2028-02-29");
+        assert_eq!(Period::parse("2028-02"), Some(february));
+        assert_eq!(february.previous().key(), "2028-01");
+
+        let ended: Vec<String> = Period::ended_since(date("2026-01-20"), date("2026-03-02"))
+            .iter()
+            .map(Period::key)
+            .collect();
+        assert_eq!(
+            ended,
+            [
+                "2026-W04", "2026-W05", "2026-W06", "2026-W07", "2026-W08", "2026-W09", "2026-01",
+                "2026-02",
+            ]
+        );
+        assert!(Period::ended_since(date("2026-03-02"), date("2026-03-02")).is_empty());
+
+        for key in ["2026-W54", "2026-W2", "2026-13", "2026-1", "moon"] {
+            assert_eq!(Period::parse(key), None, "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_prompt_quotes_openings() {
+        let poem = StoredPoem {
+            id: 1,
+            date: "2026-01-05".to_string(),
+            title: Some("Ember".to_string()),
+            content: "first line\n\nsecond line\nthird line".to_string(),
+            keyword_ids: vec![],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: "2026-01-05 23:00:00".to_string(),
+        };
+        let period = Period::week_of(date("2026-01-05"));
+        let prompt = prompt(&period, &[poem], &["ember".to_string(), "tide".to_string()]);
+
+        assert!(prompt.contains("the week of 5 January 2026"));
+        assert!(prompt.contains("2026-01-05 - Ember: first line / second line\n"));
+        assert!(!prompt.contains("third line"));
+        assert!(prompt.contains("ember, tide"));
+        assert!(!prompt.contains('{'));
+    }
+}
@@ -0,0 +1,1834 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::auth::{ApiKey, ApiKeys, Role};
+use crate::backup::DatabaseBackup;
+use crate::blockchain::parse_rpc_urls;
+use crate::consts::{
+    BlockDataSource, DEFAULT_ADMIN_RATE_LIMIT_BURST, DEFAULT_ADMIN_RATE_LIMIT_PER_SECOND,
+    DEFAULT_API_PORT, DEFAULT_AUDIO_DIR, DEFAULT_BACKUP_INTERVAL_HOURS, DEFAULT_BACKUP_KEEP,
+    DEFAULT_BLUESKY_SERVICE, DEFAULT_COLLECTION_INTERVAL_MINUTES, DEFAULT_DATABASE_URL,
+    DEFAULT_KEYWORDS_PER_DAY, DEFAULT_LANGUAGE, DEFAULT_LLM_RETRY_ATTEMPTS,
+    DEFAULT_LLM_RETRY_BASE_DELAY_MS, DEFAULT_MIN_KEYWORD_FRACTION, DEFAULT_POEM_MAX_LINES,
+    DEFAULT_POEM_MIN_LINES, DEFAULT_RATE_LIMIT_BURST, DEFAULT_RATE_LIMIT_PER_SECOND,
+    DEFAULT_READ_CACHE_SECS, DEFAULT_RETRY_JITTER, DEFAULT_RETRY_MAX_DELAY_MS, DEFAULT_RPC_BURST,
+    DEFAULT_RPC_REQUESTS_PER_SECOND, DEFAULT_RPC_RETRY_ATTEMPTS, DEFAULT_RPC_RETRY_BASE_DELAY_MS,
+    DEFAULT_SAMPLE_SIGNATURES, DEFAULT_SELF_BACKFILL_DAYS, DEFAULT_TTS_MODEL, DEFAULT_TTS_VOICE,
+    MAINNET_RPC_URL, MAX_BLOCKS_PER_TICK, MAX_KEYWORDS_FOR_POEM, MIN_KEYWORDS_FOR_POEM, ONE_DAY,
+    ONE_HOUR, ONE_MINUTE,
+};
+use crate::derivation::SourceWeights;
+use crate::notifications::NotifyTarget;
+use crate::poem_form::{FormSelection, PoemLength};
+use crate::poem_generator::SamplingOptions;
+use crate::publisher::{PublishFormat, PublishTarget, XCredentials};
+use crate::rate_limit::Quota;
+use crate::retry::RetryPolicy;
+use crate::scheduler::CollectionSchedule;
+use crate::timezone::Timezone;
+use crate::tts::{AudioFormat, TtsClient, OPENAI_SPEECH_URL};
+use crate::words::{self, ThemeSelection};
+
+/// Config file read when neither --config nor CHAIN_VERSE_CONFIG is given (optional)
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// Name recorded for requests made with ADMIN_API_KEY
+pub const ADMIN_API_KEY_NAME: &str = "admin";
+
+/// Default OpenRouter model
+pub const DEFAULT_MODEL: &str = "meta-llama/llama-3.2-3b-instruct:free";
+
+/// Complete runtime configuration
+///
+/// Values come from built-in defaults, then `config.toml` (or the file named
+/// by CHAIN_VERSE_CONFIG), then environment variables, in increasing priority.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub solana: SolanaConfig,
+    pub llm: LlmConfig,
+    pub collector: CollectorConfig,
+    pub derivation: DerivationConfig,
+    pub database: DatabaseConfig,
+    pub api: ApiConfig,
+    pub dictionary: DictionaryConfig,
+    pub poem: PoemConfig,
+    pub rate_limit: RateLimitConfig,
+    pub notifications: NotificationsConfig,
+    pub social: SocialConfig,
+    pub retry: RetryConfig,
+    pub tts: TtsConfig,
+    pub anchor: AnchorConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SolanaConfig {
+    /// RPC endpoints in priority order (env: SOLANA_RPC_URLS, comma-separated)
+    pub rpc_urls: Vec<String>,
+    /// Only sample signatures from transactions touching this program ID
+    /// (env: SOLANA_PROGRAM_FILTER)
+    pub program_filter: Option<String>,
+    /// Transaction signatures sampled from each block for entropy
+    /// (env: SOLANA_SAMPLE_SIGNATURES)
+    pub sample_signatures: usize,
+    /// Fetch blocks with parsed transactions for the program invocation, NFT
+    /// mint, and token transfer entropy sources; needs the enhanced-rpc feature
+    /// and Helius or Triton endpoints (env: SOLANA_ENHANCED_RPC)
+    pub enhanced_rpc: bool,
+    /// Sustained calls per second to each RPC endpoint, shared by every client;
+    /// 0 leaves calls unpaced (env: SOLANA_RPC_REQUESTS_PER_SECOND)
+    pub requests_per_second: f64,
+    /// Calls each endpoint may get in a burst (env: SOLANA_RPC_BURST)
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmConfig {
+    /// OpenRouter API key (env: OPENROUTER_API_KEY); prefer the environment for secrets
+    pub api_key: Option<String>,
+    /// OpenRouter model, or a comma-separated priority list to fall back
+    /// through (env: OPENROUTER_MODEL)
+    pub model: String,
+    /// Sampling temperature, 0 to 2 (env: OPENROUTER_TEMPERATURE)
+    pub temperature: Option<f32>,
+    /// Longest completion in tokens, capping spend per poem (env: OPENROUTER_MAX_TOKENS)
+    pub max_tokens: Option<u32>,
+    /// Nucleus sampling cutoff, above 0 up to 1 (env: OPENROUTER_TOP_P)
+    pub top_p: Option<f32>,
+    /// Penalty for repeated tokens, -2 to 2 (env: OPENROUTER_FREQUENCY_PENALTY)
+    pub frequency_penalty: Option<f32>,
+    /// Sampling seed, for providers that support it (env: OPENROUTER_SEED)
+    pub seed: Option<u64>,
+    /// OpenRouter embedding model for /api/poems/{date}/similar; unset
+    /// disables embeddings (env: OPENROUTER_EMBEDDING_MODEL)
+    pub embedding_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CollectorConfig {
+    /// Minutes between keyword collections (env: KEYWORD_INTERVAL_MINUTES)
+    pub interval_minutes: u64,
+    /// Collect on multiples of interval_minutes since midnight in `timezone` (30
+    /// gives :00 and :30) instead of counting from startup (env: KEYWORD_ALIGN_TO_INTERVAL)
+    pub align_to_interval: bool,
+    /// Random delay of up to this many seconds added to each collection, so
+    /// instances sharing an RPC don't all hit it at once (env: KEYWORD_JITTER_SECS)
+    pub jitter_secs: u64,
+    /// Keywords required before the day's poem is generated (env: MIN_KEYWORDS_FOR_POEM)
+    pub min_keywords_for_poem: usize,
+    /// Keywords collected per day, by the collector and backfill alike (env: KEYWORDS_PER_DAY)
+    pub keywords_per_day: usize,
+    /// Recent blocks fetched per collection, newest first (env: KEYWORD_BLOCKS_PER_TICK)
+    pub blocks_per_tick: usize,
+    /// Words derived from each block, one per data source; several per tick
+    /// reach the day's minimum sooner (env: KEYWORDS_PER_BLOCK)
+    pub keywords_per_block: usize,
+    /// Re-roll words collected that day or this many days before; 0 disables
+    /// (env: KEYWORD_DEDUP_DAYS)
+    pub dedup_days: u32,
+    /// Extra keywords a day may get from notable on-chain events, on top of
+    /// keywords_per_day; 0 disables the event watcher (env: EVENT_KEYWORDS_PER_DAY)
+    pub event_keywords_per_day: usize,
+    /// How many of the newest past days without a poem are backfilled when the
+    /// collector starts; older gaps wait for the next start, 0 disables (env: SELF_BACKFILL_DAYS)
+    pub self_backfill_days: usize,
+    /// UTC or an offset like +09:00; each day's keywords and poem run from
+    /// midnight to midnight here (env: CHAIN_VERSE_TIMEZONE)
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DerivationConfig {
+    /// Share of each day's keywords per entropy source, by source name, on top
+    /// of the defaults (blockhash 3, transaction_count 0, others 1); a weight of
+    /// 0 makes a source filler (env: DERIVATION_SOURCE_WEIGHTS, comma-separated
+    /// `source=weight`)
+    pub source_weights: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// SQLite connection URL (env: DATABASE_URL)
+    pub url: String,
+    /// Directory the collector snapshots the database into; unset disables
+    /// backups (env: BACKUP_DIR)
+    pub backup_dir: Option<PathBuf>,
+    /// (env: BACKUP_INTERVAL_HOURS)
+    pub backup_interval_hours: u64,
+    /// Backups kept; older ones are deleted (env: BACKUP_KEEP)
+    pub backup_keep: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// HTTP port (env: PORT)
+    pub port: u16,
+    /// Bearer key with the admin role, recorded as `admin` (env: ADMIN_API_KEY)
+    pub admin_api_key: Option<String>,
+    /// Named bearer keys with a role each; admin routes are disabled when no
+    /// key is configured (env: API_KEYS, comma-separated `name:role:key`)
+    pub keys: Vec<ApiKey>,
+    /// Seconds the poem list and each day's keywords and poem are served from
+    /// memory, unless a write in this process clears them first; 0 disables
+    /// the cache (env: READ_CACHE_SECS)
+    pub read_cache_secs: u64,
+    /// Compiled frontend (`npm run build`'s dist directory) to serve at `/`;
+    /// needs the frontend feature (env: FRONTEND_DIR)
+    pub frontend_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DictionaryConfig {
+    /// Word list to load instead of the default one (env: WORDS_PATH)
+    pub path: Option<PathBuf>,
+    /// Directory of word lists in other languages, each declaring its
+    /// `language`; poem.language picks one (env: WORDS_LANGUAGES_DIR)
+    pub languages_dir: Option<PathBuf>,
+    /// Directory of `<theme>.json` word lists (env: WORDS_THEMES_DIR)
+    pub themes_dir: Option<PathBuf>,
+    /// Theme name, or auto (the default) to derive it from the week's first
+    /// blockhash; requires themes_dir (env: WORDS_THEME)
+    pub theme: Option<String>,
+    /// Words never to derive, one per line (env: WORDS_BLOCKLIST)
+    pub blocklist_path: Option<PathBuf>,
+    /// Look up each collected word in the Free Dictionary API so the keyword
+    /// endpoints can show what it means; English only, so ignored for another
+    /// poem.language (env: WORDS_DEFINITIONS)
+    pub definitions: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PoemConfig {
+    /// haiku, tanka, limerick, sonnet, free_verse, acrostic, or auto to derive
+    /// the form from the day's first blockhash (env: POEM_FORM)
+    pub form: String,
+    /// Prompt template file with {keywords}, {date}, {form}, {form_name},
+    /// {guidance}, {mood}, and {language} placeholders; omit for the built-in prompt
+    /// (env: POEM_PROMPT_TEMPLATE)
+    pub prompt_template: Option<PathBuf>,
+    /// System prompt file, same placeholders; omit to send none (env: POEM_SYSTEM_PROMPT)
+    pub system_prompt: Option<PathBuf>,
+    /// Fraction of the day's keywords a poem must use, capped for short forms;
+    /// poems below it are sent back for a rewrite (env: POEM_MIN_KEYWORD_FRACTION)
+    pub min_keyword_fraction: f64,
+    /// Fewest lines asked of free verse, on the quietest days (env: POEM_MIN_LINES)
+    pub min_lines: usize,
+    /// Most lines asked of free verse, on the busiest days (env: POEM_MAX_LINES)
+    pub max_lines: usize,
+    /// Language tag like `en` or `ja`; keywords are derived from the dictionary
+    /// in this language (env: POEM_LANGUAGE)
+    pub language: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second per client IP; 0 disables rate limiting
+    /// (env: RATE_LIMIT_PER_SECOND)
+    pub requests_per_second: f64,
+    /// Requests a client may make in a burst (env: RATE_LIMIT_BURST)
+    pub burst: u32,
+    /// Quota for requests bearing an API key (env: ADMIN_RATE_LIMIT_PER_SECOND)
+    pub admin_requests_per_second: f64,
+    /// (env: ADMIN_RATE_LIMIT_BURST)
+    pub admin_burst: u32,
+    /// Key clients by X-Forwarded-For; only enable behind a proxy that sets it
+    /// (env: RATE_LIMIT_TRUST_FORWARDED_FOR)
+    pub trust_forwarded_for: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsConfig {
+    /// Discord incoming webhook each new poem is posted to (env: DISCORD_WEBHOOK_URL)
+    pub discord_webhook_url: Option<String>,
+    /// Telegram bot that posts each new poem; needs telegram_chat_id
+    /// (env: TELEGRAM_BOT_TOKEN)
+    pub telegram_bot_token: Option<String>,
+    /// Chat ID or @channel name the bot posts to (env: TELEGRAM_CHAT_ID)
+    pub telegram_chat_id: Option<String>,
+    /// Also report poems that failed to generate (env: NOTIFY_FAILURES)
+    pub notify_failures: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SocialConfig {
+    /// thread to post the poem's text, split across posts as needed, or image
+    /// to post the rendered PNG (env: SOCIAL_FORMAT)
+    pub format: String,
+    /// X app's consumer key; all four X credentials are needed to post there
+    /// (env: X_API_KEY)
+    pub x_api_key: Option<String>,
+    /// (env: X_API_SECRET)
+    pub x_api_secret: Option<String>,
+    /// Access token of the account to post as, with read and write permission
+    /// (env: X_ACCESS_TOKEN)
+    pub x_access_token: Option<String>,
+    /// (env: X_ACCESS_TOKEN_SECRET)
+    pub x_access_token_secret: Option<String>,
+    /// Bluesky handle to post as; needs bluesky_app_password (env: BLUESKY_HANDLE)
+    pub bluesky_handle: Option<String>,
+    /// App password from the account's settings (env: BLUESKY_APP_PASSWORD)
+    pub bluesky_app_password: Option<String>,
+    /// Server the account lives on (env: BLUESKY_SERVICE)
+    pub bluesky_service: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    /// Attempts per poem or meta-poem request, counting the first
+    /// (env: LLM_RETRY_ATTEMPTS)
+    pub llm_attempts: u32,
+    /// Delay before the second attempt; doubles per attempt after that
+    /// (env: LLM_RETRY_BASE_DELAY_MS)
+    pub llm_base_delay_ms: u64,
+    /// Passes over every RPC endpoint before a call fails; 1 only fails over
+    /// (env: RPC_RETRY_ATTEMPTS)
+    pub rpc_attempts: u32,
+    /// (env: RPC_RETRY_BASE_DELAY_MS)
+    pub rpc_base_delay_ms: u64,
+    /// Longest delay between attempts, before jitter (env: RETRY_MAX_DELAY_MS)
+    pub max_delay_ms: u64,
+    /// Random extra delay, as a fraction of each delay, 0 to 1 (env: RETRY_JITTER)
+    pub jitter: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TtsConfig {
+    /// OpenAI-compatible speech endpoint; set it for a local engine, or leave it
+    /// for OpenAI's. Audio is on when this or api_key is set (env: TTS_URL)
+    pub url: Option<String>,
+    /// Bearer token for the endpoint (env: TTS_API_KEY); prefer the environment for secrets
+    pub api_key: Option<String>,
+    /// (env: TTS_MODEL)
+    pub model: String,
+    /// (env: TTS_VOICE)
+    pub voice: String,
+    /// mp3 or ogg (Opus) (env: TTS_FORMAT)
+    pub format: String,
+    /// Directory recordings are written to and served from (env: TTS_AUDIO_DIR)
+    pub audio_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnchorConfig {
+    /// Keypair that pays for and signs each poem's memo; unset disables
+    /// anchoring (env: ANCHOR_KEYPAIR_PATH)
+    pub keypair_path: Option<PathBuf>,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            api_key: None,
+            model: DEFAULT_TTS_MODEL.to_string(),
+            voice: DEFAULT_TTS_VOICE.to_string(),
+            format: AudioFormat::default().to_string(),
+            audio_dir: PathBuf::from(DEFAULT_AUDIO_DIR),
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            llm_attempts: DEFAULT_LLM_RETRY_ATTEMPTS,
+            llm_base_delay_ms: DEFAULT_LLM_RETRY_BASE_DELAY_MS,
+            rpc_attempts: DEFAULT_RPC_RETRY_ATTEMPTS,
+            rpc_base_delay_ms: DEFAULT_RPC_RETRY_BASE_DELAY_MS,
+            max_delay_ms: DEFAULT_RETRY_MAX_DELAY_MS,
+            jitter: DEFAULT_RETRY_JITTER,
+        }
+    }
+}
+
+impl RetryConfig {
+    fn policy(&self, attempts: u32, base_delay_ms: u64) -> RetryPolicy {
+        RetryPolicy::new(attempts, Duration::from_millis(base_delay_ms))
+            .with_max_delay(Duration::from_millis(self.max_delay_ms))
+            .with_jitter(self.jitter)
+    }
+
+    /// Retries for poem and meta-poem requests to OpenRouter
+    pub fn llm(&self) -> RetryPolicy {
+        self.policy(self.llm_attempts, self.llm_base_delay_ms)
+    }
+
+    /// Retries for Solana RPC calls that failed on every endpoint
+    pub fn rpc(&self) -> RetryPolicy {
+        self.policy(self.rpc_attempts, self.rpc_base_delay_ms)
+    }
+}
+
+impl Default for SocialConfig {
+    fn default() -> Self {
+        Self {
+            format: PublishFormat::default().to_string(),
+            x_api_key: None,
+            x_api_secret: None,
+            x_access_token: None,
+            x_access_token_secret: None,
+            bluesky_handle: None,
+            bluesky_app_password: None,
+            bluesky_service: DEFAULT_BLUESKY_SERVICE.to_string(),
+        }
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            discord_webhook_url: None,
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            notify_failures: true,
+        }
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: DEFAULT_RATE_LIMIT_PER_SECOND,
+            burst: DEFAULT_RATE_LIMIT_BURST,
+            admin_requests_per_second: DEFAULT_ADMIN_RATE_LIMIT_PER_SECOND,
+            admin_burst: DEFAULT_ADMIN_RATE_LIMIT_BURST,
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn enabled(&self) -> bool {
+        self.requests_per_second > 0.0
+    }
+
+    pub fn public_quota(&self) -> Quota {
+        Quota {
+            per_second: self.requests_per_second,
+            burst: self.burst,
+        }
+    }
+
+    pub fn admin_quota(&self) -> Quota {
+        Quota {
+            per_second: self.admin_requests_per_second,
+            burst: self.admin_burst,
+        }
+    }
+}
+
+impl Default for PoemConfig {
+    fn default() -> Self {
+        Self {
+            form: "free_verse".to_string(),
+            prompt_template: None,
+            system_prompt: None,
+            min_keyword_fraction: DEFAULT_MIN_KEYWORD_FRACTION,
+            min_lines: DEFAULT_POEM_MIN_LINES,
+            max_lines: DEFAULT_POEM_MAX_LINES,
+            language: DEFAULT_LANGUAGE.to_string(),
+        }
+    }
+}
+
+impl Default for SolanaConfig {
+    fn default() -> Self {
+        Self {
+            rpc_urls: vec![MAINNET_RPC_URL.to_string()],
+            program_filter: None,
+            sample_signatures: DEFAULT_SAMPLE_SIGNATURES,
+            enhanced_rpc: false,
+            requests_per_second: DEFAULT_RPC_REQUESTS_PER_SECOND,
+            burst: DEFAULT_RPC_BURST,
+        }
+    }
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            model: DEFAULT_MODEL.to_string(),
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            frequency_penalty: None,
+            seed: None,
+            embedding_model: None,
+        }
+    }
+}
+
+impl Default for CollectorConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_COLLECTION_INTERVAL_MINUTES,
+            align_to_interval: false,
+            jitter_secs: 0,
+            min_keywords_for_poem: MIN_KEYWORDS_FOR_POEM,
+            keywords_per_day: DEFAULT_KEYWORDS_PER_DAY,
+            blocks_per_tick: 1,
+            keywords_per_block: 1,
+            dedup_days: 0,
+            event_keywords_per_day: 0,
+            self_backfill_days: DEFAULT_SELF_BACKFILL_DAYS,
+            timezone: "UTC".to_string(),
+        }
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_DATABASE_URL.to_string(),
+            backup_dir: None,
+            backup_interval_hours: DEFAULT_BACKUP_INTERVAL_HOURS,
+            backup_keep: DEFAULT_BACKUP_KEEP,
+        }
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_API_PORT,
+            admin_api_key: None,
+            keys: Vec::new(),
+            read_cache_secs: DEFAULT_READ_CACHE_SECS,
+            frontend_dir: None,
+        }
+    }
+}
+
+impl Config {
+    /// Load defaults, the config file, and environment overrides, then validate
+    ///
+    /// `path` (the `--config` flag) takes precedence over CHAIN_VERSE_CONFIG.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let config = Self::read(path)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Like `load`, but without requiring the OpenRouter API key (for dry runs)
+    pub fn load_without_api_key(path: Option<&Path>) -> Result<Self> {
+        let config = Self::read(path)?;
+        config.check(false)?;
+        Ok(config)
+    }
+
+    fn read(path: Option<&Path>) -> Result<Self> {
+        let explicit = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var("CHAIN_VERSE_CONFIG").ok().map(PathBuf::from));
+        let path = explicit
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+        // A missing default config.toml is fine; a missing explicit one is a mistake
+        let mut config = if path.exists() || explicit.is_some() {
+            Self::from_file(&path)?
+        } else {
+            Self::default()
+        };
+
+        config.apply_env(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Parse a TOML config file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Parse TOML config content
+    pub fn from_toml(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Override fields from environment variables looked up through `lookup`
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<()> {
+        let lookup = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+
+        if let Some(urls) = lookup("SOLANA_RPC_URLS") {
+            self.solana.rpc_urls = parse_rpc_urls(&urls);
+        }
+        if let Some(program) = lookup("SOLANA_PROGRAM_FILTER") {
+            self.solana.program_filter = Some(program.trim().to_string());
+        }
+        if let Some(size) = lookup("SOLANA_SAMPLE_SIGNATURES") {
+            self.solana.sample_signatures = parse_env("SOLANA_SAMPLE_SIGNATURES", &size)?;
+        }
+        if let Some(enhanced) = lookup("SOLANA_ENHANCED_RPC") {
+            self.solana.enhanced_rpc = parse_env("SOLANA_ENHANCED_RPC", &enhanced)?;
+        }
+        if let Some(rps) = lookup("SOLANA_RPC_REQUESTS_PER_SECOND") {
+            self.solana.requests_per_second = parse_env("SOLANA_RPC_REQUESTS_PER_SECOND", &rps)?;
+        }
+        if let Some(burst) = lookup("SOLANA_RPC_BURST") {
+            self.solana.burst = parse_env("SOLANA_RPC_BURST", &burst)?;
+        }
+        if let Some(key) = lookup("OPENROUTER_API_KEY") {
+            self.llm.api_key = Some(key);
+        }
+        if let Some(model) = lookup("OPENROUTER_MODEL") {
+            self.llm.model = model;
+        }
+        if let Some(temperature) = lookup("OPENROUTER_TEMPERATURE") {
+            self.llm.temperature = Some(parse_env("OPENROUTER_TEMPERATURE", &temperature)?);
+        }
+        if let Some(max_tokens) = lookup("OPENROUTER_MAX_TOKENS") {
+            self.llm.max_tokens = Some(parse_env("OPENROUTER_MAX_TOKENS", &max_tokens)?);
+        }
+        if let Some(top_p) = lookup("OPENROUTER_TOP_P") {
+            self.llm.top_p = Some(parse_env("OPENROUTER_TOP_P", &top_p)?);
+        }
+        if let Some(penalty) = lookup("OPENROUTER_FREQUENCY_PENALTY") {
+            self.llm.frequency_penalty = Some(parse_env("OPENROUTER_FREQUENCY_PENALTY", &penalty)?);
+        }
+        if let Some(seed) = lookup("OPENROUTER_SEED") {
+            self.llm.seed = Some(parse_env("OPENROUTER_SEED", &seed)?);
+        }
+        if let Some(model) = lookup("OPENROUTER_EMBEDDING_MODEL") {
+            self.llm.embedding_model = Some(model);
+        }
+        if let Some(minutes) = lookup("KEYWORD_INTERVAL_MINUTES") {
+            self.collector.interval_minutes = parse_env("KEYWORD_INTERVAL_MINUTES", &minutes)?;
+        }
+        if let Some(align) = lookup("KEYWORD_ALIGN_TO_INTERVAL") {
+            self.collector.align_to_interval = parse_env("KEYWORD_ALIGN_TO_INTERVAL", &align)?;
+        }
+        if let Some(jitter) = lookup("KEYWORD_JITTER_SECS") {
+            self.collector.jitter_secs = parse_env("KEYWORD_JITTER_SECS", &jitter)?;
+        }
+        if let Some(min) = lookup("MIN_KEYWORDS_FOR_POEM") {
+            self.collector.min_keywords_for_poem = parse_env("MIN_KEYWORDS_FOR_POEM", &min)?;
+        }
+        if let Some(target) = lookup("KEYWORDS_PER_DAY") {
+            self.collector.keywords_per_day = parse_env("KEYWORDS_PER_DAY", &target)?;
+        }
+        if let Some(blocks) = lookup("KEYWORD_BLOCKS_PER_TICK") {
+            self.collector.blocks_per_tick = parse_env("KEYWORD_BLOCKS_PER_TICK", &blocks)?;
+        }
+        if let Some(words) = lookup("KEYWORDS_PER_BLOCK") {
+            self.collector.keywords_per_block = parse_env("KEYWORDS_PER_BLOCK", &words)?;
+        }
+        if let Some(days) = lookup("KEYWORD_DEDUP_DAYS") {
+            self.collector.dedup_days = parse_env("KEYWORD_DEDUP_DAYS", &days)?;
+        }
+        if let Some(extra) = lookup("EVENT_KEYWORDS_PER_DAY") {
+            self.collector.event_keywords_per_day = parse_env("EVENT_KEYWORDS_PER_DAY", &extra)?;
+        }
+        if let Some(days) = lookup("SELF_BACKFILL_DAYS") {
+            self.collector.self_backfill_days = parse_env("SELF_BACKFILL_DAYS", &days)?;
+        }
+        if let Some(timezone) = lookup("CHAIN_VERSE_TIMEZONE") {
+            self.collector.timezone = timezone;
+        }
+        if let Some(weights) = lookup("DERIVATION_SOURCE_WEIGHTS") {
+            self.derivation.source_weights = weights
+                .split(',')
+                .filter(|entry| !entry.trim().is_empty())
+                .map(|entry| {
+                    let (source, weight) = entry.split_once('=').ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "DERIVATION_SOURCE_WEIGHTS entry `{}` is not source=weight",
+                            entry
+                        )
+                    })?;
+                    Ok((
+                        source.trim().to_string(),
+                        parse_env("DERIVATION_SOURCE_WEIGHTS", weight)?,
+                    ))
+                })
+                .collect::<Result<_>>()?;
+        }
+        if let Some(url) = lookup("DATABASE_URL") {
+            self.database.url = url;
+        }
+        if let Some(dir) = lookup("BACKUP_DIR") {
+            self.database.backup_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(hours) = lookup("BACKUP_INTERVAL_HOURS") {
+            self.database.backup_interval_hours = parse_env("BACKUP_INTERVAL_HOURS", &hours)?;
+        }
+        if let Some(keep) = lookup("BACKUP_KEEP") {
+            self.database.backup_keep = parse_env("BACKUP_KEEP", &keep)?;
+        }
+        if let Some(port) = lookup("PORT") {
+            self.api.port = parse_env("PORT", &port)?;
+        }
+        if let Some(key) = lookup("ADMIN_API_KEY") {
+            self.api.admin_api_key = Some(key);
+        }
+        if let Some(keys) = lookup("API_KEYS") {
+            self.api.keys = keys
+                .split(',')
+                .filter(|entry| !entry.trim().is_empty())
+                .map(|entry| {
+                    // Not parse_env, which would echo the secret into the error
+                    entry
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("API_KEYS has an invalid entry: {}", e))
+                })
+                .collect::<Result<_>>()?;
+        }
+        if let Some(secs) = lookup("READ_CACHE_SECS") {
+            self.api.read_cache_secs = parse_env("READ_CACHE_SECS", &secs)?;
+        }
+        if let Some(dir) = lookup("FRONTEND_DIR") {
+            self.api.frontend_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(path) = lookup("WORDS_PATH") {
+            self.dictionary.path = Some(PathBuf::from(path));
+        }
+        if let Some(dir) = lookup("WORDS_LANGUAGES_DIR") {
+            self.dictionary.languages_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(dir) = lookup("WORDS_THEMES_DIR") {
+            self.dictionary.themes_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(theme) = lookup("WORDS_THEME") {
+            self.dictionary.theme = Some(theme);
+        }
+        if let Some(path) = lookup("WORDS_BLOCKLIST") {
+            self.dictionary.blocklist_path = Some(PathBuf::from(path));
+        }
+        if let Some(definitions) = lookup("WORDS_DEFINITIONS") {
+            self.dictionary.definitions = parse_env("WORDS_DEFINITIONS", &definitions)?;
+        }
+        if let Some(form) = lookup("POEM_FORM") {
+            self.poem.form = form;
+        }
+        if let Some(path) = lookup("POEM_PROMPT_TEMPLATE") {
+            self.poem.prompt_template = Some(PathBuf::from(path));
+        }
+        if let Some(path) = lookup("POEM_SYSTEM_PROMPT") {
+            self.poem.system_prompt = Some(PathBuf::from(path));
+        }
+        if let Some(fraction) = lookup("POEM_MIN_KEYWORD_FRACTION") {
+            self.poem.min_keyword_fraction = parse_env("POEM_MIN_KEYWORD_FRACTION", &fraction)?;
+        }
+        if let Some(lines) = lookup("POEM_MIN_LINES") {
+            self.poem.min_lines = parse_env("POEM_MIN_LINES", &lines)?;
+        }
+        if let Some(lines) = lookup("POEM_MAX_LINES") {
+            self.poem.max_lines = parse_env("POEM_MAX_LINES", &lines)?;
+        }
+        if let Some(language) = lookup("POEM_LANGUAGE") {
+            self.poem.language = language;
+        }
+        if let Some(rps) = lookup("RATE_LIMIT_PER_SECOND") {
+            self.rate_limit.requests_per_second = parse_env("RATE_LIMIT_PER_SECOND", &rps)?;
+        }
+        if let Some(burst) = lookup("RATE_LIMIT_BURST") {
+            self.rate_limit.burst = parse_env("RATE_LIMIT_BURST", &burst)?;
+        }
+        if let Some(rps) = lookup("ADMIN_RATE_LIMIT_PER_SECOND") {
+            self.rate_limit.admin_requests_per_second =
+                parse_env("ADMIN_RATE_LIMIT_PER_SECOND", &rps)?;
+        }
+        if let Some(burst) = lookup("ADMIN_RATE_LIMIT_BURST") {
+            self.rate_limit.admin_burst = parse_env("ADMIN_RATE_LIMIT_BURST", &burst)?;
+        }
+        if let Some(trust) = lookup("RATE_LIMIT_TRUST_FORWARDED_FOR") {
+            self.rate_limit.trust_forwarded_for =
+                parse_env("RATE_LIMIT_TRUST_FORWARDED_FOR", &trust)?;
+        }
+        if let Some(url) = lookup("DISCORD_WEBHOOK_URL") {
+            self.notifications.discord_webhook_url = Some(url.trim().to_string());
+        }
+        if let Some(token) = lookup("TELEGRAM_BOT_TOKEN") {
+            self.notifications.telegram_bot_token = Some(token.trim().to_string());
+        }
+        if let Some(chat) = lookup("TELEGRAM_CHAT_ID") {
+            self.notifications.telegram_chat_id = Some(chat.trim().to_string());
+        }
+        if let Some(failures) = lookup("NOTIFY_FAILURES") {
+            self.notifications.notify_failures = parse_env("NOTIFY_FAILURES", &failures)?;
+        }
+        if let Some(format) = lookup("SOCIAL_FORMAT") {
+            self.social.format = format;
+        }
+        if let Some(key) = lookup("X_API_KEY") {
+            self.social.x_api_key = Some(key.trim().to_string());
+        }
+        if let Some(secret) = lookup("X_API_SECRET") {
+            self.social.x_api_secret = Some(secret.trim().to_string());
+        }
+        if let Some(token) = lookup("X_ACCESS_TOKEN") {
+            self.social.x_access_token = Some(token.trim().to_string());
+        }
+        if let Some(secret) = lookup("X_ACCESS_TOKEN_SECRET") {
+            self.social.x_access_token_secret = Some(secret.trim().to_string());
+        }
+        if let Some(handle) = lookup("BLUESKY_HANDLE") {
+            self.social.bluesky_handle = Some(handle.trim().to_string());
+        }
+        if let Some(password) = lookup("BLUESKY_APP_PASSWORD") {
+            self.social.bluesky_app_password = Some(password.trim().to_string());
+        }
+        if let Some(service) = lookup("BLUESKY_SERVICE") {
+            self.social.bluesky_service = service.trim().to_string();
+        }
+        if let Some(attempts) = lookup("LLM_RETRY_ATTEMPTS") {
+            self.retry.llm_attempts = parse_env("LLM_RETRY_ATTEMPTS", &attempts)?;
+        }
+        if let Some(delay) = lookup("LLM_RETRY_BASE_DELAY_MS") {
+            self.retry.llm_base_delay_ms = parse_env("LLM_RETRY_BASE_DELAY_MS", &delay)?;
+        }
+        if let Some(attempts) = lookup("RPC_RETRY_ATTEMPTS") {
+            self.retry.rpc_attempts = parse_env("RPC_RETRY_ATTEMPTS", &attempts)?;
+        }
+        if let Some(delay) = lookup("RPC_RETRY_BASE_DELAY_MS") {
+            self.retry.rpc_base_delay_ms = parse_env("RPC_RETRY_BASE_DELAY_MS", &delay)?;
+        }
+        if let Some(delay) = lookup("RETRY_MAX_DELAY_MS") {
+            self.retry.max_delay_ms = parse_env("RETRY_MAX_DELAY_MS", &delay)?;
+        }
+        if let Some(jitter) = lookup("RETRY_JITTER") {
+            self.retry.jitter = parse_env("RETRY_JITTER", &jitter)?;
+        }
+        if let Some(url) = lookup("TTS_URL") {
+            self.tts.url = Some(url.trim().to_string());
+        }
+        if let Some(key) = lookup("TTS_API_KEY") {
+            self.tts.api_key = Some(key.trim().to_string());
+        }
+        if let Some(model) = lookup("TTS_MODEL") {
+            self.tts.model = model;
+        }
+        if let Some(voice) = lookup("TTS_VOICE") {
+            self.tts.voice = voice;
+        }
+        if let Some(format) = lookup("TTS_FORMAT") {
+            self.tts.format = format;
+        }
+        if let Some(dir) = lookup("TTS_AUDIO_DIR") {
+            self.tts.audio_dir = PathBuf::from(dir);
+        }
+        if let Some(path) = lookup("ANCHOR_KEYPAIR_PATH") {
+            self.anchor.keypair_path = Some(PathBuf::from(path));
+        }
+
+        Ok(())
+    }
+
+    /// Check the configuration, reporting every problem at once
+    pub fn validate(&self) -> Result<()> {
+        self.check(true)
+    }
+
+    /// Entropy sources a block can give words from, the enhanced ones included
+    /// when `solana.enhanced_rpc` is set
+    fn data_sources(&self) -> usize {
+        let enhanced = if self.solana.enhanced_rpc {
+            BlockDataSource::enhanced().len()
+        } else {
+            0
+        };
+        BlockDataSource::all().len() + enhanced
+    }
+
+    fn check(&self, require_api_key: bool) -> Result<()> {
+        let mut problems = Vec::new();
+
+        if require_api_key && self.llm.api_key.is_none() {
+            problems.push(
+                "llm.api_key is missing: set OPENROUTER_API_KEY (https://openrouter.ai/settings/keys)"
+                    .to_string(),
+            );
+        }
+        if self.llm.model.trim().is_empty() {
+            problems.push("llm.model must not be empty".to_string());
+        }
+        if self.solana.rpc_urls.is_empty() {
+            problems.push("solana.rpc_urls must list at least one RPC URL".to_string());
+        }
+        for url in &self.solana.rpc_urls {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                problems.push(format!("solana.rpc_urls: `{}` is not an http(s) URL", url));
+            }
+        }
+        if let Some(program) = &self.solana.program_filter {
+            if program.parse::<Pubkey>().is_err() {
+                problems.push(format!(
+                    "solana.program_filter: `{}` is not a valid program ID",
+                    program
+                ));
+            }
+        }
+        if self.solana.sample_signatures == 0 {
+            problems.push("solana.sample_signatures must be at least 1".to_string());
+        }
+        if self.solana.enhanced_rpc && !cfg!(feature = "enhanced-rpc") {
+            problems
+                .push("solana.enhanced_rpc needs a build with --features enhanced-rpc".to_string());
+        }
+        let rps = self.solana.requests_per_second;
+        if !rps.is_finite() || rps < 0.0 {
+            problems.push("solana.requests_per_second must be zero or positive".to_string());
+        } else if rps > 0.0 && self.solana.burst == 0 {
+            problems.push("solana.burst must be at least 1".to_string());
+        }
+        if let Some(temperature) = self.llm.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                problems.push(format!("llm.temperature {} must be between 0 and 2", temperature));
+            }
+        }
+        if self.llm.max_tokens == Some(0) {
+            problems.push("llm.max_tokens must be at least 1".to_string());
+        }
+        if let Some(top_p) = self.llm.top_p {
+            if !(top_p > 0.0 && top_p <= 1.0) {
+                problems.push(format!("llm.top_p {} must be above 0 and at most 1", top_p));
+            }
+        }
+        if let Some(penalty) = self.llm.frequency_penalty {
+            if !(-2.0..=2.0).contains(&penalty) {
+                problems.push(format!(
+                    "llm.frequency_penalty {} must be between -2 and 2",
+                    penalty
+                ));
+            }
+        }
+        if self.collector.interval_minutes == 0 {
+            problems.push("collector.interval_minutes must be at least 1".to_string());
+        } else {
+            if self.collector.align_to_interval
+                && !ONE_DAY.is_multiple_of(self.collector.interval_minutes * ONE_MINUTE)
+            {
+                problems.push(format!(
+                    "collector.interval_minutes ({}) must divide a day evenly when align_to_interval is set",
+                    self.collector.interval_minutes
+                ));
+            }
+            if self.collector.jitter_secs >= self.collector.interval_minutes * ONE_MINUTE {
+                problems.push(
+                    "collector.jitter_secs must be shorter than interval_minutes".to_string(),
+                );
+            }
+        }
+        if self.collector.min_keywords_for_poem == 0 {
+            problems.push("collector.min_keywords_for_poem must be at least 1".to_string());
+        }
+        if self.collector.keywords_per_day < self.collector.min_keywords_for_poem
+            || self.collector.keywords_per_day > MAX_KEYWORDS_FOR_POEM
+        {
+            problems.push(format!(
+                "collector.keywords_per_day must be between min_keywords_for_poem ({}) and {}",
+                self.collector.min_keywords_for_poem, MAX_KEYWORDS_FOR_POEM
+            ));
+        }
+        if self.collector.keywords_per_day + self.collector.event_keywords_per_day
+            > MAX_KEYWORDS_FOR_POEM
+        {
+            problems.push(format!(
+                "collector.keywords_per_day plus event_keywords_per_day must be at most {}",
+                MAX_KEYWORDS_FOR_POEM
+            ));
+        }
+        if !(1..=MAX_BLOCKS_PER_TICK).contains(&self.collector.blocks_per_tick) {
+            problems.push(format!(
+                "collector.blocks_per_tick must be between 1 and {}",
+                MAX_BLOCKS_PER_TICK
+            ));
+        }
+        if !(1..=self.data_sources()).contains(&self.collector.keywords_per_block) {
+            problems.push(format!(
+                "collector.keywords_per_block must be between 1 and {} (one per data source)",
+                self.data_sources()
+            ));
+        }
+        if let Err(e) = self.collector.timezone.parse::<Timezone>() {
+            problems.push(format!("collector.timezone: {}", e));
+        }
+        for (source, weight) in &self.derivation.source_weights {
+            if BlockDataSource::from_name(source).is_none() {
+                problems.push(format!(
+                    "derivation.source_weights: `{}` is not an entropy source",
+                    source
+                ));
+            }
+            if !weight.is_finite() || *weight < 0.0 {
+                problems.push(format!(
+                    "derivation.source_weights.{} must be 0 or more",
+                    source
+                ));
+            }
+        }
+        if !self.database.url.starts_with("sqlite:") {
+            problems.push(format!(
+                "database.url `{}` must be a sqlite: URL (e.g. sqlite:chain_verse.db)",
+                self.database.url
+            ));
+        }
+        if let Err(e) = self.poem.form.parse::<FormSelection>() {
+            problems.push(format!(
+                "poem.form: {} (expected haiku, tanka, limerick, sonnet, free_verse, acrostic, or auto)",
+                e
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.poem.min_keyword_fraction) {
+            problems.push("poem.min_keyword_fraction must be between 0 and 1".to_string());
+        }
+        if self.poem.min_lines == 0 || self.poem.min_lines > self.poem.max_lines {
+            problems.push(
+                "poem.min_lines must be at least 1 and no more than poem.max_lines".to_string(),
+            );
+        }
+        if let Err(e) = words::parse_language(&self.poem.language) {
+            problems.push(format!("poem.language: {}", e));
+        }
+        if let Some(theme) = &self.dictionary.theme {
+            if self.dictionary.themes_dir.is_none() {
+                problems.push("dictionary.theme is set but dictionary.themes_dir is not".to_string());
+            } else if let Err(e) = theme.parse::<ThemeSelection>() {
+                problems.push(format!("dictionary.theme: {}", e));
+            }
+        }
+        if self.api.port == 0 {
+            problems.push("api.port must be non-zero".to_string());
+        }
+        let mut names = HashSet::new();
+        let mut secrets = HashSet::new();
+        for key in self.api_keys_list() {
+            if key.name.is_empty() || key.key.is_empty() {
+                problems.push("api.keys entries need a non-empty name and key".to_string());
+            } else if !names.insert(key.name.clone()) {
+                problems.push(format!("api.keys: `{}` is configured twice", key.name));
+            } else if !secrets.insert(key.key.clone()) {
+                problems.push(format!("api.keys: `{}` reuses another key", key.name));
+            }
+        }
+        if let Some(dir) = &self.api.frontend_dir {
+            if !cfg!(feature = "frontend") {
+                problems
+                    .push("api.frontend_dir needs a build with --features frontend".to_string());
+            } else if !dir.join("index.html").is_file() {
+                problems.push(format!(
+                    "api.frontend_dir: {} has no index.html; run `npm run build` first",
+                    dir.display()
+                ));
+            }
+        }
+        let limits = &self.rate_limit;
+        if !limits.requests_per_second.is_finite() || limits.requests_per_second < 0.0 {
+            problems.push("rate_limit.requests_per_second must be zero or positive".to_string());
+        }
+        if limits.enabled() {
+            if limits.burst == 0 {
+                problems.push("rate_limit.burst must be at least 1".to_string());
+            }
+            if !limits.admin_requests_per_second.is_finite()
+                || limits.admin_requests_per_second <= 0.0
+                || limits.admin_burst == 0
+            {
+                problems.push(
+                    "rate_limit.admin_requests_per_second and admin_burst must be positive"
+                        .to_string(),
+                );
+            }
+        }
+        let notifications = &self.notifications;
+        if let Some(url) = &notifications.discord_webhook_url {
+            // Not the URL itself, which embeds the webhook's secret token
+            if !url.starts_with("https://") {
+                problems.push("notifications.discord_webhook_url must be an https URL".to_string());
+            }
+        }
+        if notifications.telegram_bot_token.is_some() != notifications.telegram_chat_id.is_some() {
+            problems.push(
+                "notifications.telegram_bot_token and telegram_chat_id must be set together"
+                    .to_string(),
+            );
+        }
+        let social = &self.social;
+        if let Err(e) = social.format.parse::<PublishFormat>() {
+            problems.push(format!("social.format: {} (expected thread or image)", e));
+        }
+        let x_credentials = [
+            &social.x_api_key,
+            &social.x_api_secret,
+            &social.x_access_token,
+            &social.x_access_token_secret,
+        ];
+        let x_set = x_credentials.iter().filter(|value| value.is_some()).count();
+        if x_set != 0 && x_set != x_credentials.len() {
+            problems.push(
+                "social.x_api_key, x_api_secret, x_access_token, and x_access_token_secret must be set together"
+                    .to_string(),
+            );
+        }
+        if social.bluesky_handle.is_some() != social.bluesky_app_password.is_some() {
+            problems.push(
+                "social.bluesky_handle and bluesky_app_password must be set together".to_string(),
+            );
+        }
+        if !social.bluesky_service.starts_with("https://") {
+            problems.push(format!(
+                "social.bluesky_service `{}` is not an https URL",
+                social.bluesky_service
+            ));
+        }
+
+        let tts = &self.tts;
+        if let Err(e) = tts.format.parse::<AudioFormat>() {
+            problems.push(format!("tts.format: {} (expected mp3 or ogg)", e));
+        }
+        if let Some(url) = &tts.url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                problems.push(format!("tts.url `{}` is not an http(s) URL", url));
+            }
+        }
+        if tts.model.trim().is_empty() || tts.voice.trim().is_empty() {
+            problems.push("tts.model and tts.voice must not be empty".to_string());
+        }
+
+        if let Some(path) = &self.anchor.keypair_path {
+            if !path.is_file() {
+                problems.push(format!(
+                    "anchor.keypair_path: {} is not a file",
+                    path.display()
+                ));
+            }
+        }
+
+        if self.database.backup_interval_hours == 0 || self.database.backup_keep == 0 {
+            problems.push(
+                "database.backup_interval_hours and backup_keep must be at least 1".to_string(),
+            );
+        }
+
+        let retry = &self.retry;
+        if retry.llm_attempts == 0 || retry.rpc_attempts == 0 {
+            problems.push("retry.llm_attempts and rpc_attempts must be at least 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&retry.jitter) {
+            problems.push(format!(
+                "retry.jitter {} must be between 0 and 1",
+                retry.jitter
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid configuration:\n  - {}", problems.join("\n  - "))
+        }
+    }
+
+    /// Timezone days are counted in; only call after `validate`
+    pub fn timezone(&self) -> Timezone {
+        self.collector.timezone.parse().unwrap_or_default()
+    }
+
+    /// How long the read cache keeps results; None when it is disabled
+    pub fn read_cache_ttl(&self) -> Option<Duration> {
+        (self.api.read_cache_secs > 0).then(|| Duration::from_secs(self.api.read_cache_secs))
+    }
+
+    /// The collector's cadence as /api/poems/today estimates it; only call after `validate`
+    pub fn collection_schedule(&self) -> CollectionSchedule {
+        CollectionSchedule {
+            interval_minutes: self.collector.interval_minutes,
+            aligned: self.collector.align_to_interval.then(|| self.timezone()),
+            keywords_per_tick: self.collector.blocks_per_tick * self.collector.keywords_per_block,
+            keywords_per_day: self.collector.keywords_per_day,
+            min_keywords_for_poem: self.collector.min_keywords_for_poem,
+        }
+    }
+
+    /// How each day's poem form is chosen; only call after `validate`
+    pub fn form_selection(&self) -> FormSelection {
+        self.poem.form.parse().unwrap_or_default()
+    }
+
+    /// Free verse's line bounds, before each day's activity scales them
+    pub fn poem_length(&self) -> PoemLength {
+        PoemLength::new(self.poem.min_lines, self.poem.max_lines)
+    }
+
+    /// How the weekly dictionary theme is chosen, if themes are configured;
+    /// only call after `validate`
+    pub fn theme_selection(&self) -> Option<ThemeSelection> {
+        self.dictionary.themes_dir.as_ref()?;
+        Some(
+            self.dictionary
+                .theme
+                .as_deref()
+                .and_then(|theme| theme.parse().ok())
+                .unwrap_or(ThemeSelection::FromBlockhash),
+        )
+    }
+
+    /// The defaults with `derivation.source_weights` applied; only call after `validate`
+    pub fn source_weights(&self) -> SourceWeights {
+        self.derivation
+            .source_weights
+            .iter()
+            .filter_map(|(name, weight)| Some((BlockDataSource::from_name(name)?, *weight)))
+            .fold(SourceWeights::default(), |weights, (source, weight)| {
+                weights.with_weight(source, weight)
+            })
+    }
+
+    /// Program that keyword entropy is restricted to, if any; only call after `validate`
+    pub fn program_filter(&self) -> Option<Pubkey> {
+        self.solana
+            .program_filter
+            .as_deref()
+            .and_then(|program| program.parse().ok())
+    }
+
+    /// Scheduled database backups, None when no directory is set
+    pub fn database_backup(&self) -> Option<DatabaseBackup> {
+        let dir = self.database.backup_dir.clone()?;
+        Some(
+            DatabaseBackup::new(dir)
+                .with_interval(Duration::from_secs(
+                    self.database.backup_interval_hours * ONE_HOUR,
+                ))
+                .with_keep(self.database.backup_keep),
+        )
+    }
+
+    /// Pacing for each Solana RPC endpoint, None when unpaced
+    pub fn rpc_quota(&self) -> Option<Quota> {
+        (self.solana.requests_per_second > 0.0).then_some(Quota {
+            per_second: self.solana.requests_per_second,
+            burst: self.solana.burst,
+        })
+    }
+
+    /// Channels each new poem is pushed to; only call after `validate`
+    pub fn notify_targets(&self) -> Vec<NotifyTarget> {
+        let notifications = &self.notifications;
+        let discord = notifications
+            .discord_webhook_url
+            .clone()
+            .map(|webhook_url| NotifyTarget::Discord { webhook_url });
+        let telegram = notifications
+            .telegram_bot_token
+            .clone()
+            .zip(notifications.telegram_chat_id.clone())
+            .map(|(bot_token, chat_id)| NotifyTarget::Telegram { bot_token, chat_id });
+        discord.into_iter().chain(telegram).collect()
+    }
+
+    /// How each day's poem is posted; only call after `validate`
+    pub fn publish_format(&self) -> PublishFormat {
+        self.social.format.parse().unwrap_or_default()
+    }
+
+    /// Accounts each new poem is posted to; only call after `validate`
+    pub fn publish_targets(&self) -> Vec<PublishTarget> {
+        let social = &self.social;
+        let x = match (
+            &social.x_api_key,
+            &social.x_api_secret,
+            &social.x_access_token,
+            &social.x_access_token_secret,
+        ) {
+            (Some(api_key), Some(api_secret), Some(access_token), Some(access_token_secret)) => {
+                Some(PublishTarget::X(XCredentials {
+                    api_key: api_key.clone(),
+                    api_secret: api_secret.clone(),
+                    access_token: access_token.clone(),
+                    access_token_secret: access_token_secret.clone(),
+                }))
+            }
+            _ => None,
+        };
+        let bluesky = social
+            .bluesky_handle
+            .clone()
+            .zip(social.bluesky_app_password.clone())
+            .map(|(handle, app_password)| PublishTarget::Bluesky {
+                service: social.bluesky_service.clone(),
+                handle,
+                app_password,
+            });
+        x.into_iter().chain(bluesky).collect()
+    }
+
+    /// Client that reads each new poem aloud, when tts.url or tts.api_key is
+    /// set; only call after `validate`
+    pub fn tts_client(&self) -> Option<TtsClient> {
+        let tts = &self.tts;
+        if tts.url.is_none() && tts.api_key.is_none() {
+            return None;
+        }
+        let client = TtsClient::new(
+            tts.url
+                .clone()
+                .unwrap_or_else(|| OPENAI_SPEECH_URL.to_string()),
+            tts.model.clone(),
+            tts.voice.clone(),
+            tts.audio_dir.clone(),
+        )
+        .with_format(tts.format.parse().unwrap_or_default());
+        Some(match &tts.api_key {
+            Some(key) => client.with_api_key(key.clone()),
+            None => client,
+        })
+    }
+
+    /// Every key accepted by the admin API, ADMIN_API_KEY included
+    pub fn api_keys(&self) -> ApiKeys {
+        ApiKeys::new(self.api_keys_list())
+    }
+
+    fn api_keys_list(&self) -> Vec<ApiKey> {
+        let admin = self.api.admin_api_key.as_ref().map(|key| ApiKey {
+            name: ADMIN_API_KEY_NAME.to_string(),
+            key: key.clone(),
+            role: Role::Admin,
+        });
+        admin
+            .into_iter()
+            .chain(self.api.keys.iter().cloned())
+            .collect()
+    }
+
+    /// Sampling parameters for OpenRouter requests
+    pub fn sampling(&self) -> SamplingOptions {
+        SamplingOptions {
+            temperature: self.llm.temperature,
+            max_tokens: self.llm.max_tokens,
+            top_p: self.llm.top_p,
+            frequency_penalty: self.llm.frequency_penalty,
+            seed: self.llm.seed,
+        }
+    }
+
+    /// The OpenRouter API key; only call after `validate`
+    pub fn api_key(&self) -> &str {
+        self.llm.api_key.as_deref().unwrap_or_default()
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(name: &str, value: &str) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("{}=`{}` is invalid: {}", name, value, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config.solana.rpc_urls, vec![MAINNET_RPC_URL]);
+        assert_eq!(config.collector.interval_minutes, DEFAULT_COLLECTION_INTERVAL_MINUTES);
+        assert_eq!(config.api.port, DEFAULT_API_PORT);
+        assert_eq!(config.collector.keywords_per_day, DEFAULT_KEYWORDS_PER_DAY);
+        assert_eq!(
+            config.collector.self_backfill_days,
+            DEFAULT_SELF_BACKFILL_DAYS
+        );
+    }
+
+    #[test]
+    fn test_file_then_env_override() {
+        let mut config = Config::from_toml(
+            r#"
+            [llm]
+            model = "from-file"
+
+            [collector]
+            interval_minutes = 30
+
+            [api]
+            port = 8080
+            "#,
+        )
+        .unwrap();
+
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("OPENROUTER_API_KEY", "key"),
+            ("PORT", "9000"),
+            ("READ_CACHE_SECS", "0"),
+            ("KEYWORD_DEDUP_DAYS", "3"),
+            ("KEYWORDS_PER_BLOCK", "2"),
+            ("SELF_BACKFILL_DAYS", "0"),
+            ("CHAIN_VERSE_TIMEZONE", "-05:00"),
+            ("SOLANA_RPC_URLS", "https://a.example,https://b.example"),
+        ]);
+        config
+            .apply_env(|name| env.get(name).map(|v| v.to_string()))
+            .unwrap();
+
+        assert_eq!(config.llm.model, "from-file");
+        assert_eq!(config.collector.interval_minutes, 30);
+        assert_eq!(config.api.port, 9000);
+        assert_eq!(config.read_cache_ttl(), None);
+        assert_eq!(config.collector.dedup_days, 3);
+        assert_eq!(config.collector.keywords_per_block, 2);
+        assert_eq!(config.collector.self_backfill_days, 0);
+        assert_eq!(config.solana.rpc_urls.len(), 2);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.timezone().to_string(), "-05:00");
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        assert!(Config::from_toml("[collector]\ninterval = 5").is_err());
+    }
+
+    #[test]
+    fn test_enhanced_rpc() {
+        let mut config = Config::from_toml(
+            r#"
+            [solana]
+            enhanced_rpc = true
+
+            [collector]
+            keywords_per_block = 10
+            "#,
+        )
+        .unwrap();
+        config.llm.api_key = Some("key".to_string());
+
+        let result = config.validate();
+        if cfg!(feature = "enhanced-rpc") {
+            assert!(result.is_ok());
+        } else {
+            let message = result.unwrap_err().to_string();
+            assert!(message.contains("--features enhanced-rpc"));
+        }
+
+        config.collector.keywords_per_block = 11;
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("between 1 and 10"), "{}", message);
+    }
+
+    #[test]
+    fn test_frontend_dir() {
+        let mut config = Config::from_toml("[api]\nfrontend_dir = \"missing\"").unwrap();
+        config.llm.api_key = Some("key".to_string());
+
+        let message = config.validate().unwrap_err().to_string();
+        if cfg!(feature = "frontend") {
+            assert!(message.contains("no index.html"), "{}", message);
+        } else {
+            assert!(message.contains("--features frontend"), "{}", message);
+        }
+
+        config.api.frontend_dir = None;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_source_weights() {
+        let mut config = Config::from_toml(
+            r#"
+            [derivation.source_weights]
+            leader = 2.0
+            "#,
+        )
+        .unwrap();
+        config.llm.api_key = Some("key".to_string());
+        assert!(config.validate().is_ok());
+        // File weights apply on top of the defaults
+        let weights = config.source_weights();
+        assert_eq!(weights.weight(BlockDataSource::Leader), 2.0);
+        assert_eq!(weights.weight(BlockDataSource::Blockhash), 3.0);
+        assert_eq!(weights.weight(BlockDataSource::TotalFees), 1.0);
+
+        // The environment replaces the file's weights
+        config
+            .apply_env(|name| {
+                (name == "DERIVATION_SOURCE_WEIGHTS")
+                    .then(|| "blockhash=1, transaction_count=0.5".to_string())
+            })
+            .unwrap();
+        let weights = config.source_weights();
+        assert_eq!(weights.weight(BlockDataSource::Leader), 1.0);
+        assert_eq!(weights.weight(BlockDataSource::Blockhash), 1.0);
+        assert_eq!(weights.weight(BlockDataSource::TransactionCount), 0.5);
+
+        config
+            .derivation
+            .source_weights
+            .extend([("txcount".to_string(), 1.0), ("leader".to_string(), -1.0)]);
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("`txcount` is not an entropy source"));
+        assert!(message.contains("source_weights.leader must be 0 or more"));
+
+        let err = Config::default()
+            .apply_env(|name| (name == "DERIVATION_SOURCE_WEIGHTS").then(|| "leader".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("not source=weight"));
+    }
+
+    #[test]
+    fn test_invalid_env_value() {
+        let mut config = Config::default();
+        let err = config
+            .apply_env(|name| (name == "PORT").then(|| "abc".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("PORT"));
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut config = Config::default();
+        config.collector.interval_minutes = 0;
+        config.collector.keywords_per_day = config.collector.min_keywords_for_poem - 1;
+        config.solana.rpc_urls = vec!["ftp://nope".to_string()];
+        config.poem.min_keyword_fraction = 1.5;
+        config.solana.sample_signatures = 0;
+        config.collector.blocks_per_tick = 0;
+        config.collector.keywords_per_block = 8;
+        config.collector.timezone = "America/New_York".to_string();
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("OPENROUTER_API_KEY"));
+        assert!(message.contains("interval_minutes"));
+        assert!(message.contains("keywords_per_day"));
+        assert!(message.contains("ftp://nope"));
+        assert!(message.contains("min_keyword_fraction"));
+        assert!(message.contains("sample_signatures"));
+        assert!(message.contains("blocks_per_tick"));
+        assert!(message.contains("keywords_per_block"));
+        assert!(message.contains("collector.timezone"));
+    }
+
+    #[test]
+    fn test_collection_schedule() {
+        let mut config = Config::default();
+        config.collector.interval_minutes = 7 * 60;
+        config.collector.align_to_interval = true;
+        config.collector.jitter_secs = 7 * 60 * 60;
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("must divide a day evenly"));
+        assert!(message.contains("jitter_secs"));
+
+        config.collector.interval_minutes = 30;
+        config.collector.jitter_secs = 120;
+        let message = config.validate().unwrap_err().to_string();
+        assert!(!message.contains("collector."));
+    }
+
+    #[test]
+    fn test_sampling_options() {
+        let mut config = Config::from_toml("[llm]\ntemperature = 0.9\nmax_tokens = 800").unwrap();
+        config
+            .apply_env(|name| (name == "OPENROUTER_SEED").then(|| "7".to_string()))
+            .unwrap();
+        let sampling = config.sampling();
+        assert_eq!(sampling.temperature, Some(0.9));
+        assert_eq!(sampling.max_tokens, Some(800));
+        assert_eq!(sampling.seed, Some(7));
+        assert_eq!(sampling.top_p, None);
+
+        config.llm.temperature = Some(3.0);
+        config.llm.top_p = Some(0.0);
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("llm.temperature"));
+        assert!(message.contains("llm.top_p"));
+    }
+
+    #[test]
+    fn test_retry_policies() {
+        let config = Config::default();
+        assert_eq!(config.retry.llm(), RetryPolicy::llm());
+        assert_eq!(config.retry.rpc(), RetryPolicy::rpc());
+
+        let mut config = Config::from_toml("[retry]\nrpc_attempts = 3\njitter = 0").unwrap();
+        config
+            .apply_env(|name| (name == "RPC_RETRY_BASE_DELAY_MS").then(|| "100".to_string()))
+            .unwrap();
+        assert_eq!(
+            config.retry.rpc(),
+            RetryPolicy::new(3, Duration::from_millis(100))
+        );
+
+        config.retry.llm_attempts = 0;
+        config.retry.jitter = 1.5;
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("retry.llm_attempts"));
+        assert!(message.contains("retry.jitter"));
+    }
+
+    #[test]
+    fn test_database_backup() {
+        assert_eq!(Config::default().database_backup(), None);
+
+        let mut config = Config::from_toml("[database]\nbackup_dir = \"backups\"").unwrap();
+        config
+            .apply_env(|name| (name == "BACKUP_KEEP").then(|| "3".to_string()))
+            .unwrap();
+        assert_eq!(
+            config.database_backup(),
+            Some(
+                DatabaseBackup::new(PathBuf::from("backups"))
+                    .with_interval(Duration::from_secs(24 * ONE_HOUR))
+                    .with_keep(3)
+            )
+        );
+
+        config.database.backup_keep = 0;
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("backup_keep must be at least 1"));
+    }
+
+    #[test]
+    fn test_anchor_keypair_path() {
+        assert_eq!(Config::default().anchor.keypair_path, None);
+
+        let mut config = Config::from_toml("[anchor]\nkeypair_path = \"missing.json\"").unwrap();
+        assert_eq!(
+            config.anchor.keypair_path,
+            Some(PathBuf::from("missing.json"))
+        );
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("anchor.keypair_path"), "{}", message);
+
+        let keypair =
+            std::env::temp_dir().join(format!("chain_verse_anchor_{}.json", std::process::id()));
+        std::fs::write(&keypair, "[]").unwrap();
+        let path = keypair.display().to_string();
+        config
+            .apply_env(|name| (name == "ANCHOR_KEYPAIR_PATH").then(|| path.clone()))
+            .unwrap();
+        assert_eq!(config.anchor.keypair_path, Some(keypair.clone()));
+        assert!(config.check(false).is_ok());
+        std::fs::remove_file(keypair).unwrap();
+    }
+
+    #[test]
+    fn test_poem_length() {
+        assert_eq!(Config::default().poem_length(), PoemLength::DEFAULT);
+
+        let mut config = Config::from_toml("[poem]\nmin_lines = 8").unwrap();
+        config
+            .apply_env(|name| (name == "POEM_MAX_LINES").then(|| "16".to_string()))
+            .unwrap();
+        assert_eq!(config.poem_length(), PoemLength::new(8, 16));
+
+        config.poem.max_lines = 4;
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("poem.min_lines"));
+    }
+
+    #[test]
+    fn test_poem_language() {
+        let mut config = Config::from_toml("[poem]\nlanguage = \"ja\"").unwrap();
+        assert_eq!(config.poem.language, "ja");
+        config
+            .apply_env(|name| (name == "POEM_LANGUAGE").then(|| "pt br".to_string()))
+            .unwrap();
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("poem.language"));
+    }
+
+    #[test]
+    fn test_api_key_optional_without_credentials() {
+        let config = Config::default();
+        assert!(config.validate().is_err());
+        assert!(config.check(false).is_ok());
+    }
+
+    #[test]
+    fn test_theme_selection() {
+        let mut config = Config::default();
+        assert_eq!(config.theme_selection(), None);
+
+        config.dictionary.themes_dir = Some(PathBuf::from("themes"));
+        assert_eq!(config.theme_selection(), Some(ThemeSelection::FromBlockhash));
+
+        config.dictionary.theme = Some("Ocean".to_string());
+        assert_eq!(
+            config.theme_selection(),
+            Some(ThemeSelection::Fixed("ocean".to_string()))
+        );
+
+        config.dictionary.themes_dir = None;
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("dictionary.themes_dir"));
+    }
+
+    #[test]
+    fn test_program_filter() {
+        let mut config = Config::default();
+        assert_eq!(config.program_filter(), None);
+
+        config
+            .apply_env(|name| {
+                (name == "SOLANA_PROGRAM_FILTER")
+                    .then(|| "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string())
+            })
+            .unwrap();
+        assert_eq!(
+            config.program_filter(),
+            Some(solana_sdk::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"))
+        );
+
+        config.solana.program_filter = Some("not-a-program".to_string());
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("solana.program_filter"));
+    }
+
+    #[test]
+    fn test_rpc_quota() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.rpc_quota(),
+            Some(Quota {
+                per_second: DEFAULT_RPC_REQUESTS_PER_SECOND,
+                burst: DEFAULT_RPC_BURST,
+            })
+        );
+
+        config
+            .apply_env(|name| (name == "SOLANA_RPC_REQUESTS_PER_SECOND").then(|| "0".to_string()))
+            .unwrap();
+        assert_eq!(config.rpc_quota(), None);
+
+        config.solana.requests_per_second = 4.0;
+        config.solana.burst = 0;
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("solana.burst"));
+    }
+
+    #[test]
+    fn test_api_keys() {
+        let mut config = Config::from_toml(
+            r#"
+            [api]
+            admin_api_key = "root-secret"
+
+            [[api.keys]]
+            name = "alice"
+            key = "alice-secret"
+            role = "curator"
+            "#,
+        )
+        .unwrap();
+        config
+            .apply_env(|name| (name == "API_KEYS").then(|| "dash:reader:dash-secret".to_string()))
+            .unwrap();
+        assert!(config.check(false).is_ok());
+
+        // API_KEYS replaces the file's list; ADMIN_API_KEY is kept alongside
+        let keys = config.api_keys_list();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].name, "admin");
+        assert_eq!(keys[0].role, Role::Admin);
+        assert_eq!(keys[1].name, "dash");
+        assert_eq!(keys[1].role, Role::Reader);
+
+        let keys = &mut config.api.keys;
+        keys.push("admin:reader:other".parse().unwrap());
+        keys.push("eve:reader:dash-secret".parse().unwrap());
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("`admin` is configured twice"));
+        assert!(message.contains("`eve` reuses"));
+
+        let err = config
+            .apply_env(|name| (name == "API_KEYS").then(|| "dash:owner:x".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("API_KEYS"));
+    }
+
+    #[test]
+    fn test_notify_targets() {
+        let mut config = Config::from_toml(
+            r#"
+            [notifications]
+            discord_webhook_url = "https://discord.com/api/webhooks/1/abc"
+            notify_failures = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.notify_targets().len(), 1);
+        assert!(!config.notifications.notify_failures);
+
+        config
+            .apply_env(|name| (name == "TELEGRAM_BOT_TOKEN").then(|| "123:secret".to_string()))
+            .unwrap();
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("telegram_chat_id"));
+
+        config.notifications.telegram_chat_id = Some("@chain_verse".to_string());
+        config.notifications.discord_webhook_url =
+            Some("http://discord.com/api/webhooks/1/abc".to_string());
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("discord_webhook_url"));
+        assert!(!message.contains("abc"));
+
+        config.notifications.discord_webhook_url = None;
+        assert!(config.check(false).is_ok());
+        assert!(matches!(
+            config.notify_targets().as_slice(),
+            [NotifyTarget::Telegram { chat_id, .. }] if chat_id == "@chain_verse"
+        ));
+    }
+
+    #[test]
+    fn test_publish_targets() {
+        let mut config = Config::from_toml(
+            r#"
+            [social]
+            format = "image"
+            bluesky_handle = "chainverse.bsky.social"
+            "#,
+        )
+        .unwrap();
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("bluesky_app_password"));
+
+        config
+            .apply_env(|name| match name {
+                "BLUESKY_APP_PASSWORD" => Some("abcd-efgh-ijkl-mnop".to_string()),
+                "X_API_KEY" => Some("key".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("x_access_token_secret must be set together"));
+        assert!(!message.contains("bluesky"));
+
+        config.social.x_api_key = None;
+        assert!(config.check(false).is_ok());
+        assert_eq!(config.publish_format(), PublishFormat::Image);
+        assert!(matches!(
+            config.publish_targets().as_slice(),
+            [PublishTarget::Bluesky { service, .. }] if service == DEFAULT_BLUESKY_SERVICE
+        ));
+
+        config.social.format = "video".to_string();
+        let message = config.check(false).unwrap_err().to_string();
+        assert!(message.contains("social.format"));
+    }
+}
@@ -1,5 +1,5 @@
-/// Chain Verse Constants
-/// Inspired by ORE's well-organized constants pattern
+//! Chain Verse Constants
+//! Inspired by ORE's well-organized constants pattern
 
 // =============================================================================
 // TIME CONSTANTS (in seconds)
@@ -14,28 +14,13 @@ pub const ONE_HOUR: u64 = 60 * ONE_MINUTE;
 /// One day in seconds
 pub const ONE_DAY: u64 = 24 * ONE_HOUR;
 
-/// One week in seconds
-pub const ONE_WEEK: u64 = 7 * ONE_DAY;
-
 // =============================================================================
 // SLOT CONSTANTS
 // Solana produces ~2.5 slots per second (400ms per slot)
 // =============================================================================
 
-/// Slots per second (approximate)
-pub const SLOTS_PER_SECOND: u64 = 2;
-
-/// One minute in slots (~150 slots)
-pub const ONE_MINUTE_SLOTS: u64 = 60 * SLOTS_PER_SECOND;
-
-/// One hour in slots (~7,200 slots)
-pub const ONE_HOUR_SLOTS: u64 = 60 * ONE_MINUTE_SLOTS;
-
-/// One day in slots (~172,800 slots)
-pub const ONE_DAY_SLOTS: u64 = 24 * ONE_HOUR_SLOTS;
-
-/// One week in slots (~1,209,600 slots)
-pub const ONE_WEEK_SLOTS: u64 = 7 * ONE_DAY_SLOTS;
+/// Observed slots per day (~2.5 slots/second), used to estimate historical slots
+pub const ESTIMATED_SLOTS_PER_DAY: u64 = 216_000;
 
 // =============================================================================
 // RPC ENDPOINTS
@@ -44,18 +29,69 @@ pub const ONE_WEEK_SLOTS: u64 = 7 * ONE_DAY_SLOTS;
 /// Solana Mainnet RPC URL
 pub const MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
 
-/// Solana Devnet RPC URL (for testing)
-pub const DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+/// Initial cooldown for an RPC endpoint after a failure (doubles per consecutive failure)
+pub const RPC_FAILURE_COOLDOWN_SECS: u64 = 30;
+
+/// Longest an RPC endpoint is benched after repeated failures
+pub const RPC_MAX_COOLDOWN_SECS: u64 = 10 * ONE_MINUTE;
+
+/// Default sustained Solana RPC calls per second per endpoint (public mainnet
+/// allows about 10)
+pub const DEFAULT_RPC_REQUESTS_PER_SECOND: f64 = 8.0;
+
+/// Default burst of Solana RPC calls per endpoint
+pub const DEFAULT_RPC_BURST: u32 = 8;
+
+/// Solana RPC calls per second per endpoint for /api/blocks/{slot}, paced
+/// apart from the collector's (together they stay under public mainnet's 10)
+pub const BLOCK_LOOKUP_REQUESTS_PER_SECOND: f64 = 1.0;
+
+/// Burst of Solana RPC calls per endpoint for /api/blocks/{slot}
+pub const BLOCK_LOOKUP_BURST: u32 = 2;
+
+/// First pause after an RPC endpoint answers 429 without Retry-After; doubles per 429 in a row
+pub const RPC_THROTTLE_BACKOFF_MS: u64 = 500;
+
+/// Longest pause after a 429, whatever Retry-After asks for
+pub const RPC_THROTTLE_MAX_PAUSE_SECS: u64 = ONE_MINUTE;
+
+/// Consecutive failed calls after which a provider's circuit breaker opens
+pub const CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long an open circuit breaker fails calls fast before letting a probe through
+pub const CIRCUIT_BREAKER_OPEN_SECS: u64 = 5 * ONE_MINUTE;
 
-/// Solana Testnet RPC URL
-pub const TESTNET_RPC_URL: &str = "https://api.testnet.solana.com";
+/// Attempts per poem or meta-poem request to the LLM, counting the first
+pub const DEFAULT_LLM_RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay before the second LLM attempt; doubles per attempt after that
+pub const DEFAULT_LLM_RETRY_BASE_DELAY_MS: u64 = 2000;
+
+/// Passes over every RPC endpoint before a call fails (1 = fail over only)
+pub const DEFAULT_RPC_RETRY_ATTEMPTS: u32 = 1;
+
+/// Delay before the second pass over the RPC endpoints; doubles per pass after that
+pub const DEFAULT_RPC_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// Longest delay between retries, before jitter
+pub const DEFAULT_RETRY_MAX_DELAY_MS: u64 = 60_000;
+
+/// Random extra delay per retry, as a fraction of the delay
+pub const DEFAULT_RETRY_JITTER: f64 = 0.1;
 
 // =============================================================================
 // CHAIN VERSE CONFIGURATION
 // =============================================================================
 
-/// Default number of keywords needed to generate a poem
-pub const DEFAULT_KEYWORDS_PER_POEM: usize = 16;
+/// Default number of keywords collected per day; collection stops once a day has this many
+pub const DEFAULT_KEYWORDS_PER_DAY: usize = 16;
+
+/// How many of the newest past days without a poem the collector backfills on
+/// start (collector.self_backfill_days)
+pub const DEFAULT_SELF_BACKFILL_DAYS: usize = 7;
+
+/// Most blocks the collector may fetch per tick (collector.blocks_per_tick)
+pub const MAX_BLOCKS_PER_TICK: usize = 10;
 
 /// Minimum keywords required before poem generation
 pub const MIN_KEYWORDS_FOR_POEM: usize = 8;
@@ -63,15 +99,102 @@ pub const MIN_KEYWORDS_FOR_POEM: usize = 8;
 /// Maximum keywords to use in a single poem
 pub const MAX_KEYWORDS_FOR_POEM: usize = 24;
 
+/// Fewest daily poems a week or month needs before it gets a meta-poem
+pub const MIN_POEMS_FOR_COLLECTION: usize = 3;
+
+/// Most words looked up in the dictionary per collection tick; a backlog
+/// (e.g. after enabling definitions on an old archive) drains over several ticks
+pub const DEFINITIONS_PER_TICK: i64 = 10;
+
+/// Most poems embedded per collection tick (see `embeddings`); an archive
+/// embedded for the first time catches up over several ticks
+pub const EMBEDDINGS_PER_TICK: i64 = 10;
+
+/// Most poems read aloud per collection tick (see `tts`); speech is slower
+/// and costlier than embedding, so an old archive catches up gradually
+pub const NARRATIONS_PER_TICK: i64 = 3;
+
 /// Default keyword collection interval in minutes
 pub const DEFAULT_COLLECTION_INTERVAL_MINUTES: u64 = 90;
 
 /// Number of slots to go back for confirmed blocks
 pub const CONFIRMATION_SLOTS: u64 = 32;
 
-/// Default poem line count range
-pub const POEM_MIN_LINES: usize = 20;
-pub const POEM_MAX_LINES: usize = 30;
+/// Block fetches kept in flight at once by `get_recent_blocks` and backfill
+pub const BLOCK_FETCH_CONCURRENCY: usize = 6;
+
+/// Default transaction signatures sampled per block for entropy
+pub const DEFAULT_SAMPLE_SIGNATURES: usize = 5;
+
+/// Vote program ID; transactions that call it are validator votes
+pub const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+/// First delay before retrying a failed poem generation; doubles per failure
+pub const POEM_RETRY_BASE_SECS: u64 = 5 * ONE_MINUTE;
+
+/// Longest delay between poem generation retries
+pub const POEM_RETRY_MAX_SECS: u64 = 6 * ONE_HOUR;
+
+/// How often the collector checks the latest block for notable on-chain events
+pub const EVENT_WATCH_INTERVAL_SECS: u64 = ONE_MINUTE;
+
+/// Recent blocks whose average transaction count a block is compared against
+pub const EVENT_BASELINE_BLOCKS: usize = 30;
+
+/// A block with this many times the average transaction count is a surge
+pub const EVENT_SURGE_FACTOR: f64 = 3.0;
+
+/// Skipped slots before a block that count as a notable gap (~5s of silence)
+pub const EVENT_MIN_SLOT_GAP: u64 = 12;
+
+/// Slots after an event during which further events are ignored (~30 minutes)
+pub const EVENT_COOLDOWN_SLOTS: u64 = 4_500;
+
+/// Tag stored on keywords collected because of an on-chain event
+pub const KEYWORD_TAG_EVENT: &str = "event";
+
+/// `scheduler_state` rows for the collector's last keyword collection and poem
+pub const SCHEDULER_TASK_COLLECTION: &str = "collection";
+pub const SCHEDULER_TASK_POEM: &str = "poem";
+
+/// How often the collector looks for poem retries that are due
+pub const POEM_RETRY_CHECK_SECS: u64 = ONE_MINUTE;
+
+/// How long a collector's lease on generating a day's poem lasts; covers the
+/// LLM's retries and lets another instance take over from one that died
+/// mid-generation. The lease is renewed just before the poem is stored, which
+/// covers the minting, anchoring and posting that follow
+pub const POEM_LOCK_TTL_SECS: u64 = 30 * ONE_MINUTE;
+
+/// How often the collector re-reads collected slots at finalized commitment
+pub const REORG_CHECK_INTERVAL_SECS: u64 = 10 * ONE_MINUTE;
+
+/// Age at which a keyword's slot is re-read; blocks finalize within seconds,
+/// so an hour leaves a wide margin for a slow endpoint
+pub const REORG_CHECK_DELAY_SECS: u64 = ONE_HOUR;
+
+/// Keywords collected longer ago than this aren't checked: they predate the
+/// check, or a long outage kept it from running
+pub const REORG_CHECK_WINDOW_SECS: u64 = 2 * ONE_DAY;
+
+/// Most keywords re-read per check; a backlog drains over several checks
+pub const REORG_CHECKS_PER_TICK: i64 = 50;
+
+/// Longest silence tolerated mid-stream from the LLM before the attempt is abandoned
+pub const LLM_STREAM_IDLE_TIMEOUT_SECS: u64 = 30;
+
+/// Default free verse line count range
+pub const DEFAULT_POEM_MIN_LINES: usize = 20;
+pub const DEFAULT_POEM_MAX_LINES: usize = 30;
+
+/// Language tag of dictionaries that don't declare one, and of poems by default
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Lines a free verse poem may miss the day's scaled line count by
+pub const POEM_LINE_TOLERANCE: usize = 2;
+
+/// Fraction of the day's keywords a poem must use before it is stored
+pub const DEFAULT_MIN_KEYWORD_FRACTION: f64 = 0.5;
 
 // =============================================================================
 // DATABASE
@@ -80,6 +203,24 @@ pub const POEM_MAX_LINES: usize = 30;
 /// Default SQLite database path
 pub const DEFAULT_DATABASE_URL: &str = "sqlite:chain_verse.db";
 
+/// How long a connection waits on another writer's lock before failing with SQLITE_BUSY
+pub const DATABASE_BUSY_TIMEOUT_SECS: u64 = 5;
+
+/// Default hours between database backups (config: database.backup_interval_hours)
+pub const DEFAULT_BACKUP_INTERVAL_HOURS: u64 = 24;
+
+/// Default number of database backups kept (config: database.backup_keep)
+pub const DEFAULT_BACKUP_KEEP: usize = 7;
+
+/// Attempts to open the database before giving up on transient failures
+pub const DATABASE_CONNECT_ATTEMPTS: u32 = 5;
+
+/// First delay between database connection attempts (doubles per failure)
+pub const DATABASE_CONNECT_BACKOFF_MS: u64 = 250;
+
+/// Connections in the pool the collector and the API share
+pub const DATABASE_MAX_CONNECTIONS: u32 = 10;
+
 // =============================================================================
 // API SERVER
 // =============================================================================
@@ -87,16 +228,172 @@ pub const DEFAULT_DATABASE_URL: &str = "sqlite:chain_verse.db";
 /// Default API server port
 pub const DEFAULT_API_PORT: u16 = 3000;
 
-/// API version prefix
-pub const API_VERSION: &str = "v1";
+/// Default number of word pairs returned by the co-occurrence endpoint
+pub const DEFAULT_COOCCURRENCE_LIMIT: i64 = 200;
+
+/// Upper bound on word pairs returned by the co-occurrence endpoint
+pub const MAX_COOCCURRENCE_LIMIT: i64 = 2000;
+
+/// How long caches may serve a past day's poem before revalidating its ETag
+pub const HISTORICAL_POEM_MAX_AGE_SECS: u64 = ONE_DAY;
+
+/// How long caches may serve today's poem, the poem list, and the archive
+pub const RECENT_CACHE_MAX_AGE_SECS: u64 = ONE_MINUTE;
+
+/// Default for how long the in-process read cache trusts a result that no
+/// local write has invalidated (config: api.read_cache_secs)
+pub const DEFAULT_READ_CACHE_SECS: u64 = 30;
+
+/// Bluesky server (PDS) accounts log into unless they set another
+/// (config: social.bluesky_service)
+pub const DEFAULT_BLUESKY_SERVICE: &str = "https://bsky.social";
+
+/// Speech model and voice poems are read in, OpenAI's names (config: tts.model, tts.voice)
+pub const DEFAULT_TTS_MODEL: &str = "tts-1";
+pub const DEFAULT_TTS_VOICE: &str = "alloy";
+
+/// Where poem recordings are written (config: tts.audio_dir)
+pub const DEFAULT_AUDIO_DIR: &str = "audio";
+
+/// Days a word must go unseen for its return to count as rare; a keyword
+/// this long after the word's last appearance scores 0.5
+pub const RARE_KEYWORD_GAP_DAYS: i64 = 30;
+
+/// Poems returned by the full-text search endpoint by default
+pub const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+/// Upper bound on poems returned by the full-text search endpoint
+pub const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// Keywords read per query when exporting the keyword dataset
+pub const KEYWORD_EXPORT_PAGE_SIZE: i64 = 5000;
+
+/// Poems returned by the similar-poems endpoint by default
+pub const DEFAULT_SIMILAR_LIMIT: i64 = 5;
+
+/// Upper bound on poems returned by the similar-poems endpoint
+pub const MAX_SIMILAR_LIMIT: i64 = 20;
+
+/// API key requests returned by the usage endpoint by default
+pub const DEFAULT_API_USAGE_LIMIT: i64 = 100;
+
+/// Upper bound on API key requests returned by the usage endpoint
+pub const MAX_API_USAGE_LIMIT: i64 = 1000;
+
+/// Most frequent words returned by the stats endpoint by default
+pub const DEFAULT_STATS_TOP_WORDS: i64 = 10;
+
+/// Upper bound on most frequent words returned by the stats endpoint
+pub const MAX_STATS_TOP_WORDS: i64 = 100;
+
+/// Poems returned by the GraphQL `poems` query by default
+#[cfg(feature = "graphql")]
+pub const DEFAULT_GRAPHQL_POEMS: i64 = 20;
+
+/// Upper bound on poems returned by one GraphQL `poems` query
+#[cfg(feature = "graphql")]
+pub const MAX_GRAPHQL_POEMS: i64 = 100;
+
+/// Collector events buffered per /api/events subscriber before it skips ahead;
+/// sized to hold a streamed poem's tokens
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Admin API commands that may wait for the collector before more are refused
+pub const COLLECTOR_COMMAND_CAPACITY: usize = 8;
+
+/// Default sustained requests per second per client IP
+pub const DEFAULT_RATE_LIMIT_PER_SECOND: f64 = 5.0;
+
+/// Default burst size per client IP
+pub const DEFAULT_RATE_LIMIT_BURST: u32 = 20;
+
+/// Default sustained requests per second for the admin key
+pub const DEFAULT_ADMIN_RATE_LIMIT_PER_SECOND: f64 = 50.0;
+
+/// Default burst size for the admin key
+pub const DEFAULT_ADMIN_RATE_LIMIT_BURST: u32 = 200;
+
+/// Time allowed for each dependency probe in the deep health check
+pub const HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+
+/// How long a deep health check's report is reused, so polling /health/deep
+/// can't turn into a stream of RPC and OpenRouter calls
+pub const HEALTH_CHECK_CACHE_SECS: u64 = 10;
+
+/// Collection intervals without a new keyword before the collector counts as stale
+pub const COLLECTOR_STALE_INTERVALS: u64 = 2;
+
+/// Client buckets kept before idle ones are pruned
+pub const RATE_LIMIT_MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Longest curator note on a keyword, in characters
+pub const MAX_ANNOTATION_CHARS: usize = 280;
+
+/// Emoji readers can react to a poem with
+pub const POEM_REACTIONS: &[&str] = &["❤️", "🔥", "✨", "🌊", "🤯", "😢"];
+
+/// Sustained reactions per minute per client IP, on top of the API-wide limit
+pub const REACTIONS_PER_MINUTE: f64 = 6.0;
+
+/// Reactions a client IP can leave in a burst
+pub const REACTION_BURST: u32 = 10;
+
+/// How often poem views counted in memory are written to the database
+pub const VIEW_FLUSH_INTERVAL_SECS: u64 = 10;
+
+// =============================================================================
+// TIME-TRAVEL PREVIEWS
+// =============================================================================
+
+/// Blocks sampled across a historical day for a preview poem
+pub const PREVIEW_BLOCKS: usize = 12;
+
+/// Maximum preview poems generated per hour (cached previews don't count)
+pub const PREVIEW_MAX_PER_HOUR: usize = 6;
+
+/// Maximum preview poems kept in memory
+pub const PREVIEW_CACHE_CAPACITY: usize = 256;
+
+/// Widest slot range accepted by the on-demand custom poem endpoint (~1 week)
+pub const CUSTOM_POEM_MAX_RANGE_SLOTS: u64 = 7 * ESTIMATED_SLOTS_PER_DAY;
+
+/// Longest input POST /api/derive traces; signatures are 88 characters at most
+pub const MAX_DERIVE_INPUT_LEN: usize = 256;
 
 // =============================================================================
 // BLOCKCHAIN DATA SOURCES
 // Each source provides different entropy for keyword derivation
 // =============================================================================
 
+/// Non-vote transactions per block from which a day's mood counts as busy
+pub const MOOD_BUSY_TRANSACTIONS_PER_BLOCK: u64 = 800;
+
+/// Non-vote transactions per block below which a day's mood counts as quiet
+pub const MOOD_QUIET_TRANSACTIONS_PER_BLOCK: u64 = 300;
+
+/// Average lamports paid per transaction from which a day's fees count as
+/// heavy (three times the 5,000 lamport base fee)
+pub const MOOD_HEAVY_FEE_LAMPORTS: u64 = 15_000;
+
+/// Version of the keyword derivation algorithm, recorded in each poem's provenance;
+/// bump it whenever a change would derive different words from the same block
+pub const DERIVATION_VERSION: u32 = 1;
+
+/// Re-hashes tried when a derived word is blocked (or recently used) before giving up
+pub const MAX_BLOCKLIST_REHASHES: u32 = 1000;
+
+/// Source weights used unless `[derivation.source_weights]` overrides them;
+/// unlisted sources weigh 1, and a weight of 0 makes a source filler
+pub const DEFAULT_SOURCE_WEIGHTS: &[(BlockDataSource, f64)] = &[
+    (BlockDataSource::Blockhash, 3.0),
+    (BlockDataSource::TransactionCount, 0.0),
+];
+
+/// Source recorded for keywords a curator added through the admin API
+pub const MANUAL_KEYWORD_SOURCE: &str = "manual";
+
 /// Data sources for keyword derivation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BlockDataSource {
     /// Use blockhash (default)
     Blockhash,
@@ -108,9 +405,48 @@ pub enum BlockDataSource {
     Rewards,
     /// Use number of transactions
     TransactionCount,
+    /// Use lamports paid in transaction fees
+    TotalFees,
+    /// Use the counts of vote and non-vote transactions
+    VoteCounts,
+    /// Use the identity of the validator that produced the block
+    Leader,
+    /// Use the programs the block invoked most (enhanced RPC only)
+    ProgramInvocations,
+    /// Use the NFTs minted in the block (enhanced RPC only)
+    NftMints,
+    /// Use the number of SPL token transfers (enhanced RPC only)
+    TokenTransfers,
 }
 
 impl BlockDataSource {
+    /// Stable name stored with each keyword and shown in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockDataSource::Blockhash => "blockhash",
+            BlockDataSource::PreviousBlockhash => "previous_blockhash",
+            BlockDataSource::TransactionRoot => "transaction_root",
+            BlockDataSource::Rewards => "rewards",
+            BlockDataSource::TransactionCount => "transaction_count",
+            BlockDataSource::TotalFees => "total_fees",
+            BlockDataSource::VoteCounts => "vote_counts",
+            BlockDataSource::Leader => "leader",
+            BlockDataSource::ProgramInvocations => "program_invocations",
+            BlockDataSource::NftMints => "nft_mints",
+            BlockDataSource::TokenTransfers => "token_transfers",
+        }
+    }
+
+    /// The source stored as `name` (see `as_str`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        [BlockDataSource::Rewards]
+            .iter()
+            .chain(Self::all())
+            .chain(Self::enhanced())
+            .copied()
+            .find(|source| source.as_str() == name)
+    }
+
     /// Get all available data sources
     pub fn all() -> &'static [BlockDataSource] {
         &[
@@ -118,6 +454,19 @@ impl BlockDataSource {
             BlockDataSource::PreviousBlockhash,
             BlockDataSource::TransactionRoot,
             BlockDataSource::TransactionCount,
+            BlockDataSource::TotalFees,
+            BlockDataSource::VoteCounts,
+            BlockDataSource::Leader,
+        ]
+    }
+
+    /// Sources read from the parsed transactions an enhanced RPC returns
+    /// (`solana.enhanced_rpc`), used after `all()` when a block has them
+    pub fn enhanced() -> &'static [BlockDataSource] {
+        &[
+            BlockDataSource::ProgramInvocations,
+            BlockDataSource::NftMints,
+            BlockDataSource::TokenTransfers,
         ]
     }
 }
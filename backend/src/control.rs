@@ -0,0 +1,101 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::consts::COLLECTOR_COMMAND_CAPACITY;
+
+/// Operator command for a running collector, sent by the admin API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectorCommand {
+    /// Collect keywords now, off the schedule; runs while paused too
+    CollectNow,
+    /// Skip scheduled and event collections until resumed
+    Pause,
+    Resume,
+}
+
+impl CollectorCommand {
+    /// Name in responses and logs, matching the serialized form
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CollectNow => "collect_now",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+        }
+    }
+}
+
+/// Sending half of the collector's command channel, held by the API
+#[derive(Debug, Clone)]
+pub struct CollectorControl {
+    sender: mpsc::Sender<CollectorCommand>,
+    /// Whether the last pause or resume queued was a pause
+    paused: Arc<AtomicBool>,
+}
+
+impl CollectorControl {
+    /// A control and the receiver to hand to `KeywordCollector::with_control`
+    pub fn channel() -> (Self, mpsc::Receiver<CollectorCommand>) {
+        let (sender, receiver) = mpsc::channel(COLLECTOR_COMMAND_CAPACITY);
+        let control = Self {
+            sender,
+            paused: Arc::default(),
+        };
+        (control, receiver)
+    }
+
+    /// Queue `command` without waiting; fails while the collector already has
+    /// COLLECTOR_COMMAND_CAPACITY commands waiting, or once it has stopped
+    pub fn send(&self, command: CollectorCommand) -> anyhow::Result<()> {
+        self.sender.try_send(command).map_err(|e| match e {
+            TrySendError::Full(_) => {
+                anyhow::anyhow!("The collector is busy with earlier commands; try again shortly")
+            }
+            TrySendError::Closed(_) => anyhow::anyhow!("The collector has stopped"),
+        })?;
+        match command {
+            CollectorCommand::Pause => self.paused.store(true, Ordering::Relaxed),
+            CollectorCommand::Resume => self.paused.store(false, Ordering::Relaxed),
+            CollectorCommand::CollectNow => {}
+        }
+        Ok(())
+    }
+
+    /// Whether an operator paused the collector (and hasn't resumed it since)
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_control_channel() {
+        let (control, mut receiver) = CollectorControl::channel();
+        assert!(!control.is_paused());
+        control.send(CollectorCommand::Pause).unwrap();
+        assert_eq!(receiver.recv().await, Some(CollectorCommand::Pause));
+        assert!(control.is_paused());
+
+        for _ in 0..COLLECTOR_COMMAND_CAPACITY {
+            control.send(CollectorCommand::CollectNow).unwrap();
+        }
+        let err = control.send(CollectorCommand::Resume).unwrap_err();
+        assert!(err.to_string().contains("busy"));
+        // A resume that wasn't queued leaves the collector paused
+        assert!(control.is_paused());
+
+        drop(receiver);
+        let err = control.send(CollectorCommand::Resume).unwrap_err();
+        assert!(err.to_string().contains("stopped"));
+
+        assert_eq!(
+            serde_json::to_value(CollectorCommand::CollectNow).unwrap(),
+            CollectorCommand::CollectNow.name()
+        );
+    }
+}
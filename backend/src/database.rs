@@ -1,251 +1,4197 @@
-use anyhow::Result;
-use chrono::Utc;
+use anyhow::Context;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sha2::{Digest, Sha256};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteRow,
+};
 use sqlx::Row;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use crate::archive::Archive;
+use crate::auth::Role;
+use crate::collections::Period;
+use crate::consts::{
+    DATABASE_BUSY_TIMEOUT_SECS, DATABASE_CONNECT_ATTEMPTS, DATABASE_CONNECT_BACKOFF_MS,
+    DATABASE_MAX_CONNECTIONS, MANUAL_KEYWORD_SOURCE, SCHEDULER_TASK_COLLECTION,
+    SCHEDULER_TASK_POEM,
+};
+use crate::definitions::WordDefinition;
 use crate::derivation::DerivedKeyword;
+use crate::embeddings;
+use crate::error::{ChainVerseError, Result};
+use crate::mood::{BlockActivity, Mood};
+use crate::poem_generator::TokenUsage;
+use crate::provenance::PoemProvenance;
+use crate::rarity::KeywordRarity;
+use crate::read_cache::ReadCache;
+use crate::retry::RetryPolicy;
+use crate::timezone::Timezone;
+
+/// Versioned schema migrations from `migrations/`, embedded at compile time
+///
+/// Applied migrations are recorded in `_sqlx_migrations`, so each file runs
+/// exactly once per database.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
 
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: SqlitePool,
+    /// Decides which day a keyword belongs to; stored timestamps stay UTC
+    timezone: Timezone,
+    /// Copies of hot reads, shared with every handle on this database in the process
+    cache: Option<Arc<ReadCache>>,
+    /// Opened with `open_read_only`: queries work, writes fail
+    read_only: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
 pub struct StoredKeyword {
     pub id: i64,
     pub word: String,
     pub slot: i64,
     pub blockhash: String,
+    /// Parent blockhash; None for keywords stored before it was recorded
+    pub previous_blockhash: Option<String>,
     pub block_time: Option<i64>,
     pub word_index: i64,
+    /// Entropy the word was derived from (see BlockDataSource)
+    pub source: String,
+    /// Dictionary theme the word was drawn from; None for the base dictionary
+    pub theme: Option<String>,
+    /// Re-hashes it took to skip blocked or recently used words
+    pub rerolls: i64,
+    /// Why the keyword was collected off-schedule (`event`); None for scheduled collection
+    pub tag: Option<String>,
+    /// Solana epoch of the slot; None for keywords collected before it was recorded
+    pub epoch: Option<i64>,
+    /// The slot's block was abandoned by a reorg: the finalized chain has
+    /// another block there, or none. Orphaned keywords are left out of poems
+    #[serde(default)]
+    pub orphaned: bool,
+    pub created_at: String,
+}
+
+/// A stored keyword along with the dates of poems that used it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordOccurrence {
+    #[serde(flatten)]
+    pub keyword: StoredKeyword,
+    pub poems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMilestone {
+    pub id: i64,
+    pub kind: String,
+    pub value: i64,
+    pub date: String,
+    pub achieved_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMint {
+    pub date: String,
+    pub signature: String,
+    pub merkle_tree: String,
+    pub minted_at: String,
+}
+
+/// An on-demand poem generated for a user-chosen slot range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCustomPoem {
+    pub id: i64,
+    pub start_slot: i64,
+    pub end_slot: i64,
+    pub title: Option<String>,
+    pub content: String,
+    pub keywords: Vec<String>,
+    pub created_at: String,
+}
+
+/// A weekly or monthly meta-poem synthesized from the period's daily poems
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredCollection {
+    pub id: i64,
+    /// `2026-W02` for a week, `2026-01` for a month
+    pub period: String,
+    /// `week` or `month`
+    pub kind: String,
+    pub start_date: String,
+    /// Last day of the period, inclusive
+    pub end_date: String,
+    pub title: Option<String>,
+    pub content: String,
+    /// Daily poems it drew on
+    pub poem_dates: Vec<String>,
     pub created_at: String,
 }
 
+/// Outcome of a batch keyword insert
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct InsertSummary {
+    pub inserted: usize,
+    /// Keywords whose block and source were already stored
+    pub skipped: usize,
+}
+
+/// Saved progress of an interrupted backfill run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillState {
+    pub start_date: String,
+    pub end_date: String,
+    pub last_completed_date: Option<String>,
+    pub last_slot_tried: Option<i64>,
+    pub updated_at: String,
+}
+
+/// When the collector last finished a keyword collection and a poem, UTC
+/// `YYYY-MM-DD HH:MM:SS`; None until it first has
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchedulerState {
+    pub last_collection_at: Option<String>,
+    pub last_poem_at: Option<String>,
+}
+
+/// How often a day's poem was read and how readers reacted to it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoemEngagement {
+    pub views: i64,
+    /// Count per emoji; emoji nobody picked are left out
+    pub reactions: BTreeMap<String, i64>,
+}
+
+/// A day whose poem generation failed and is waiting to be retried
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PendingPoem {
+    pub date: String,
+    pub attempts: i64,
+    pub next_attempt_at: String,
+    pub last_error: Option<String>,
+}
+
+/// A derived keyword whose insert failed, waiting to be replayed into `keywords`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FailedKeyword {
+    pub id: i64,
+    pub word: String,
+    pub slot: i64,
+    pub tag: Option<String>,
+    /// When it was collected (UTC, `YYYY-MM-DD HH:MM:SS`), so it lands on that day
+    pub collected_at: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+}
+
+/// Two words that were written into the same poem, with how many poems that happened
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordCooccurrence {
+    pub source: String,
+    pub target: String,
+    pub count: i64,
+}
+
+/// A poem matching a full-text search, best match first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoemSearchHit {
+    pub date: String,
+    pub title: Option<String>,
+    /// Excerpt around the matches as HTML: poem text escaped, matched terms in `<mark>`
+    pub snippet: String,
+}
+
+/// A poem close in theme to another, by the cosine similarity of their embeddings
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarPoem {
+    pub date: String,
+    pub title: Option<String>,
+    /// From -1 to 1; higher is closer
+    pub similarity: f32,
+}
+
+/// Where a poem's spoken recording is kept (see `tts`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoemAudio {
+    pub path: String,
+    /// `mp3` or `ogg`
+    pub format: String,
+    pub voice: String,
+    pub created_at: String,
+}
+
+/// A word and how many times it has been collected
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WordCount {
+    pub word: String,
+    pub count: i64,
+}
+
+/// Number of keywords derived from one entropy source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceCount {
+    pub source: String,
+    pub count: i64,
+}
+
+/// LLM tokens and estimated spend for one month of poems
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonthlyUsage {
+    /// `YYYY-MM`
+    pub month: String,
+    /// Poems with recorded usage; earlier poems aren't counted
+    pub poems: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// A curator's addition or removal of a keyword, from the `keyword_audit` table
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct KeywordAuditEntry {
+    pub id: i64,
+    /// `add` or `remove`
+    pub action: String,
+    pub keyword_id: i64,
+    pub date: String,
+    pub word: String,
+    pub curator: String,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+/// A curator's note on a keyword, from the `keyword_annotations` table
+///
+/// Carries the keyword's word and slot so it can be matched to the entries
+/// of a provenance manifest, which don't record keyword ids.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct KeywordAnnotation {
+    pub id: i64,
+    pub keyword_id: i64,
+    pub word: String,
+    pub slot: i64,
+    pub note: String,
+    pub curator: String,
+    pub created_at: String,
+}
+
+/// A request made with an admin API key, from the `api_key_usage` table
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ApiKeyUsage {
+    pub id: i64,
+    pub key_name: String,
+    pub role: String,
+    pub method: String,
+    pub path: String,
+    pub status: i64,
+    pub used_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StoredPoem {
     pub id: i64,
     pub date: String,
     pub title: Option<String>,
     pub content: String,
     pub keyword_ids: Vec<i64>,
+    /// SHA-256 of the poem and its keywords, when anchored on-chain
+    pub anchor_hash: Option<String>,
+    /// Memo transaction that recorded `anchor_hash`
+    pub anchor_signature: Option<String>,
+    /// Content address of this version of the poem (see [`poem_hash`])
+    #[serde(default)]
+    pub poem_hash: Option<String>,
     pub created_at: String,
 }
 
 impl Database {
     /// Create a new database connection and initialize schema
+    ///
+    /// Connections use WAL journaling and a busy timeout, so other processes
+    /// (a separate collector daemon, read-only API replicas) can use the file
+    /// concurrently without SQLITE_BUSY errors. Transient failures to open the
+    /// database are retried with exponential backoff.
     pub async fn new(database_url: &str) -> Result<Self> {
         let options = SqliteConnectOptions::from_str(database_url)?
-            .create_if_missing(true);
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(Duration::from_secs(DATABASE_BUSY_TIMEOUT_SECS))
+            .foreign_keys(true);
+        let pool = connect(database_url, options).await?;
 
-        let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect_with(options)
-            .await?;
+        MIGRATOR
+            .run(&pool)
+            .await
+            .map_err(|e| ChainVerseError::Database(e.into()))?;
 
-        // Run migrations
-        sqlx::query(include_str!("../schema.sql"))
-            .execute(&pool)
-            .await?;
+        let db = Self {
+            pool,
+            timezone: Timezone::default(),
+            cache: None,
+            read_only: false,
+        };
+        db.fill_poem_hashes().await?;
+        Ok(db)
+    }
+
+    /// Open an existing database without write access, for API replicas
+    /// reading alongside one writer
+    ///
+    /// Migrations can't run on a read-only connection, so this fails unless
+    /// the writer has already brought the schema up to this build's version.
+    pub async fn open_read_only(database_url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .read_only(true)
+            .busy_timeout(Duration::from_secs(DATABASE_BUSY_TIMEOUT_SECS))
+            .foreign_keys(true);
+        let pool = connect(database_url, options).await?;
 
-        Ok(Self { pool })
+        let applied: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+                .fetch_one(&pool)
+                .await
+                .with_context(|| format!("{} has no schema yet", database_url))
+                .map_err(ChainVerseError::Database)?;
+        let applied = applied.unwrap_or_default();
+        let expected = MIGRATOR.iter().map(|m| m.version).max().unwrap_or_default();
+        if applied < expected {
+            pool.close().await;
+            return Err(ChainVerseError::Database(anyhow::anyhow!(
+                "{} is at schema version {} but this build needs {}; start the writer first so it migrates",
+                database_url,
+                applied,
+                expected
+            )));
+        }
+
+        Ok(Self {
+            pool,
+            timezone: Timezone::default(),
+            cache: None,
+            read_only: true,
+        })
     }
 
-    /// Insert a derived keyword into the database
-    pub async fn insert_keyword(&self, keyword: &DerivedKeyword) -> Result<i64> {
-        let result = sqlx::query(
+    /// Bucket keywords into days, and name today, in `timezone` instead of UTC
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    pub fn timezone(&self) -> Timezone {
+        self.timezone
+    }
+
+    /// Whether writes are refused (see `open_read_only`)
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Serve the poem list and each day's keywords and poem from `cache`;
+    /// None reads everything from SQLite
+    ///
+    /// Handles that write to the same database in this process should share
+    /// the cache, so their writes invalidate it.
+    pub fn with_read_cache(mut self, cache: Option<Arc<ReadCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Drop cached reads after a write to keywords or poems
+    fn invalidate_reads(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Cache generation to store a read under, taken before the query runs
+    fn read_generation(&self) -> u64 {
+        self.cache.as_ref().map_or(0, |cache| cache.generation())
+    }
+
+    /// Close the pool, waiting for in-flight queries to finish
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Insert a derived keyword into the database; None if that block's word
+    /// from the same source was already stored
+    pub async fn insert_keyword(&self, keyword: &DerivedKeyword) -> Result<Option<i64>> {
+        self.insert_tagged_keyword(keyword, None).await
+    }
+
+    /// Insert a derived keyword collected for a reason other than the schedule,
+    /// e.g. `event`; None if it was already stored
+    pub async fn insert_tagged_keyword(
+        &self,
+        keyword: &DerivedKeyword,
+        tag: Option<&str>,
+    ) -> Result<Option<i64>> {
+        // last_insert_rowid would be stale when the conflict skips the row
+        let id = sqlx::query_scalar::<_, i64>(
             r#"
-            INSERT INTO keywords (word, slot, blockhash, block_time, word_index)
-            VALUES (?, ?, ?, ?, ?)
-            ON CONFLICT(slot) DO NOTHING
+            INSERT INTO keywords
+                (word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+                 rerolls, tag, epoch)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(slot, source, word_index) DO NOTHING
+            RETURNING id
             "#,
         )
         .bind(&keyword.word)
         .bind(keyword.slot as i64)
         .bind(&keyword.blockhash)
+        .bind(&keyword.previous_blockhash)
         .bind(keyword.block_time)
         .bind(keyword.word_index as i64)
-        .execute(&self.pool)
+        .bind(keyword.source.as_str())
+        .bind(&keyword.theme)
+        .bind(keyword.rerolls)
+        .bind(tag)
+        .bind(keyword.epoch.map(|epoch| epoch as i64))
+        .fetch_optional(&self.pool)
         .await?;
+        self.invalidate_reads();
 
-        Ok(result.last_insert_rowid())
+        Ok(id)
     }
 
-    /// Insert a derived keyword with a specific date (for backfilling historical data)
-    pub async fn insert_keyword_with_date(&self, keyword: &DerivedKeyword, date: &str) -> Result<i64> {
+    /// Insert a derived keyword with a specific date (for backfilling historical
+    /// data); None if it was already stored
+    #[cfg(test)]
+    pub async fn insert_keyword_with_date(
+        &self,
+        keyword: &DerivedKeyword,
+        date: &str,
+    ) -> Result<Option<i64>> {
         // Create a timestamp for noon on the specified date
-        let created_at = format!("{} 12:00:00", date);
+        let created_at = self.noon_of(date);
 
-        let result = sqlx::query(
+        let id = sqlx::query_scalar::<_, i64>(
             r#"
-            INSERT INTO keywords (word, slot, blockhash, block_time, word_index, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-            ON CONFLICT(slot) DO NOTHING
+            INSERT INTO keywords
+                (word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+                 rerolls, epoch, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(slot, source, word_index) DO NOTHING
+            RETURNING id
             "#,
         )
         .bind(&keyword.word)
         .bind(keyword.slot as i64)
         .bind(&keyword.blockhash)
+        .bind(&keyword.previous_blockhash)
         .bind(keyword.block_time)
         .bind(keyword.word_index as i64)
+        .bind(keyword.source.as_str())
+        .bind(&keyword.theme)
+        .bind(keyword.rerolls)
+        .bind(keyword.epoch.map(|epoch| epoch as i64))
         .bind(&created_at)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
+        self.invalidate_reads();
 
-        Ok(result.last_insert_rowid())
+        Ok(id)
+    }
+
+    /// Insert many keywords for a specific date (used for backfilling) in one transaction
+    pub async fn insert_keywords_with_date(
+        &self,
+        keywords: &[DerivedKeyword],
+        date: &str,
+    ) -> Result<InsertSummary> {
+        self.insert_keyword_batch(keywords, Some(self.noon_of(date)))
+            .await
+    }
+
+    async fn insert_keyword_batch(
+        &self,
+        keywords: &[DerivedKeyword],
+        created_at: Option<String>,
+    ) -> Result<InsertSummary> {
+        let mut summary = InsertSummary::default();
+        let mut tx = self.pool.begin().await?;
+
+        for keyword in keywords {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO keywords
+                    (word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                     theme, rerolls, epoch, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, COALESCE(?, CURRENT_TIMESTAMP))
+                ON CONFLICT(slot, source, word_index) DO NOTHING
+                "#,
+            )
+            .bind(&keyword.word)
+            .bind(keyword.slot as i64)
+            .bind(&keyword.blockhash)
+            .bind(&keyword.previous_blockhash)
+            .bind(keyword.block_time)
+            .bind(keyword.word_index as i64)
+            .bind(keyword.source.as_str())
+            .bind(&keyword.theme)
+        .bind(keyword.rerolls)
+            .bind(keyword.epoch.map(|epoch| epoch as i64))
+            .bind(&created_at)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() > 0 {
+                summary.inserted += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+
+        tx.commit().await?;
+        self.invalidate_reads();
+        Ok(summary)
     }
 
     /// Get all keywords for a specific date
     pub async fn get_keywords_for_date(&self, date: &str) -> Result<Vec<StoredKeyword>> {
-        let keywords = sqlx::query_as::<_, (i64, String, i64, String, Option<i64>, i64, String)>(
+        if let Some(keywords) = self.cache.as_ref().and_then(|c| c.keywords_for_date(date)) {
+            return Ok(keywords);
+        }
+        let generation = self.read_generation();
+
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
             r#"
-            SELECT id, word, slot, blockhash, block_time, word_index, created_at
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
             FROM keywords
-            WHERE DATE(created_at) = ?
+            WHERE DATE(created_at, ?) = ?
             ORDER BY created_at ASC
             "#,
         )
+        .bind(self.timezone.sqlite_modifier())
         .bind(date)
         .fetch_all(&self.pool)
-        .await?
-        .into_iter()
-        .map(|(id, word, slot, blockhash, block_time, word_index, created_at)| StoredKeyword {
-            id,
-            word,
-            slot,
-            blockhash,
-            block_time,
-            word_index,
-            created_at,
-        })
-        .collect();
+        .await?;
+
+        if let Some(cache) = &self.cache {
+            cache.store_keywords_for_date(generation, date, &keywords);
+        }
+        Ok(keywords)
+    }
+
+    /// The keywords a date's poem is written from: the day's keywords minus
+    /// those whose block was abandoned (see `StoredKeyword::orphaned`)
+    pub async fn get_poem_keywords_for_date(&self, date: &str) -> Result<Vec<StoredKeyword>> {
+        let mut keywords = self.get_keywords_for_date(date).await?;
+        keywords.retain(|k| !k.orphaned);
+        Ok(keywords)
+    }
+
+    /// Every stored keyword, in insertion order
+    pub async fn get_all_keywords(&self) -> Result<Vec<StoredKeyword>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
         Ok(keywords)
     }
 
-    /// Get recent keywords (for today's poem in progress)
-    pub async fn get_recent_keywords(&self, limit: i64) -> Result<Vec<StoredKeyword>> {
-        let keywords = sqlx::query_as::<_, (i64, String, i64, String, Option<i64>, i64, String)>(
+    /// Up to `limit` keywords with ids above `after_id`, in insertion order, so
+    /// the whole table can be read a page at a time
+    pub async fn get_keywords_after(
+        &self,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<StoredKeyword>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
             r#"
-            SELECT id, word, slot, blockhash, block_time, word_index, created_at
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
             FROM keywords
-            ORDER BY created_at DESC
+            WHERE id > ?
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(keywords)
+    }
+
+    /// Distinct words collected on any day from `from` to `to` inclusive
+    pub async fn get_words_between(&self, from: &str, to: &str) -> Result<HashSet<String>> {
+        let words = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT word FROM keywords WHERE DATE(created_at, ?) BETWEEN ? AND ?",
+        )
+        .bind(self.timezone.sqlite_modifier())
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(words.into_iter().collect())
+    }
+
+    /// Full-text search over poem titles and text, best matches first
+    ///
+    /// Every word in `query` must appear (stemmed, so `rain` finds `raining`);
+    /// FTS5 operators are not interpreted. Returns nothing for a query without words.
+    pub async fn search_poems(&self, query: &str, limit: i64) -> Result<Vec<PoemSearchHit>> {
+        let Some(query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let hits = sqlx::query_as::<_, (String, Option<String>, String)>(
+            r#"
+            SELECT p.date, p.title,
+                   snippet(poems_fts, 1, char(57344), char(57345), '…', 16)
+            FROM poems_fts
+            JOIN poems p ON p.id = poems_fts.rowid
+            WHERE poems_fts MATCH ?
+            ORDER BY rank, p.date DESC
             LIMIT ?
             "#,
         )
+        .bind(query)
         .bind(limit)
         .fetch_all(&self.pool)
         .await?
         .into_iter()
-        .map(|(id, word, slot, blockhash, block_time, word_index, created_at)| StoredKeyword {
-            id,
-            word,
-            slot,
-            blockhash,
-            block_time,
-            word_index,
-            created_at,
+        .map(|(date, title, snippet)| PoemSearchHit {
+            date,
+            title,
+            snippet: highlight_snippet(&snippet),
         })
         .collect();
 
-        Ok(keywords)
+        Ok(hits)
     }
 
-    /// Insert a poem into the database
-    pub async fn insert_poem(
+    /// Store a poem's embedding, replacing any from an earlier model
+    pub async fn store_poem_embedding(
         &self,
-        date: &str,
-        title: Option<&str>,
-        content: &str,
-        keyword_ids: &[i64],
-    ) -> Result<i64> {
-        let keyword_ids_json = serde_json::to_string(keyword_ids)?;
-
-        let result = sqlx::query(
+        poem_id: i64,
+        model: &str,
+        vector: &[f32],
+    ) -> Result<()> {
+        sqlx::query(
             r#"
-            INSERT INTO poems (date, title, content, keyword_ids)
-            VALUES (?, ?, ?, ?)
-            ON CONFLICT(date) DO UPDATE SET
-                title = excluded.title,
-                content = excluded.content,
-                keyword_ids = excluded.keyword_ids
+            INSERT OR REPLACE INTO poem_embeddings (poem_id, model, vector)
+            VALUES (?, ?, ?)
             "#,
         )
-        .bind(date)
-        .bind(title)
-        .bind(content)
-        .bind(keyword_ids_json)
+        .bind(poem_id)
+        .bind(model)
+        .bind(embeddings::to_bytes(vector))
         .execute(&self.pool)
         .await?;
+        Ok(())
+    }
 
-        Ok(result.last_insert_rowid())
+    /// Up to `limit` poems without an embedding from `model`, newest first
+    pub async fn get_unembedded_poems(&self, model: &str, limit: i64) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, p.date, p.title, p.content, p.keyword_ids, p.anchor_hash,
+                   p.anchor_signature, p.poem_hash, p.created_at
+            FROM poems p
+            LEFT JOIN poem_embeddings e ON e.poem_id = p.id AND e.model = ?
+            WHERE e.poem_id IS NULL
+            ORDER BY p.date DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(model)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
     }
 
-    /// Get a poem by date
-    pub async fn get_poem_by_date(&self, date: &str) -> Result<Option<StoredPoem>> {
-        let row = sqlx::query(
+    /// The `limit` poems closest in theme to the one for `date`, closest first
+    ///
+    /// Only poems embedded by the same model are compared. None when the
+    /// poem doesn't exist or hasn't been embedded yet.
+    pub async fn get_similar_poems(
+        &self,
+        date: &str,
+        limit: usize,
+    ) -> Result<Option<Vec<SimilarPoem>>> {
+        let target = sqlx::query_as::<_, (i64, String, Vec<u8>)>(
             r#"
-            SELECT id, date, title, content, keyword_ids, created_at
-            FROM poems
-            WHERE date = ?
+            SELECT p.id, e.model, e.vector
+            FROM poems p
+            JOIN poem_embeddings e ON e.poem_id = p.id
+            WHERE p.date = ?
             "#,
         )
         .bind(date)
         .fetch_optional(&self.pool)
         .await?;
+        let Some((poem_id, model, vector)) = target else {
+            return Ok(None);
+        };
+        let vector = embeddings::from_bytes(&vector);
 
-        if let Some(row) = row {
-            let keyword_ids: Vec<i64> =
-                serde_json::from_str(&row.get::<String, _>("keyword_ids"))?;
-
-            Ok(Some(StoredPoem {
-                id: row.get("id"),
-                date: row.get("date"),
-                title: row.get("title"),
-                content: row.get("content"),
-                keyword_ids,
-                created_at: row.get("created_at"),
-            }))
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// Get all poems, ordered by date descending
-    pub async fn get_all_poems(&self) -> Result<Vec<StoredPoem>> {
-        let rows = sqlx::query(
+        let others = sqlx::query_as::<_, (String, Option<String>, Vec<u8>)>(
             r#"
-            SELECT id, date, title, content, keyword_ids, created_at
-            FROM poems
-            ORDER BY date DESC
+            SELECT p.date, p.title, e.vector
+            FROM poem_embeddings e
+            JOIN poems p ON p.id = e.poem_id
+            WHERE e.model = ? AND e.poem_id != ?
             "#,
         )
+        .bind(&model)
+        .bind(poem_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let poems = rows
+        let mut similar: Vec<SimilarPoem> = others
             .into_iter()
-            .map(|row| {
-                let keyword_ids: Vec<i64> =
-                    serde_json::from_str(&row.get::<String, _>("keyword_ids")).unwrap_or_default();
-
-                StoredPoem {
-                    id: row.get("id"),
-                    date: row.get("date"),
-                    title: row.get("title"),
-                    content: row.get("content"),
-                    keyword_ids,
-                    created_at: row.get("created_at"),
-                }
+            .map(|(date, title, other)| SimilarPoem {
+                similarity: embeddings::cosine_similarity(&vector, &embeddings::from_bytes(&other)),
+                date,
+                title,
             })
             .collect();
+        similar.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| b.date.cmp(&a.date))
+        });
+        similar.truncate(limit);
+        Ok(Some(similar))
+    }
 
-        Ok(poems)
+    /// Record a poem's recording, replacing any earlier one
+    pub async fn store_poem_audio(
+        &self,
+        poem_id: i64,
+        path: &str,
+        format: &str,
+        voice: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO poem_audio (poem_id, path, format, voice)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(poem_id)
+        .bind(path)
+        .bind(format)
+        .bind(voice)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
     }
 
-    /// Get today's date in YYYY-MM-DD format
-    pub fn today() -> String {
-        Utc::now().format("%Y-%m-%d").to_string()
+    /// Up to `limit` poems without a recording in `format` by `voice`, newest first
+    pub async fn get_unnarrated_poems(
+        &self,
+        format: &str,
+        voice: &str,
+        limit: i64,
+    ) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, p.date, p.title, p.content, p.keyword_ids, p.anchor_hash,
+                   p.anchor_signature, p.poem_hash, p.created_at
+            FROM poems p
+            LEFT JOIN poem_audio a ON a.poem_id = p.id AND a.format = ? AND a.voice = ?
+            WHERE a.poem_id IS NULL
+            ORDER BY p.date DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(format)
+        .bind(voice)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
+    }
+
+    /// The recording of the poem for `date`; None until it has been narrated
+    pub async fn get_poem_audio(&self, date: &str) -> Result<Option<PoemAudio>> {
+        let audio = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"
+            SELECT a.path, a.format, a.voice, a.created_at
+            FROM poem_audio a
+            JOIN poems p ON p.id = a.poem_id
+            WHERE p.date = ?
+            "#,
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(audio.map(|(path, format, voice, created_at)| PoemAudio {
+            path,
+            format,
+            voice,
+            created_at,
+        }))
+    }
+
+    /// Find every stored occurrence of a word (case-insensitive) and the poems it appeared in
+    pub async fn search_keyword_occurrences(&self, word: &str) -> Result<Vec<KeywordOccurrence>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            WHERE word = ? COLLATE NOCASE
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(word)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let poem_links = sqlx::query_as::<_, (i64, String)>(
+            r#"
+            SELECT j.value, p.date
+            FROM poems p, json_each(p.keyword_ids) j
+            WHERE j.value IN (SELECT id FROM keywords WHERE word = ? COLLATE NOCASE)
+            ORDER BY p.date ASC
+            "#,
+        )
+        .bind(word)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let occurrences = keywords
+            .into_iter()
+            .map(|keyword| {
+                let poems = poem_links
+                    .iter()
+                    .filter(|(keyword_id, _)| *keyword_id == keyword.id)
+                    .map(|(_, date)| date.clone())
+                    .collect();
+                KeywordOccurrence { keyword, poems }
+            })
+            .collect();
+
+        Ok(occurrences)
+    }
+
+    /// Insert a poem into the database
+    pub async fn insert_poem(
+        &self,
+        date: &str,
+        title: Option<&str>,
+        content: &str,
+        keyword_ids: &[i64],
+    ) -> Result<i64> {
+        let keyword_ids_json = serde_json::to_string(keyword_ids).map_err(json_error)?;
+        let slots = self.keyword_slots(keyword_ids).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO poems (date, title, content, keyword_ids, poem_hash)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(date) DO UPDATE SET
+                title = excluded.title,
+                content = excluded.content,
+                keyword_ids = excluded.keyword_ids,
+                poem_hash = excluded.poem_hash,
+                anchor_hash = NULL,
+                anchor_signature = NULL,
+                prompt_tokens = NULL,
+                completion_tokens = NULL,
+                cost_usd = NULL
+            "#,
+        )
+        .bind(date)
+        .bind(title)
+        .bind(content)
+        .bind(keyword_ids_json)
+        .bind(poem_hash(date, content, &slots))
+        .execute(&self.pool)
+        .await?;
+        self.invalidate_reads();
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Slots of the given keywords, in the same order; removed keywords are skipped
+    async fn keyword_slots(&self, keyword_ids: &[i64]) -> Result<Vec<i64>> {
+        let mut slots = Vec::with_capacity(keyword_ids.len());
+        for &id in keyword_ids {
+            let slot: Option<i64> = sqlx::query_scalar("SELECT slot FROM keywords WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+            slots.extend(slot);
+        }
+        Ok(slots)
+    }
+
+    /// Hash poems stored before poem_hash existed (or restored from an older archive)
+    async fn fill_poem_hashes(&self) -> Result<()> {
+        let rows =
+            sqlx::query("SELECT id, date, content, keyword_ids FROM poems WHERE poem_hash IS NULL")
+                .fetch_all(&self.pool)
+                .await?;
+        for row in &rows {
+            let keyword_ids: Vec<i64> =
+                serde_json::from_str(&row.get::<String, _>("keyword_ids")).map_err(json_error)?;
+            let slots = self.keyword_slots(&keyword_ids).await?;
+            let date: String = row.get("date");
+            let content: String = row.get("content");
+            sqlx::query("UPDATE poems SET poem_hash = ? WHERE id = ?")
+                .bind(poem_hash(&date, &content, &slots))
+                .bind(row.get::<i64, _>("id"))
+                .execute(&self.pool)
+                .await?;
+        }
+        if !rows.is_empty() {
+            self.invalidate_reads();
+        }
+        Ok(())
+    }
+
+    /// The poem with content hash `hash`, including earlier versions of
+    /// regenerated poems (returned as they were, without their anchor)
+    pub async fn get_poem_by_hash(&self, hash: &str) -> Result<Option<StoredPoem>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at, 0 AS replaced
+            FROM poems
+            WHERE poem_hash = ?1
+            UNION ALL
+            SELECT poem_id AS id, date, title, content, keyword_ids, NULL AS anchor_hash,
+                   NULL AS anchor_signature, poem_hash, created_at, 1 AS replaced
+            FROM poem_revisions
+            WHERE poem_hash = ?1
+            ORDER BY replaced
+            LIMIT 1
+            "#,
+        )
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(poem_from_row).transpose()
+    }
+
+    /// Get a poem by date
+    pub async fn get_poem_by_date(&self, date: &str) -> Result<Option<StoredPoem>> {
+        if let Some(poem) = self.cache.as_ref().and_then(|c| c.poem_by_date(date)) {
+            return Ok(poem);
+        }
+        let generation = self.read_generation();
+
+        let row = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            WHERE date = ?
+            "#,
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let poem = row.as_ref().map(poem_from_row).transpose()?;
+        if let Some(cache) = &self.cache {
+            cache.store_poem_by_date(generation, date, &poem);
+        }
+        Ok(poem)
+    }
+
+    /// Get one poem chosen at random; None before the first poem
+    pub async fn get_random_poem(&self) -> Result<Option<StoredPoem>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            ORDER BY RANDOM()
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(poem_from_row).transpose()
+    }
+
+    /// Poems from earlier years on the same month and day as `date`, newest first
+    pub async fn get_poems_on_this_day(&self, date: &str) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            WHERE SUBSTR(date, 6) = SUBSTR(?1, 6) AND date < ?1
+            ORDER BY date DESC
+            "#,
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
+    }
+
+    /// Poems dated `from` to `to` inclusive, oldest first
+    pub async fn get_poems_between(&self, from: &str, to: &str) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            WHERE date BETWEEN ? AND ?
+            ORDER BY date ASC
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
+    }
+
+    /// Poems using a keyword from Solana epoch `epoch`, oldest first
+    ///
+    /// An epoch lasts two to three days and rarely starts at midnight, so a
+    /// poem whose keywords straddle a boundary belongs to both epochs.
+    pub async fn get_poems_for_epoch(&self, epoch: u64) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            WHERE id IN (
+                SELECT p.id
+                FROM poems p, json_each(p.keyword_ids) j
+                JOIN keywords k ON k.id = j.value
+                WHERE k.epoch = ?
+            )
+            ORDER BY date ASC
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
+    }
+
+    /// Up to `limit` poems dated before `before` (or from the latest), newest
+    /// first, so the archive can be read a page at a time
+    #[cfg(feature = "graphql")]
+    pub async fn get_poems_before(
+        &self,
+        before: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<StoredPoem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            WHERE ?1 IS NULL OR date < ?1
+            ORDER BY date DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(poem_from_row).collect()
+    }
+
+    /// Get all poems, ordered by date descending
+    pub async fn get_all_poems(&self) -> Result<Vec<StoredPoem>> {
+        if let Some(poems) = self.cache.as_ref().and_then(|c| c.all_poems()) {
+            return Ok(poems);
+        }
+        let generation = self.read_generation();
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                   poem_hash, created_at
+            FROM poems
+            ORDER BY date DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let poems: Vec<StoredPoem> = rows
+            .into_iter()
+            .map(|row| {
+                let keyword_ids: Vec<i64> =
+                    serde_json::from_str(&row.get::<String, _>("keyword_ids")).unwrap_or_default();
+
+                StoredPoem {
+                    id: row.get("id"),
+                    date: row.get("date"),
+                    title: row.get("title"),
+                    content: row.get("content"),
+                    keyword_ids,
+                    anchor_hash: row.get("anchor_hash"),
+                    anchor_signature: row.get("anchor_signature"),
+                    poem_hash: row.get("poem_hash"),
+                    created_at: row.get("created_at"),
+                }
+            })
+            .collect();
+
+        if let Some(cache) = &self.cache {
+            cache.store_all_poems(generation, &poems);
+        }
+        Ok(poems)
+    }
+
+    /// Record the on-chain anchor of a stored poem
+    pub async fn set_poem_anchor(&self, date: &str, hash: &str, signature: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE poems
+            SET anchor_hash = ?, anchor_signature = ?
+            WHERE date = ?
+            "#,
+        )
+        .bind(hash)
+        .bind(signature)
+        .bind(date)
+        .execute(&self.pool)
+        .await?;
+        self.invalidate_reads();
+
+        Ok(())
+    }
+
+    /// Record what generating a stored poem cost
+    pub async fn set_poem_usage(&self, date: &str, usage: &TokenUsage) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE poems
+            SET prompt_tokens = ?, completion_tokens = ?, cost_usd = ?
+            WHERE date = ?
+            "#,
+        )
+        .bind(usage.prompt_tokens)
+        .bind(usage.completion_tokens)
+        .bind(usage.cost_usd)
+        .bind(date)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Token and cost totals per month, oldest first, over poems with recorded usage
+    pub async fn get_monthly_usage(&self) -> Result<Vec<MonthlyUsage>> {
+        let months = sqlx::query_as::<_, (String, i64, i64, i64, f64)>(
+            r#"
+            SELECT substr(date, 1, 7) AS month,
+                   COUNT(*),
+                   SUM(prompt_tokens),
+                   SUM(completion_tokens),
+                   COALESCE(SUM(cost_usd), 0.0)
+            FROM poems
+            WHERE prompt_tokens IS NOT NULL
+            GROUP BY month
+            ORDER BY month ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(
+            |(month, poems, prompt_tokens, completion_tokens, cost_usd)| MonthlyUsage {
+                month,
+                poems,
+                prompt_tokens,
+                completion_tokens,
+                cost_usd,
+            },
+        )
+        .collect();
+
+        Ok(months)
+    }
+
+    /// Get pairs of words that appeared in the same poem's keywords, most frequent first
+    pub async fn get_word_cooccurrences(
+        &self,
+        min_count: i64,
+        limit: i64,
+    ) -> Result<Vec<WordCooccurrence>> {
+        let pairs = sqlx::query_as::<_, (String, String, i64)>(
+            r#"
+            WITH poem_words AS (
+                SELECT DISTINCT p.id AS poem_id, k.word
+                FROM poems p, json_each(p.keyword_ids) j
+                JOIN keywords k ON k.id = j.value
+            )
+            SELECT a.word, b.word, COUNT(*) AS poems
+            FROM poem_words a
+            JOIN poem_words b ON a.poem_id = b.poem_id AND a.word < b.word
+            GROUP BY a.word, b.word
+            HAVING poems >= ?
+            ORDER BY poems DESC, a.word ASC, b.word ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(min_count)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|(source, target, count)| WordCooccurrence {
+            source,
+            target,
+            count,
+        })
+        .collect();
+
+        Ok(pairs)
+    }
+
+    /// Most frequently collected words (case-insensitive), most frequent first
+    pub async fn get_top_words(&self, limit: i64) -> Result<Vec<WordCount>> {
+        let words = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT LOWER(word) AS w, COUNT(*) AS n
+            FROM keywords
+            GROUP BY w
+            ORDER BY n DESC, w ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|(word, count)| WordCount { word, count })
+        .collect();
+
+        Ok(words)
+    }
+
+    /// Keyword counts per entropy source, largest first
+    pub async fn get_source_counts(&self) -> Result<Vec<SourceCount>> {
+        let sources = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT source, COUNT(*) AS n
+            FROM keywords
+            GROUP BY source
+            ORDER BY n DESC, source ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|(source, count)| SourceCount { source, count })
+        .collect();
+
+        Ok(sources)
+    }
+
+    /// Mean number of keywords per poem; None when there are no poems
+    pub async fn get_average_keywords_per_poem(&self) -> Result<Option<f64>> {
+        let average = sqlx::query_scalar::<_, Option<f64>>(
+            "SELECT AVG(json_array_length(keyword_ids)) FROM poems",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(average)
+    }
+
+    /// Get a day's entry in the poem retry queue
+    pub async fn get_pending_poem(&self, date: &str) -> Result<Option<PendingPoem>> {
+        let pending = sqlx::query_as::<_, PendingPoem>(
+            r#"
+            SELECT date, attempts, next_attempt_at, last_error
+            FROM pending_poems
+            WHERE date = ?
+            "#,
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    /// Queue (or requeue) a day whose poem generation failed
+    pub async fn queue_pending_poem(
+        &self,
+        date: &str,
+        attempts: i64,
+        next_attempt_at: &str,
+        error: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pending_poems (date, attempts, next_attempt_at, last_error)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(date) DO UPDATE SET
+                attempts = excluded.attempts,
+                next_attempt_at = excluded.next_attempt_at,
+                last_error = excluded.last_error
+            "#,
+        )
+        .bind(date)
+        .bind(attempts)
+        .bind(next_attempt_at)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move a queued poem's next attempt without counting one
+    pub async fn postpone_pending_poem(&self, date: &str, next_attempt_at: &str) -> Result<()> {
+        sqlx::query("UPDATE pending_poems SET next_attempt_at = ? WHERE date = ?")
+            .bind(next_attempt_at)
+            .bind(date)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Queued poems whose next attempt is at or before `now` (UTC, `YYYY-MM-DD HH:MM:SS`)
+    pub async fn get_due_pending_poems(&self, now: &str) -> Result<Vec<PendingPoem>> {
+        let pending = sqlx::query_as::<_, PendingPoem>(
+            r#"
+            SELECT date, attempts, next_attempt_at, last_error
+            FROM pending_poems
+            WHERE next_attempt_at <= ?
+            ORDER BY date ASC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    /// Number of days waiting in the retry queue
+    pub async fn count_pending_poems(&self) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pending_poems")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Number of days from `from` to `to` inclusive waiting in the retry queue
+    pub async fn count_pending_poems_between(&self, from: &str, to: &str) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM pending_poems WHERE date BETWEEN ? AND ?",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// The keywords with `ids`, in the order given; missing ids are skipped
+    #[cfg(feature = "graphql")]
+    pub async fn get_keywords_by_ids(&self, ids: &[i64]) -> Result<Vec<StoredKeyword>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            WHERE id IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(serde_json::to_string(ids).map_err(json_error)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut by_id: HashMap<i64, StoredKeyword> = keywords
+            .into_iter()
+            .map(|keyword| (keyword.id, keyword))
+            .collect();
+        Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+    }
+
+    /// Get a single keyword by id
+    pub async fn get_keyword(&self, id: i64) -> Result<Option<StoredKeyword>> {
+        let keyword = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(keyword)
+    }
+
+    /// Get every keyword derived from a slot, by source and word index
+    ///
+    /// A slot can yield several keywords: one per word index when several are
+    /// drawn from a block, and one per source that read the same slot.
+    pub async fn get_keywords_by_slot(&self, slot: i64) -> Result<Vec<StoredKeyword>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            WHERE slot = ?
+            ORDER BY source, word_index
+            "#,
+        )
+        .bind(slot)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(keywords)
+    }
+
+    /// Add a curator-chosen word to a date and record it in the audit log
+    ///
+    /// Manual keywords come from no block: they get placeholder slots counting
+    /// down from -1 (below any block's slot), an empty blockhash, word_index -1, and
+    /// source `manual`. Today's are stamped now; other days at noon.
+    pub async fn add_manual_keyword(
+        &self,
+        date: &str,
+        word: &str,
+        curator: &str,
+        reason: Option<&str>,
+    ) -> Result<StoredKeyword> {
+        let created_at = (date != self.today()).then(|| self.noon_of(date));
+        let mut tx = self.pool.begin().await?;
+
+        let id = sqlx::query(
+            r#"
+            INSERT INTO keywords (word, slot, blockhash, word_index, source, created_at)
+            VALUES (
+                ?,
+                (SELECT MIN(COALESCE(MIN(slot), 0), 0) - 1 FROM keywords),
+                '',
+                -1,
+                ?,
+                COALESCE(?, CURRENT_TIMESTAMP)
+            )
+            "#,
+        )
+        .bind(word)
+        .bind(MANUAL_KEYWORD_SOURCE)
+        .bind(&created_at)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        Self::record_keyword_audit(&mut tx, "add", id, date, word, curator, reason).await?;
+        tx.commit().await?;
+        self.invalidate_reads();
+
+        self.get_keyword(id).await?.ok_or_else(|| {
+            ChainVerseError::Database(anyhow::anyhow!("Manual keyword vanished after insert"))
+        })
+    }
+
+    /// Delete a keyword and record it in the audit log; None if no such keyword
+    pub async fn remove_keyword(
+        &self,
+        id: i64,
+        curator: &str,
+        reason: Option<&str>,
+    ) -> Result<Option<StoredKeyword>> {
+        let Some(keyword) = self.get_keyword(id).await? else {
+            return Ok(None);
+        };
+        let date = self.keyword_date(&keyword);
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM keywords WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        Self::record_keyword_audit(&mut tx, "remove", id, &date, &keyword.word, curator, reason)
+            .await?;
+        tx.commit().await?;
+        self.invalidate_reads();
+
+        Ok(Some(keyword))
+    }
+
+    async fn record_keyword_audit(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        action: &str,
+        keyword_id: i64,
+        date: &str,
+        word: &str,
+        curator: &str,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO keyword_audit (action, keyword_id, date, word, curator, reason)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(action)
+        .bind(keyword_id)
+        .bind(date)
+        .bind(word)
+        .bind(curator)
+        .bind(reason)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Audit log of curator keyword edits for a date, oldest first
+    pub async fn get_keyword_audit(&self, date: &str) -> Result<Vec<KeywordAuditEntry>> {
+        let entries = sqlx::query_as::<_, KeywordAuditEntry>(
+            r#"
+            SELECT id, action, keyword_id, date, word, curator, reason, created_at
+            FROM keyword_audit
+            WHERE date = ?
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Attach a curator's note to a keyword; None if there is no keyword `keyword_id`
+    pub async fn add_keyword_annotation(
+        &self,
+        keyword_id: i64,
+        note: &str,
+        curator: &str,
+    ) -> Result<Option<KeywordAnnotation>> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO keyword_annotations (keyword_id, note, curator)
+            SELECT id, ?, ? FROM keywords WHERE id = ?
+            "#,
+        )
+        .bind(note)
+        .bind(curator)
+        .bind(keyword_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        let id = result.last_insert_rowid();
+        self.get_keyword_annotation(id).await
+    }
+
+    /// Delete a note, returning it; None if there is no note `id`
+    pub async fn remove_keyword_annotation(&self, id: i64) -> Result<Option<KeywordAnnotation>> {
+        let Some(annotation) = self.get_keyword_annotation(id).await? else {
+            return Ok(None);
+        };
+        sqlx::query("DELETE FROM keyword_annotations WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(Some(annotation))
+    }
+
+    async fn get_keyword_annotation(&self, id: i64) -> Result<Option<KeywordAnnotation>> {
+        let annotation = sqlx::query_as::<_, KeywordAnnotation>(
+            r#"
+            SELECT a.id, a.keyword_id, k.word, k.slot, a.note, a.curator, a.created_at
+            FROM keyword_annotations a
+            JOIN keywords k ON k.id = a.keyword_id
+            WHERE a.id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(annotation)
+    }
+
+    /// Curator notes on a date's keywords, oldest first
+    pub async fn get_keyword_annotations(&self, date: &str) -> Result<Vec<KeywordAnnotation>> {
+        let annotations = sqlx::query_as::<_, KeywordAnnotation>(
+            r#"
+            SELECT a.id, a.keyword_id, k.word, k.slot, a.note, a.curator, a.created_at
+            FROM keyword_annotations a
+            JOIN keywords k ON k.id = a.keyword_id
+            WHERE DATE(k.created_at, ?) = ?
+            ORDER BY a.id ASC
+            "#,
+        )
+        .bind(self.timezone.sqlite_modifier())
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(annotations)
+    }
+
+    /// Keep a keyword that failed to insert so it can be replayed later
+    ///
+    /// `collected_at` (UTC, `YYYY-MM-DD HH:MM:SS`) becomes the keyword's
+    /// `created_at`, so a late replay still counts toward the day it was collected.
+    pub async fn dead_letter_keyword(
+        &self,
+        keyword: &DerivedKeyword,
+        tag: Option<&str>,
+        collected_at: &str,
+        error: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO failed_keywords
+                (word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+                 rerolls, tag, epoch, collected_at, last_error)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(slot, source, word_index) DO UPDATE SET last_error = excluded.last_error
+            "#,
+        )
+        .bind(&keyword.word)
+        .bind(keyword.slot as i64)
+        .bind(&keyword.blockhash)
+        .bind(&keyword.previous_blockhash)
+        .bind(keyword.block_time)
+        .bind(keyword.word_index as i64)
+        .bind(keyword.source.as_str())
+        .bind(&keyword.theme)
+        .bind(keyword.rerolls)
+        .bind(tag)
+        .bind(keyword.epoch.map(|epoch| epoch as i64))
+        .bind(collected_at)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Keywords waiting to be replayed, oldest first
+    pub async fn get_failed_keywords(&self) -> Result<Vec<FailedKeyword>> {
+        let failed = sqlx::query_as::<_, FailedKeyword>(
+            r#"
+            SELECT id, word, slot, tag, collected_at, attempts, last_error
+            FROM failed_keywords
+            ORDER BY collected_at ASC, id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(failed)
+    }
+
+    /// Move a dead-lettered keyword into `keywords`
+    ///
+    /// Returns false if the keyword was already stored (the original insert
+    /// may have landed after all); the dead letter is dropped either way.
+    pub async fn replay_failed_keyword(&self, id: i64) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO keywords
+                (word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+                 rerolls, tag, epoch, created_at)
+            SELECT word, slot, blockhash, previous_blockhash, block_time, word_index, source, theme,
+                   rerolls, tag, epoch, collected_at
+            FROM failed_keywords
+            WHERE id = ?
+            ON CONFLICT(slot, source, word_index) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query("DELETE FROM failed_keywords WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.invalidate_reads();
+
+        Ok(inserted > 0)
+    }
+
+    /// Drop a dead-lettered keyword without storing it
+    pub async fn discard_failed_keyword(&self, id: i64) -> Result<()> {
+        sqlx::query("DELETE FROM failed_keywords WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Note a failed replay of a dead-lettered keyword
+    pub async fn record_failed_keyword_error(&self, id: i64, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE failed_keywords SET attempts = attempts + 1, last_error = ? WHERE id = ?",
+        )
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Number of keywords waiting to be replayed
+    pub async fn count_failed_keywords(&self) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM failed_keywords")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Drop a day from the retry queue once its poem exists
+    pub async fn remove_pending_poem(&self, date: &str) -> Result<()> {
+        sqlx::query("DELETE FROM pending_poems WHERE date = ?")
+            .bind(date)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the first date the project has any keywords or poems for
+    pub async fn get_project_start_date(&self) -> Result<Option<String>> {
+        let start = sqlx::query_scalar::<_, Option<String>>(
+            r#"
+            SELECT MIN(day) FROM (
+                SELECT MIN(date) AS day FROM poems
+                UNION ALL
+                SELECT MIN(DATE(created_at, ?)) AS day FROM keywords
+            )
+            "#,
+        )
+        .bind(self.timezone.sqlite_modifier())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(start)
+    }
+
+    /// Total number of stored poems
+    pub async fn count_poems(&self) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM poems")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Total number of stored keywords
+    pub async fn count_keywords(&self) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM keywords")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Dates that have a poem, oldest first
+    pub async fn get_poem_dates(&self) -> Result<Vec<String>> {
+        let dates = sqlx::query_scalar::<_, String>("SELECT date FROM poems ORDER BY date ASC")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(dates)
+    }
+
+    /// Record a milestone; returns it only if it wasn't already recorded
+    pub async fn insert_milestone(
+        &self,
+        kind: &str,
+        value: i64,
+        date: &str,
+    ) -> Result<Option<StoredMilestone>> {
+        let milestone = sqlx::query_as::<_, (i64, String, i64, String, String)>(
+            r#"
+            INSERT INTO milestones (kind, value, date)
+            VALUES (?, ?, ?)
+            ON CONFLICT(kind, value) DO NOTHING
+            RETURNING id, kind, value, date, achieved_at
+            "#,
+        )
+        .bind(kind)
+        .bind(value)
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|(id, kind, value, date, achieved_at)| StoredMilestone {
+            id,
+            kind,
+            value,
+            date,
+            achieved_at,
+        });
+
+        Ok(milestone)
+    }
+
+    /// All recorded milestones, most recent first
+    pub async fn get_milestones(&self) -> Result<Vec<StoredMilestone>> {
+        let milestones = sqlx::query_as::<_, (i64, String, i64, String, String)>(
+            r#"
+            SELECT id, kind, value, date, achieved_at
+            FROM milestones
+            ORDER BY achieved_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|(id, kind, value, date, achieved_at)| StoredMilestone {
+            id,
+            kind,
+            value,
+            date,
+            achieved_at,
+        })
+        .collect();
+
+        Ok(milestones)
+    }
+
+    /// Record the mint transaction for a poem
+    pub async fn insert_poem_mint(&self, date: &str, signature: &str, merkle_tree: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO poem_mints (date, signature, merkle_tree)
+            VALUES (?, ?, ?)
+            ON CONFLICT(date) DO NOTHING
+            "#,
+        )
+        .bind(date)
+        .bind(signature)
+        .bind(merkle_tree)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the mint record for a poem, if it was minted
+    pub async fn get_poem_mint(&self, date: &str) -> Result<Option<StoredMint>> {
+        let mint = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"
+            SELECT date, signature, merkle_tree, minted_at
+            FROM poem_mints
+            WHERE date = ?
+            "#,
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|(date, signature, merkle_tree, minted_at)| StoredMint {
+            date,
+            signature,
+            merkle_tree,
+            minted_at,
+        });
+
+        Ok(mint)
+    }
+
+    /// Add blocks a day's keywords were derived from to its activity, and
+    /// store the mood its totals now give; returns that mood
+    pub async fn record_day_activity(
+        &self,
+        date: &str,
+        activity: &BlockActivity,
+    ) -> Result<Option<Mood>> {
+        let mut tx = self.pool.begin().await?;
+        let (blocks, transactions, non_vote_transactions, fees): (i64, i64, i64, i64) =
+            sqlx::query_as(
+                r#"
+                INSERT INTO day_activity (date, blocks, transactions, non_vote_transactions, fees)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(date) DO UPDATE SET
+                    blocks = blocks + excluded.blocks,
+                    transactions = transactions + excluded.transactions,
+                    non_vote_transactions = non_vote_transactions + excluded.non_vote_transactions,
+                    fees = fees + excluded.fees,
+                    updated_at = CURRENT_TIMESTAMP
+                RETURNING blocks, transactions, non_vote_transactions, fees
+                "#,
+            )
+            .bind(date)
+            .bind(activity.blocks)
+            .bind(activity.transactions)
+            .bind(activity.non_vote_transactions)
+            .bind(activity.fees)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let mood = Mood::of(&BlockActivity {
+            blocks,
+            transactions,
+            non_vote_transactions,
+            fees,
+        });
+        sqlx::query("UPDATE day_activity SET mood = ? WHERE date = ?")
+            .bind(mood.map(|mood| mood.name()))
+            .bind(date)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(mood)
+    }
+
+    /// Totals recorded for a day; empty before any of its blocks were
+    pub async fn get_day_activity(&self, date: &str) -> Result<BlockActivity> {
+        let totals: Option<(i64, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT blocks, transactions, non_vote_transactions, fees
+            FROM day_activity
+            WHERE date = ?
+            "#,
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(totals
+            .map(
+                |(blocks, transactions, non_vote_transactions, fees)| BlockActivity {
+                    blocks,
+                    transactions,
+                    non_vote_transactions,
+                    fees,
+                },
+            )
+            .unwrap_or_default())
+    }
+
+    /// Store a poem's provenance manifest, replacing any from an earlier generation
+    pub async fn insert_poem_provenance(&self, provenance: &PoemProvenance) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO poem_provenance (date, manifest)
+            VALUES (?, ?)
+            ON CONFLICT(date) DO UPDATE SET
+                manifest = excluded.manifest,
+                created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(&provenance.date)
+        .bind(serde_json::to_string(provenance).map_err(json_error)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Every stored provenance manifest, oldest poem first
+    pub async fn get_all_poem_provenance(&self) -> Result<Vec<PoemProvenance>> {
+        let manifests = sqlx::query_scalar::<_, String>(
+            "SELECT manifest FROM poem_provenance ORDER BY date ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        manifests
+            .iter()
+            .map(|manifest| {
+                serde_json::from_str(manifest)
+                    .context("Invalid stored provenance manifest")
+                    .map_err(ChainVerseError::Database)
+            })
+            .collect()
+    }
+
+    /// Get a poem's provenance manifest; None for poems generated before it was recorded
+    pub async fn get_poem_provenance(&self, date: &str) -> Result<Option<PoemProvenance>> {
+        let manifest = sqlx::query_scalar::<_, String>(
+            "SELECT manifest FROM poem_provenance WHERE date = ?",
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        manifest
+            .map(|manifest| {
+                serde_json::from_str(&manifest)
+                    .context("Invalid stored provenance manifest")
+                    .map_err(ChainVerseError::Database)
+            })
+            .transpose()
+    }
+
+    /// Store an on-demand poem for a slot range, returning its id
+    pub async fn insert_custom_poem(
+        &self,
+        start_slot: u64,
+        end_slot: u64,
+        title: Option<&str>,
+        content: &str,
+        keywords: &[String],
+    ) -> Result<i64> {
+        let keywords_json = serde_json::to_string(keywords).map_err(json_error)?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO custom_poems (start_slot, end_slot, title, content, keywords)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(start_slot as i64)
+        .bind(end_slot as i64)
+        .bind(title)
+        .bind(content)
+        .bind(keywords_json)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Check the database answers queries
+    pub async fn ping(&self) -> Result<()> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Blockhash of the first keyword stored from `start_date` up to (not including) `end_date`
+    pub async fn get_first_blockhash_between(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Option<String>> {
+        let blockhash = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT blockhash
+            FROM keywords
+            WHERE DATE(created_at, ?1) >= ?2 AND DATE(created_at, ?1) < ?3
+            ORDER BY created_at ASC, slot ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(self.timezone.sqlite_modifier())
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(blockhash)
+    }
+
+    /// When the most recent keyword was stored (UTC, `YYYY-MM-DD HH:MM:SS`)
+    pub async fn get_last_keyword_time(&self) -> Result<Option<String>> {
+        let last = sqlx::query_scalar::<_, Option<String>>("SELECT MAX(created_at) FROM keywords")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(last)
+    }
+
+    /// Get a stored on-demand poem by id
+    pub async fn get_custom_poem(&self, id: i64) -> Result<Option<StoredCustomPoem>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, start_slot, end_slot, title, content, keywords, created_at
+            FROM custom_poems
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(row) = row {
+            let keywords: Vec<String> =
+                serde_json::from_str(&row.get::<String, _>("keywords")).map_err(json_error)?;
+
+            Ok(Some(StoredCustomPoem {
+                id: row.get("id"),
+                start_slot: row.get("start_slot"),
+                end_slot: row.get("end_slot"),
+                title: row.get("title"),
+                content: row.get("content"),
+                keywords,
+                created_at: row.get("created_at"),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Store a period's meta-poem, replacing any earlier one
+    pub async fn insert_poem_collection(
+        &self,
+        period: &Period,
+        title: Option<&str>,
+        content: &str,
+        poem_dates: &[String],
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO poem_collections
+                (period, kind, start_date, end_date, title, content, poem_dates)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(period) DO UPDATE SET
+                title = excluded.title,
+                content = excluded.content,
+                poem_dates = excluded.poem_dates,
+                created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(period.key())
+        .bind(period.kind.to_string())
+        .bind(period.start_date())
+        .bind(period.end_date())
+        .bind(title)
+        .bind(content)
+        .bind(serde_json::to_string(poem_dates).map_err(json_error)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get the meta-poem for a period key (`2026-W02`, `2026-01`)
+    pub async fn get_poem_collection(&self, period: &str) -> Result<Option<StoredCollection>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, period, kind, start_date, end_date, title, content, poem_dates, created_at
+            FROM poem_collections
+            WHERE period = ?
+            "#,
+        )
+        .bind(period)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(collection_from_row).transpose()
+    }
+
+    /// Every stored meta-poem, latest period first
+    pub async fn get_poem_collections(&self) -> Result<Vec<StoredCollection>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, period, kind, start_date, end_date, title, content, poem_dates, created_at
+            FROM poem_collections
+            ORDER BY end_date DESC, kind ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(collection_from_row).collect()
+    }
+
+    /// Collected words not yet looked up in the dictionary, most recent first
+    pub async fn get_undefined_words(&self, limit: i64) -> Result<Vec<String>> {
+        let words = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT LOWER(k.word)
+            FROM keywords k
+            LEFT JOIN word_definitions d ON d.word = LOWER(k.word)
+            WHERE d.word IS NULL
+            GROUP BY LOWER(k.word)
+            ORDER BY MAX(k.id) DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(words)
+    }
+
+    /// Record a dictionary lookup; None remembers that the word has no entry
+    pub async fn store_definition(
+        &self,
+        word: &str,
+        definition: Option<&WordDefinition>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO word_definitions
+                (word, definition, part_of_speech, origin, source)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(word.to_lowercase())
+        .bind(definition.map(|d| &d.definition))
+        .bind(definition.and_then(|d| d.part_of_speech.as_ref()))
+        .bind(definition.and_then(|d| d.origin.as_ref()))
+        .bind(definition.map(|d| &d.source))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stored definitions for `words`, keyed by lowercased word
+    ///
+    /// Words that haven't been looked up, or have no dictionary entry, are absent.
+    pub async fn get_definitions(&self, words: &[&str]) -> Result<HashMap<String, WordDefinition>> {
+        let lowercased: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        let rows = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, String)>(
+            r#"
+            SELECT word, definition, part_of_speech, origin, source
+            FROM word_definitions
+            WHERE definition IS NOT NULL
+              AND word IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(serde_json::to_string(&lowercased).map_err(json_error)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(word, definition, part_of_speech, origin, source)| {
+                (
+                    word,
+                    WordDefinition {
+                        definition,
+                        part_of_speech,
+                        origin,
+                        source,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// How rare each keyword's word was when it was collected, by keyword id
+    ///
+    /// Counts the same word's earlier keywords (case-insensitively) and dates
+    /// the latest of them in the instance timezone.
+    pub async fn get_keyword_rarity(
+        &self,
+        keywords: &[StoredKeyword],
+    ) -> Result<HashMap<i64, KeywordRarity>> {
+        let ids: Vec<i64> = keywords.iter().map(|keyword| keyword.id).collect();
+        let history = sqlx::query_as::<_, (i64, i64, Option<String>)>(
+            r#"
+            SELECT k.id, COUNT(p.id), MAX(p.created_at)
+            FROM keywords k
+            LEFT JOIN keywords p
+                ON p.word = k.word COLLATE NOCASE
+               AND (p.created_at < k.created_at OR (p.created_at = k.created_at AND p.id < k.id))
+            WHERE k.id IN (SELECT value FROM json_each(?))
+            GROUP BY k.id
+            "#,
+        )
+        .bind(serde_json::to_string(&ids).map_err(json_error)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let days: HashMap<i64, NaiveDate> = keywords
+            .iter()
+            .filter_map(|keyword| {
+                let day = self.timezone.date_of_timestamp(&keyword.created_at)?;
+                Some((keyword.id, day))
+            })
+            .collect();
+        Ok(history
+            .into_iter()
+            .filter_map(|(id, previous, last_seen)| {
+                let last_seen = last_seen.and_then(|at| self.timezone.date_of_timestamp(&at));
+                let rarity = KeywordRarity::new(previous, last_seen, *days.get(&id)?);
+                Some((id, rarity))
+            })
+            .collect())
+    }
+
+    /// Record a request made with an admin API key
+    pub async fn record_api_key_usage(
+        &self,
+        key_name: &str,
+        role: Role,
+        method: &str,
+        path: &str,
+        status: u16,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_key_usage (key_name, role, method, path, status)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(key_name)
+        .bind(role.as_str())
+        .bind(method)
+        .bind(path)
+        .bind(status)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The most recent admin API key requests, newest first, optionally for one key
+    pub async fn get_api_key_usage(
+        &self,
+        key_name: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ApiKeyUsage>> {
+        let usage = sqlx::query_as::<_, ApiKeyUsage>(
+            r#"
+            SELECT id, key_name, role, method, path, status, used_at
+            FROM api_key_usage
+            WHERE ?1 IS NULL OR key_name = ?1
+            ORDER BY id DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(key_name)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    /// Write a consistent copy of the whole database to a new file at `path`
+    pub async fn vacuum_into(&self, path: &std::path::Path) -> Result<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Restore an exported archive into this database in one transaction
+    ///
+    /// Rows keep their ids and timestamps. The database must not have any
+    /// keywords or poems yet, so restored ids can't collide.
+    pub async fn import_archive(&self, archive: &Archive) -> Result<()> {
+        if self.count_keywords().await? > 0 || self.count_poems().await? > 0 {
+            return Err(ChainVerseError::Database(anyhow::anyhow!(
+                "Refusing to import into a database that already has keywords or poems"
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for keyword in &archive.keywords {
+            sqlx::query(
+                r#"
+                INSERT INTO keywords
+                    (id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                     theme, rerolls, tag, epoch, orphaned, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(keyword.id)
+            .bind(&keyword.word)
+            .bind(keyword.slot)
+            .bind(&keyword.blockhash)
+            .bind(&keyword.previous_blockhash)
+            .bind(keyword.block_time)
+            .bind(keyword.word_index)
+            .bind(&keyword.source)
+            .bind(&keyword.theme)
+            .bind(keyword.rerolls)
+            .bind(&keyword.tag)
+            .bind(keyword.epoch)
+            .bind(keyword.orphaned)
+            .bind(&keyword.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for poem in &archive.poems {
+            sqlx::query(
+                r#"
+                INSERT INTO poems
+                    (id, date, title, content, keyword_ids, anchor_hash, anchor_signature,
+                     poem_hash, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(poem.id)
+            .bind(&poem.date)
+            .bind(&poem.title)
+            .bind(&poem.content)
+            .bind(serde_json::to_string(&poem.keyword_ids).map_err(json_error)?)
+            .bind(&poem.anchor_hash)
+            .bind(&poem.anchor_signature)
+            .bind(&poem.poem_hash)
+            .bind(&poem.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for provenance in &archive.provenance {
+            sqlx::query("INSERT INTO poem_provenance (date, manifest) VALUES (?, ?)")
+                .bind(&provenance.date)
+                .bind(serde_json::to_string(provenance).map_err(json_error)?)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for collection in &archive.collections {
+            sqlx::query(
+                r#"
+                INSERT INTO poem_collections
+                    (period, kind, start_date, end_date, title, content, poem_dates, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&collection.period)
+            .bind(&collection.kind)
+            .bind(&collection.start_date)
+            .bind(&collection.end_date)
+            .bind(&collection.title)
+            .bind(&collection.content)
+            .bind(serde_json::to_string(&collection.poem_dates).map_err(json_error)?)
+            .bind(&collection.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        self.invalidate_reads();
+        self.fill_poem_hashes().await
+    }
+
+    /// Copy every table of the SQLite database at `path` into this one in one
+    /// transaction, restoring what `import_archive` leaves out (audit log,
+    /// annotations, revisions, engagement, and the rest)
+    ///
+    /// `path` must already be migrated to this build's schema, so its tables
+    /// match this database's column for column. The full-text index isn't
+    /// copied; the `poems` insert triggers rebuild it. The database must not
+    /// have any keywords or poems yet.
+    pub async fn import_database(&self, path: &std::path::Path) -> Result<()> {
+        if self.count_keywords().await? > 0 || self.count_poems().await? > 0 {
+            return Err(ChainVerseError::Database(anyhow::anyhow!(
+                "Refusing to import into a database that already has keywords or poems"
+            )));
+        }
+
+        // ATTACH and the foreign key switch are per connection and can't
+        // change inside a transaction. Tables are copied in name order, not
+        // dependency order, so keys are checked once everything is in.
+        let mut conn = self.pool.acquire().await?;
+        let attached = async {
+            sqlx::query("ATTACH DATABASE ? AS dump")
+                .bind(path.to_string_lossy().into_owned())
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query("PRAGMA foreign_keys = OFF")
+                .execute(&mut *conn)
+                .await
+        }
+        .await;
+        if let Err(e) = attached {
+            // Don't hand a half-set-up connection back to the pool
+            conn.detach();
+            return Err(e.into());
+        }
+
+        let copied = async {
+            let tables: Vec<String> = sqlx::query_scalar(
+                r#"
+                SELECT name FROM dump.sqlite_master AS t
+                WHERE type = 'table'
+                  AND name NOT LIKE 'sqlite\_%' ESCAPE '\'
+                  AND name != '_sqlx_migrations'
+                  AND NOT EXISTS (
+                      SELECT 1 FROM dump.sqlite_master AS v
+                      WHERE v.sql LIKE 'CREATE VIRTUAL TABLE%'
+                        AND (t.name = v.name OR t.name LIKE v.name || '\_%' ESCAPE '\')
+                  )
+                ORDER BY name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+            for table in &tables {
+                sqlx::query(&format!(
+                    r#"INSERT INTO main."{0}" SELECT * FROM dump."{0}""#,
+                    table
+                ))
+                .execute(&mut *tx)
+                .await?;
+            }
+            let dangling = sqlx::query("PRAGMA main.foreign_key_check")
+                .fetch_all(&mut *tx)
+                .await?;
+            if !dangling.is_empty() {
+                return Err(ChainVerseError::Database(anyhow::anyhow!(
+                    "{} rows in the dump reference missing rows",
+                    dangling.len()
+                )));
+            }
+            tx.commit().await?;
+            Ok(())
+        }
+        .await;
+
+        let restored = async {
+            sqlx::query("DETACH DATABASE dump")
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query("PRAGMA foreign_keys = ON")
+                .execute(&mut *conn)
+                .await
+        }
+        .await;
+        if restored.is_err() {
+            conn.detach();
+        }
+        copied?;
+        restored?;
+
+        self.invalidate_reads();
+        Ok(())
+    }
+
+    /// Get saved progress for a backfill over `start_date..=end_date`
+    pub async fn get_backfill_state(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Option<BackfillState>> {
+        let state = sqlx::query_as::<_, (String, String, Option<String>, Option<i64>, String)>(
+            r#"
+            SELECT start_date, end_date, last_completed_date, last_slot_tried, updated_at
+            FROM backfill_state
+            WHERE start_date = ? AND end_date = ?
+            "#,
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(
+            |(start_date, end_date, last_completed_date, last_slot_tried, updated_at)| BackfillState {
+                start_date,
+                end_date,
+                last_completed_date,
+                last_slot_tried,
+                updated_at,
+            },
+        );
+
+        Ok(state)
+    }
+
+    /// Record the last sample slot fetched for the day being backfilled
+    pub async fn save_backfill_slot(&self, start_date: &str, end_date: &str, slot: u64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_state (start_date, end_date, last_slot_tried)
+            VALUES (?, ?, ?)
+            ON CONFLICT(start_date, end_date) DO UPDATE SET
+                last_slot_tried = excluded.last_slot_tried,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a day of a backfill run as done
+    pub async fn complete_backfill_day(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        date: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_state (start_date, end_date, last_completed_date)
+            VALUES (?, ?, ?)
+            ON CONFLICT(start_date, end_date) DO UPDATE SET
+                last_completed_date = excluded.last_completed_date,
+                last_slot_tried = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(date)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Forget a finished backfill run
+    pub async fn clear_backfill_state(&self, start_date: &str, end_date: &str) -> Result<()> {
+        sqlx::query("DELETE FROM backfill_state WHERE start_date = ? AND end_date = ?")
+            .bind(start_date)
+            .bind(end_date)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// When the collector last finished each task (see `record_collection_run`)
+    pub async fn get_scheduler_state(&self) -> Result<SchedulerState> {
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT task, last_success_at FROM scheduler_state",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut state = SchedulerState::default();
+        for (task, at) in rows {
+            match task.as_str() {
+                SCHEDULER_TASK_COLLECTION => state.last_collection_at = Some(at),
+                SCHEDULER_TASK_POEM => state.last_poem_at = Some(at),
+                _ => {}
+            }
+        }
+        Ok(state)
+    }
+
+    /// Note that a keyword collection just finished
+    pub async fn record_collection_run(&self) -> Result<()> {
+        self.record_scheduler_run(SCHEDULER_TASK_COLLECTION).await
+    }
+
+    /// Note that a poem was just generated
+    pub async fn record_poem_run(&self) -> Result<()> {
+        self.record_scheduler_run(SCHEDULER_TASK_POEM).await
+    }
+
+    async fn record_scheduler_run(&self, task: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO scheduler_state (task, last_success_at)
+            VALUES (?, CURRENT_TIMESTAMP)
+            ON CONFLICT(task) DO UPDATE SET last_success_at = excluded.last_success_at
+            "#,
+        )
+        .bind(task)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Count `views` more reads of `date`'s poem
+    ///
+    /// Engagement counts aren't cached, so this leaves the read cache alone.
+    pub async fn add_poem_views(&self, date: &str, views: i64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO poem_views (date, views) VALUES (?, ?)
+            ON CONFLICT(date) DO UPDATE SET views = views + excluded.views
+            "#,
+        )
+        .bind(date)
+        .bind(views)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Count one `emoji` reaction to `date`'s poem
+    pub async fn add_poem_reaction(&self, date: &str, emoji: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO poem_reactions (date, emoji, count) VALUES (?, ?, 1)
+            ON CONFLICT(date, emoji) DO UPDATE SET count = count + 1
+            "#,
+        )
+        .bind(date)
+        .bind(emoji)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Views and reactions for `date`'s poem; zero for a poem nobody has read
+    pub async fn get_poem_engagement(&self, date: &str) -> Result<PoemEngagement> {
+        let views = sqlx::query_scalar::<_, i64>("SELECT views FROM poem_views WHERE date = ?")
+            .bind(date)
+            .fetch_optional(&self.pool)
+            .await?
+            .unwrap_or(0);
+        let reactions = sqlx::query_as::<_, (String, i64)>(
+            "SELECT emoji, count FROM poem_reactions WHERE date = ?",
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PoemEngagement {
+            views,
+            reactions: reactions.into_iter().collect(),
+        })
+    }
+
+    /// Views and reactions for the poems of `dates` that have any, keyed by date
+    pub async fn get_poems_engagement(
+        &self,
+        dates: &[&str],
+    ) -> Result<HashMap<String, PoemEngagement>> {
+        let dates = serde_json::to_string(dates).map_err(json_error)?;
+        let mut engagement: HashMap<String, PoemEngagement> = HashMap::new();
+        let views = sqlx::query_as::<_, (String, i64)>(
+            "SELECT date, views FROM poem_views WHERE date IN (SELECT value FROM json_each(?))",
+        )
+        .bind(&dates)
+        .fetch_all(&self.pool)
+        .await?;
+        for (date, views) in views {
+            engagement.entry(date).or_default().views = views;
+        }
+        let reactions = sqlx::query_as::<_, (String, String, i64)>(
+            r#"
+            SELECT date, emoji, count FROM poem_reactions
+            WHERE date IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(&dates)
+        .fetch_all(&self.pool)
+        .await?;
+        for (date, emoji, count) in reactions {
+            engagement
+                .entry(date)
+                .or_default()
+                .reactions
+                .insert(emoji, count);
+        }
+
+        Ok(engagement)
+    }
+
+    /// Take the lease on generating `date`'s poem for `holder` for `ttl_secs`;
+    /// false while another holder's lease is still running
+    ///
+    /// Renewing a lease `holder` already has succeeds.
+    pub async fn try_acquire_poem_lock(
+        &self,
+        date: &str,
+        holder: &str,
+        ttl_secs: u64,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO poem_locks (date, holder, expires_at)
+            VALUES (?, ?, DATETIME('now', ?))
+            ON CONFLICT(date) DO UPDATE SET
+                holder = excluded.holder,
+                expires_at = excluded.expires_at
+            WHERE poem_locks.holder = excluded.holder
+               OR poem_locks.expires_at <= CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(date)
+        .bind(holder)
+        .bind(format!("+{} seconds", ttl_secs))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Give up `holder`'s lease on `date`'s poem; a lease taken over by
+    /// another holder is left alone
+    pub async fn release_poem_lock(&self, date: &str, holder: &str) -> Result<()> {
+        sqlx::query("DELETE FROM poem_locks WHERE date = ? AND holder = ?")
+            .bind(date)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Up to `limit` keywords collected between `from` and `to` (UTC,
+    /// `YYYY-MM-DD HH:MM:SS`) whose slot hasn't been checked against the
+    /// finalized chain yet, oldest first; manual keywords have no slot to check
+    pub async fn get_unverified_keywords(
+        &self,
+        from: &str,
+        to: &str,
+        limit: i64,
+    ) -> Result<Vec<StoredKeyword>> {
+        let keywords = sqlx::query_as::<_, StoredKeyword>(
+            r#"
+            SELECT id, word, slot, blockhash, previous_blockhash, block_time, word_index, source,
+                   theme, rerolls, tag, epoch, orphaned, created_at
+            FROM keywords
+            WHERE verified_at IS NULL AND slot >= 0 AND created_at BETWEEN ? AND ?
+            ORDER BY created_at ASC, id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(keywords)
+    }
+
+    /// Record a keyword's check against the finalized chain; an orphaned
+    /// keyword is left out of poems from then on
+    pub async fn record_keyword_verification(&self, id: i64, orphaned: bool) -> Result<()> {
+        sqlx::query(
+            "UPDATE keywords SET orphaned = ?, verified_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(orphaned)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        self.invalidate_reads();
+
+        Ok(())
+    }
+
+    /// Get today's date in YYYY-MM-DD format, in the instance timezone
+    pub fn today(&self) -> String {
+        self.timezone.today().format("%Y-%m-%d").to_string()
+    }
+
+    /// The day `keyword` counts toward in the instance timezone, `YYYY-MM-DD`
+    pub fn keyword_date(&self, keyword: &StoredKeyword) -> String {
+        match self.timezone.date_of_timestamp(&keyword.created_at) {
+            Some(day) => day.format("%Y-%m-%d").to_string(),
+            None => keyword.created_at.get(..10).unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Local noon on `date`, the timestamp given to keywords added to a day after the fact
+    fn noon_of(&self, date: &str) -> String {
+        match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(day) => self.timezone.noon_timestamp(day),
+            Err(_) => format!("{} 12:00:00", date),
+        }
+    }
+}
+
+/// Content address of a poem: hex SHA-256 of its date, its keywords' slots
+/// in order, and its text
+///
+/// The title is left out, so retitling a poem keeps its hash; regenerating it
+/// (new text or keywords) gives a new one.
+pub fn poem_hash(date: &str, content: &str, slots: &[i64]) -> String {
+    let slots: Vec<String> = slots.iter().map(i64::to_string).collect();
+    let mut hasher = Sha256::new();
+    hasher.update(date.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(slots.join(",").as_bytes());
+    hasher.update(b"\n");
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// A JSON column (keyword ids, provenance) that doesn't (de)serialize
+fn json_error(e: serde_json::Error) -> ChainVerseError {
+    ChainVerseError::Database(e.into())
+}
+
+fn poem_from_row(row: &SqliteRow) -> Result<StoredPoem> {
+    let keyword_ids: Vec<i64> =
+        serde_json::from_str(&row.get::<String, _>("keyword_ids")).map_err(json_error)?;
+
+    Ok(StoredPoem {
+        id: row.get("id"),
+        date: row.get("date"),
+        title: row.get("title"),
+        content: row.get("content"),
+        keyword_ids,
+        anchor_hash: row.get("anchor_hash"),
+        anchor_signature: row.get("anchor_signature"),
+        poem_hash: row.get("poem_hash"),
+        created_at: row.get("created_at"),
+    })
+}
+
+fn collection_from_row(row: &SqliteRow) -> Result<StoredCollection> {
+    let poem_dates: Vec<String> =
+        serde_json::from_str(&row.get::<String, _>("poem_dates")).map_err(json_error)?;
+
+    Ok(StoredCollection {
+        id: row.get("id"),
+        period: row.get("period"),
+        kind: row.get("kind"),
+        start_date: row.get("start_date"),
+        end_date: row.get("end_date"),
+        title: row.get("title"),
+        content: row.get("content"),
+        poem_dates,
+        created_at: row.get("created_at"),
+    })
+}
+
+/// Quote each word of a reader's query so FTS5 matches them all literally
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|term| term.trim_matches('\''))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"", term))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Turn an FTS5 snippet marked with private-use characters into escaped HTML
+///
+/// `search_poems` asks SQLite to wrap matches in U+E000/U+E001 so the poem
+/// text can be escaped before the `<mark>` tags go in.
+fn highlight_snippet(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            '\u{E000}' => html.push_str("<mark>"),
+            '\u{E001}' => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+/// Open a pool on `options`, retrying transient failures with exponential backoff
+async fn connect(database_url: &str, options: SqliteConnectOptions) -> Result<SqlitePool> {
+    RetryPolicy::new(
+        DATABASE_CONNECT_ATTEMPTS,
+        Duration::from_millis(DATABASE_CONNECT_BACKOFF_MS),
+    )
+    .run("database connection", is_transient, |_| {
+        SqlitePoolOptions::new()
+            .max_connections(DATABASE_MAX_CONNECTIONS)
+            .connect_with(options.clone())
+    })
+    .await
+    .map_err(|e| {
+        let e = anyhow::Error::from(e).context(format!("Failed to open database {}", database_url));
+        ChainVerseError::Database(e)
+    })
+}
+
+/// Whether opening the database may succeed if tried again
+///
+/// Covers I/O errors, pool timeouts, and SQLite's BUSY/LOCKED codes (compared
+/// on the primary code, so extended codes like SQLITE_BUSY_RECOVERY match).
+fn is_transient(error: &sqlx::Error) -> bool {
+    const SQLITE_BUSY: i32 = 5;
+    const SQLITE_LOCKED: i32 = 6;
+
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(e) => e
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED)),
+        _ => false,
+    }
+}
+
+/// A database file for test `name` in the temp dir, and its URL
+#[cfg(test)]
+pub(crate) fn temp_database_url(name: &str) -> (std::path::PathBuf, String) {
+    let path = std::env::temp_dir().join(format!("chain_verse_{}_{}.db", name, std::process::id()));
+    let url = format!("sqlite:{}", path.display());
+    (path, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consts::BlockDataSource;
+
+    fn keyword(word: &str, slot: u64) -> DerivedKeyword {
+        DerivedKeyword {
+            word: word.to_string(),
+            slot,
+            blockhash: format!("hash{}", slot),
+            previous_blockhash: format!("hash{}", slot - 1),
+            block_time: None,
+            word_index: 0,
+            source: BlockDataSource::Blockhash,
+            theme: None,
+            rerolls: 0,
+            epoch: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrations_apply_once() {
+        let (path, url) = temp_database_url("migrate");
+
+        let db = Database::new(&url).await.unwrap();
+        assert_eq!(db.count_poems().await.unwrap(), 0);
+        db.close().await;
+
+        // Reopening must not re-run applied migrations
+        let db = Database::new(&url).await.unwrap();
+        let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(applied as usize, MIGRATOR.iter().count());
+        db.close().await;
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_connection_pragmas() {
+        let (path, url) = temp_database_url("pragmas");
+        let db = Database::new(&url).await.unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(busy_timeout as u64, DATABASE_BUSY_TIMEOUT_SECS * 1000);
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(foreign_keys, 1);
+
+        // A second pool on the same file (another process's) opens alongside the first
+        let other = Database::new(&url).await.unwrap();
+        assert_eq!(other.count_poems().await.unwrap(), 0);
+        other.close().await;
+        db.close().await;
+
+        assert!(is_transient(&sqlx::Error::PoolTimedOut));
+        assert!(!is_transient(&sqlx::Error::RowNotFound));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_open_read_only() {
+        let (path, url) = temp_database_url("read_only");
+        // Nothing to read until a writer has created the schema
+        assert!(Database::open_read_only(&url).await.is_err());
+
+        let db = Database::new(&url).await.unwrap();
+        db.insert_keyword(&keyword("abandon", 100)).await.unwrap();
+        assert!(!db.is_read_only());
+
+        let replica = Database::open_read_only(&url).await.unwrap();
+        assert!(replica.is_read_only());
+        assert_eq!(replica.get_all_keywords().await.unwrap().len(), 1);
+        assert!(replica
+            .insert_keyword(&keyword("ability", 101))
+            .await
+            .is_err());
+
+        // A replica newer than the writer waits for it to migrate
+        replica.close().await;
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = (SELECT MAX(version) FROM _sqlx_migrations)")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let err = Database::open_read_only(&url).await.unwrap_err();
+        assert!(err.to_string().contains("start the writer"), "{}", err);
+        db.close().await;
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_insert_keywords_batch() {
+        let (path, url) = temp_database_url("batch");
+        let db = Database::new(&url).await.unwrap();
+
+        let first = db
+            .insert_keywords_with_date(&[keyword("moon", 10), keyword("tide", 11)], "2026-01-05")
+            .await
+            .unwrap();
+        assert_eq!(first, InsertSummary { inserted: 2, skipped: 0 });
+
+        // Slot 11 is already stored
+        let second = db
+            .insert_keywords_with_date(&[keyword("tide", 11), keyword("salt", 12)], "2026-01-05")
+            .await
+            .unwrap();
+        assert_eq!(second, InsertSummary { inserted: 1, skipped: 1 });
+
+        // A block gives one word per source
+        let mut brine = keyword("brine", 12);
+        brine.source = BlockDataSource::PreviousBlockhash;
+        let third = db
+            .insert_keywords_with_date(&[brine, keyword("foam", 12)], "2026-01-05")
+            .await
+            .unwrap();
+        assert_eq!(third, InsertSummary { inserted: 1, skipped: 1 });
+        let from_slot: Vec<String> = db
+            .get_keywords_by_slot(12)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|k| k.word)
+            .collect();
+        assert_eq!(from_slot, vec!["salt", "brine"]);
+        assert!(db.get_keywords_by_slot(13).await.unwrap().is_empty());
+
+        let stored = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        assert_eq!(stored.len(), 4);
+        assert_eq!(stored[0].source, "blockhash");
+        assert_eq!(stored[0].previous_blockhash.as_deref(), Some("hash9"));
+        assert_eq!(stored[0].rerolls, 0);
+        assert_eq!(stored[0].tag, None);
+
+        let id = db
+            .insert_tagged_keyword(&keyword("surge", 20), Some("event"))
+            .await
+            .unwrap();
+        assert!(id.is_some());
+        // Storing the same slot and source again reports it rather than a stale id
+        assert_eq!(db.insert_keyword(&keyword("surge", 20)).await.unwrap(), None);
+        let today = db.get_keywords_for_date(&db.today()).await.unwrap();
+        assert_eq!(today[0].tag.as_deref(), Some("event"));
+
+        let words = db.get_words_between("2026-01-01", "2026-01-05").await.unwrap();
+        assert_eq!(words.len(), 4);
+        assert!(db.get_words_between("2026-01-06", "2026-01-09").await.unwrap().is_empty());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_corpus_stats_queries() {
+        let (path, url) = temp_database_url("stats");
+        let db = Database::new(&url).await.unwrap();
+
+        assert_eq!(db.get_average_keywords_per_poem().await.unwrap(), None);
+
+        let mut keywords = vec![keyword("moon", 1), keyword("Moon", 2), keyword("tide", 3)];
+        keywords[2].source = BlockDataSource::PreviousBlockhash;
+        db.insert_keywords_with_date(&keywords, "2026-01-05").await.unwrap();
+        db.insert_poem("2026-01-05", None, "a", &[1, 2, 3]).await.unwrap();
+        db.insert_poem("2026-01-06", None, "b", &[3]).await.unwrap();
+
+        let top = db.get_top_words(1).await.unwrap();
+        assert_eq!(top, vec![WordCount { word: "moon".to_string(), count: 2 }]);
+
+        let sources = db.get_source_counts().await.unwrap();
+        assert_eq!(sources[0], SourceCount { source: "blockhash".to_string(), count: 2 });
+        assert_eq!(sources[1].source, "previous_blockhash");
+
+        assert_eq!(db.get_average_keywords_per_poem().await.unwrap(), Some(2.0));
+
+        // Only poems with recorded usage are totalled, and regenerating clears it
+        assert!(db.get_monthly_usage().await.unwrap().is_empty());
+        let usage = TokenUsage {
+            prompt_tokens: 100,
+            completion_tokens: 250,
+            cost_usd: Some(0.5),
+        };
+        db.set_poem_usage("2026-01-05", &usage).await.unwrap();
+        db.set_poem_usage("2026-01-06", &usage).await.unwrap();
+        assert_eq!(
+            db.get_monthly_usage().await.unwrap(),
+            vec![MonthlyUsage {
+                month: "2026-01".to_string(),
+                poems: 2,
+                prompt_tokens: 200,
+                completion_tokens: 500,
+                cost_usd: 1.0,
+            }]
+        );
+        db.insert_poem("2026-01-06", None, "c", &[3]).await.unwrap();
+        assert_eq!(db.get_monthly_usage().await.unwrap()[0].poems, 1);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_word_cooccurrences() {
+        let (path, url) = temp_database_url("cooccurrence");
+        let db = Database::new(&url).await.unwrap();
+
+        let day_one = vec![keyword("moon", 1), keyword("tide", 2), keyword("star", 3)];
+        db.insert_keywords_with_date(&day_one, "2026-01-05").await.unwrap();
+        let day_two = vec![keyword("moon", 4), keyword("tide", 5)];
+        db.insert_keywords_with_date(&day_two, "2026-01-06").await.unwrap();
+
+        // "star" was collected on the first day but left out of its poem
+        db.insert_poem("2026-01-05", None, "a", &[1, 2]).await.unwrap();
+        db.insert_poem("2026-01-06", None, "b", &[4, 5]).await.unwrap();
+
+        let pairs = db.get_word_cooccurrences(1, 10).await.unwrap();
+        let pairs: Vec<_> = pairs
+            .iter()
+            .map(|p| (p.source.as_str(), p.target.as_str(), p.count))
+            .collect();
+        assert_eq!(pairs, vec![("moon", "tide", 2)]);
+        assert!(db.get_word_cooccurrences(3, 10).await.unwrap().is_empty());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_search_poems() {
+        let (path, url) = temp_database_url("search");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_poem("2026-01-05", Some("Ledger of Tides"), "the rain on <glass>", &[])
+            .await
+            .unwrap();
+        db.insert_poem("2026-01-06", None, "moon over water", &[]).await.unwrap();
+
+        let hits = db.search_poems("raining", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].date, "2026-01-05");
+        assert_eq!(hits[0].snippet, "the <mark>rain</mark> on &lt;glass&gt;");
+        assert_eq!(db.search_poems("tides", 10).await.unwrap().len(), 1);
+
+        // Every word must match, and FTS5 syntax is taken literally
+        assert!(db.search_poems("rain moon", 10).await.unwrap().is_empty());
+        assert!(db.search_poems("moon OR \"", 10).await.unwrap().is_empty());
+        assert!(db.search_poems("  ", 10).await.unwrap().is_empty());
+
+        // Regenerating a poem re-indexes it
+        db.insert_poem("2026-01-05", None, "dry stone", &[]).await.unwrap();
+        assert!(db.search_poems("rain", 10).await.unwrap().is_empty());
+        assert_eq!(db.search_poems("stone", 10).await.unwrap()[0].date, "2026-01-05");
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_similar_poems() {
+        let (path, url) = temp_database_url("similar");
+        let db = Database::new(&url).await.unwrap();
+
+        let rain = db
+            .insert_poem("2026-01-05", None, "rain", &[])
+            .await
+            .unwrap();
+        let storm = db
+            .insert_poem("2026-01-06", None, "storm", &[])
+            .await
+            .unwrap();
+        let stone = db
+            .insert_poem("2026-01-07", None, "stone", &[])
+            .await
+            .unwrap();
+        assert_eq!(db.get_unembedded_poems("m", 10).await.unwrap().len(), 3);
+        assert_eq!(db.get_similar_poems("2026-01-05", 5).await.unwrap(), None);
+
+        db.store_poem_embedding(rain, "m", &[1.0, 0.1])
+            .await
+            .unwrap();
+        db.store_poem_embedding(storm, "m", &[0.9, 0.2])
+            .await
+            .unwrap();
+        db.store_poem_embedding(stone, "m", &[0.0, 1.0])
+            .await
+            .unwrap();
+        assert!(db.get_unembedded_poems("m", 10).await.unwrap().is_empty());
+        assert_eq!(db.get_unembedded_poems("other", 10).await.unwrap().len(), 3);
+
+        let similar = db
+            .get_similar_poems("2026-01-05", 5)
+            .await
+            .unwrap()
+            .unwrap();
+        let dates: Vec<&str> = similar.iter().map(|p| p.date.as_str()).collect();
+        assert_eq!(dates, ["2026-01-06", "2026-01-07"]);
+        assert!(similar[0].similarity > similar[1].similarity);
+        assert_eq!(
+            db.get_similar_poems("2026-01-05", 1)
+                .await
+                .unwrap()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Regenerating a poem drops its stale embedding
+        db.insert_poem("2026-01-06", None, "drought", &[])
+            .await
+            .unwrap();
+        let unembedded = db.get_unembedded_poems("m", 10).await.unwrap();
+        assert_eq!(unembedded.len(), 1);
+        assert_eq!(unembedded[0].id, storm);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_audio() {
+        let (path, url) = temp_database_url("audio");
+        let db = Database::new(&url).await.unwrap();
+
+        let rain = db
+            .insert_poem("2026-01-05", None, "rain", &[])
+            .await
+            .unwrap();
+        assert_eq!(db.get_poem_audio("2026-01-05").await.unwrap(), None);
+        assert_eq!(
+            db.get_unnarrated_poems("mp3", "alloy", 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        db.store_poem_audio(rain, "audio/2026-01-05.mp3", "mp3", "alloy")
+            .await
+            .unwrap();
+        let audio = db.get_poem_audio("2026-01-05").await.unwrap().unwrap();
+        assert_eq!(audio.path, "audio/2026-01-05.mp3");
+        assert!(db
+            .get_unnarrated_poems("mp3", "alloy", 10)
+            .await
+            .unwrap()
+            .is_empty());
+        // Another voice reads it again
+        assert_eq!(
+            db.get_unnarrated_poems("mp3", "nova", 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Regenerating a poem drops its stale recording
+        db.insert_poem("2026-01-05", None, "drought", &[])
+            .await
+            .unwrap();
+        assert_eq!(db.get_poem_audio("2026-01-05").await.unwrap(), None);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_read_cache_invalidation() {
+        let (path, url) = temp_database_url("read_cache");
+        let cache = Some(Arc::new(ReadCache::new(Duration::from_secs(60))));
+        // Like `serve`: the collector and the API each have a pool but share the cache
+        let collector = Database::new(&url)
+            .await
+            .unwrap()
+            .with_read_cache(cache.clone());
+        let api = Database::new(&url).await.unwrap().with_read_cache(cache);
+        let today = api.today();
+
+        assert!(api.get_all_poems().await.unwrap().is_empty());
+        assert!(api.get_keywords_for_date(&today).await.unwrap().is_empty());
+        assert!(api.get_poem_by_date(&today).await.unwrap().is_none());
+
+        let id = collector
+            .insert_keyword(&keyword("ember", 100))
+            .await
+            .unwrap()
+            .unwrap();
+        collector
+            .insert_poem(&today, None, "ember", &[id])
+            .await
+            .unwrap();
+        assert_eq!(api.get_keywords_for_date(&today).await.unwrap().len(), 1);
+        assert_eq!(api.get_all_poems().await.unwrap().len(), 1);
+        assert_eq!(
+            api.get_poem_by_date(&today).await.unwrap().unwrap().content,
+            "ember"
+        );
+
+        // A handle without the cache (another process) goes unnoticed until the entries expire
+        let other = Database::new(&url).await.unwrap();
+        other
+            .insert_poem(&today, None, "rain", &[id])
+            .await
+            .unwrap();
+        assert_eq!(
+            api.get_poem_by_date(&today).await.unwrap().unwrap().content,
+            "ember"
+        );
+
+        api.remove_keyword(id, "alice", None).await.unwrap();
+        assert!(api.get_keywords_for_date(&today).await.unwrap().is_empty());
+        assert_eq!(
+            api.get_poem_by_date(&today).await.unwrap().unwrap().content,
+            "rain"
+        );
+
+        for db in [collector, api, other] {
+            db.close().await;
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_random_and_on_this_day() {
+        let (path, url) = temp_database_url("discovery");
+        let db = Database::new(&url).await.unwrap();
+
+        assert!(db.get_random_poem().await.unwrap().is_none());
+        for date in ["2024-03-14", "2025-03-14", "2025-03-15", "2026-03-14"] {
+            db.insert_poem(date, None, date, &[]).await.unwrap();
+        }
+
+        let random = db.get_random_poem().await.unwrap().unwrap();
+        assert_eq!(random.content, random.date);
+
+        let dates = |poems: Vec<StoredPoem>| -> Vec<String> {
+            poems.into_iter().map(|p| p.date).collect()
+        };
+        assert_eq!(
+            dates(db.get_poems_on_this_day("2026-03-14").await.unwrap()),
+            vec!["2025-03-14", "2024-03-14"]
+        );
+        assert!(db.get_poems_on_this_day("2026-03-16").await.unwrap().is_empty());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poems_for_epoch() {
+        let (path, url) = temp_database_url("epochs");
+        let db = Database::new(&url).await.unwrap();
+
+        let in_epoch = |word: &str, slot: u64, epoch: u64| DerivedKeyword {
+            epoch: Some(epoch),
+            ..keyword(word, slot)
+        };
+        let first = db
+            .insert_keyword_with_date(&in_epoch("ember", 100, 700), "2026-01-05")
+            .await
+            .unwrap()
+            .unwrap();
+        let second = db
+            .insert_keyword_with_date(&in_epoch("tide", 200, 701), "2026-01-06")
+            .await
+            .unwrap()
+            .unwrap();
+        let unknown = db
+            .insert_keyword_with_date(&keyword("moss", 300), "2026-01-07")
+            .await
+            .unwrap()
+            .unwrap();
+        db.insert_poem("2026-01-05", None, "one", &[first])
+            .await
+            .unwrap();
+        db.insert_poem("2026-01-06", None, "two", &[first, second])
+            .await
+            .unwrap();
+        db.insert_poem("2026-01-07", None, "three", &[unknown])
+            .await
+            .unwrap();
+
+        let dates =
+            |poems: Vec<StoredPoem>| -> Vec<String> { poems.into_iter().map(|p| p.date).collect() };
+        assert_eq!(
+            dates(db.get_poems_for_epoch(700).await.unwrap()),
+            vec!["2026-01-05", "2026-01-06"]
+        );
+        assert_eq!(
+            dates(db.get_poems_for_epoch(701).await.unwrap()),
+            vec!["2026-01-06"]
+        );
+        assert!(db.get_poems_for_epoch(702).await.unwrap().is_empty());
+        assert_eq!(
+            db.get_keyword(second).await.unwrap().unwrap().epoch,
+            Some(701)
+        );
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_hash_survives_regeneration() {
+        let (path, url) = temp_database_url("poem_hash");
+        let db = Database::new(&url).await.unwrap();
+
+        let id = db
+            .insert_keyword_with_date(&keyword("ember", 100), "2026-01-05")
+            .await
+            .unwrap()
+            .unwrap();
+        db.insert_poem("2026-01-05", Some("First"), "one", &[id])
+            .await
+            .unwrap();
+        let original = db.get_poem_by_date("2026-01-05").await.unwrap().unwrap();
+        let original_hash = poem_hash("2026-01-05", "one", &[100]);
+        assert_eq!(original.poem_hash.as_deref(), Some(original_hash.as_str()));
+
+        // Retitling keeps the hash; new text gets a new one
+        db.insert_poem("2026-01-05", Some("Renamed"), "one", &[id])
+            .await
+            .unwrap();
+        let retitled = db.get_poem_by_hash(&original_hash).await.unwrap().unwrap();
+        assert_eq!(retitled.title.as_deref(), Some("Renamed"));
+        db.insert_poem("2026-01-05", Some("Second"), "two", &[id])
+            .await
+            .unwrap();
+
+        let current = db.get_poem_by_date("2026-01-05").await.unwrap().unwrap();
+        let current_hash = current.poem_hash.clone().unwrap();
+        assert_ne!(current_hash, original_hash);
+        assert_eq!(
+            db.get_poem_by_hash(&current_hash).await.unwrap(),
+            Some(current)
+        );
+
+        let earlier = db.get_poem_by_hash(&original_hash).await.unwrap().unwrap();
+        assert_eq!(earlier.id, original.id);
+        assert_eq!(earlier.content, "one");
+        assert_eq!(earlier.poem_hash, Some(original_hash));
+        let unknown = "0".repeat(64);
+        assert!(db.get_poem_by_hash(&unknown).await.unwrap().is_none());
+
+        // Poems stored before the column existed are hashed on the next start
+        sqlx::query("UPDATE poems SET poem_hash = NULL")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        db.close().await;
+        let db = Database::new(&url).await.unwrap();
+        let filled = db.get_poem_by_date("2026-01-05").await.unwrap().unwrap();
+        assert_eq!(filled.poem_hash, Some(current_hash));
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_pending_poem_queue() {
+        let (path, url) = temp_database_url("pending");
+        let db = Database::new(&url).await.unwrap();
+
+        db.queue_pending_poem("2026-01-05", 1, "2026-01-05 12:01:00", "timeout")
+            .await
+            .unwrap();
+        db.queue_pending_poem("2026-01-05", 2, "2026-01-05 12:02:00", "429")
+            .await
+            .unwrap();
+        assert_eq!(db.get_pending_poem("2026-01-05").await.unwrap().unwrap().attempts, 2);
+
+        assert!(db.get_due_pending_poems("2026-01-05 12:01:59").await.unwrap().is_empty());
+        let due = db.get_due_pending_poems("2026-01-05 12:02:00").await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].attempts, 2);
+        assert_eq!(due[0].last_error.as_deref(), Some("429"));
+
+        db.postpone_pending_poem("2026-01-05", "2026-01-05 12:30:00")
+            .await
+            .unwrap();
+        assert!(db.get_due_pending_poems("2026-01-05 12:29:59").await.unwrap().is_empty());
+        let pending = db.get_pending_poem("2026-01-05").await.unwrap().unwrap();
+        assert_eq!(pending.attempts, 2);
+        assert_eq!(pending.last_error.as_deref(), Some("429"));
+
+        db.remove_pending_poem("2026-01-05").await.unwrap();
+        assert_eq!(db.count_pending_poems().await.unwrap(), 0);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_collections() {
+        let (path, url) = temp_database_url("collections");
+        let db = Database::new(&url).await.unwrap();
+
+        for date in ["2026-01-04", "2026-01-05", "2026-01-11", "2026-01-12"] {
+            db.insert_poem(date, None, "A poem", &[]).await.unwrap();
+        }
+        let week = Period::parse("2026-W02").unwrap();
+        let dates: Vec<String> = db
+            .get_poems_between(&week.start_date(), &week.end_date())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|poem| poem.date)
+            .collect();
+        assert_eq!(dates, ["2026-01-05", "2026-01-11"]);
+
+        db.queue_pending_poem("2026-01-11", 1, "2026-01-11 12:00:00", "timeout")
+            .await
+            .unwrap();
+        assert_eq!(
+            db.count_pending_poems_between("2026-01-05", "2026-01-11")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.count_pending_poems_between("2026-01-12", "2026-01-18")
+                .await
+                .unwrap(),
+            0
+        );
+
+        db.insert_poem_collection(&week, Some("Draft"), "first", &dates)
+            .await
+            .unwrap();
+        db.insert_poem_collection(&week, Some("Week Two"), "second", &dates)
+            .await
+            .unwrap();
+        let month = Period::parse("2026-01").unwrap();
+        db.insert_poem_collection(&month, None, "month", &dates)
+            .await
+            .unwrap();
+
+        let stored = db.get_poem_collection("2026-W02").await.unwrap().unwrap();
+        assert_eq!(stored.kind, "week");
+        assert_eq!(stored.title.as_deref(), Some("Week Two"));
+        assert_eq!(stored.content, "second");
+        assert_eq!(stored.poem_dates, dates);
+        assert!(db.get_poem_collection("2026-W03").await.unwrap().is_none());
+
+        let periods: Vec<String> = db
+            .get_poem_collections()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|collection| collection.period)
+            .collect();
+        assert_eq!(periods, ["2026-01", "2026-W02"]);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_word_definitions() {
+        let (path, url) = temp_database_url("definitions");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_keyword(&keyword("Ember", 100)).await.unwrap();
+        db.insert_keyword(&keyword("zyx", 101)).await.unwrap();
+        db.insert_keyword(&keyword("ember", 102)).await.unwrap();
+        assert_eq!(db.get_undefined_words(10).await.unwrap(), ["ember", "zyx"]);
+        assert_eq!(db.get_undefined_words(1).await.unwrap(), ["ember"]);
+
+        let ember = WordDefinition {
+            definition: "A glowing piece of coal.".to_string(),
+            part_of_speech: Some("noun".to_string()),
+            origin: None,
+            source: "dictionaryapi.dev".to_string(),
+        };
+        db.store_definition("ember", Some(&ember)).await.unwrap();
+        db.store_definition("zyx", None).await.unwrap();
+        assert!(db.get_undefined_words(10).await.unwrap().is_empty());
+
+        // Words without an entry are remembered but not returned
+        let definitions = db.get_definitions(&["Ember", "zyx", "tide"]).await.unwrap();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions["ember"], ember);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_usage() {
+        let (path, url) = temp_database_url("api_key_usage");
+        let db = Database::new(&url).await.unwrap();
+
+        db.record_api_key_usage("alice", Role::Curator, "POST", "/api/admin/keywords", 201)
+            .await
+            .unwrap();
+        db.record_api_key_usage("dash", Role::Reader, "POST", "/api/admin/keywords", 403)
+            .await
+            .unwrap();
+        db.record_api_key_usage("alice", Role::Curator, "DELETE", "/api/admin/usage", 200)
+            .await
+            .unwrap();
+
+        let usage = db.get_api_key_usage(None, 10).await.unwrap();
+        assert_eq!(usage.len(), 3);
+        assert_eq!(usage[0].path, "/api/admin/usage");
+        assert_eq!(usage[1].status, 403);
+
+        let alice = db.get_api_key_usage(Some("alice"), 1).await.unwrap();
+        assert_eq!(alice.len(), 1);
+        assert_eq!(alice[0].method, "DELETE");
+        assert_eq!(alice[0].role, "curator");
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_state() {
+        let (path, url) = temp_database_url("scheduler_state");
+        let db = Database::new(&url).await.unwrap();
+
+        let state = db.get_scheduler_state().await.unwrap();
+        assert_eq!(state, SchedulerState::default());
+
+        db.record_collection_run().await.unwrap();
+        db.record_collection_run().await.unwrap();
+        let state = db.get_scheduler_state().await.unwrap();
+        assert!(state.last_collection_at.is_some());
+        assert_eq!(state.last_poem_at, None);
+
+        db.record_poem_run().await.unwrap();
+        let state = db.get_scheduler_state().await.unwrap();
+        assert!(state.last_poem_at.is_some());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_engagement() {
+        let (path, url) = temp_database_url("engagement");
+        let db = Database::new(&url).await.unwrap();
+
+        assert_eq!(
+            db.get_poem_engagement("2026-01-05").await.unwrap(),
+            PoemEngagement::default()
+        );
+
+        db.add_poem_views("2026-01-05", 1).await.unwrap();
+        db.add_poem_views("2026-01-05", 1).await.unwrap();
+        db.add_poem_reaction("2026-01-05", "🔥").await.unwrap();
+        db.add_poem_reaction("2026-01-05", "🔥").await.unwrap();
+        db.add_poem_reaction("2026-01-05", "✨").await.unwrap();
+        db.add_poem_reaction("2026-01-06", "✨").await.unwrap();
+
+        let engagement = db.get_poem_engagement("2026-01-05").await.unwrap();
+        assert_eq!(engagement.views, 2);
+        assert_eq!(
+            engagement.reactions,
+            BTreeMap::from([("✨".to_string(), 1), ("🔥".to_string(), 2)])
+        );
+
+        let all = db
+            .get_poems_engagement(&["2026-01-05", "2026-01-06"])
+            .await
+            .unwrap();
+        assert_eq!(all["2026-01-05"], engagement);
+        assert_eq!(all["2026-01-06"].views, 0);
+        assert_eq!(all["2026-01-06"].reactions.len(), 1);
+
+        let some = db.get_poems_engagement(&["2026-01-06"]).await.unwrap();
+        assert!(!some.contains_key("2026-01-05"));
+        assert!(db.get_poems_engagement(&[]).await.unwrap().is_empty());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_lock() {
+        let (path, url) = temp_database_url("poem_lock");
+        let db = Database::new(&url).await.unwrap();
+        let lock = |date, holder| db.try_acquire_poem_lock(date, holder, 60);
+
+        assert!(lock("2026-01-05", "a").await.unwrap());
+        assert!(!lock("2026-01-05", "b").await.unwrap());
+        assert!(lock("2026-01-05", "a").await.unwrap());
+        assert!(lock("2026-01-06", "b").await.unwrap());
+
+        // Only the holder can release its lease
+        db.release_poem_lock("2026-01-05", "b").await.unwrap();
+        assert!(!lock("2026-01-05", "b").await.unwrap());
+        db.release_poem_lock("2026-01-05", "a").await.unwrap();
+        assert!(lock("2026-01-05", "b").await.unwrap());
+
+        // An expired lease is taken over
+        sqlx::query("UPDATE poem_locks SET expires_at = '2000-01-01 00:00:00'")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        assert!(lock("2026-01-05", "a").await.unwrap());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_keyword_verification() {
+        let (path, url) = temp_database_url("verification");
+        let db = Database::new(&url).await.unwrap();
+        db.insert_keywords_with_date(
+            &[
+                keyword("moon", 10),
+                keyword("tide", 11),
+                keyword("salt", 12),
+            ],
+            "2026-01-05",
+        )
+        .await
+        .unwrap();
+        db.add_manual_keyword("2026-01-05", "river", "alice", None)
+            .await
+            .unwrap();
+
+        let day = ("2026-01-05 00:00:00", "2026-01-05 23:59:59");
+        let unverified = db.get_unverified_keywords(day.0, day.1, 10).await.unwrap();
+        let words: Vec<&str> = unverified.iter().map(|k| k.word.as_str()).collect();
+        assert_eq!(words, vec!["moon", "tide", "salt"]);
+        assert!(db
+            .get_unverified_keywords("2026-01-06 00:00:00", "2026-01-06 23:59:59", 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        db.record_keyword_verification(unverified[0].id, false)
+            .await
+            .unwrap();
+        db.record_keyword_verification(unverified[1].id, true)
+            .await
+            .unwrap();
+        let unverified = db.get_unverified_keywords(day.0, day.1, 10).await.unwrap();
+        assert_eq!(unverified.len(), 1);
+        assert_eq!(unverified[0].word, "salt");
+
+        // The orphaned keyword stays visible but is left out of the poem
+        let keywords = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        assert_eq!(keywords.len(), 4);
+        assert!(keywords.iter().any(|k| k.word == "tide" && k.orphaned));
+        let poem_words: Vec<String> = db
+            .get_poem_keywords_for_date("2026-01-05")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|k| k.word)
+            .collect();
+        assert!(!poem_words.contains(&"tide".to_string()));
+        assert_eq!(poem_words.len(), 3);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_failed_keyword_replay() {
+        let (path, url) = temp_database_url("failed_keywords");
+        let db = Database::new(&url).await.unwrap();
+
+        db.dead_letter_keyword(
+            &keyword("ember", 100),
+            Some("event"),
+            "2026-01-05 09:30:00",
+            "locked",
+        )
+        .await
+        .unwrap();
+        db.dead_letter_keyword(
+            &keyword("ember", 100),
+            Some("event"),
+            "2026-01-05 09:30:00",
+            "busy",
+        )
+        .await
+        .unwrap();
+        db.dead_letter_keyword(&keyword("tide", 101), None, "2026-01-05 10:00:00", "locked")
+            .await
+            .unwrap();
+
+        let failed = db.get_failed_keywords().await.unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].word, "ember");
+        assert_eq!(failed[0].last_error.as_deref(), Some("busy"));
+
+        db.record_failed_keyword_error(failed[0].id, "still locked")
+            .await
+            .unwrap();
+        assert_eq!(db.get_failed_keywords().await.unwrap()[0].attempts, 1);
+
+        // Replayed keywords land on the day they were collected, tag and all
+        assert!(db.replay_failed_keyword(failed[0].id).await.unwrap());
+        let stored = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].word, "ember");
+        assert_eq!(stored[0].tag.as_deref(), Some("event"));
+
+        // A slot that was stored after all just clears the dead letter
+        db.insert_keyword(&keyword("tide", 101)).await.unwrap();
+        assert!(!db.replay_failed_keyword(failed[1].id).await.unwrap());
+        assert_eq!(db.count_failed_keywords().await.unwrap(), 0);
+
+        // A discarded dead letter is dropped without being stored
+        db.dead_letter_keyword(&keyword("moss", 102), None, "2026-01-05 11:00:00", "locked")
+            .await
+            .unwrap();
+        let moss = db.get_failed_keywords().await.unwrap()[0].id;
+        db.discard_failed_keyword(moss).await.unwrap();
+        assert_eq!(db.count_failed_keywords().await.unwrap(), 0);
+        assert_eq!(
+            db.get_keywords_for_date("2026-01-05").await.unwrap().len(),
+            1
+        );
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_manual_keyword_audit() {
+        let (path, url) = temp_database_url("keyword_audit");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_keywords_with_date(&[keyword("moon", 100)], "2026-01-05")
+            .await
+            .unwrap();
+
+        let first = db
+            .add_manual_keyword("2026-01-05", "river", "alice", Some("missing word"))
+            .await
+            .unwrap();
+        let second = db.add_manual_keyword("2026-01-05", "stone", "alice", None).await.unwrap();
+        assert_eq!((first.slot, second.slot), (-1, -2));
+        assert_eq!(first.source, MANUAL_KEYWORD_SOURCE);
+        assert_eq!(db.get_keywords_for_date("2026-01-05").await.unwrap().len(), 3);
+
+        let removed = db.remove_keyword(first.id, "bob", Some("typo")).await.unwrap();
+        assert_eq!(removed.unwrap().word, "river");
+        assert!(db.remove_keyword(first.id, "bob", None).await.unwrap().is_none());
+        assert_eq!(db.get_keywords_for_date("2026-01-05").await.unwrap().len(), 2);
+
+        let audit = db.get_keyword_audit("2026-01-05").await.unwrap();
+        let actions: Vec<(&str, &str, &str)> = audit
+            .iter()
+            .map(|e| (e.action.as_str(), e.word.as_str(), e.curator.as_str()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![("add", "river", "alice"), ("add", "stone", "alice"), ("remove", "river", "bob")]
+        );
+        assert_eq!(audit[2].reason.as_deref(), Some("typo"));
+
+        // Stamped at UTC noon, which is already the next day at +13:00
+        assert_eq!(db.keyword_date(&second), "2026-01-05");
+        let auckland = db.clone().with_timezone("+13:00".parse().unwrap());
+        assert_eq!(auckland.keyword_date(&second), "2026-01-06");
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_keyword_annotations() {
+        let (path, url) = temp_database_url("keyword_annotations");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_keywords_with_date(&[keyword("moon", 100), keyword("tide", 101)], "2026-01-05")
+            .await
+            .unwrap();
+        db.insert_keywords_with_date(&[keyword("ember", 102)], "2026-01-06")
+            .await
+            .unwrap();
+        let keywords = db.get_all_keywords().await.unwrap();
+        let annotate = |index: usize, note: &'static str, curator: &'static str| {
+            let id = keywords.get(index).map_or(9999, |k| k.id);
+            db.add_keyword_annotation(id, note, curator)
+        };
+        let notes = || async {
+            let annotations = db.get_keyword_annotations("2026-01-05").await.unwrap();
+            annotations
+                .into_iter()
+                .map(|a| (a.word, a.curator))
+                .collect::<Vec<_>>()
+        };
+
+        let first = annotate(0, "Block landed during an outage", "alice")
+            .await
+            .unwrap();
+        let first = first.unwrap();
+        assert_eq!((first.word.as_str(), first.slot), ("moon", 100));
+        annotate(1, "Rerolled past a blocked word", "bob")
+            .await
+            .unwrap();
+        annotate(2, "Next day", "alice").await.unwrap();
+        assert!(annotate(3, "No such keyword", "alice")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            notes().await,
+            vec![
+                ("moon".into(), "alice".into()),
+                ("tide".into(), "bob".into())
+            ]
+        );
+
+        let removed = db.remove_keyword_annotation(first.id).await.unwrap();
+        assert_eq!(removed, Some(first.clone()));
+        assert!(db
+            .remove_keyword_annotation(first.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(notes().await.len(), 1);
+
+        // Removing a keyword takes its notes with it
+        db.remove_keyword(keywords[1].id, "bob", None)
+            .await
+            .unwrap();
+        assert!(notes().await.is_empty());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_keyword_rarity() {
+        let (path, url) = temp_database_url("rarity");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_keywords_with_date(&[keyword("moon", 100)], "2025-06-07")
+            .await
+            .unwrap();
+        db.insert_keywords_with_date(&[keyword("Moon", 101), keyword("tide", 102)], "2026-01-05")
+            .await
+            .unwrap();
+
+        let keywords = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        let rarity = db.get_keyword_rarity(&keywords).await.unwrap();
+        let of = |word: &str| {
+            let keyword = keywords.iter().find(|k| k.word == word).unwrap();
+            rarity[&keyword.id].clone()
+        };
+        let moon = of("Moon");
+        assert_eq!(moon.previous_occurrences, 1);
+        assert_eq!(moon.last_seen.as_deref(), Some("2025-06-07"));
+        assert_eq!(moon.summary, "First appearance in 212 days");
+        assert_eq!(of("tide").summary, "First appearance");
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poem_provenance_round_trip() {
+        use crate::derivation::KeywordDerivation;
+        use crate::poem_form::PoemForm;
+        use crate::poem_generator::PoemGenerator;
+        use crate::words::WordDictionary;
+
+        let (path, url) = temp_database_url("provenance");
+        let db = Database::new(&url).await.unwrap();
+
+        db.insert_keywords_with_date(&[keyword("moon", 100)], "2026-01-05")
+            .await
+            .unwrap();
+        let keywords = db.get_keywords_for_date("2026-01-05").await.unwrap();
+        let words = vec!["moon".to_string()];
+
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let generator = PoemGenerator::new("key".to_string(), "model-a".to_string());
+        let provenance = PoemProvenance::new(
+            "2026-01-05",
+            derivation.provenance(),
+            &keywords,
+            generator.provenance("model-a", &words, PoemForm::Haiku, "2026-01-05", None),
+        );
+        assert_eq!(provenance.keywords[0].slot, 100);
+        assert_eq!(provenance.derivation.blocklist_sha256, None);
+        assert_eq!(provenance.llm.prompt_sha256.len(), 64);
+
+        assert!(db.get_poem_provenance("2026-01-05").await.unwrap().is_none());
+        db.insert_poem_provenance(&provenance).await.unwrap();
+        assert_eq!(
+            db.get_poem_provenance("2026-01-05").await.unwrap(),
+            Some(provenance.clone())
+        );
+
+        // Regenerating the poem replaces its manifest
+        let mut regenerated = provenance;
+        regenerated.llm.model = "model-b".to_string();
+        db.insert_poem_provenance(&regenerated).await.unwrap();
+        let stored = db.get_poem_provenance("2026-01-05").await.unwrap().unwrap();
+        assert_eq!(stored.llm.model, "model-b");
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_day_activity_accumulates() {
+        let (path, url) = temp_database_url("day_activity");
+        let db = Database::new(&url).await.unwrap();
+
+        assert_eq!(
+            db.get_day_activity("2026-01-05").await.unwrap(),
+            BlockActivity::default()
+        );
+
+        // A quiet, cheap morning...
+        let quiet = BlockActivity {
+            blocks: 2,
+            transactions: 2_000,
+            non_vote_transactions: 200,
+            fees: 2_000 * 5_000,
+        };
+        let mood = db.record_day_activity("2026-01-05", &quiet).await.unwrap();
+        assert_eq!(mood, Some(Mood::Tranquil));
+
+        // ...turns steady once busier blocks are added to the day's totals
+        let busy = BlockActivity {
+            blocks: 2,
+            transactions: 4_000,
+            non_vote_transactions: 2_000,
+            fees: 4_000 * 5_000,
+        };
+        let mood = db.record_day_activity("2026-01-05", &busy).await.unwrap();
+        assert_eq!(mood, Some(Mood::Steady));
+        let day = db.get_day_activity("2026-01-05").await.unwrap();
+        assert_eq!(day.blocks, 4);
+        assert_eq!(day.non_vote_transactions, 2_200);
+        assert_eq!(Mood::of(&day), Some(Mood::Steady));
+        assert_eq!(
+            db.get_day_activity("2026-01-06").await.unwrap(),
+            BlockActivity::default()
+        );
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
     }
 }
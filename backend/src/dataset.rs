@@ -0,0 +1,353 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use futures::stream::{self, Stream, StreamExt};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::consts::KEYWORD_EXPORT_PAGE_SIZE;
+use crate::database::{Database, StoredKeyword};
+
+/// Content type of GET /api/export/keywords.csv
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+/// Columns of the keyword dataset, in order; `date` is the day the keyword
+/// counts toward in the instance timezone
+pub const KEYWORD_COLUMNS: [&str; 14] = [
+    "id",
+    "date",
+    "word",
+    "slot",
+    "blockhash",
+    "previous_blockhash",
+    "block_time",
+    "word_index",
+    "source",
+    "theme",
+    "rerolls",
+    "tag",
+    "epoch",
+    "created_at",
+];
+
+/// The same columns for Parquet. Every column is optional so each is
+/// written the same way, with a definition level per row.
+#[cfg(feature = "parquet")]
+const PARQUET_SCHEMA: &str = "
+    message keyword {
+        OPTIONAL INT64 id;
+        OPTIONAL BYTE_ARRAY date (UTF8);
+        OPTIONAL BYTE_ARRAY word (UTF8);
+        OPTIONAL INT64 slot;
+        OPTIONAL BYTE_ARRAY blockhash (UTF8);
+        OPTIONAL BYTE_ARRAY previous_blockhash (UTF8);
+        OPTIONAL INT64 block_time;
+        OPTIONAL INT64 word_index;
+        OPTIONAL BYTE_ARRAY source (UTF8);
+        OPTIONAL BYTE_ARRAY theme (UTF8);
+        OPTIONAL INT64 rerolls;
+        OPTIONAL BYTE_ARRAY tag (UTF8);
+        OPTIONAL INT64 epoch;
+        OPTIONAL BYTE_ARRAY created_at (UTF8);
+    }
+";
+
+/// File format for `chain-verse export-keywords`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum KeywordFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// Apache Parquet, one row group per page of keywords (builds with
+    /// `--features parquet` only)
+    Parquet,
+}
+
+/// One value in a keyword row
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Int(Option<i64>),
+    Text(Option<String>),
+}
+
+impl Field {
+    /// The value as a CSV field: empty when missing, quoted when it holds a
+    /// comma, quote, or line break
+    fn to_csv(&self) -> String {
+        match self {
+            Field::Int(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+            Field::Text(None) => String::new(),
+            Field::Text(Some(text)) if text.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            }
+            Field::Text(Some(text)) => text.clone(),
+        }
+    }
+}
+
+/// `keyword` as KEYWORD_COLUMNS values
+fn keyword_fields(db: &Database, keyword: &StoredKeyword) -> [Field; 14] {
+    [
+        Field::Int(Some(keyword.id)),
+        Field::Text(Some(db.keyword_date(keyword))),
+        Field::Text(Some(keyword.word.clone())),
+        Field::Int(Some(keyword.slot)),
+        Field::Text(Some(keyword.blockhash.clone())),
+        Field::Text(keyword.previous_blockhash.clone()),
+        Field::Int(keyword.block_time),
+        Field::Int(Some(keyword.word_index)),
+        Field::Text(Some(keyword.source.clone())),
+        Field::Text(keyword.theme.clone()),
+        Field::Int(Some(keyword.rerolls)),
+        Field::Text(keyword.tag.clone()),
+        Field::Int(keyword.epoch),
+        Field::Text(Some(keyword.created_at.clone())),
+    ]
+}
+
+/// The CSV header line
+pub fn csv_header() -> String {
+    format!("{}\n", KEYWORD_COLUMNS.join(","))
+}
+
+/// Keywords as CSV lines, without the header
+pub fn csv_rows(db: &Database, keywords: &[StoredKeyword]) -> String {
+    let mut csv = String::new();
+    for keyword in keywords {
+        let fields: Vec<String> = keyword_fields(db, keyword)
+            .iter()
+            .map(Field::to_csv)
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// The whole keywords table as CSV, read a page at a time so large
+/// archives never sit in memory at once
+pub fn csv_stream(db: Database) -> impl Stream<Item = Result<String>> + Send + 'static {
+    let rows = stream::try_unfold((db, 0), |(db, after_id)| async move {
+        let keywords = db
+            .get_keywords_after(after_id, KEYWORD_EXPORT_PAGE_SIZE)
+            .await?;
+        let Some(last) = keywords.last() else {
+            return Ok(None);
+        };
+        let next_id = last.id;
+        let csv = csv_rows(&db, &keywords);
+        Ok(Some((csv, (db, next_id))))
+    });
+    stream::once(async { Ok(csv_header()) }).chain(rows)
+}
+
+/// Write every keyword to `out` as `format`, returning how many were written
+pub async fn export_keywords(db: &Database, format: KeywordFormat, out: &Path) -> Result<usize> {
+    let mut writer = KeywordWriter::create(format, out)?;
+
+    let mut after_id = 0;
+    let mut written = 0;
+    loop {
+        let keywords = db
+            .get_keywords_after(after_id, KEYWORD_EXPORT_PAGE_SIZE)
+            .await?;
+        let Some(last) = keywords.last() else {
+            break;
+        };
+        after_id = last.id;
+        written += keywords.len();
+        writer.write(db, &keywords)?;
+    }
+
+    writer
+        .finish()
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    Ok(written)
+}
+
+/// An export file being written a page of keywords at a time
+enum KeywordWriter {
+    Csv(BufWriter<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::file::writer::SerializedFileWriter<File>),
+}
+
+impl KeywordWriter {
+    fn create(format: KeywordFormat, out: &Path) -> Result<Self> {
+        let create =
+            || File::create(out).with_context(|| format!("Failed to create {}", out.display()));
+        match format {
+            KeywordFormat::Csv => {
+                let mut csv = BufWriter::new(create()?);
+                csv.write_all(csv_header().as_bytes())?;
+                Ok(Self::Csv(csv))
+            }
+            #[cfg(feature = "parquet")]
+            KeywordFormat::Parquet => Ok(Self::Parquet(parquet_writer(create()?)?)),
+            #[cfg(not(feature = "parquet"))]
+            KeywordFormat::Parquet => {
+                anyhow::bail!("Parquet export needs a build with --features parquet")
+            }
+        }
+    }
+
+    fn write(&mut self, db: &Database, keywords: &[StoredKeyword]) -> Result<()> {
+        match self {
+            Self::Csv(csv) => csv.write_all(csv_rows(db, keywords).as_bytes())?,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(writer) => write_row_group(writer, db, keywords)?,
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            Self::Csv(mut csv) => csv.flush()?,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(writer) => {
+                writer.close()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_writer(file: File) -> Result<parquet::file::writer::SerializedFileWriter<File>> {
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    Ok(parquet::file::writer::SerializedFileWriter::new(
+        file, schema, properties,
+    )?)
+}
+
+/// Write `keywords` as one row group, column by column
+#[cfg(feature = "parquet")]
+fn write_row_group(
+    writer: &mut parquet::file::writer::SerializedFileWriter<File>,
+    db: &Database,
+    keywords: &[StoredKeyword],
+) -> Result<()> {
+    use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+
+    let rows: Vec<[Field; 14]> = keywords.iter().map(|k| keyword_fields(db, k)).collect();
+    let mut row_group = writer.next_row_group()?;
+    for index in 0..KEYWORD_COLUMNS.len() {
+        let Some(mut column) = row_group.next_column()? else {
+            anyhow::bail!(
+                "Parquet schema is missing column {}",
+                KEYWORD_COLUMNS[index]
+            );
+        };
+        let mut levels = Vec::with_capacity(rows.len());
+        let mut ints = Vec::new();
+        let mut texts = Vec::new();
+        for row in &rows {
+            match &row[index] {
+                Field::Int(Some(value)) => ints.push(*value),
+                Field::Text(Some(text)) => texts.push(ByteArray::from(text.as_str())),
+                Field::Int(None) | Field::Text(None) => {
+                    levels.push(0);
+                    continue;
+                }
+            }
+            levels.push(1);
+        }
+        if matches!(rows.first().map(|row| &row[index]), Some(Field::Int(_))) {
+            column
+                .typed::<Int64Type>()
+                .write_batch(&ints, Some(&levels), None)?;
+        } else {
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&texts, Some(&levels), None)?;
+        }
+        column.close()?;
+    }
+    row_group.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::BlockDataSource;
+    use crate::derivation::DerivedKeyword;
+    use futures::TryStreamExt;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "chain_verse_dataset_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_csv_fields() {
+        assert_eq!(Field::Int(Some(42)).to_csv(), "42");
+        assert_eq!(Field::Int(None).to_csv(), "");
+        assert_eq!(Field::Text(None).to_csv(), "");
+        assert_eq!(Field::Text(Some("ember".to_string())).to_csv(), "ember");
+        assert_eq!(
+            Field::Text(Some("say \"hi\", then\nleave".to_string())).to_csv(),
+            "\"say \"\"hi\"\", then\nleave\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_keywords() {
+        let db_path = temp_path("keywords.db");
+        let db = Database::new(&format!("sqlite:{}", db_path.display()))
+            .await
+            .unwrap();
+        let keyword = DerivedKeyword {
+            word: "ember".to_string(),
+            slot: 250_000_000,
+            blockhash: "hash".to_string(),
+            previous_blockhash: "parent".to_string(),
+            block_time: Some(1_700_000_000),
+            word_index: 7,
+            source: BlockDataSource::Blockhash,
+            theme: None,
+            rerolls: 1,
+            epoch: None,
+        };
+        db.insert_keyword_with_date(&keyword, "2026-01-05")
+            .await
+            .unwrap();
+        db.add_manual_keyword("2026-01-06", "river, bend", "alice", None)
+            .await
+            .unwrap();
+
+        let streamed: Vec<String> = csv_stream(db.clone()).try_collect().await.unwrap();
+        let csv = streamed.concat();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], KEYWORD_COLUMNS.join(","));
+        assert!(lines[1].starts_with("1,2026-01-05,ember,250000000,hash,parent,1700000000,7,"));
+        assert!(lines[2].contains(",2026-01-06,\"river, bend\","));
+
+        let out = temp_path("keywords.csv");
+        let written = export_keywords(&db, KeywordFormat::Csv, &out)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), csv);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&out);
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Free Dictionary API; entries live at `{DICTIONARY_API_URL}/{language}/{word}`
+const DICTIONARY_API_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries";
+
+/// Languages the Free Dictionary API has entries for
+const DICTIONARY_LANGUAGES: &[&str] = &["en"];
+
+/// Recorded as a definition's `source`
+const DICTIONARY_SOURCE: &str = "dictionaryapi.dev";
+
+const LOOKUP_TIMEOUT_SECS: u64 = 10;
+
+/// Definitions are cut to this many characters so they fit in a tooltip
+const MAX_DEFINITION_CHARS: usize = 200;
+
+/// A short explanation of a derived word, shown alongside its keyword
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordDefinition {
+    pub definition: String,
+    pub part_of_speech: Option<String>,
+    /// Etymology, when the dictionary has one
+    pub origin: Option<String>,
+    pub source: String,
+}
+
+/// Looks up words in the Free Dictionary API
+pub struct DefinitionClient {
+    client: reqwest::Client,
+    language: String,
+}
+
+impl DefinitionClient {
+    /// Client for words in `language`; None when the dictionary has no
+    /// entries in it
+    pub fn for_language(language: &str) -> Option<Self> {
+        DICTIONARY_LANGUAGES.contains(&language).then(|| Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(LOOKUP_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            language: language.to_string(),
+        })
+    }
+
+    fn url(&self, word: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            DICTIONARY_API_URL,
+            self.language,
+            word.to_lowercase()
+        )
+    }
+
+    /// Look up `word`; Ok(None) when the dictionary has no entry for it
+    ///
+    /// Network and server errors are returned as errors so the word is
+    /// tried again later rather than remembered as undefined.
+    pub async fn lookup(&self, word: &str) -> Result<Option<WordDefinition>> {
+        let response = self
+            .client
+            .get(self.url(word))
+            .send()
+            .await
+            .with_context(|| format!("Failed to look up `{}`", word))?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = response
+            .error_for_status()
+            .with_context(|| format!("Dictionary lookup for `{}` failed", word))?
+            .text()
+            .await?;
+        parse_entries(&body)
+    }
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    origin: Option<String>,
+    #[serde(default)]
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meaning {
+    part_of_speech: Option<String>,
+    #[serde(default)]
+    definitions: Vec<Sense>,
+}
+
+#[derive(Deserialize)]
+struct Sense {
+    definition: String,
+}
+
+/// Pick the first sense of the first meaning from an API response
+fn parse_entries(body: &str) -> Result<Option<WordDefinition>> {
+    let entries: Vec<Entry> =
+        serde_json::from_str(body).context("Unexpected dictionary response")?;
+
+    let origin = entries
+        .iter()
+        .filter_map(|entry| entry.origin.as_deref())
+        .map(str::trim)
+        .find(|origin| !origin.is_empty())
+        .map(truncate);
+
+    let definition = entries
+        .iter()
+        .flat_map(|entry| &entry.meanings)
+        .find_map(|meaning| {
+            meaning
+                .definitions
+                .iter()
+                .map(|sense| sense.definition.trim())
+                .find(|definition| !definition.is_empty())
+                .map(|definition| (meaning.part_of_speech.clone(), definition))
+        });
+
+    Ok(
+        definition.map(|(part_of_speech, definition)| WordDefinition {
+            definition: truncate(definition),
+            part_of_speech,
+            origin,
+            source: DICTIONARY_SOURCE.to_string(),
+        }),
+    )
+}
+
+/// Cut `text` at a word boundary so it is at most MAX_DEFINITION_CHARS long
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_DEFINITION_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_DEFINITION_CHARS - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => &cut,
+    };
+    format!("{}…", cut.trim_end_matches([',', ';', ' ']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        let body = r#"[
+            {
+                "word": "ember",
+                "origin": "Old English ǣmyrge",
+                "meanings": [
+                    { "partOfSpeech": "noun", "definitions": [
+                        { "definition": "A glowing piece of coal or wood from a fire." },
+                        { "definition": "A lingering feeling." }
+                    ] }
+                ]
+            },
+            {
+                "word": "ember",
+                "meanings": [
+                    { "partOfSpeech": "verb", "definitions": [{ "definition": "To glow." }] }
+                ]
+            }
+        ]"#;
+        assert_eq!(
+            parse_entries(body).unwrap(),
+            Some(WordDefinition {
+                definition: "A glowing piece of coal or wood from a fire.".to_string(),
+                part_of_speech: Some("noun".to_string()),
+                origin: Some("Old English ǣmyrge".to_string()),
+                source: "dictionaryapi.dev".to_string(),
+            })
+        );
+
+        // Entries without any sense have nothing to show
+        assert_eq!(
+            parse_entries(r#"[{ "word": "zyx", "meanings": [] }]"#).unwrap(),
+            None
+        );
+        assert!(parse_entries(r#"{ "title": "No Definitions Found" }"#).is_err());
+    }
+
+    #[test]
+    fn test_for_language() {
+        let client = DefinitionClient::for_language("en").unwrap();
+        assert_eq!(
+            client.url("Ember"),
+            "https://api.dictionaryapi.dev/api/v2/entries/en/ember"
+        );
+        assert!(DefinitionClient::for_language("ja").is_none());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short"), "short");
+
+        let long = "word, ".repeat(60);
+        let cut = truncate(&long);
+        assert!(cut.chars().count() <= MAX_DEFINITION_CHARS);
+        assert!(cut.ends_with("word…"), "{}", cut);
+    }
+}
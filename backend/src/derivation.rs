@@ -1,17 +1,52 @@
-use anyhow::Result;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 
 use crate::blockchain::BlockInfo;
-use crate::consts::BlockDataSource;
+use crate::consts::{
+    BlockDataSource, DEFAULT_SOURCE_WEIGHTS, DERIVATION_VERSION, MAX_BLOCKLIST_REHASHES,
+};
+use crate::error::{ChainVerseError, Result};
+use crate::provenance::DerivationProvenance;
+#[cfg(test)]
 use crate::words::WordDictionary;
+use crate::words::{Blocklist, DictionaryHandle};
 
 pub struct KeywordDerivation {
-    dictionary: WordDictionary,
+    dictionary: DictionaryHandle,
+    blocklist: Blocklist,
 }
 
 impl KeywordDerivation {
+    #[cfg(test)]
     pub fn new(dictionary: WordDictionary) -> Self {
-        Self { dictionary }
+        Self::with_handle(DictionaryHandle::from_dictionary(dictionary))
+    }
+
+    /// Derive from a shared dictionary so runtime reloads take effect
+    pub fn with_handle(dictionary: DictionaryHandle) -> Self {
+        Self {
+            dictionary,
+            blocklist: Blocklist::default(),
+        }
+    }
+
+    /// Never derive a blocked word; blocked picks are re-hashed (see `pick_word`)
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    /// Identify the algorithm version and word lists derivation currently uses
+    pub fn provenance(&self) -> DerivationProvenance {
+        let dictionary = self.dictionary.read();
+        DerivationProvenance {
+            version: DERIVATION_VERSION,
+            dictionary_sha256: dictionary.sha256(),
+            theme: dictionary.theme().map(str::to_string),
+            language: Some(dictionary.language.clone()),
+            blocklist_sha256: self.blocklist.sha256(),
+        }
     }
 
     /// Derive a keyword from block information using blockhash (default)
@@ -20,74 +55,201 @@ impl KeywordDerivation {
         self.derive_keyword_from_source(block, BlockDataSource::Blockhash)
     }
 
+    /// Like `derive_keyword`, but re-roll words in `recent` (the last few days'
+    /// keywords) the same way blocked words are; `rerolls` records how many
+    /// re-hashes it took
+    pub fn derive_keyword_avoiding(
+        &self,
+        block: &BlockInfo,
+        recent: &HashSet<String>,
+    ) -> Result<DerivedKeyword> {
+        self.derive_from_source(block, BlockDataSource::Blockhash, recent)
+    }
+
     /// Derive a keyword using a specific data source
     pub fn derive_keyword_from_source(
         &self,
         block: &BlockInfo,
         source: BlockDataSource,
+    ) -> Result<DerivedKeyword> {
+        self.derive_from_source(block, source, &HashSet::new())
+    }
+
+    fn derive_from_source(
+        &self,
+        block: &BlockInfo,
+        source: BlockDataSource,
+        avoid: &HashSet<String>,
     ) -> Result<DerivedKeyword> {
         let entropy = self.get_entropy_for_source(block, source);
         let seed = self.hash_to_seed(&entropy);
 
-        let word_count = self.dictionary.total_count();
-        let word_index = (seed % word_count as u64) as usize;
-
-        let all_words = self.dictionary.all_words();
-        let word = all_words
-            .get(word_index)
-            .ok_or_else(|| anyhow::anyhow!("Word index out of bounds"))?
-            .clone();
+        let dictionary = self.dictionary.read();
+        let (word_index, word, rerolls) = self.pick_word(&dictionary.all_words(), seed, avoid)?;
 
         Ok(DerivedKeyword {
             word,
             slot: block.slot,
             blockhash: block.blockhash.clone(),
+            previous_blockhash: block.previous_blockhash.clone(),
             block_time: block.block_time,
             word_index,
             source,
+            theme: dictionary.theme().map(str::to_string),
+            rerolls,
+            epoch: None,
         })
     }
 
-    /// Derive multiple keywords from a single block using different entropy sources
-    pub fn derive_multiple_keywords(&self, block: &BlockInfo) -> Vec<DerivedKeyword> {
-        let mut keywords = Vec::new();
+    /// The index and word derivation lands on from `block` after `rerolls`
+    /// re-hashes, drawn from `theme`'s dictionary (the base one for None)
+    ///
+    /// A stored keyword's `rerolls` records where `pick_word` stopped, so this
+    /// re-derives it without knowing the blocklist or the recent words it
+    /// avoided. None when the theme isn't loaded.
+    pub fn rederive(
+        &self,
+        block: &BlockInfo,
+        source: BlockDataSource,
+        theme: Option<&str>,
+        rerolls: u32,
+    ) -> Option<(usize, String)> {
+        let words = self.dictionary.words_for(theme)?;
+        if words.is_empty() {
+            return None;
+        }
+        let seed = self.hash_to_seed(&self.get_entropy_for_source(block, source));
+        let index = (self.rehash(seed, rerolls) % words.len() as u64) as usize;
+        Some((index, words[index].clone()))
+    }
 
-        // Use blockhash (primary)
-        if let Ok(kw) = self.derive_keyword_from_source(block, BlockDataSource::Blockhash) {
-            keywords.push(kw);
+    /// Derive the word for an arbitrary entropy string, e.g. a blockhash or a
+    /// transaction signature, recording every step along the way
+    ///
+    /// This is `derive_keyword` with the string standing in for the block's
+    /// entropy: a blockhash gives the word its block's blockhash keyword would
+    /// get. Blocked words are re-hashed past as usual; recent words aren't
+    /// avoided, since there's no day to avoid them for.
+    pub fn trace(&self, input: &str) -> Result<DerivationTrace> {
+        let digest = Sha256::digest(input.as_bytes());
+        let seed = self.hash_to_seed(input);
+
+        let dictionary = self.dictionary.read();
+        let words = dictionary.all_words();
+        let (word_index, word, rerolls) = self.pick_word(&words, seed, &HashSet::new())?;
+        let steps = (0..=rerolls)
+            .map(|counter| {
+                let current = self.rehash(seed, counter);
+                let index = (current % words.len() as u64) as usize;
+                TraceStep {
+                    seed: current.to_string(),
+                    word_index: index,
+                    word: words[index].clone(),
+                    blocked: counter < rerolls,
+                }
+            })
+            .collect();
+
+        Ok(DerivationTrace {
+            input: input.to_string(),
+            sha256: format!("{:x}", digest),
+            seed: seed.to_string(),
+            dictionary_size: words.len(),
+            dictionary_sha256: dictionary.sha256(),
+            language: dictionary.language.clone(),
+            theme: dictionary.theme().map(str::to_string),
+            version: DERIVATION_VERSION,
+            steps,
+            word,
+            word_index,
+            rerolls,
+        })
+    }
+
+    /// Derive up to `count` different words from one block, one per data
+    /// source in `BlockDataSource::all()` order (then `enhanced()` when the
+    /// block came from an enhanced RPC), re-rolling words in `recent`
+    ///
+    /// The first comes from the blockhash, so a count of 1 matches
+    /// `derive_keyword_avoiding`. Words already taken from the block are
+    /// re-rolled like recent ones, so each source adds a new word.
+    pub fn derive_keywords_avoiding(
+        &self,
+        block: &BlockInfo,
+        count: usize,
+        recent: &HashSet<String>,
+    ) -> Result<Vec<DerivedKeyword>> {
+        let enhanced: &[BlockDataSource] = match block.enhanced {
+            Some(_) => BlockDataSource::enhanced(),
+            None => &[],
+        };
+        let mut avoid = recent.clone();
+        let mut keywords = Vec::new();
+        for &source in BlockDataSource::all().iter().chain(enhanced).take(count) {
+            let keyword = self.derive_from_source(block, source, &avoid)?;
+            avoid.insert(keyword.word.clone());
+            keywords.push(keyword);
         }
+        Ok(keywords)
+    }
 
-        // Use previous blockhash for additional word
-        if let Ok(kw) = self.derive_keyword_from_source(block, BlockDataSource::PreviousBlockhash) {
-            // Only add if different from first word
-            if keywords.is_empty() || keywords[0].word != kw.word {
-                keywords.push(kw);
-            }
+    /// Derive up to `count` words from one block for a day that already has
+    /// `taken` keywords from each source, adding the kept ones to `taken`
+    ///
+    /// Every source derives a word and `weights` picks which to keep (see
+    /// `SourceWeights::compose`), so a day's composition follows the weights
+    /// from its first block on, not only once the day is nearly full.
+    pub fn derive_weighted(
+        &self,
+        block: &BlockInfo,
+        count: usize,
+        recent: &HashSet<String>,
+        weights: &SourceWeights,
+        taken: &mut HashMap<BlockDataSource, usize>,
+    ) -> Result<Vec<DerivedKeyword>> {
+        let candidates = self.derive_keywords_avoiding(block, usize::MAX, recent)?;
+        Ok(weights.compose(candidates, taken, count))
+    }
+
+    /// Map a seed to a word, skipping blocked words and words in `avoid`;
+    /// returns the index, the word, and the counter it was found at
+    ///
+    /// A skipped pick is re-hashed as `sha256("{seed}:{counter}")` with the
+    /// counter starting at 1, so the replacement is as deterministic as the
+    /// original word. If every try lands on a blocked or avoided word, the
+    /// first allowed word is used even though it repeats.
+    fn pick_word(
+        &self,
+        words: &[String],
+        seed: u64,
+        avoid: &HashSet<String>,
+    ) -> Result<(usize, String, u32)> {
+        if words.is_empty() {
+            return Err(ChainVerseError::Derivation(
+                "Dictionary contains no words".to_string(),
+            ));
         }
 
-        // Use transaction signatures for more variety
-        for (i, sig) in block.sample_signatures.iter().take(3).enumerate() {
-            let entropy = format!("{}:{}", sig, i);
-            let seed = self.hash_to_seed(&entropy);
-            let word_count = self.dictionary.total_count();
-            let word_index = (seed % word_count as u64) as usize;
-
-            if let Some(word) = self.dictionary.all_words().get(word_index) {
-                // Only add if unique
-                if !keywords.iter().any(|k| k.word == *word) {
-                    keywords.push(DerivedKeyword {
-                        word: word.clone(),
-                        slot: block.slot,
-                        blockhash: block.blockhash.clone(),
-                        block_time: block.block_time,
-                        word_index,
-                        source: BlockDataSource::TransactionRoot,
-                    });
-                }
+        let mut first_allowed = None;
+        for counter in 0..=MAX_BLOCKLIST_REHASHES {
+            let current = self.rehash(seed, counter);
+            let index = (current % words.len() as u64) as usize;
+            if self.blocklist.is_blocked(&words[index]) {
+                continue;
             }
+            if !avoid.contains(&words[index]) {
+                return Ok((index, words[index].clone(), counter));
+            }
+            first_allowed.get_or_insert((index, counter));
         }
 
-        keywords
+        if let Some((index, counter)) = first_allowed {
+            return Ok((index, words[index].clone(), counter));
+        }
+        Err(ChainVerseError::Derivation(format!(
+            "No allowed word after {} re-hashes; does the blocklist cover the dictionary?",
+            MAX_BLOCKLIST_REHASHES
+        )))
     }
 
     /// Get entropy string for a specific data source
@@ -106,6 +268,50 @@ impl KeywordDerivation {
             BlockDataSource::TransactionCount => {
                 format!("txcount:{}:{}", block.transaction_count, block.slot)
             }
+            BlockDataSource::TotalFees => format!("fees:{}:{}", block.total_fees, block.slot),
+            BlockDataSource::VoteCounts => format!(
+                "votes:{}:{}:{}",
+                block.vote_transaction_count, block.non_vote_transaction_count, block.slot
+            ),
+            BlockDataSource::Leader => {
+                // A leader produces several slots in a row, so the slot keeps words apart
+                let leader = block.leader.as_deref().unwrap_or("");
+                format!("leader:{}:{}", leader, block.slot)
+            }
+            // Blocks fetched without an enhanced RPC read as empty
+            BlockDataSource::ProgramInvocations => {
+                let programs: Vec<String> = block
+                    .enhanced
+                    .iter()
+                    .flat_map(|enhanced| &enhanced.program_invocations)
+                    .map(|program| format!("{}={}", program.program_id, program.count))
+                    .collect();
+                format!("programs:{}:{}", programs.join(","), block.slot)
+            }
+            BlockDataSource::NftMints => {
+                let mints = block
+                    .enhanced
+                    .as_ref()
+                    .map(|enhanced| enhanced.nft_mints.join(","))
+                    .unwrap_or_default();
+                format!("nft_mints:{}:{}", mints, block.slot)
+            }
+            BlockDataSource::TokenTransfers => {
+                let transfers = block
+                    .enhanced
+                    .as_ref()
+                    .map_or(0, |enhanced| enhanced.token_transfers);
+                format!("token_transfers:{}:{}", transfers, block.slot)
+            }
+        }
+    }
+
+    /// The seed after `counter` re-hashes (see `pick_word`)
+    fn rehash(&self, seed: u64, counter: u32) -> u64 {
+        if counter == 0 {
+            seed
+        } else {
+            self.hash_to_seed(&format!("{}:{}", seed, counter))
         }
     }
 
@@ -119,55 +325,126 @@ impl KeywordDerivation {
         bytes.copy_from_slice(&result[0..8]);
         u64::from_le_bytes(bytes)
     }
+}
+
+/// How much of a day's keywords each entropy source should supply
+/// (`[derivation.source_weights]`)
+///
+/// Sources share the day in proportion to their weights. A source weighing 0
+/// is filler: its words are kept only when no weighted word is left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceWeights {
+    weights: HashMap<BlockDataSource, f64>,
+}
+
+impl Default for SourceWeights {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_SOURCE_WEIGHTS.iter().copied().collect(),
+        }
+    }
+}
+
+impl SourceWeights {
+    /// Override the weight of `source`
+    pub fn with_weight(mut self, source: BlockDataSource, weight: f64) -> Self {
+        self.weights.insert(source, weight);
+        self
+    }
 
-    /// Derive keywords from multiple blocks for batch processing
-    pub fn derive_keywords_from_blocks(&self, blocks: &[BlockInfo]) -> Vec<DerivedKeyword> {
-        let mut all_keywords = Vec::new();
-        let mut seen_words = std::collections::HashSet::new();
+    /// Weight of `source`; 1 unless set
+    pub fn weight(&self, source: BlockDataSource) -> f64 {
+        self.weights.get(&source).copied().unwrap_or(1.0)
+    }
 
-        for block in blocks {
-            let keywords = self.derive_multiple_keywords(block);
-            for kw in keywords {
-                if seen_words.insert(kw.word.clone()) {
-                    all_keywords.push(kw);
+    /// Keep up to `limit` of `candidates` for a day that already has `taken`
+    /// keywords from each source, adding the kept ones to `taken`
+    ///
+    /// Each pick is the word whose source is furthest below its share, the
+    /// highest `weight / (taken + 1)`, so the day is apportioned like seats
+    /// under D'Hondt. Ties go to the earlier candidate, so equal weights keep
+    /// candidate order.
+    pub fn compose(
+        &self,
+        mut candidates: Vec<DerivedKeyword>,
+        taken: &mut HashMap<BlockDataSource, usize>,
+        limit: usize,
+    ) -> Vec<DerivedKeyword> {
+        let mut kept = Vec::new();
+        while kept.len() < limit && !candidates.is_empty() {
+            let mut best = 0;
+            let mut best_priority = f64::NEG_INFINITY;
+            for (i, candidate) in candidates.iter().enumerate() {
+                let weight = self.weight(candidate.source);
+                // Filler comes after every weighted word, in candidate order
+                let priority = if weight > 0.0 {
+                    weight / (taken.get(&candidate.source).copied().unwrap_or(0) + 1) as f64
+                } else {
+                    0.0
+                };
+                if priority > best_priority {
+                    best = i;
+                    best_priority = priority;
                 }
             }
+            let keyword = candidates.remove(best);
+            *taken.entry(keyword.source).or_default() += 1;
+            kept.push(keyword);
         }
-
-        all_keywords
+        kept
     }
 }
 
+/// How an input string became a word (see `KeywordDerivation::trace`)
+///
+/// Seeds are decimal strings: they use all 64 bits, more than a JSON number
+/// keeps exactly in JavaScript.
+#[derive(Debug, Clone, Serialize)]
+pub struct DerivationTrace {
+    pub input: String,
+    /// Hex SHA-256 of the input; its first 8 bytes, little-endian, are the seed
+    pub sha256: String,
+    pub seed: String,
+    pub dictionary_size: usize,
+    pub dictionary_sha256: String,
+    pub language: String,
+    /// Active dictionary theme; None for the language's base dictionary
+    pub theme: Option<String>,
+    /// DERIVATION_VERSION of this binary
+    pub version: u32,
+    /// Every pick in order; all but the last landed on a blocked word
+    pub steps: Vec<TraceStep>,
+    pub word: String,
+    pub word_index: usize,
+    pub rerolls: u32,
+}
+
+/// One pick: `seed % dictionary_size` is the index; re-hashes use
+/// `sha256("{seed}:{rerolls}")` of the original seed
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceStep {
+    pub seed: String,
+    pub word_index: usize,
+    pub word: String,
+    pub blocked: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct DerivedKeyword {
     pub word: String,
     pub slot: u64,
     pub blockhash: String,
+    pub previous_blockhash: String,
     pub block_time: Option<i64>,
     pub word_index: usize,
     pub source: BlockDataSource,
-}
-
-impl DerivedKeyword {
-    /// Get a human-readable timestamp
-    pub fn formatted_time(&self) -> Option<String> {
-        self.block_time.map(|ts| {
-            let dt = chrono::DateTime::from_timestamp(ts, 0)
-                .expect("Invalid timestamp");
-            dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
-        })
-    }
-
-    /// Get the data source as a string
-    pub fn source_name(&self) -> &'static str {
-        match self.source {
-            BlockDataSource::Blockhash => "blockhash",
-            BlockDataSource::PreviousBlockhash => "previous_blockhash",
-            BlockDataSource::TransactionRoot => "transaction",
-            BlockDataSource::Rewards => "rewards",
-            BlockDataSource::TransactionCount => "tx_count",
-        }
-    }
+    /// Dictionary theme the word was drawn from; None for the base dictionary
+    pub theme: Option<String>,
+    /// Re-hashes needed to skip blocked or recently used words (see `pick_word`)
+    pub rerolls: u32,
+    /// Solana epoch of the slot; derivation leaves it None and the collector
+    /// fills it in from getEpochInfo
+    pub epoch: Option<u64>,
 }
 
 #[cfg(test)]
@@ -188,6 +465,11 @@ mod tests {
                 "sig2".to_string(),
                 "sig3".to_string(),
             ],
+            total_fees: 250_000,
+            vote_transaction_count: 40,
+            non_vote_transaction_count: 10,
+            leader: Some("leader1".to_string()),
+            enhanced: None,
         }
     }
 
@@ -227,14 +509,16 @@ mod tests {
         let derivation = KeywordDerivation::new(dict);
 
         let block = create_test_block();
-        let keywords = derivation.derive_multiple_keywords(&block);
+        let keywords = derivation
+            .derive_keywords_avoiding(&block, usize::MAX, &HashSet::new())
+            .unwrap();
 
         println!("Derived {} keywords from single block:", keywords.len());
         for kw in &keywords {
-            println!("  {} (from {})", kw.word, kw.source_name());
+            println!("  {} (from {})", kw.word, kw.source.as_str());
         }
 
-        assert!(keywords.len() >= 1);
+        assert!(!keywords.is_empty());
     }
 
     #[test]
@@ -251,6 +535,11 @@ mod tests {
             parent_slot: 12344,
             transaction_count: 50,
             sample_signatures: vec![],
+            total_fees: 0,
+            vote_transaction_count: 0,
+            non_vote_transaction_count: 0,
+            leader: None,
+            enhanced: None,
         };
 
         let block2 = BlockInfo {
@@ -262,6 +551,11 @@ mod tests {
             parent_slot: 12345,
             transaction_count: 45,
             sample_signatures: vec![],
+            total_fees: 0,
+            vote_transaction_count: 0,
+            non_vote_transaction_count: 0,
+            leader: None,
+            enhanced: None,
         };
 
         let keyword1 = derivation.derive_keyword(&block1).unwrap();
@@ -270,4 +564,247 @@ mod tests {
         println!("Block 1 -> {}", keyword1.word);
         println!("Block 2 -> {}", keyword2.word);
     }
+
+    #[test]
+    fn test_block_stat_sources() {
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let block = create_test_block();
+
+        for &source in &[
+            BlockDataSource::TotalFees,
+            BlockDataSource::VoteCounts,
+            BlockDataSource::Leader,
+        ] {
+            let keyword = derivation.derive_keyword_from_source(&block, source);
+            assert_eq!(keyword.unwrap().source, source);
+        }
+
+        // Each source draws only on its own field
+        let mut other = create_test_block();
+        other.total_fees += 5000;
+        assert_ne!(
+            derivation.get_entropy_for_source(&block, BlockDataSource::TotalFees),
+            derivation.get_entropy_for_source(&other, BlockDataSource::TotalFees)
+        );
+        assert_eq!(
+            derivation.get_entropy_for_source(&block, BlockDataSource::Leader),
+            derivation.get_entropy_for_source(&other, BlockDataSource::Leader)
+        );
+        other.leader = None;
+        assert_ne!(
+            derivation.get_entropy_for_source(&block, BlockDataSource::Leader),
+            derivation.get_entropy_for_source(&other, BlockDataSource::Leader)
+        );
+    }
+
+    #[test]
+    fn test_blocklist_rehashes_deterministically() {
+        let block = create_test_block();
+        let original = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .derive_keyword(&block)
+            .unwrap();
+
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .with_blocklist(Blocklist::parse(&original.word));
+        let replaced = derivation.derive_keyword(&block).unwrap();
+        assert_ne!(replaced.word, original.word);
+        assert_eq!(replaced.word, derivation.derive_keyword(&block).unwrap().word);
+        assert_eq!(
+            WordDictionary::load().unwrap().get_word(replaced.word_index),
+            Some(replaced.word)
+        );
+
+        // A blocklist covering the whole dictionary fails instead of looping
+        let everything = WordDictionary::load().unwrap().all_words().join("\n");
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .with_blocklist(Blocklist::parse(&everything));
+        assert!(derivation.derive_keyword(&block).is_err());
+    }
+
+    #[test]
+    fn test_trace() {
+        let block = create_test_block();
+        let original = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .derive_keyword(&block)
+            .unwrap();
+
+        // A blockhash traces to its block's blockhash keyword
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let trace = derivation.trace(&block.blockhash).unwrap();
+        assert_eq!(
+            (trace.word.as_str(), trace.word_index, trace.rerolls),
+            (original.word.as_str(), original.word_index, 0)
+        );
+        assert_eq!(
+            trace.seed,
+            derivation.hash_to_seed(&block.blockhash).to_string()
+        );
+        assert_eq!(trace.dictionary_size, 2048);
+        assert_eq!(trace.steps.len(), 1);
+
+        // Blocked picks show up as steps before the word
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .with_blocklist(Blocklist::parse(&original.word));
+        let trace = derivation.trace(&block.blockhash).unwrap();
+        assert_eq!(trace.word, derivation.derive_keyword(&block).unwrap().word);
+        assert_eq!(trace.steps.len() as u32, trace.rerolls + 1);
+        assert_eq!(trace.steps[0].word, original.word);
+        assert!(trace.steps[0].blocked);
+        assert!(!trace.steps.last().unwrap().blocked);
+    }
+
+    #[test]
+    fn test_recent_words_rerolled() {
+        let block = create_test_block();
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let original = derivation.derive_keyword(&block).unwrap();
+        assert_eq!(original.rerolls, 0);
+
+        let recent = HashSet::from([original.word.clone()]);
+        let rerolled = derivation.derive_keyword_avoiding(&block, &recent).unwrap();
+        assert_ne!(rerolled.word, original.word);
+        assert!(rerolled.rerolls >= 1);
+
+        // The recorded count is enough to recompute the word from the block
+        let seed = derivation.hash_to_seed(&block.blockhash);
+        let chained = derivation.hash_to_seed(&format!("{}:{}", seed, rerolled.rerolls));
+        let words = WordDictionary::load().unwrap().all_words();
+        assert_eq!(words[(chained % words.len() as u64) as usize], rerolled.word);
+        assert_eq!(
+            derivation.rederive(&block, BlockDataSource::Blockhash, None, rerolled.rerolls),
+            Some((rerolled.word_index, rerolled.word.clone()))
+        );
+        assert_eq!(
+            derivation.rederive(&block, BlockDataSource::Blockhash, Some("ocean"), 0),
+            None
+        );
+
+        // A window covering the whole dictionary falls back to the first allowed word
+        let everything: HashSet<String> = words.into_iter().collect();
+        let fallback = derivation.derive_keyword_avoiding(&block, &everything).unwrap();
+        assert_eq!((fallback.word, fallback.rerolls), (original.word, 0));
+    }
+
+    #[test]
+    fn test_derive_keywords_avoiding() {
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let block = create_test_block();
+        let first = derivation.derive_keyword(&block).unwrap();
+
+        let keywords = derivation
+            .derive_keywords_avoiding(&block, 4, &HashSet::new())
+            .unwrap();
+        assert_eq!(keywords.len(), 4);
+        assert_eq!(keywords[0].word, first.word);
+        let sources: Vec<BlockDataSource> = keywords.iter().map(|k| k.source).collect();
+        assert_eq!(sources, BlockDataSource::all()[..4]);
+        let words: HashSet<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+        assert_eq!(words.len(), 4);
+
+        // Never more than one word per source
+        let all = derivation
+            .derive_keywords_avoiding(&block, 100, &HashSet::new())
+            .unwrap();
+        assert_eq!(all.len(), BlockDataSource::all().len());
+
+        let recent = HashSet::from([first.word.clone()]);
+        let avoiding = derivation
+            .derive_keywords_avoiding(&block, 1, &recent)
+            .unwrap();
+        assert_ne!(avoiding[0].word, first.word);
+    }
+
+    #[test]
+    fn test_compose_by_source_weights() {
+        use BlockDataSource::{Blockhash, PreviousBlockhash, TransactionCount, TransactionRoot};
+
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        // Three blocks, each giving blockhash, previous blockhash, and
+        // transaction root words, then a transaction count word
+        let candidates: Vec<DerivedKeyword> = (0..3)
+            .flat_map(|i| {
+                let mut block = create_test_block();
+                block.slot += i;
+                block.blockhash = format!("hash_{}", i);
+                derivation
+                    .derive_keywords_avoiding(&block, 4, &HashSet::new())
+                    .unwrap()
+            })
+            .collect();
+        let sources = |keywords: &[DerivedKeyword]| -> Vec<BlockDataSource> {
+            keywords.iter().map(|k| k.source).collect()
+        };
+
+        // Blockhash (weight 3) gets three of the first five picks
+        let weights = SourceWeights::default();
+        let mut taken = HashMap::new();
+        let kept = weights.compose(candidates.clone(), &mut taken, 5);
+        assert_eq!(
+            sources(&kept),
+            vec![
+                Blockhash,
+                Blockhash,
+                PreviousBlockhash,
+                TransactionRoot,
+                Blockhash
+            ]
+        );
+        assert_eq!(taken.get(&Blockhash), Some(&3));
+        assert_eq!(taken.get(&TransactionCount), None);
+        // Ties keep candidate order: block 0's previous blockhash word comes first
+        assert_eq!(kept[2].slot, candidates[1].slot);
+
+        // Words already stored today count against their source's share
+        let mut taken = HashMap::from([(Blockhash, 6)]);
+        let kept = weights.compose(candidates.clone(), &mut taken, 2);
+        assert!(kept.iter().all(|k| k.source != Blockhash));
+
+        // Filler is only kept once every weighted word is
+        let mut taken = HashMap::new();
+        let kept = weights.compose(candidates.clone(), &mut taken, 12);
+        assert_eq!(sources(&kept[9..]), vec![TransactionCount; 3]);
+        assert_eq!(
+            weights
+                .compose(candidates.clone(), &mut HashMap::new(), 9)
+                .len(),
+            9
+        );
+
+        // Equal weights keep candidate order
+        let equal = SourceWeights::default()
+            .with_weight(Blockhash, 1.0)
+            .with_weight(TransactionCount, 1.0);
+        let kept = equal.compose(candidates.clone(), &mut HashMap::new(), 4);
+        assert_eq!(sources(&kept), sources(&candidates[..4]));
+    }
+
+    #[test]
+    fn test_derive_weighted() {
+        use BlockDataSource::{Blockhash, TransactionCount};
+
+        let derivation = KeywordDerivation::new(WordDictionary::load().unwrap());
+        let weights = SourceWeights::default();
+
+        // A day collected one and two words at a time never takes filler
+        for per_block in [1, 2] {
+            let mut taken = HashMap::new();
+            let mut kept = Vec::new();
+            for i in 0..24 {
+                let mut block = create_test_block();
+                block.slot += i;
+                block.blockhash = format!("hash_{}", i);
+                kept.extend(
+                    derivation
+                        .derive_weighted(&block, per_block, &HashSet::new(), &weights, &mut taken)
+                        .unwrap(),
+                );
+            }
+            assert_eq!(kept.len(), 24 * per_block);
+            assert!(kept.iter().all(|k| k.source != TransactionCount));
+            assert_eq!(taken.get(&TransactionCount), None);
+            // Blockhash keeps its larger share all day
+            let blockhash = taken[&Blockhash];
+            assert!(taken.values().all(|&count| count <= blockhash));
+        }
+    }
 }
@@ -0,0 +1,149 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// OpenRouter's OpenAI-compatible embeddings endpoint
+const OPENROUTER_EMBEDDINGS_URL: &str = "https://openrouter.ai/api/v1/embeddings";
+
+const EMBEDDING_TIMEOUT_SECS: u64 = 30;
+
+/// Turns poems into vectors with an OpenRouter embedding model, so poems
+/// can be compared by theme rather than by shared words
+pub struct EmbeddingClient {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingClient {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(EMBEDDING_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            api_key,
+            model,
+        }
+    }
+
+    /// Model the vectors come from; vectors from different models can't be compared
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Embed `text` as a single vector
+    pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let response: EmbeddingResponse = self
+            .client
+            .post(OPENROUTER_EMBEDDINGS_URL)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&EmbeddingRequest {
+                model: &self.model,
+                input: text,
+            })
+            .send()
+            .await
+            .context("Failed to reach OpenRouter embeddings")?
+            .error_for_status()
+            .context("OpenRouter rejected the embedding request")?
+            .json()
+            .await
+            .context("Unexpected OpenRouter embeddings response")?;
+
+        let vector = response
+            .data
+            .into_iter()
+            .next()
+            .map(|data| data.embedding)
+            .unwrap_or_default();
+        if vector.is_empty() {
+            anyhow::bail!("OpenRouter returned no embedding");
+        }
+        Ok(vector)
+    }
+}
+
+/// What is embedded for a poem: its title, then its text
+pub fn poem_text(title: Option<&str>, content: &str) -> String {
+    match title {
+        Some(title) => format!("{}\n\n{}", title, content.trim()),
+        None => content.trim().to_string(),
+    }
+}
+
+/// Cosine similarity from -1 to 1; 0 when the lengths differ or a vector is all zeros
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// A vector as little-endian f32s, the way `poem_embeddings` stores it
+pub fn to_bytes(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+/// Read back a vector written by `to_bytes`
+pub fn from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]) + 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let vector = vec![0.25, -1.5, 3.0e-7, f32::MAX];
+        let bytes = to_bytes(&vector);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(from_bytes(&bytes), vector);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body = r#"{"object":"list","data":[{"object":"embedding","index":0,"embedding":[0.5,-0.25]}],"model":"openai/text-embedding-3-small"}"#;
+        let response: EmbeddingResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.data[0].embedding, vec![0.5, -0.25]);
+        assert_eq!(poem_text(Some("Tide"), "rain\n"), "Tide\n\nrain");
+    }
+}
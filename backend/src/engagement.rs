@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time;
+use tracing::warn;
+
+use crate::consts::{
+    POEM_REACTIONS, REACTIONS_PER_MINUTE, REACTION_BURST, VIEW_FLUSH_INTERVAL_SECS,
+};
+use crate::database::{Database, PoemEngagement, StoredPoem};
+use crate::export;
+use crate::rate_limit::{Quota, RateLimiter};
+
+/// A stored poem as the API returns it, with its views and reactions
+#[derive(Debug, Clone, Serialize)]
+pub struct EngagedPoem {
+    #[serde(flatten)]
+    pub poem: StoredPoem,
+    #[serde(flatten)]
+    pub engagement: PoemEngagement,
+}
+
+/// Attach each poem's counts from `engagement`, keyed by date; poems without
+/// an entry get zero
+pub fn engage_poems(
+    poems: Vec<StoredPoem>,
+    engagement: &HashMap<String, PoemEngagement>,
+) -> Vec<EngagedPoem> {
+    poems
+        .into_iter()
+        .map(|poem| EngagedPoem {
+            engagement: engagement.get(&poem.date).cloned().unwrap_or_default(),
+            poem,
+        })
+        .collect()
+}
+
+/// The reaction `emoji` names, as stored; None if it isn't one of POEM_REACTIONS
+///
+/// Emoji match with or without the variation selector some keyboards leave
+/// off, so `❤` counts as `❤️`.
+pub fn reaction(emoji: &str) -> Option<&'static str> {
+    let bare = |emoji: &str| emoji.trim().trim_end_matches('\u{FE0F}').to_string();
+    POEM_REACTIONS
+        .iter()
+        .copied()
+        .find(|&known| bare(known) == bare(emoji))
+}
+
+/// Per-IP limiter for POST /api/poems/{date}/react, separate from the
+/// API-wide one so reading stays cheap while reacting is scarce
+pub fn reaction_limiter(trust_forwarded_for: bool) -> RateLimiter {
+    let quota = Quota {
+        per_second: REACTIONS_PER_MINUTE / 60.0,
+        burst: REACTION_BURST,
+    };
+    // With no API keys, key holders are limited by IP like everyone else
+    RateLimiter::new(quota, quota, Arc::default()).with_trust_forwarded_for(trust_forwarded_for)
+}
+
+/// Poem views counted in memory and written to the database every
+/// VIEW_FLUSH_INTERVAL_SECS, so reading a poem never waits on a write
+pub struct ViewCounter {
+    db: Arc<Database>,
+    /// Views per date not written yet
+    pending: DashMap<String, i64>,
+}
+
+impl ViewCounter {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            db,
+            pending: DashMap::new(),
+        }
+    }
+
+    /// Count one read of `date`'s poem; a read-only replica can't store views,
+    /// so it doesn't count them
+    pub fn record(&self, date: &str) {
+        if !self.db.is_read_only() {
+            *self.pending.entry(date.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Views of `date`'s poem counted but not written yet
+    pub fn pending(&self, date: &str) -> i64 {
+        self.pending.get(date).map_or(0, |views| *views)
+    }
+
+    /// Write the views counted so far; a date whose write fails keeps its
+    /// views for the next flush
+    pub async fn flush(&self) {
+        let dates: Vec<String> = self
+            .pending
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+        for date in dates {
+            let Some((date, views)) = self.pending.remove(&date) else {
+                continue;
+            };
+            if let Err(e) = self.db.add_poem_views(&date, views).await {
+                warn!(%date, error = %e, "Failed to store poem views");
+                *self.pending.entry(date).or_default() += views;
+            }
+        }
+    }
+
+    /// Flush every VIEW_FLUSH_INTERVAL_SECS until `shutdown` flips to true,
+    /// then once more
+    pub async fn run(&self, mut shutdown: watch::Receiver<bool>) {
+        let mut flushes = time::interval(Duration::from_secs(VIEW_FLUSH_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = flushes.tick() => self.flush().await,
+                _ = async { shutdown.wait_for(|&stop| stop).await.is_ok() } => break,
+            }
+        }
+        self.flush().await;
+    }
+}
+
+/// Middleware: count a view of the day's poem for each successful read of
+/// /api/poems/{date} or one of its exports
+pub async fn count_views(
+    State(views): State<Arc<ViewCounter>>,
+    Path(date): Path<String>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+
+    let status = response.status();
+    if status.is_success() || status == StatusCode::NOT_MODIFIED {
+        let date = export::split_export_path(&date).map_or(date.as_str(), |(date, _)| date);
+        views.record(date);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_view_counter() {
+        let path =
+            std::env::temp_dir().join(format!("chain_verse_views_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = Database::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        let views = ViewCounter::new(Arc::new(db.clone()));
+
+        views.record("2026-01-05");
+        views.record("2026-01-05");
+        views.record("2026-01-06");
+        assert_eq!(views.pending("2026-01-05"), 2);
+        // Nothing is written until a flush
+        assert_eq!(db.get_poem_engagement("2026-01-05").await.unwrap().views, 0);
+
+        views.flush().await;
+        assert_eq!(views.pending("2026-01-05"), 0);
+        views.record("2026-01-05");
+        views.flush().await;
+        assert_eq!(db.get_poem_engagement("2026-01-05").await.unwrap().views, 3);
+        assert_eq!(db.get_poem_engagement("2026-01-06").await.unwrap().views, 1);
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_reaction() {
+        assert_eq!(reaction("🔥"), Some("🔥"));
+        assert_eq!(reaction(" ✨ "), Some("✨"));
+        assert_eq!(reaction("❤"), Some("❤️"));
+        assert_eq!(reaction("❤️"), Some("❤️"));
+        assert_eq!(reaction("👍"), None);
+        assert_eq!(reaction(""), None);
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+#[cfg(feature = "enhanced-rpc")]
+use crate::consts::VOTE_PROGRAM_ID;
+
+/// Programs kept per block in `EnhancedBlockData::program_invocations`
+#[cfg(feature = "enhanced-rpc")]
+const TOP_PROGRAMS: usize = 10;
+
+/// What a block's parsed transactions add to `BlockInfo` in enhanced-RPC mode
+/// (`solana.enhanced_rpc`)
+///
+/// The block is fetched with every transaction in `jsonParsed` form, several
+/// megabytes per mainnet block: more than public endpoints will serve on a
+/// schedule, so the mode is meant for Helius or Triton endpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnhancedBlockData {
+    /// Programs the block's non-vote transactions invoked most, inner
+    /// instructions included, most-invoked first
+    pub program_invocations: Vec<ProgramInvocations>,
+    /// Mints that received a single token (`mintTo` of 1 raw unit), i.e. new
+    /// NFTs; compressed NFTs aren't counted
+    pub nft_mints: Vec<String>,
+    /// SPL token `transfer` and `transferChecked` instructions
+    pub token_transfers: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramInvocations {
+    pub program_id: String,
+    pub count: usize,
+}
+
+/// Read program invocations, NFT mints, and token transfers out of a block
+/// fetched with `UiTransactionEncoding::JsonParsed` and full transaction details
+#[cfg(feature = "enhanced-rpc")]
+pub fn analyze(
+    transactions: &[EncodedTransactionWithStatusMeta],
+) -> anyhow::Result<EnhancedBlockData> {
+    use solana_transaction_status::{
+        EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction,
+    };
+    use std::collections::HashMap;
+
+    let mut data = EnhancedBlockData::default();
+    let mut invocations: HashMap<&str, usize> = HashMap::new();
+    for transaction in transactions {
+        let EncodedTransaction::Json(ui) = &transaction.transaction else {
+            anyhow::bail!("The RPC returned a block without parsed transactions");
+        };
+        let UiMessage::Parsed(message) = &ui.message else {
+            continue;
+        };
+        if message
+            .account_keys
+            .iter()
+            .any(|account| account.pubkey == VOTE_PROGRAM_ID)
+        {
+            continue;
+        }
+
+        let inner = transaction
+            .meta
+            .as_ref()
+            .map_or(&[][..], |meta| {
+                meta.inner_instructions
+                    .as_ref()
+                    .map_or(&[][..], |inner| &inner[..])
+            })
+            .iter()
+            .flat_map(|inner| &inner.instructions);
+        for instruction in message.instructions.iter().chain(inner) {
+            match instruction {
+                UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => {
+                    *invocations.entry(&parsed.program_id).or_default() += 1;
+                    if parsed.program.starts_with("spl-token") {
+                        tally_token_instruction(&parsed.parsed, &mut data);
+                    }
+                }
+                UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(decoded)) => {
+                    *invocations.entry(&decoded.program_id).or_default() += 1;
+                }
+                // jsonParsed always names the program; compiled ones only index it
+                UiInstruction::Compiled(_) => {}
+            }
+        }
+    }
+
+    let mut programs: Vec<ProgramInvocations> = invocations
+        .into_iter()
+        .map(|(program_id, count)| ProgramInvocations {
+            program_id: program_id.to_string(),
+            count,
+        })
+        .collect();
+    programs.sort_by(|a, b| b.count.cmp(&a.count).then(a.program_id.cmp(&b.program_id)));
+    programs.truncate(TOP_PROGRAMS);
+    data.program_invocations = programs;
+    Ok(data)
+}
+
+#[cfg(not(feature = "enhanced-rpc"))]
+pub fn analyze(
+    _transactions: &[EncodedTransactionWithStatusMeta],
+) -> anyhow::Result<EnhancedBlockData> {
+    anyhow::bail!("Built without the enhanced-rpc feature")
+}
+
+/// Count a parsed SPL token instruction (`{"type": ..., "info": {...}}`)
+#[cfg(feature = "enhanced-rpc")]
+fn tally_token_instruction(parsed: &serde_json::Value, data: &mut EnhancedBlockData) {
+    let info = &parsed["info"];
+    let amount = match parsed["type"].as_str() {
+        Some("transfer" | "transferChecked") => {
+            data.token_transfers += 1;
+            return;
+        }
+        Some("mintTo") => info["amount"].as_str(),
+        Some("mintToChecked") if info["tokenAmount"]["decimals"] == 0 => {
+            info["tokenAmount"]["amount"].as_str()
+        }
+        _ => return,
+    };
+    if amount == Some("1") {
+        if let Some(mint) = info["mint"].as_str() {
+            data.nft_mints.push(mint.to_string());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "enhanced-rpc"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transaction(account_keys: &[&str], instructions: serde_json::Value) -> serde_json::Value {
+        let keys: Vec<serde_json::Value> = account_keys
+            .iter()
+            .map(|key| json!({"pubkey": key, "writable": false, "signer": false, "source": "transaction"}))
+            .collect();
+        json!({
+            "transaction": {
+                "signatures": ["sig"],
+                "message": {
+                    "accountKeys": keys,
+                    "recentBlockhash": "hash",
+                    "instructions": instructions
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [],
+                "postBalances": [],
+                "innerInstructions": [{
+                    "index": 0,
+                    "instructions": [{
+                        "program": "spl-token",
+                        "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                        "parsed": {"type": "transfer", "info": {"amount": "40"}},
+                        "stackHeight": 2
+                    }]
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_analyze() {
+        let transactions: Vec<EncodedTransactionWithStatusMeta> = serde_json::from_value(json!([
+            transaction(
+                &["payer"],
+                json!([
+                    {"programId": "JUP6", "accounts": [], "data": "", "stackHeight": null},
+                    {
+                        "program": "spl-token",
+                        "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                        "parsed": {"type": "mintTo", "info": {"mint": "nft1", "amount": "1"}},
+                        "stackHeight": null
+                    }
+                ])
+            ),
+            transaction(
+                &["validator", VOTE_PROGRAM_ID],
+                json!([{"programId": VOTE_PROGRAM_ID, "accounts": [], "data": "", "stackHeight": null}])
+            )
+        ]))
+        .unwrap();
+
+        let data = analyze(&transactions).unwrap();
+        assert_eq!(
+            data.program_invocations,
+            vec![
+                ProgramInvocations {
+                    program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                    count: 2,
+                },
+                ProgramInvocations {
+                    program_id: "JUP6".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+        assert_eq!(data.nft_mints, vec!["nft1"]);
+        // The vote transaction's inner transfer isn't counted
+        assert_eq!(data.token_transfers, 1);
+    }
+}
@@ -0,0 +1,95 @@
+use thiserror::Error;
+
+use crate::circuit_breaker::CircuitOpen;
+
+/// Errors from the blockchain, database, derivation, and poem generator modules,
+/// classed by what failed
+///
+/// Callers that only log or propagate can keep using `anyhow` (`?` converts);
+/// the API uses the class to pick a status code. Provider variants keep the
+/// underlying `anyhow` chain, so `{:#}` still prints every cause.
+#[derive(Debug, Error)]
+pub enum ChainVerseError {
+    /// A Solana RPC call failed on every endpoint, or its circuit breaker is open
+    #[error(transparent)]
+    Rpc(anyhow::Error),
+    /// A query, migration, or stored value failed
+    #[error(transparent)]
+    Database(anyhow::Error),
+    /// OpenRouter failed, or kept returning unusable poems
+    #[error(transparent)]
+    LlmProvider(anyhow::Error),
+    /// No word could be derived (empty dictionary, blocklist covering it)
+    #[error("{0}")]
+    Derivation(String),
+    /// The client was set up without something it needs
+    #[error("{0}")]
+    Config(String),
+}
+
+/// Result type for the modules that return `ChainVerseError`
+pub type Result<T, E = ChainVerseError> = std::result::Result<T, E>;
+
+impl ChainVerseError {
+    /// Whether a circuit breaker refused the call without trying the provider
+    pub fn is_circuit_open(&self) -> bool {
+        match self {
+            ChainVerseError::Rpc(e) | ChainVerseError::LlmProvider(e) => e.is::<CircuitOpen>(),
+            _ => false,
+        }
+    }
+
+    /// Whether the database is overloaded rather than broken
+    pub fn is_database_busy(&self) -> bool {
+        let ChainVerseError::Database(e) = self else {
+            return false;
+        };
+        match e.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::PoolTimedOut) => true,
+            Some(sqlx::Error::Database(db)) => db.code().is_some_and(|code| {
+                // SQLITE_BUSY and SQLITE_LOCKED, with or without an extended code
+                matches!(code.parse::<i32>().map(|code| code & 0xff), Ok(5 | 6))
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl From<sqlx::Error> for ChainVerseError {
+    fn from(e: sqlx::Error) -> Self {
+        ChainVerseError::Database(e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_error_classes() {
+        let open = ChainVerseError::Rpc(
+            CircuitOpen {
+                name: "solana_rpc",
+                retry_in: Duration::from_secs(30),
+            }
+            .into(),
+        );
+        assert!(open.is_circuit_open());
+        assert!(!ChainVerseError::Rpc(anyhow::anyhow!("connection refused")).is_circuit_open());
+
+        let busy: ChainVerseError = sqlx::Error::PoolTimedOut.into();
+        assert!(busy.is_database_busy());
+        assert!(!ChainVerseError::from(sqlx::Error::RowNotFound).is_database_busy());
+
+        // Provider errors display as their cause; anyhow's alternate form keeps the chain
+        let error = ChainVerseError::LlmProvider(
+            anyhow::anyhow!("timed out").context("OpenRouter API error"),
+        );
+        assert_eq!(error.to_string(), "OpenRouter API error");
+        assert_eq!(
+            format!("{:#}", anyhow::Error::from(error)),
+            "OpenRouter API error: timed out"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use serde::Serialize;
+use std::fmt;
+use tokio::sync::broadcast;
+
+use crate::consts::EVENT_CHANNEL_CAPACITY;
+
+/// Collector lifecycle event, streamed to dashboards by GET /api/events
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollectorEvent {
+    BlockFetched {
+        slot: u64,
+        blockhash: String,
+    },
+    KeywordDerived {
+        date: String,
+        word: String,
+        slot: u64,
+    },
+    PoemStarted {
+        date: String,
+        keywords: usize,
+        form: String,
+    },
+    /// A chunk of the poem as the LLM streams it; a new `attempt` starts the text over
+    PoemToken {
+        date: String,
+        attempt: u32,
+        text: String,
+    },
+    PoemFinished {
+        date: String,
+        title: Option<String>,
+    },
+    /// A poem, keyword, or streak total crossed one of its thresholds
+    MilestoneReached {
+        kind: String,
+        value: i64,
+        date: String,
+    },
+    /// A step failed; `stage` is block_fetch, keyword_store, or poem_generation
+    Error {
+        stage: String,
+        message: String,
+    },
+}
+
+impl CollectorEvent {
+    /// SSE event name, matching the serialized `type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BlockFetched { .. } => "block_fetched",
+            Self::KeywordDerived { .. } => "keyword_derived",
+            Self::PoemStarted { .. } => "poem_started",
+            Self::PoemToken { .. } => "poem_token",
+            Self::PoemFinished { .. } => "poem_finished",
+            Self::MilestoneReached { .. } => "milestone_reached",
+            Self::Error { .. } => "error",
+        }
+    }
+
+    pub fn error(stage: &str, error: &dyn fmt::Display) -> Self {
+        Self::Error {
+            stage: stage.to_string(),
+            message: format!("{:#}", error),
+        }
+    }
+}
+
+/// Broadcast channel shared by the collector (publisher) and the API (subscribers)
+///
+/// Events are dropped when nobody is listening; a subscriber that falls more
+/// than EVENT_CHANNEL_CAPACITY events behind skips ahead.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<CollectorEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: CollectorEvent) {
+        // Err only means there are no subscribers right now
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<CollectorEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_event_bus() {
+        let bus = EventBus::new();
+        // Publishing with no subscribers is not an error
+        bus.publish(CollectorEvent::PoemFinished {
+            date: "2026-01-05".to_string(),
+            title: None,
+        });
+
+        let mut receiver = bus.subscribe();
+        let event = CollectorEvent::KeywordDerived {
+            date: "2026-01-05".to_string(),
+            word: "moon".to_string(),
+            slot: 42,
+        };
+        bus.publish(event.clone());
+        assert_eq!(receiver.recv().await.unwrap(), event);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], event.name());
+        assert_eq!(json["word"], "moon");
+
+        let milestone = CollectorEvent::MilestoneReached {
+            kind: "poems".to_string(),
+            value: 100,
+            date: "2026-01-05".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&milestone).unwrap()["type"],
+            milestone.name()
+        );
+    }
+}
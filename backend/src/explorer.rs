@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::database::StoredKeyword;
+use crate::definitions::WordDefinition;
+use crate::rarity::KeywordRarity;
+
+/// Block explorer pages for a slot, so readers can click through to the block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorerLinks {
+    pub solscan: String,
+    pub solana_explorer: String,
+}
+
+impl ExplorerLinks {
+    pub fn for_slot(slot: u64) -> Self {
+        Self {
+            solscan: format!("https://solscan.io/block/{}", slot),
+            solana_explorer: format!("https://explorer.solana.com/block/{}", slot),
+        }
+    }
+
+    /// Links for a keyword's block; None for manual keywords' placeholder slots
+    pub fn for_keyword(keyword: &StoredKeyword) -> Option<Self> {
+        u64::try_from(keyword.slot).ok().map(Self::for_slot)
+    }
+}
+
+/// A stored keyword as the API returns it, with links to its block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedKeyword {
+    #[serde(flatten)]
+    pub keyword: StoredKeyword,
+    pub explorer: Option<ExplorerLinks>,
+    /// What the word means; null until it has been looked up, or when the
+    /// dictionary has no entry for it
+    pub definition: Option<WordDefinition>,
+    /// How rare the word was when collected; null where it isn't looked up
+    pub rarity: Option<KeywordRarity>,
+}
+
+impl LinkedKeyword {
+    /// Link the keyword and pick its definition from `definitions`, keyed by lowercased word
+    pub fn new(keyword: StoredKeyword, definitions: &HashMap<String, WordDefinition>) -> Self {
+        Self {
+            explorer: ExplorerLinks::for_keyword(&keyword),
+            definition: definitions.get(&keyword.word.to_lowercase()).cloned(),
+            rarity: None,
+            keyword,
+        }
+    }
+
+    pub fn with_rarity(mut self, rarity: Option<KeywordRarity>) -> Self {
+        self.rarity = rarity;
+        self
+    }
+}
+
+impl From<StoredKeyword> for LinkedKeyword {
+    fn from(keyword: StoredKeyword) -> Self {
+        Self::new(keyword, &HashMap::new())
+    }
+}
+
+/// Attach explorer links, definitions, and rarity (keyed by keyword id) to each keyword
+pub fn link_keywords(
+    keywords: Vec<StoredKeyword>,
+    definitions: &HashMap<String, WordDefinition>,
+    rarity: &HashMap<i64, KeywordRarity>,
+) -> Vec<LinkedKeyword> {
+    keywords
+        .into_iter()
+        .map(|keyword| {
+            let rarity = rarity.get(&keyword.id).cloned();
+            LinkedKeyword::new(keyword, definitions).with_rarity(rarity)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword(slot: i64) -> StoredKeyword {
+        StoredKeyword {
+            id: 1,
+            word: "ember".to_string(),
+            slot,
+            blockhash: "hash".to_string(),
+            previous_blockhash: None,
+            block_time: None,
+            word_index: 0,
+            source: "blockhash".to_string(),
+            theme: None,
+            rerolls: 0,
+            tag: None,
+            epoch: None,
+            orphaned: false,
+            created_at: "2026-01-05 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_keyword_links() {
+        let linked = LinkedKeyword::from(keyword(250_000_000));
+        assert_eq!(
+            linked.explorer,
+            Some(ExplorerLinks {
+                solscan: "https://solscan.io/block/250000000".to_string(),
+                solana_explorer: "https://explorer.solana.com/block/250000000".to_string(),
+            })
+        );
+
+        // Links sit alongside the keyword's own fields
+        let json = serde_json::to_value(&linked).unwrap();
+        assert_eq!(json["word"], "ember");
+        assert_eq!(
+            json["explorer"]["solscan"],
+            "https://solscan.io/block/250000000"
+        );
+
+        assert!(json["definition"].is_null());
+
+        // Manual keywords have no block to link to
+        assert!(LinkedKeyword::from(keyword(-1)).explorer.is_none());
+
+        let definition = WordDefinition {
+            definition: "A glowing piece of coal.".to_string(),
+            part_of_speech: Some("noun".to_string()),
+            origin: None,
+            source: "dictionaryapi.dev".to_string(),
+        };
+        let definitions = HashMap::from([("ember".to_string(), definition.clone())]);
+        let rarity = HashMap::from([(
+            1,
+            KeywordRarity::new(0, None, chrono::Utc::now().date_naive()),
+        )]);
+        let linked = link_keywords(vec![keyword(250_000_000)], &definitions, &rarity);
+        assert_eq!(linked[0].definition, Some(definition));
+        assert_eq!(
+            linked[0].rarity.as_ref().unwrap().summary,
+            "First appearance"
+        );
+    }
+}
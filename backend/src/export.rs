@@ -0,0 +1,340 @@
+use ab_glyph::{FontRef, PxScale};
+use anyhow::Result;
+use image::{ImageFormat, Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use std::io::Cursor;
+
+use crate::database::{StoredKeyword, StoredPoem};
+
+/// DejaVu Serif (Bitstream Vera license, see fonts/LICENSE)
+const BODY_FONT: &[u8] = include_bytes!("../fonts/DejaVuSerif.ttf");
+const TITLE_FONT: &[u8] = include_bytes!("../fonts/DejaVuSerif-Bold.ttf");
+
+/// PNG exports are this wide, and at least this tall (square for social feeds)
+const IMAGE_WIDTH: u32 = 1080;
+const IMAGE_MARGIN: u32 = 96;
+const TITLE_SIZE: f32 = 52.0;
+const BODY_SIZE: f32 = 34.0;
+const FOOTER_SIZE: f32 = 24.0;
+/// Line advance as a multiple of the font size
+const LINE_SPACING: f32 = 1.45;
+
+const BACKGROUND: Rgb<u8> = Rgb([247, 243, 234]);
+const INK: Rgb<u8> = Rgb([34, 31, 28]);
+const MUTED: Rgb<u8> = Rgb([122, 114, 104]);
+
+/// Shareable poem formats served at `/api/poems/{date}.{ext}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Text,
+    Markdown,
+    Png,
+}
+
+impl ExportFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "txt" => Some(Self::Text),
+            "md" => Some(Self::Markdown),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text => "text/plain; charset=utf-8",
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Png => "image/png",
+        }
+    }
+
+    /// Render a poem and the keywords it was written from
+    pub fn render(&self, poem: &StoredPoem, keywords: &[StoredKeyword]) -> Result<Vec<u8>> {
+        let words: Vec<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+        match self {
+            Self::Text => Ok(poem_text(poem, &words).into_bytes()),
+            Self::Markdown => Ok(poem_markdown(poem, &words).into_bytes()),
+            Self::Png => poem_png(poem, &words),
+        }
+    }
+
+    /// `render` on the blocking pool, so drawing a PNG doesn't stall the runtime
+    pub async fn render_blocking(
+        self,
+        poem: StoredPoem,
+        keywords: Vec<StoredKeyword>,
+    ) -> Result<Vec<u8>> {
+        tokio::task::spawn_blocking(move || self.render(&poem, &keywords)).await?
+    }
+}
+
+/// Split `2026-01-05.png` into the date and its export format
+pub fn split_export_path(path: &str) -> Option<(&str, ExportFormat)> {
+    let (date, extension) = path.rsplit_once('.')?;
+    Some((date, ExportFormat::from_extension(extension)?))
+}
+
+fn footer(date: &str, words: &[&str]) -> String {
+    format!("chain_verse · {} · {}", date, words.join(", "))
+}
+
+fn poem_text(poem: &StoredPoem, words: &[&str]) -> String {
+    let mut text = String::new();
+    if let Some(title) = &poem.title {
+        text.push_str(title);
+        text.push_str("\n\n");
+    }
+    text.push_str(poem.content.trim());
+    text.push_str("\n\n— ");
+    text.push_str(&footer(&poem.date, words));
+    text.push('\n');
+    text
+}
+
+fn poem_markdown(poem: &StoredPoem, words: &[&str]) -> String {
+    let title = poem
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Chain Verse, {}", poem.date));
+    let mut markdown = format!("# {}\n\n", escape_markdown(&title));
+
+    // Trailing double spaces keep the poem's line breaks
+    let lines: Vec<String> = poem
+        .content
+        .trim()
+        .lines()
+        .map(|line| match line.trim() {
+            "" => String::new(),
+            line => format!("{}  ", escape_markdown(line)),
+        })
+        .collect();
+    markdown.push_str(&lines.join("\n"));
+
+    let words: Vec<String> = words.iter().map(|word| format!("`{}`", word)).collect();
+    markdown.push_str(&format!(
+        "\n\n---\n\n*{}* · keywords: {}\n",
+        poem.date,
+        words.join(", ")
+    ));
+    markdown
+}
+
+/// Escape characters that would turn a line of verse into Markdown syntax
+fn escape_markdown(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for c in line.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    // A leading "- ", "+ ", or "1. " would start a list
+    let list_marker = escaped.starts_with("- ")
+        || escaped.starts_with("+ ")
+        || escaped.split_once(". ").is_some_and(|(number, _)| {
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+        });
+    if list_marker {
+        if let Some(position) = escaped.find(['-', '+', '.']) {
+            escaped.insert(position, '\\');
+        }
+    }
+    escaped
+}
+
+/// Break `text` into lines no wider than `max_width`, at spaces where possible
+fn wrap(text: &str, max_width: u32, measure: impl Fn(&str) -> u32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if current.is_empty() || measure(&candidate) <= max_width {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+
+    lines.push(current);
+    lines
+}
+
+enum Block {
+    Line {
+        text: String,
+        title: bool,
+        size: f32,
+        color: Rgb<u8>,
+    },
+    Gap(u32),
+    Rule,
+}
+
+fn poem_png(poem: &StoredPoem, words: &[&str]) -> Result<Vec<u8>> {
+    let body_font = FontRef::try_from_slice(BODY_FONT)?;
+    let title_font = FontRef::try_from_slice(TITLE_FONT)?;
+    let max_width = IMAGE_WIDTH - 2 * IMAGE_MARGIN;
+    let line_height = |size: f32| (size * LINE_SPACING).round() as u32;
+
+    let mut blocks = Vec::new();
+    if let Some(title) = &poem.title {
+        let measure = |text: &str| text_size(PxScale::from(TITLE_SIZE), &title_font, text).0;
+        for text in wrap(title, max_width, measure) {
+            blocks.push(Block::Line {
+                text,
+                title: true,
+                size: TITLE_SIZE,
+                color: INK,
+            });
+        }
+        blocks.push(Block::Gap(line_height(BODY_SIZE)));
+    }
+    for line in poem.content.trim().lines() {
+        if line.trim().is_empty() {
+            blocks.push(Block::Gap(line_height(BODY_SIZE) / 2));
+            continue;
+        }
+        let measure = |text: &str| text_size(PxScale::from(BODY_SIZE), &body_font, text).0;
+        for text in wrap(line, max_width, measure) {
+            blocks.push(Block::Line {
+                text,
+                title: false,
+                size: BODY_SIZE,
+                color: INK,
+            });
+        }
+    }
+    blocks.push(Block::Gap(line_height(BODY_SIZE)));
+    blocks.push(Block::Rule);
+    let measure = |text: &str| text_size(PxScale::from(FOOTER_SIZE), &body_font, text).0;
+    for text in wrap(&footer(&poem.date, words), max_width, measure) {
+        blocks.push(Block::Line {
+            text,
+            title: false,
+            size: FOOTER_SIZE,
+            color: MUTED,
+        });
+    }
+
+    let block_height = |block: &Block| match block {
+        Block::Line { size, .. } => line_height(*size),
+        Block::Gap(height) => *height,
+        Block::Rule => line_height(FOOTER_SIZE) / 2,
+    };
+    let content_height: u32 = blocks.iter().map(block_height).sum();
+    let height = (content_height + 2 * IMAGE_MARGIN).max(IMAGE_WIDTH);
+
+    // Short poems sit in the middle of the square
+    let mut image = RgbImage::from_pixel(IMAGE_WIDTH, height, BACKGROUND);
+    let mut y = (height - content_height) / 2;
+    for block in &blocks {
+        match block {
+            Block::Line {
+                text,
+                title,
+                size,
+                color,
+            } => {
+                let font = if *title { &title_font } else { &body_font };
+                draw_text_mut(
+                    &mut image,
+                    *color,
+                    IMAGE_MARGIN as i32,
+                    y as i32,
+                    PxScale::from(*size),
+                    font,
+                    text,
+                );
+            }
+            Block::Rule => {
+                let rule = Rect::at(IMAGE_MARGIN as i32, y as i32).of_size(IMAGE_MARGIN, 2);
+                draw_filled_rect_mut(&mut image, rule, MUTED);
+            }
+            Block::Gap(_) => {}
+        }
+        y += block_height(block);
+    }
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poem(title: Option<&str>) -> StoredPoem {
+        StoredPoem {
+            id: 1,
+            date: "2026-01-05".to_string(),
+            title: title.map(str::to_string),
+            content: "moon over water\n\n# silence *between* blocks\n1. the ledger turns"
+                .to_string(),
+            keyword_ids: vec![1, 2],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: "2026-01-05 12:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_split_export_path() {
+        assert_eq!(
+            split_export_path("2026-01-05.png"),
+            Some(("2026-01-05", ExportFormat::Png))
+        );
+        assert_eq!(
+            split_export_path("2026-01-05.md"),
+            Some(("2026-01-05", ExportFormat::Markdown))
+        );
+        assert_eq!(split_export_path("2026-01-05.pdf"), None);
+        assert_eq!(split_export_path("2026-01-05"), None);
+    }
+
+    #[test]
+    fn test_text_and_markdown() {
+        let text = poem_text(&poem(Some("Ledger of Tides")), &["moon", "water"]);
+        assert!(text.starts_with("Ledger of Tides\n\nmoon over water\n"));
+        assert!(text.ends_with("— chain_verse · 2026-01-05 · moon, water\n"));
+
+        let markdown = poem_markdown(&poem(None), &["moon", "water"]);
+        assert!(markdown.starts_with("# Chain Verse, 2026-01-05\n\nmoon over water  \n\n"));
+        assert!(markdown.contains("\\# silence \\*between\\* blocks  \n"));
+        assert!(markdown.contains("1\\. the ledger turns  \n"));
+        assert!(markdown.ends_with("*2026-01-05* · keywords: `moon`, `water`\n"));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let measure = |text: &str| text.chars().count() as u32;
+        assert_eq!(
+            wrap("the moon over water", 9, measure),
+            vec!["the moon", "over", "water"]
+        );
+        // A single overlong word still gets its own line
+        assert_eq!(wrap("incandescent", 5, measure), vec!["incandescent"]);
+    }
+
+    #[test]
+    fn test_png_export() {
+        let png = ExportFormat::Png
+            .render(&poem(Some("Ledger of Tides")), &[])
+            .unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!(image.width(), IMAGE_WIDTH);
+        assert!(image.height() >= IMAGE_WIDTH);
+    }
+}
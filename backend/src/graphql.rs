@@ -0,0 +1,364 @@
+use std::sync::Arc;
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Json, Object, Result, Schema, SimpleObject,
+};
+use axum::{extract::State, response::Html, routing::get, Router};
+
+use crate::consts::{
+    DEFAULT_GRAPHQL_POEMS, DEFAULT_STATS_TOP_WORDS, MAX_GRAPHQL_POEMS, MAX_STATS_TOP_WORDS,
+};
+use crate::database::{Database, StoredKeyword, StoredPoem};
+use crate::explorer::ExplorerLinks;
+use crate::provenance::PoemProvenance;
+use crate::stats::{self, CorpusStats};
+
+/// Deepest selection nesting accepted; poem -> keywords -> block is 3
+const MAX_DEPTH: usize = 8;
+
+/// Highest query complexity accepted; `poems` costs its page size times its
+/// selection, so a full page fits unless it selects nearly every field
+const MAX_COMPLEXITY: usize = 2000;
+
+pub type ChainVerseSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Read-only schema over the poem archive
+pub fn schema(db: Arc<Database>) -> ChainVerseSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(db)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// POST /graphql runs queries; GET /graphql serves GraphiQL
+pub fn router<S: Clone + Send + Sync + 'static>(db: Arc<Database>) -> Router<S> {
+    Router::new()
+        .route("/graphql", get(graphiql).post(execute))
+        .with_state(schema(db))
+}
+
+async fn execute(
+    State(schema): State<ChainVerseSchema>,
+    axum::Json(request): axum::Json<async_graphql::Request>,
+) -> axum::Json<async_graphql::Response> {
+    axum::Json(schema.execute(request).await)
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+fn database<'a>(ctx: &Context<'a>) -> Result<&'a Arc<Database>> {
+    ctx.data::<Arc<Database>>()
+}
+
+/// Poems a `poems(first:)` page returns
+fn poem_page_size(first: Option<i64>) -> i64 {
+    first
+        .unwrap_or(DEFAULT_GRAPHQL_POEMS)
+        .clamp(1, MAX_GRAPHQL_POEMS)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Up to `first` poems (default 20, at most 100) dated before `after`,
+    /// latest first; pass the last poem's date as `after` for the next page
+    #[graphql(complexity = "poem_page_size(first) as usize * child_complexity")]
+    async fn poems(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i64>,
+        after: Option<String>,
+    ) -> Result<Vec<Poem>> {
+        let poems = database(ctx)?
+            .get_poems_before(after.as_deref(), poem_page_size(first))
+            .await?;
+        Ok(poems.into_iter().map(Poem).collect())
+    }
+
+    /// The poem for a date (YYYY-MM-DD)
+    async fn poem(&self, ctx: &Context<'_>, date: String) -> Result<Option<Poem>> {
+        Ok(database(ctx)?.get_poem_by_date(&date).await?.map(Poem))
+    }
+
+    /// Keywords collected on a date (YYYY-MM-DD)
+    async fn keywords(&self, ctx: &Context<'_>, date: String) -> Result<Vec<Keyword>> {
+        let keywords = database(ctx)?.get_keywords_for_date(&date).await?;
+        Ok(keywords.into_iter().map(Keyword).collect())
+    }
+
+    /// The poem with a content hash, including earlier versions of regenerated poems
+    async fn poem_by_hash(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Poem>> {
+        Ok(database(ctx)?.get_poem_by_hash(&hash).await?.map(Poem))
+    }
+
+    async fn keyword(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Keyword>> {
+        Ok(database(ctx)?.get_keyword(id).await?.map(Keyword))
+    }
+
+    /// Reproducibility manifest for a date's poem, as in /api/poems/{date}/provenance
+    async fn provenance(
+        &self,
+        ctx: &Context<'_>,
+        date: String,
+    ) -> Result<Option<Json<PoemProvenance>>> {
+        Ok(database(ctx)?.get_poem_provenance(&date).await?.map(Json))
+    }
+
+    /// Corpus analytics, as in /api/stats
+    async fn stats(&self, ctx: &Context<'_>, top: Option<i64>) -> Result<Json<CorpusStats>> {
+        let top = top
+            .unwrap_or(DEFAULT_STATS_TOP_WORDS)
+            .clamp(1, MAX_STATS_TOP_WORDS);
+        Ok(Json(stats::corpus_stats(database(ctx)?, top).await?))
+    }
+}
+
+pub struct Poem(StoredPoem);
+
+#[Object]
+impl Poem {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn date(&self) -> &str {
+        &self.0.date
+    }
+
+    async fn title(&self) -> Option<&str> {
+        self.0.title.as_deref()
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    /// SHA-256 of the poem and its keywords, when anchored on-chain
+    async fn anchor_hash(&self) -> Option<&str> {
+        self.0.anchor_hash.as_deref()
+    }
+
+    /// Memo transaction that recorded `anchorHash`
+    async fn anchor_signature(&self) -> Option<&str> {
+        self.0.anchor_signature.as_deref()
+    }
+
+    /// Content address of this version of the poem, as in /api/poems/hash/{hash}
+    async fn poem_hash(&self) -> Option<&str> {
+        self.0.poem_hash.as_deref()
+    }
+
+    async fn created_at(&self) -> &str {
+        &self.0.created_at
+    }
+
+    /// The keywords the poem was written from; removed keywords are skipped
+    async fn keywords(&self, ctx: &Context<'_>) -> Result<Vec<Keyword>> {
+        let keywords = database(ctx)?
+            .get_keywords_by_ids(&self.0.keyword_ids)
+            .await?;
+        Ok(keywords.into_iter().map(Keyword).collect())
+    }
+
+    async fn provenance(&self, ctx: &Context<'_>) -> Result<Option<Json<PoemProvenance>>> {
+        Ok(database(ctx)?
+            .get_poem_provenance(&self.0.date)
+            .await?
+            .map(Json))
+    }
+}
+
+pub struct Keyword(StoredKeyword);
+
+#[Object]
+impl Keyword {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn word(&self) -> &str {
+        &self.0.word
+    }
+
+    async fn word_index(&self) -> i64 {
+        self.0.word_index
+    }
+
+    /// Entropy the word was derived from (see BlockDataSource)
+    async fn source(&self) -> &str {
+        &self.0.source
+    }
+
+    /// Dictionary theme the word was drawn from; null for the base dictionary
+    async fn theme(&self) -> Option<&str> {
+        self.0.theme.as_deref()
+    }
+
+    /// Re-hashes it took to skip blocked or recently used words
+    async fn rerolls(&self) -> i64 {
+        self.0.rerolls
+    }
+
+    /// Why the keyword was collected off-schedule (`event`); null for scheduled collection
+    async fn tag(&self) -> Option<&str> {
+        self.0.tag.as_deref()
+    }
+
+    /// Solana epoch of the slot; null for keywords collected before it was recorded
+    async fn epoch(&self) -> Option<i64> {
+        self.0.epoch
+    }
+
+    /// The block was abandoned by a reorg; orphaned keywords are left out of the day's poem
+    async fn orphaned(&self) -> bool {
+        self.0.orphaned
+    }
+
+    async fn created_at(&self) -> &str {
+        &self.0.created_at
+    }
+
+    /// The block the word was derived from
+    async fn block(&self) -> Block {
+        let links = ExplorerLinks::for_keyword(&self.0);
+        Block {
+            slot: self.0.slot,
+            blockhash: self.0.blockhash.clone(),
+            previous_blockhash: self.0.previous_blockhash.clone(),
+            block_time: self.0.block_time,
+            solscan_url: links.as_ref().map(|links| links.solscan.clone()),
+            solana_explorer_url: links.map(|links| links.solana_explorer),
+        }
+    }
+}
+
+/// A keyword's source block, as recorded when it was collected
+#[derive(SimpleObject)]
+pub struct Block {
+    slot: i64,
+    blockhash: String,
+    /// Null for keywords stored before it was recorded
+    previous_blockhash: Option<String>,
+    /// Unix timestamp, when the RPC reported one
+    block_time: Option<i64>,
+    /// Null for manual keywords, which come from no block
+    solscan_url: Option<String>,
+    solana_explorer_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::BlockDataSource;
+    use crate::derivation::DerivedKeyword;
+
+    #[tokio::test]
+    async fn test_poem_keyword_blocks() {
+        let path =
+            std::env::temp_dir().join(format!("chain_verse_graphql_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = Database::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+
+        let keyword = DerivedKeyword {
+            word: "ember".to_string(),
+            slot: 250_000_000,
+            blockhash: "hash".to_string(),
+            previous_blockhash: "parent".to_string(),
+            block_time: Some(1_700_000_000),
+            word_index: 7,
+            source: BlockDataSource::Blockhash,
+            theme: None,
+            rerolls: 0,
+            epoch: None,
+        };
+        let id = db
+            .insert_keyword_with_date(&keyword, "2026-01-05")
+            .await
+            .unwrap()
+            .unwrap();
+        db.insert_poem("2026-01-05", Some("Ember"), "A poem", &[id])
+            .await
+            .unwrap();
+
+        let schema = schema(Arc::new(db));
+        let response = schema
+            .execute(
+                r#"{ poem(date: "2026-01-05") { title keywords { word block { slot previousBlockhash } } } }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "poem": {
+                    "title": "Ember",
+                    "keywords": [{
+                        "word": "ember",
+                        "block": { "slot": 250_000_000, "previousBlockhash": "parent" }
+                    }]
+                }
+            })
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_poems_pages() {
+        let (path, url) = crate::database::temp_database_url("graphql_pages");
+        let db = Database::new(&url).await.unwrap();
+        for date in ["2026-01-05", "2026-01-06", "2026-01-07"] {
+            db.insert_poem(date, None, "A poem", &[]).await.unwrap();
+        }
+        let schema = schema(Arc::new(db));
+
+        let dates = |query: &'static str| {
+            let schema = schema.clone();
+            async move {
+                let response = schema.execute(query).await;
+                assert!(response.errors.is_empty(), "{:?}", response.errors);
+                response.data.into_json().unwrap()["poems"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|poem| poem["date"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(
+            dates("{ poems { date } }").await,
+            ["2026-01-07", "2026-01-06", "2026-01-05"]
+        );
+        assert_eq!(
+            dates("{ poems(first: 2) { date } }").await,
+            ["2026-01-07", "2026-01-06"]
+        );
+        assert_eq!(
+            dates(r#"{ poems(first: 2, after: "2026-01-06") { date } }"#).await,
+            ["2026-01-05"]
+        );
+
+        // A full page selecting everything is too complex to run
+        let response = schema
+            .execute(
+                r#"{ poems(first: 100) { id date title content anchorHash anchorSignature poemHash createdAt
+                    keywords { id word wordIndex source theme rerolls tag epoch orphaned createdAt
+                        block { slot blockhash previousBlockhash blockTime solscanUrl solanaExplorerUrl } } } }"#,
+            )
+            .await;
+        assert!(
+            response.errors[0].message.contains("too complex"),
+            "{:?}",
+            response.errors
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,305 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::blockchain::{EndpointHealth, SolanaClient};
+use crate::circuit_breaker::{BreakerState, BreakerStatus};
+use crate::consts::{
+    COLLECTOR_STALE_INTERVALS, DEFAULT_KEYWORDS_PER_DAY, HEALTH_CHECK_CACHE_SECS,
+    HEALTH_CHECK_TIMEOUT_SECS, ONE_MINUTE,
+};
+use crate::control::CollectorControl;
+use crate::database::Database;
+use crate::poem_generator::PoemGenerator;
+use crate::scheduler::scheduled_keywords;
+
+/// Overall verdict of a deep health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Everything answered
+    Ok,
+    /// The collector and database are fine but an upstream (RPC, LLM) is not,
+    /// or its circuit breaker hasn't closed again yet
+    Degraded,
+    /// The collector is stale or the database is unreachable
+    Unhealthy,
+}
+
+/// Result of probing one dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbeResult {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    fn from_result(result: Result<()>) -> Self {
+        match result {
+            Ok(()) => Self { ok: true, error: None },
+            Err(e) => Self {
+                ok: false,
+                error: Some(format!("{:#}", e)),
+            },
+        }
+    }
+}
+
+/// How recently the collector stored a keyword
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorHealth {
+    pub last_keyword_at: Option<String>,
+    pub age_seconds: Option<i64>,
+    pub stale_after_seconds: u64,
+    /// Why no new keywords are expected (`paused`, or `complete` once today
+    /// has its keywords); the collector isn't stale then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle: Option<CollectorIdle>,
+    pub stale: bool,
+}
+
+/// Why a collector that stored nothing lately is still healthy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectorIdle {
+    /// An operator paused collection
+    Paused,
+    /// Today already has `keywords_per_day` keywords
+    Complete,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SolanaHealth {
+    pub ok: bool,
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+/// Response body of GET /health/deep
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub timestamp: String,
+    pub collector: CollectorHealth,
+    pub database: ProbeResult,
+    pub solana: SolanaHealth,
+    pub openrouter: ProbeResult,
+    /// Breakers shared with the collector's Solana and OpenRouter clients
+    pub circuit_breakers: Vec<BreakerStatus>,
+}
+
+/// Probes the collector, database, Solana RPC, and OpenRouter for /health/deep
+pub struct HealthChecker {
+    database: Database,
+    solana_client: SolanaClient,
+    poem_generator: PoemGenerator,
+    stale_after: Duration,
+    keywords_per_day: usize,
+    control: Option<CollectorControl>,
+    /// The last report and when it was taken
+    last_report: Mutex<Option<(Instant, HealthReport)>>,
+}
+
+impl HealthChecker {
+    /// The collector counts as stale after COLLECTOR_STALE_INTERVALS missed intervals
+    pub fn new(
+        database: Database,
+        solana_client: SolanaClient,
+        poem_generator: PoemGenerator,
+        interval_minutes: u64,
+    ) -> Self {
+        Self {
+            database,
+            solana_client,
+            poem_generator,
+            stale_after: Duration::from_secs(
+                interval_minutes * ONE_MINUTE * COLLECTOR_STALE_INTERVALS,
+            ),
+            keywords_per_day: DEFAULT_KEYWORDS_PER_DAY,
+            control: None,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Don't count the collector stale once today has `keywords_per_day` keywords
+    pub fn with_keywords_per_day(mut self, keywords_per_day: usize) -> Self {
+        self.keywords_per_day = keywords_per_day;
+        self
+    }
+
+    /// Don't count the collector stale while an operator has it paused
+    pub fn with_control(mut self, control: CollectorControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Why the collector isn't expected to store keywords right now, if it isn't
+    async fn collector_idle(&self) -> Result<Option<CollectorIdle>> {
+        if self
+            .control
+            .as_ref()
+            .is_some_and(CollectorControl::is_paused)
+        {
+            return Ok(Some(CollectorIdle::Paused));
+        }
+        let stored = self
+            .database
+            .get_keywords_for_date(&self.database.today())
+            .await?;
+        let complete = scheduled_keywords(&stored).len() >= self.keywords_per_day;
+        Ok(complete.then_some(CollectorIdle::Complete))
+    }
+
+    /// The latest report, probing again once it's HEALTH_CHECK_CACHE_SECS old
+    ///
+    /// Concurrent callers wait for one probe instead of each starting their own.
+    pub async fn check(&self) -> HealthReport {
+        let mut last_report = self.last_report.lock().await;
+        if let Some((taken_at, report)) = last_report.as_ref() {
+            if taken_at.elapsed() < Duration::from_secs(HEALTH_CHECK_CACHE_SECS) {
+                return report.clone();
+            }
+        }
+        let report = self.probe().await;
+        *last_report = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    /// Run every probe concurrently, each bounded by HEALTH_CHECK_TIMEOUT_SECS
+    async fn probe(&self) -> HealthReport {
+        let (last_keyword, idle, database, solana, openrouter) = tokio::join!(
+            with_timeout(self.database.get_last_keyword_time()),
+            with_timeout(self.collector_idle()),
+            with_timeout(self.database.ping()),
+            with_timeout(self.solana_client.health_check()),
+            with_timeout(self.poem_generator.check_api()),
+        );
+
+        let now = Utc::now();
+        let last_keyword = last_keyword.unwrap_or_else(|e| {
+            warn!(error = %e, "Could not read last keyword time");
+            None
+        });
+        let idle = idle.unwrap_or_else(|e| {
+            warn!(error = %e, "Could not read today's keywords");
+            None
+        });
+        let collector = collector_health(last_keyword, idle, now, self.stale_after);
+        let database = ProbeResult::from_result(database);
+        let solana = SolanaHealth {
+            ok: matches!(solana, Ok(true)),
+            endpoints: self.solana_client.endpoint_health(),
+        };
+        let openrouter = ProbeResult::from_result(openrouter);
+        let circuit_breakers = vec![
+            self.solana_client.breaker().status(),
+            self.poem_generator.breaker().status(),
+        ];
+        let breaker_open = circuit_breakers
+            .iter()
+            .any(|breaker| breaker.state != BreakerState::Closed);
+
+        let status = if collector.stale || !database.ok {
+            HealthStatus::Unhealthy
+        } else if !solana.ok || !openrouter.ok || breaker_open {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+
+        HealthReport {
+            status,
+            timestamp: now.to_rfc3339(),
+            collector,
+            database,
+            solana,
+            openrouter,
+            circuit_breakers,
+        }
+    }
+}
+
+async fn with_timeout<T, E>(probe: impl Future<Output = Result<T, E>>) -> Result<T>
+where
+    E: Into<anyhow::Error>,
+{
+    tokio::time::timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS), probe)
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out after {}s", HEALTH_CHECK_TIMEOUT_SECS))?
+        .map_err(Into::into)
+}
+
+/// Judge collector liveness from the newest keyword's `created_at` (UTC);
+/// an idle collector is never stale
+fn collector_health(
+    last_keyword_at: Option<String>,
+    idle: Option<CollectorIdle>,
+    now: DateTime<Utc>,
+    stale_after: Duration,
+) -> CollectorHealth {
+    let age_seconds = last_keyword_at
+        .as_deref()
+        .and_then(|ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S").ok())
+        .map(|ts| (now - ts.and_utc()).num_seconds());
+
+    CollectorHealth {
+        stale: idle.is_none() && age_seconds.is_none_or(|age| age > stale_after.as_secs() as i64),
+        last_keyword_at,
+        age_seconds,
+        stale_after_seconds: stale_after.as_secs(),
+        idle,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_collector_health() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap();
+        let stale_after = Duration::from_secs(3 * 60 * 60);
+
+        let health = |last: Option<&str>, idle| {
+            collector_health(last.map(str::to_string), idle, now, stale_after)
+        };
+
+        let fresh = health(Some("2026-01-05 10:30:00"), None);
+        assert_eq!(fresh.age_seconds, Some(90 * 60));
+        assert!(!fresh.stale);
+
+        let stale = health(Some("2026-01-05 08:00:00"), None);
+        assert!(stale.stale);
+
+        let never = health(None, None);
+        assert!(never.stale);
+        assert_eq!(never.age_seconds, None);
+
+        // Nothing new is expected while paused or once today is complete
+        assert!(!health(Some("2026-01-05 08:00:00"), Some(CollectorIdle::Paused)).stale);
+        assert!(!health(Some("2026-01-04 08:00:00"), Some(CollectorIdle::Complete)).stale);
+    }
+
+    #[tokio::test]
+    async fn test_check_reuses_report() {
+        let (path, url) = crate::database::temp_database_url("health_cache");
+        let checker = HealthChecker::new(
+            Database::new(&url).await.unwrap(),
+            SolanaClient::with_url("http://127.0.0.1:1"),
+            PoemGenerator::new("key".to_string(), "model-a".to_string()),
+            60,
+        );
+
+        let first = checker.check().await;
+        assert!(!first.solana.ok);
+        let second = checker.check().await;
+        assert_eq!(second.timestamp, first.timestamp);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
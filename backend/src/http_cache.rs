@@ -0,0 +1,139 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+
+/// Strong ETag over everything a response is rendered from
+pub fn etag(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    // 128 bits is plenty to tell versions of one resource apart
+    format!("\"{}\"", &format!("{:x}", hasher.finalize())[..32])
+}
+
+/// Whether the client's If-None-Match already names `etag`
+///
+/// Weak validators (`W/"..."`) match too: GET revalidation uses weak comparison.
+fn not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Answer 304 if the client's copy is current, otherwise build the response
+///
+/// Successful responses carry `ETag` and `Cache-Control: public,
+/// max-age=<max_age_secs>`, so caches can serve them that long and then
+/// revalidate. Errors from `build` go out without either.
+pub fn respond<R: IntoResponse>(
+    request_headers: &HeaderMap,
+    etag: &str,
+    max_age_secs: u64,
+    build: impl FnOnce() -> R,
+) -> Response {
+    let response = if not_modified(request_headers, etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        build().into_response()
+    };
+    with_validators(response, etag, max_age_secs)
+}
+
+/// `respond` for responses that are built asynchronously
+pub async fn respond_async<R: IntoResponse, F: Future<Output = R>>(
+    request_headers: &HeaderMap,
+    etag: &str,
+    max_age_secs: u64,
+    build: impl FnOnce() -> F,
+) -> Response {
+    let response = if not_modified(request_headers, etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        build().await.into_response()
+    };
+    with_validators(response, etag, max_age_secs)
+}
+
+/// Tag a successful or 304 response with its `ETag` and `Cache-Control`
+fn with_validators(mut response: Response, etag: &str, max_age_secs: u64) -> Response {
+    if !response.status().is_success() && response.status() != StatusCode::NOT_MODIFIED {
+        return response;
+    }
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!("public, max-age={}", max_age_secs)) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditional_response() {
+        let tag = etag(&[b"poem", b"json"]);
+        assert_eq!(tag.len(), 34);
+        assert_ne!(tag, etag(&[b"poemj", b"son"]));
+
+        let response = respond(&HeaderMap::new(), &tag, 60, || "body");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], tag.as_str());
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "public, max-age=60"
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"stale\", W/{}", tag)).unwrap(),
+        );
+        let response = respond(&headers, &tag, 60, || -> &'static str {
+            panic!("a current copy is not rebuilt")
+        });
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], tag.as_str());
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""));
+        assert_eq!(
+            respond(&headers, &tag, 60, || "body").status(),
+            StatusCode::OK
+        );
+
+        // Errors aren't cached
+        let response = respond(&HeaderMap::new(), &tag, 60, || {
+            StatusCode::INTERNAL_SERVER_ERROR
+        });
+        assert!(response.headers().get(header::ETAG).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_conditional_response_async() {
+        let tag = etag(&[b"poem", b"png"]);
+        let response = respond_async(&HeaderMap::new(), &tag, 60, || async { "body" }).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], tag.as_str());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&tag).unwrap());
+        let response = respond_async(&headers, &tag, 60, || -> std::future::Ready<&str> {
+            panic!("a current copy is not rebuilt")
+        })
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+}
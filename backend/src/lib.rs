@@ -1,8 +1,53 @@
+pub mod anchor;
 pub mod api;
+pub mod archive;
+pub mod audit;
+pub mod auth;
+pub mod backfill;
+pub mod backup;
 pub mod blockchain;
+pub mod circuit_breaker;
+pub mod cli;
+pub mod collections;
+pub mod config;
 pub mod consts;
+pub mod control;
 pub mod database;
+pub mod dataset;
+pub mod definitions;
 pub mod derivation;
+pub mod embeddings;
+pub mod engagement;
+pub mod enhanced_rpc;
+pub mod error;
+pub mod events;
+pub mod explorer;
+pub mod export;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod health;
+pub mod http_cache;
+pub mod logging;
+pub mod metrics;
+pub mod milestones;
+pub mod minting;
+pub mod mood;
+pub mod notifications;
+pub mod openapi;
+pub mod poem_form;
 pub mod poem_generator;
+pub mod poem_quality;
+pub mod preview;
+pub mod prompt;
+pub mod provenance;
+pub mod publisher;
+pub mod rarity;
+pub mod rate_limit;
+pub mod read_cache;
+pub mod retry;
+pub mod rpc_throttle;
 pub mod scheduler;
+pub mod stats;
+pub mod timezone;
+pub mod tts;
 pub mod words;
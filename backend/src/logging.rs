@@ -0,0 +1,94 @@
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+/// Base level used when neither RUST_LOG nor LOG_LEVEL is set
+const DEFAULT_LOG_LEVEL: &str = "info";
+
+/// Dependencies that are chatty at info level and quieted by default
+const DEFAULT_QUIET_MODULES: &[&str] = &[
+    "sqlx=warn",
+    "hyper=warn",
+    "reqwest=warn",
+    "solana_rpc_client=warn",
+];
+
+/// Prefix of this crate's log targets, which LOG_MODULES may leave off
+const CRATE_NAME: &str = env!("CARGO_CRATE_NAME");
+
+/// Initialize the global tracing subscriber
+///
+/// RUST_LOG, when set, is used verbatim. Otherwise the filter is LOG_LEVEL
+/// (default `info`) with noisy dependencies quieted, followed by any
+/// comma-separated LOG_MODULES overrides such as `scheduler=debug,sqlx=error`.
+///
+/// LOG_FORMAT=json emits one flat JSON object per event for log aggregation;
+/// events carry fields such as `slot`, `date`, `model`, and `duration_ms`.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        let level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| DEFAULT_LOG_LEVEL.to_string());
+        let modules = std::env::var("LOG_MODULES").unwrap_or_default();
+        EnvFilter::new(build_filter_directives(&level, &modules))
+    });
+
+    let json = std::env::var("LOG_FORMAT")
+        .map(|format| format.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    if json {
+        builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init();
+    } else {
+        builder.init();
+    }
+}
+
+/// Build an EnvFilter directive string from a base level and module overrides
+/// Later directives win, so overrides can loosen or tighten the quiet defaults
+fn build_filter_directives(level: &str, modules: &str) -> String {
+    let mut directives = vec![level.trim().to_string()];
+    directives.extend(DEFAULT_QUIET_MODULES.iter().map(|d| d.to_string()));
+
+    for directive in modules.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        directives.push(directive.to_string());
+        // A bare name may be one of our modules; under the crate prefix it
+        // matches nothing when it is a dependency instead
+        let module = directive.split('=').next().unwrap_or_default();
+        let bare = !module.contains("::") && module != CRATE_NAME;
+        if bare && module.parse::<LevelFilter>().is_err() {
+            directives.push(format!("{}::{}", CRATE_NAME, directive));
+        }
+    }
+
+    directives.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_filter_directives() {
+        let directives = build_filter_directives("info", "scheduler=debug, sqlx=error,,");
+        assert!(directives.starts_with("info,sqlx=warn"));
+        assert!(directives.contains(",scheduler=debug,chain_verse::scheduler=debug,"));
+        assert!(directives.ends_with(",sqlx=error,chain_verse::sqlx=error"));
+        assert!(EnvFilter::try_new(&directives).is_ok());
+
+        // Full paths and bare levels are passed through as they are
+        let directives = build_filter_directives("info", "chain_verse::api=trace,debug");
+        assert!(directives.ends_with(",chain_verse::api=trace,debug"));
+    }
+
+    #[test]
+    fn test_build_filter_directives_defaults() {
+        let directives = build_filter_directives("warn", "");
+        assert_eq!(directives.split(',').next(), Some("warn"));
+        assert_eq!(directives.split(',').count(), 1 + DEFAULT_QUIET_MODULES.len());
+    }
+}
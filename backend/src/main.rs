@@ -1,110 +1,682 @@
+mod anchor;
 mod api;
+mod archive;
+mod audit;
+mod auth;
+mod backfill;
+mod backup;
 mod blockchain;
+mod circuit_breaker;
+mod cli;
+mod collections;
+mod config;
 mod consts;
+mod control;
 mod database;
+mod dataset;
+mod definitions;
 mod derivation;
+mod embeddings;
+mod engagement;
+mod enhanced_rpc;
+mod error;
+mod events;
+mod explorer;
+mod export;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod health;
+mod http_cache;
+mod logging;
+mod metrics;
+mod milestones;
+mod minting;
+mod mood;
+mod notifications;
+mod openapi;
+mod poem_form;
 mod poem_generator;
+mod poem_quality;
+mod preview;
+mod prompt;
+mod provenance;
+mod publisher;
+mod rarity;
+mod rate_limit;
+mod read_cache;
+mod retry;
+mod rpc_throttle;
 mod scheduler;
+mod stats;
+mod timezone;
+mod tts;
 mod words;
 
-use anyhow::Result;
-use consts::{DEFAULT_API_PORT, DEFAULT_COLLECTION_INTERVAL_MINUTES, DEFAULT_DATABASE_URL};
+use anchor::PoemAnchor;
+use anyhow::{Context, Result};
+use api::AppState;
+use audit::ArchiveAuditor;
+use backfill::Backfill;
+use blockchain::SolanaClient;
+use circuit_breaker::CircuitBreaker;
+use clap::Parser;
+use cli::{Cli, Command};
+use config::Config;
+use control::CollectorControl;
 use database::Database;
+use definitions::DefinitionClient;
+use derivation::KeywordDerivation;
+use embeddings::EmbeddingClient;
+use events::EventBus;
+use health::HealthChecker;
+use minting::{MintConfig, PoemMinter};
+use notifications::Notifier;
+use poem_generator::PoemGenerator;
+use preview::PreviewService;
+use prompt::PromptTemplate;
+use publisher::PoemPublisher;
+use rate_limit::RateLimiter;
+use read_cache::ReadCache;
+use rpc_throttle::RpcThrottle;
 use scheduler::KeywordCollector;
-use words::WordDictionary;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+use words::{Blocklist, DictionaryHandle, ThemeSelection};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    println!("🔗 Chain Verse - Blockchain Poetry Generator\n");
-
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Configuration from environment variables
-    let api_key = std::env::var("OPENROUTER_API_KEY")
-        .expect("OPENROUTER_API_KEY must be set in .env file");
-    let model = std::env::var("OPENROUTER_MODEL")
-        .unwrap_or_else(|_| "meta-llama/llama-3.2-3b-instruct:free".to_string());
-    let interval_minutes: u64 = std::env::var("KEYWORD_INTERVAL_MINUTES")
-        .unwrap_or_else(|_| DEFAULT_COLLECTION_INTERVAL_MINUTES.to_string())
-        .parse()
-        .unwrap_or(DEFAULT_COLLECTION_INTERVAL_MINUTES);
-    let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| DEFAULT_DATABASE_URL.to_string());
-    let port: u16 = std::env::var("PORT")
-        .unwrap_or_else(|_| DEFAULT_API_PORT.to_string())
-        .parse()
-        .unwrap_or(DEFAULT_API_PORT);
-
-    // Load word dictionary
-    println!("📚 Loading word dictionary...");
-    let dictionary = WordDictionary::load()?;
-    println!("   Loaded {} words\n", dictionary.total_count());
-
-    // Initialize database
-    println!("💾 Initializing database...");
-    let db = Database::new(&database_url).await?;
-    println!("   Database ready\n");
+    // Parse arguments before logging so --help and usage errors print cleanly
+    let cli = Cli::parse();
+
+    logging::init();
+    info!("Chain Verse - Blockchain Poetry Generator");
+
+    // Configuration: defaults < config file < environment
+    // Dry runs never call OpenRouter, so they don't need its key
+    let config = if cli.command.needs_api_key() {
+        Config::load(cli.config.as_deref())?
+    } else {
+        Config::load_without_api_key(cli.config.as_deref())?
+    };
+    let api_key = config.api_key().to_string();
+    let model = config.llm.model.clone();
+    let llm_sampling = config.sampling();
+    let llm_retry = config.retry.llm();
+    let rpc_retry = config.retry.rpc();
+    let rpc_urls = config.solana.rpc_urls.clone();
+    let program_filter = config.program_filter();
+    let database_url = config.database.url.clone();
+    let timezone = config.timezone();
+    let port = config.api.port;
+    info!(
+        model = %model,
+        sampling = ?llm_sampling,
+        rpc_endpoints = rpc_urls.len(),
+        interval_minutes = config.collector.interval_minutes,
+        %timezone,
+        port,
+        "Configuration loaded"
+    );
+    if let Some(program) = program_filter {
+        info!(%program, "Sampling only transactions that touch the filtered program");
+    }
+    // Every client of a provider shares one breaker, so an outage trips them together
+    let rpc_breaker = CircuitBreaker::new("solana_rpc");
+    let llm_breaker = CircuitBreaker::new("openrouter");
+    // ...and one throttle, so together they stay under each endpoint's rate limit
+    let rpc_throttle = RpcThrottle::new(config.rpc_quota());
+    let rpc_client = || {
+        SolanaClient::with_urls(&rpc_urls)
+            .with_breaker(rpc_breaker.clone())
+            .with_throttle(rpc_throttle.clone())
+            .with_retry(rpc_retry)
+            .with_sample_size(config.solana.sample_signatures)
+    };
+    // Clients that feed keyword derivation honour the program filter and
+    // fetch parsed blocks for the enhanced sources
+    let sampling_client = || {
+        let mut client = rpc_client();
+        if let Some(program) = program_filter {
+            client = client.with_program_filter(program);
+        }
+        if config.solana.enhanced_rpc {
+            client = client.with_enhanced_rpc();
+        }
+        client
+    };
+
+    // Load word dictionary (the built-in default unless a path is configured)
+    let mut dictionary = DictionaryHandle::load(config.dictionary.path.clone())?;
+    info!(
+        words = dictionary.read().total_count(),
+        path = ?dictionary.path(),
+        "Loaded word dictionary"
+    );
+    if let Some(dir) = &config.dictionary.languages_dir {
+        dictionary = dictionary.with_languages_dir(dir.clone())?;
+    }
+    dictionary
+        .set_language(&config.poem.language)
+        .context("poem.language has no dictionary; add one to dictionary.languages_dir")?;
+    info!(
+        language = %config.poem.language,
+        languages = ?dictionary.languages(),
+        "Selected dictionary language"
+    );
+    let blocklist = match &config.dictionary.blocklist_path {
+        Some(path) => {
+            let blocklist = Blocklist::load_from(path)?;
+            info!(words = blocklist.len(), path = %path.display(), "Loaded word blocklist");
+            blocklist
+        }
+        None => Blocklist::default(),
+    };
+    let theme_selection = config.theme_selection();
+    if let Some(dir) = &config.dictionary.themes_dir {
+        dictionary = dictionary.with_themes_dir(dir.clone())?;
+        let themes = dictionary.theme_names();
+        if let Some(ThemeSelection::Fixed(theme)) = &theme_selection {
+            if !themes.contains(theme) {
+                anyhow::bail!("Dictionary theme `{}` not found in {}", theme, dir.display());
+            }
+        }
+        info!(?themes, selection = ?theme_selection, "Loaded dictionary themes");
+    }
+
+    // Prompt templates are checked for unknown placeholders before anything runs
+    let prompt = PromptTemplate::load(
+        config.poem.system_prompt.as_deref(),
+        config.poem.prompt_template.as_deref(),
+    )?;
+    info!(
+        template = ?config.poem.prompt_template,
+        system_prompt = ?config.poem.system_prompt,
+        "Loaded prompt template"
+    );
+
+    // Initialize database. The collector and the API share this one pool and
+    // its read cache, so the collector's writes clear what the API would serve.
+    // A read-only replica leaves migrations to the writer.
+    let read_only = matches!(
+        cli.command,
+        Command::Serve {
+            read_only: true,
+            ..
+        }
+    );
+    let read_cache = config
+        .read_cache_ttl()
+        .map(|ttl| Arc::new(ReadCache::new(ttl)));
+    let db = if read_only {
+        Database::open_read_only(&database_url).await?
+    } else {
+        Database::new(&database_url).await?
+    }
+    .with_timezone(timezone)
+    .with_read_cache(read_cache);
+    info!(read_only, "Database ready");
+
+    // Time-travel previews for the API, sharing the collector's configuration
+    let preview = PreviewService::new(
+        sampling_client(),
+        KeywordDerivation::with_handle(dictionary.clone()).with_blocklist(blocklist.clone()),
+        PoemGenerator::new(api_key.clone(), model.clone())
+            .with_prompt(prompt.clone())
+            .with_language(dictionary.language())
+            .with_sampling(llm_sampling)
+            .with_retry(llm_retry)
+            .with_min_keyword_fraction(config.poem.min_keyword_fraction)
+            .with_breaker(llm_breaker.clone()),
+        db.clone(),
+    )
+    .with_min_keywords_for_poem(config.collector.min_keywords_for_poem)
+    .with_form_selection(config.form_selection())
+    .with_poem_length(config.poem_length());
+
+    // Deep health checks probe the same upstreams the collector depends on
+    let health = HealthChecker::new(
+        db.clone(),
+        rpc_client(),
+        PoemGenerator::new(api_key.clone(), model.clone()).with_breaker(llm_breaker.clone()),
+        config.collector.interval_minutes,
+    )
+    .with_keywords_per_day(config.collector.keywords_per_day);
+
+    // Unfiltered, so /api/blocks/{slot} shows the whole block. Public lookups
+    // get their own breaker and pacing, so they can't use up the collector's
+    // RPC budget or trip its breaker.
+    let block_client = SolanaClient::with_urls(&rpc_urls)
+        .with_breaker(CircuitBreaker::new("solana_rpc_lookups"))
+        .with_throttle(RpcThrottle::block_lookups())
+        .with_retry(rpc_retry)
+        .with_sample_size(config.solana.sample_signatures);
+
+    let api_dictionary = dictionary.clone();
+    let api_derivation =
+        KeywordDerivation::with_handle(dictionary.clone()).with_blocklist(blocklist.clone());
+    let schedule = config.collection_schedule();
+    let frontend_dir = config.api.frontend_dir.clone();
+    let api_keys = Arc::new(config.api_keys());
+    let rate_limiter = config.rate_limit.enabled().then(|| {
+        Arc::new(
+            RateLimiter::new(
+                config.rate_limit.public_quota(),
+                config.rate_limit.admin_quota(),
+                api_keys.clone(),
+            )
+            .with_trust_forwarded_for(config.rate_limit.trust_forwarded_for),
+        )
+    });
+    let reaction_limiter = Arc::new(engagement::reaction_limiter(
+        config.rate_limit.trust_forwarded_for,
+    ));
+    // The API streams collector events only when both run in this process
+    let build_api_state =
+        move |db: Database, events: Option<EventBus>, control: Option<CollectorControl>| {
+            let mut state = AppState::new(db);
+            state.preview = Some(Arc::new(preview));
+            state.health = Some(Arc::new(match &control {
+                Some(control) => health.with_control(control.clone()),
+                None => health,
+            }));
+            state.blockchain = Some(Arc::new(block_client));
+            state.dictionary = Some(api_dictionary);
+            state.derivation = Some(Arc::new(api_derivation));
+            state.api_keys = api_keys;
+            state.rate_limiter = rate_limiter;
+            state.reaction_limiter = reaction_limiter;
+            state.events = events;
+            state.control = control;
+            state.schedule = schedule;
+            state.frontend_dir = frontend_dir;
+            state
+        };
+
+    // Reload the dictionary on SIGHUP so curators can edit the word list live
+    spawn_dictionary_reload_on_hangup(dictionary.clone());
+
+    // Historical backfill, run on demand via `chain-verse backfill <start> [end]`
+    // and by the collector on start for days that never got a poem
+    let new_backfill = || {
+        let backfill = Backfill::new(
+            sampling_client(),
+            KeywordDerivation::with_handle(dictionary.clone()).with_blocklist(blocklist.clone()),
+            db.clone(),
+            PoemGenerator::new(api_key.clone(), model.clone())
+                .with_prompt(prompt.clone())
+                .with_language(dictionary.language())
+                .with_sampling(llm_sampling)
+                .with_retry(llm_retry)
+                .with_min_keyword_fraction(config.poem.min_keyword_fraction)
+                .with_breaker(llm_breaker.clone()),
+        )
+        .with_min_keywords_for_poem(config.collector.min_keywords_for_poem)
+        .with_keywords_per_day(config.collector.keywords_per_day)
+        .with_dedup_days(config.collector.dedup_days)
+        .with_source_weights(config.source_weights())
+        .with_form_selection(config.form_selection())
+        .with_poem_length(config.poem_length());
+        match theme_selection.clone() {
+            Some(selection) => backfill.with_theme_selection(dictionary.clone(), selection),
+            None => backfill,
+        }
+    };
+    let backfill = new_backfill();
+    let self_backfill = (config.collector.self_backfill_days > 0).then(new_backfill);
+
+    // Archive audit, run on demand via `chain-verse verify-archive`
+    let auditor = ArchiveAuditor::new(KeywordDerivation::with_handle(dictionary.clone()));
+    let audit_client = sampling_client();
 
     // Create keyword collector
-    let collector = KeywordCollector::new(
+    let mut collector = KeywordCollector::new(
         dictionary,
-        db,
-        api_key,
+        db.clone(),
+        &rpc_urls,
+        api_key.clone(),
         model,
-        interval_minutes,
-    );
+        config.collector.interval_minutes,
+    )
+    .with_min_keywords_for_poem(config.collector.min_keywords_for_poem)
+    .with_keywords_per_day(config.collector.keywords_per_day)
+    .with_blocks_per_tick(config.collector.blocks_per_tick)
+    .with_keywords_per_block(config.collector.keywords_per_block)
+    .with_source_weights(config.source_weights())
+    .with_dedup_days(config.collector.dedup_days)
+    .with_event_keywords_per_day(config.collector.event_keywords_per_day)
+    .with_alignment(config.collector.align_to_interval)
+    .with_jitter(config.collector.jitter_secs)
+    .with_form_selection(config.form_selection())
+    .with_poem_length(config.poem_length())
+    .with_prompt(prompt)
+    .with_sampling(llm_sampling)
+    .with_min_keyword_fraction(config.poem.min_keyword_fraction)
+    .with_circuit_breakers(rpc_breaker, llm_breaker)
+    .with_retry_policies(rpc_retry, llm_retry)
+    .with_rpc_throttle(rpc_throttle.clone())
+    .with_sample_size(config.solana.sample_signatures)
+    .with_blocklist(blocklist);
+
+    if let Some(selection) = theme_selection {
+        collector = collector.with_theme_selection(selection);
+    }
+    if let Some(backfill) = self_backfill {
+        collector = collector.with_self_backfill(backfill, config.collector.self_backfill_days);
+    }
+    if let Some(program) = program_filter {
+        collector = collector.with_program_filter(program);
+    }
+    if config.solana.enhanced_rpc {
+        info!("Enhanced RPC entropy sources enabled");
+        collector = collector.with_enhanced_rpc();
+    }
+    if config.dictionary.definitions {
+        match DefinitionClient::for_language(&config.poem.language) {
+            Some(definitions) => {
+                info!("Word definitions enabled");
+                collector = collector.with_definitions(definitions);
+            }
+            None => warn!(
+                language = %config.poem.language,
+                "The dictionary has no definitions in this language; not looking them up"
+            ),
+        }
+    }
+    if let Some(model) = &config.llm.embedding_model {
+        info!(%model, "Poem embeddings enabled");
+        collector = collector.with_embeddings(EmbeddingClient::new(api_key, model.clone()));
+    }
+    if let Some(tts) = config.tts_client() {
+        info!(
+            voice = tts.voice(),
+            format = %tts.format(),
+            dir = %config.tts.audio_dir.display(),
+            "Poem audio enabled"
+        );
+        collector = collector.with_tts(tts);
+    }
+
+    // Optionally anchor each day's poem hash on-chain via a memo transaction
+    if let Some(keypair_path) = &config.anchor.keypair_path {
+        let anchor = PoemAnchor::new(
+            SolanaClient::with_urls(&rpc_urls).with_throttle(rpc_throttle.clone()),
+            keypair_path,
+        )?;
+        info!(signer = %anchor.signer(), "On-chain poem anchoring enabled");
+        collector = collector.with_anchor(anchor);
+    }
+
+    // Optionally mint each day's poem as a compressed NFT
+    if let Some(mint_config) = MintConfig::from_env()? {
+        let minter = PoemMinter::new(
+            SolanaClient::with_urls(&rpc_urls).with_throttle(rpc_throttle.clone()),
+            mint_config,
+        )?;
+        info!(merkle_tree = %minter.merkle_tree(), "NFT minting enabled");
+        collector = collector.with_minter(minter);
+    }
 
-    // Check command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    let mode = args.get(1).map(|s| s.as_str()).unwrap_or("test");
+    // Optionally snapshot the database on a schedule
+    if let Some(backup) = config.database_backup() {
+        info!(
+            dir = %backup.dir().display(),
+            keep = backup.keep(),
+            interval_hours = backup.interval().as_secs() / 3600,
+            "Database backups enabled"
+        );
+        collector = collector.with_backup(backup);
+    }
+
+    // Optionally push each day's poem to Discord and Telegram
+    let notify_targets = config.notify_targets();
+    if !notify_targets.is_empty() {
+        let notifier =
+            Notifier::new(notify_targets).with_failures(config.notifications.notify_failures);
+        info!(
+            targets = %notifier.targets().collect::<Vec<_>>().join(", "),
+            "Poem notifications enabled"
+        );
+        collector = collector.with_notifier(notifier);
+    }
+
+    // Optionally post each day's poem to X and Bluesky
+    let publish_targets = config.publish_targets();
+    if !publish_targets.is_empty() {
+        let publisher = PoemPublisher::new(publish_targets, config.publish_format());
+        info!(
+            targets = %publisher.targets().collect::<Vec<_>>().join(", "),
+            format = %publisher.format(),
+            "Poem publishing enabled"
+        );
+        collector = collector.with_publisher(publisher);
+    }
 
-    match mode {
-        "daemon" => {
+    match cli.command {
+        Command::Daemon => {
             // Run keyword collector continuously
-            println!("🔄 Starting keyword collector daemon...\n");
-            collector.start().await?;
+            info!("Starting keyword collector daemon");
+            collector.start(spawn_shutdown_listener()).await?;
         }
-        "api" => {
+        Command::Serve { api_only: true, .. } => {
             // Run API server only
-            println!("🌐 Starting API server...\n");
-            let db = Database::new(&database_url).await?;
-            api::serve(db, port).await?;
+            info!(read_only, "Starting API server");
+            api::serve(
+                build_api_state(db.clone(), None, None),
+                port,
+                spawn_shutdown_listener(),
+            )
+            .await?;
         }
-        "full" => {
+        Command::Serve {
+            api_only: false, ..
+        } => {
             // Run both collector and API server
-            println!("🚀 Starting full system (collector + API)...\n");
+            info!("Starting full system (collector + API)");
+
+            let shutdown_rx = spawn_shutdown_listener();
+            let events = EventBus::new();
+            let (control, commands) = CollectorControl::channel();
+            let collector = collector.with_events(events.clone()).with_control(commands);
 
             // Spawn collector in background
+            let collector_shutdown = shutdown_rx.clone();
             let collector_handle = tokio::spawn(async move {
-                if let Err(e) = collector.start().await {
-                    eprintln!("Collector error: {}", e);
+                if let Err(e) = collector.start(collector_shutdown).await {
+                    error!(error = %e, "Collector error");
                 }
             });
 
-            // Run API server in foreground
-            let db = Database::new(&database_url).await?;
+            // Run API server in foreground, on the collector's pool
+            let api_state = build_api_state(db.clone(), Some(events), Some(control));
             let api_handle = tokio::spawn(async move {
-                if let Err(e) = api::serve(db, port).await {
-                    eprintln!("API error: {}", e);
+                if let Err(e) = api::serve(api_state, port, shutdown_rx).await {
+                    error!(error = %e, "API error");
                 }
             });
 
             // Wait for both
             tokio::try_join!(collector_handle, api_handle)?;
         }
-        _ => {
-            // Run once for testing
-            println!("🧪 Running in test mode (collecting one keyword)...\n");
+        Command::Backfill { start, end } => {
+            // Fill in past days; interrupted runs over the same range resume
+            let end = end.unwrap_or(start);
+            info!(%start, %end, "Starting backfill");
+            let summary = backfill.run(start, end, spawn_shutdown_listener()).await?;
+            info!(
+                days = summary.days_processed,
+                keywords = summary.keywords_collected,
+                poems = summary.poems_generated,
+                "Backfill finished"
+            );
+        }
+        Command::Regen { date, force } => {
+            let date = date.format("%Y-%m-%d").to_string();
+            collector.regenerate_poem(&date, force).await?;
+        }
+        Command::DryRun => {
+            // Derive a keyword and print the prompt; nothing is stored or sent to the LLM
+            info!("Running in dry-run mode");
+            collector.dry_run().await?;
+        }
+        Command::Export { format, out } => {
+            let db = Database::new(&database_url).await?.with_timezone(timezone);
+            let summary = archive::export(&db, format, &out).await?;
+            info!(
+                path = %out.display(),
+                keywords = summary.keywords,
+                poems = summary.poems,
+                provenance = summary.provenance,
+                collections = summary.collections,
+                "Archive exported"
+            );
+        }
+        Command::Import { format, file } => {
+            let db = Database::new(&database_url).await?.with_timezone(timezone);
+            let summary = archive::import(&db, format, &file).await?;
+            info!(
+                path = %file.display(),
+                keywords = summary.keywords,
+                poems = summary.poems,
+                provenance = summary.provenance,
+                collections = summary.collections,
+                "Archive imported"
+            );
+        }
+        Command::ExportKeywords { format, out } => {
+            let db = Database::new(&database_url).await?.with_timezone(timezone);
+            let keywords = dataset::export_keywords(&db, format, &out).await?;
+            info!(path = %out.display(), ?format, keywords, "Keyword dataset exported");
+        }
+        Command::VerifyArchive {
+            out,
+            keypair,
+            offline,
+        } => {
+            // Read the signing key first, so a missing one fails before the audit
+            let keypair_path = match keypair {
+                Some(path) => path,
+                None => match config.anchor.keypair_path.clone() {
+                    Some(path) => path,
+                    None => anyhow::bail!(
+                        "verify-archive signs its report: pass --keypair or set anchor.keypair_path"
+                    ),
+                },
+            };
+            let keypair = read_keypair_file(&keypair_path)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", keypair_path.display(), e))?;
+
+            let auditor = if offline {
+                auditor
+            } else {
+                auditor.with_client(audit_client)
+            };
+            info!(offline, "Auditing stored keywords");
+            let report = auditor.audit(&db).await?;
+            let sig_path = audit::write_signed(&report, &keypair, &out)?;
+            info!(
+                path = %out.display(),
+                signature = %sig_path.display(),
+                signer = %keypair.pubkey(),
+                keywords = report.keywords,
+                verified = report.verified,
+                manual = report.manual,
+                skipped = report.skipped.len(),
+                mismatches = report.mismatches.len(),
+                "Audit report written"
+            );
+            if !report.mismatches.is_empty() {
+                db.close().await;
+                anyhow::bail!(
+                    "{} keywords don't match their derivation; see {}",
+                    report.mismatches.len(),
+                    out.display()
+                );
+            }
+        }
+        Command::CollectOnce => {
+            info!("Collecting one keyword");
             collector.run_once().await?;
-            println!("\n✅ Test complete!");
-            println!("\n💡 Available modes:");
-            println!("   cargo run           - Test mode (collect one keyword)");
-            println!("   cargo run -- daemon - Run keyword collector continuously");
-            println!("   cargo run -- api    - Run API server only");
-            println!("   cargo run -- full   - Run collector + API server");
+            info!("Collection complete");
         }
     }
 
+    // Only now that the collector and the API have both stopped
+    db.close().await;
+
     Ok(())
 }
+
+/// Watch channel that flips to true on ctrl-c / SIGTERM so long-running modes can stop cleanly
+fn spawn_shutdown_listener() -> watch::Receiver<bool> {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        info!("Shutdown signal received, finishing in-flight work");
+        let _ = shutdown_tx.send(true);
+    });
+    shutdown_rx
+}
+
+/// Resolve on ctrl-c or, on Unix, SIGTERM (what container runtimes send on stop)
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "Could not listen for ctrl-c");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "Could not install SIGTERM handler");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Reload the word dictionary whenever the process receives SIGHUP
+#[cfg(unix)]
+fn spawn_dictionary_reload_on_hangup(dictionary: DictionaryHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                warn!(error = %e, "Could not install SIGHUP handler");
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            match dictionary.reload() {
+                Ok(words) => info!(words, "Reloaded word dictionary on SIGHUP"),
+                Err(e) => error!(error = %e, "Dictionary reload failed, keeping previous words"),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn spawn_dictionary_reload_on_hangup(_dictionary: DictionaryHandle) {}
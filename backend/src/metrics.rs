@@ -0,0 +1,201 @@
+use prometheus::core::Collector;
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounter, IntGauge, Registry, TextEncoder};
+use std::sync::LazyLock;
+
+/// Content type for the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Registry holding every Chain Verse metric
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+/// Keywords successfully stored by the collector
+pub static KEYWORDS_COLLECTED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_keywords_collected_total",
+        "Keywords derived from blocks and stored",
+    ))
+});
+
+/// Poems generated and stored
+pub static POEMS_GENERATED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_poems_generated_total",
+        "Poems generated and stored",
+    ))
+});
+
+/// Failed Solana RPC calls
+pub static RPC_FAILURES: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_rpc_failures_total",
+        "Solana RPC calls that returned an error",
+    ))
+});
+
+/// Times a Solana RPC endpoint answered 429 Too Many Requests
+pub static RPC_THROTTLED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_rpc_throttled_total",
+        "Solana RPC calls the endpoint rate limited",
+    ))
+});
+
+/// LLM requests retried after a failed attempt
+pub static LLM_RETRIES: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_llm_retries_total",
+        "Poem generation attempts retried after a failure",
+    ))
+});
+
+/// Poem generations handed to the next model in the fallback chain
+pub static LLM_MODEL_FALLBACKS: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_llm_model_fallbacks_total",
+        "Poem generations that fell through to the next model after one failed",
+    ))
+});
+
+/// Generated poems turned down by the quality checks
+pub static POEMS_REJECTED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_poems_rejected_total",
+        "Generated poems rejected by the quality checks and sent back for a rewrite",
+    ))
+});
+
+/// Reads served from the in-process read cache
+pub static READ_CACHE_HITS: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_read_cache_hits_total",
+        "Poem and keyword reads served from the in-process cache",
+    ))
+});
+
+/// Cacheable reads that went to SQLite
+pub static READ_CACHE_MISSES: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_read_cache_misses_total",
+        "Poem and keyword reads the in-process cache could not serve",
+    ))
+});
+
+/// Days waiting in the poem retry queue
+pub static PENDING_POEMS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new(
+        "chain_verse_pending_poems",
+        "Days whose poem generation failed and is queued for retry",
+    ))
+});
+
+/// Keywords whose block the finalized chain abandoned
+pub static KEYWORDS_ORPHANED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new(
+        "chain_verse_keywords_orphaned_total",
+        "Keywords flagged orphaned because a reorg abandoned their block",
+    ))
+});
+
+/// Keywords that failed to store and are waiting to be replayed
+pub static FAILED_KEYWORDS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new(
+        "chain_verse_failed_keywords",
+        "Derived keywords whose insert failed, queued for replay",
+    ))
+});
+
+/// Unix timestamp of the last stored keyword, for staleness alerts
+pub static LAST_KEYWORD_TIMESTAMP: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new(
+        "chain_verse_last_keyword_timestamp_seconds",
+        "Unix time of the most recently stored keyword",
+    ))
+});
+
+/// Time to fetch a single block from the RPC
+/// Unix timestamp of the last successful database backup, for alerting when
+/// backups stop
+pub static LAST_BACKUP_TIMESTAMP: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new(
+        "chain_verse_last_backup_timestamp_seconds",
+        "Unix time of the most recent successful database backup",
+    ))
+});
+
+pub static BLOCK_FETCH_SECONDS: LazyLock<Histogram> = LazyLock::new(|| {
+    register(Histogram::with_opts(
+        HistogramOpts::new(
+            "chain_verse_block_fetch_seconds",
+            "Latency of fetching a block from Solana RPC",
+        )
+        .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+    ))
+});
+
+/// Time to generate a poem, including retries
+pub static POEM_GENERATION_SECONDS: LazyLock<Histogram> = LazyLock::new(|| {
+    register(Histogram::with_opts(
+        HistogramOpts::new(
+            "chain_verse_poem_generation_seconds",
+            "Latency of generating a poem via the LLM, including retries",
+        )
+        .buckets(vec![1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0]),
+    ))
+});
+
+/// Register a freshly built metric and hand it back
+fn register<C: Collector + Clone + 'static>(metric: prometheus::Result<C>) -> C {
+    let metric = metric.expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(metric.clone()))
+        .expect("metric registered once");
+    metric
+}
+
+/// Render all metrics in the Prometheus text format
+pub fn render() -> String {
+    // Touch every metric so it is exported (as zero) before its first update
+    LazyLock::force(&KEYWORDS_COLLECTED);
+    LazyLock::force(&POEMS_GENERATED);
+    LazyLock::force(&KEYWORDS_ORPHANED);
+    LazyLock::force(&RPC_FAILURES);
+    LazyLock::force(&RPC_THROTTLED);
+    LazyLock::force(&LLM_RETRIES);
+    LazyLock::force(&LLM_MODEL_FALLBACKS);
+    LazyLock::force(&POEMS_REJECTED);
+    LazyLock::force(&READ_CACHE_HITS);
+    LazyLock::force(&READ_CACHE_MISSES);
+    LazyLock::force(&LAST_KEYWORD_TIMESTAMP);
+    LazyLock::force(&LAST_BACKUP_TIMESTAMP);
+    LazyLock::force(&BLOCK_FETCH_SECONDS);
+    LazyLock::force(&POEM_GENERATION_SECONDS);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("text encoding never fails");
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_all_metrics() {
+        KEYWORDS_COLLECTED.inc();
+        let output = render();
+
+        assert!(output.contains("chain_verse_keywords_collected_total"));
+        assert!(output.contains("chain_verse_poems_generated_total"));
+        assert!(output.contains("chain_verse_rpc_failures_total"));
+        assert!(output.contains("chain_verse_rpc_throttled_total"));
+        assert!(output.contains("chain_verse_llm_retries_total"));
+        assert!(output.contains("chain_verse_llm_model_fallbacks_total"));
+        assert!(output.contains("chain_verse_poems_rejected_total"));
+        assert!(output.contains("chain_verse_read_cache_hits_total"));
+        assert!(output.contains("chain_verse_read_cache_misses_total"));
+        assert!(output.contains("chain_verse_block_fetch_seconds_bucket"));
+        assert!(output.contains("chain_verse_poem_generation_seconds_bucket"));
+    }
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::Serialize;
+use tracing::info;
+
+use crate::database::{Database, StoredMilestone};
+
+/// Poem counts worth celebrating
+const POEM_MILESTONES: &[i64] = &[1, 10, 50, 100, 250, 365, 500, 1000];
+
+/// Keyword counts worth celebrating
+const KEYWORD_MILESTONES: &[i64] = &[100, 500, 1000, 2500, 5000, 10000];
+
+/// Consecutive-day streaks worth celebrating
+const STREAK_MILESTONES: &[i64] = &[7, 30, 100, 365];
+
+/// Kind of achievement a milestone tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneKind {
+    Poems,
+    Keywords,
+    Streak,
+}
+
+impl MilestoneKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MilestoneKind::Poems => "poems",
+            MilestoneKind::Keywords => "keywords",
+            MilestoneKind::Streak => "streak",
+        }
+    }
+
+    fn thresholds(&self) -> &'static [i64] {
+        match self {
+            MilestoneKind::Poems => POEM_MILESTONES,
+            MilestoneKind::Keywords => KEYWORD_MILESTONES,
+            MilestoneKind::Streak => STREAK_MILESTONES,
+        }
+    }
+}
+
+/// The next milestone of a kind that hasn't been reached yet
+#[derive(Debug, Clone, Serialize)]
+pub struct UpcomingMilestone {
+    pub kind: MilestoneKind,
+    pub value: i64,
+    pub remaining: i64,
+}
+
+/// Response for GET /api/stats/milestones
+#[derive(Debug, Clone, Serialize)]
+pub struct MilestoneSummary {
+    pub total_poems: i64,
+    pub total_keywords: i64,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub achieved: Vec<StoredMilestone>,
+    pub upcoming: Vec<UpcomingMilestone>,
+}
+
+/// A reached milestone in a sentence, for notifications and posts
+pub fn announcement(kind: &str, value: i64) -> String {
+    match kind {
+        "poems" if value == 1 => "Chain Verse wrote its first poem".to_string(),
+        "poems" => format!("Chain Verse has written {} poems", value),
+        "keywords" => format!("Chain Verse has collected {} keywords from Solana", value),
+        "streak" => format!("Chain Verse has written a poem {} days in a row", value),
+        _ => format!("Chain Verse reached {} {}", value, kind),
+    }
+}
+
+/// Record any newly reached milestones as of `date` and return them
+/// Each milestone is only ever recorded once
+pub async fn check_milestones(db: &Database, date: &str) -> Result<Vec<StoredMilestone>> {
+    let totals = [
+        (MilestoneKind::Poems, db.count_poems().await?),
+        (MilestoneKind::Keywords, db.count_keywords().await?),
+        (MilestoneKind::Streak, current_streak(&db.get_poem_dates().await?, date)),
+    ];
+
+    let mut reached = Vec::new();
+    for (kind, total) in totals {
+        for &value in kind.thresholds().iter().filter(|&&value| value <= total) {
+            if let Some(milestone) = db.insert_milestone(kind.as_str(), value, date).await? {
+                info!(
+                    kind = kind.as_str(),
+                    value,
+                    %date,
+                    "🎉 Milestone reached: {} {}",
+                    value,
+                    kind.as_str()
+                );
+                reached.push(milestone);
+            }
+        }
+    }
+
+    Ok(reached)
+}
+
+/// Build the milestone summary served by the API
+pub async fn summarize(db: &Database, today: &str) -> Result<MilestoneSummary> {
+    let total_poems = db.count_poems().await?;
+    let total_keywords = db.count_keywords().await?;
+    let dates = db.get_poem_dates().await?;
+    let current = current_streak(&dates, today);
+
+    let upcoming = [
+        (MilestoneKind::Poems, total_poems),
+        (MilestoneKind::Keywords, total_keywords),
+        (MilestoneKind::Streak, current),
+    ]
+    .into_iter()
+    .filter_map(|(kind, total)| {
+        kind.thresholds()
+            .iter()
+            .find(|&&value| value > total)
+            .map(|&value| UpcomingMilestone {
+                kind,
+                value,
+                remaining: value - total,
+            })
+    })
+    .collect();
+
+    Ok(MilestoneSummary {
+        total_poems,
+        total_keywords,
+        current_streak: current,
+        longest_streak: longest_streak(&dates),
+        achieved: db.get_milestones().await?,
+        upcoming,
+    })
+}
+
+/// Consecutive days with poems ending at `today`, or at the day before if
+/// today's poem hasn't been generated yet. `dates` must be sorted ascending.
+pub fn current_streak(dates: &[String], today: &str) -> i64 {
+    let Ok(today) = NaiveDate::parse_from_str(today, "%Y-%m-%d") else {
+        return 0;
+    };
+    let days = parse_dates(dates);
+
+    let mut expected = match days.last() {
+        Some(&last) if last == today => today,
+        Some(&last) if Some(last) == today.pred_opt() => last,
+        _ => return 0,
+    };
+
+    let mut streak = 0;
+    for &day in days.iter().rev() {
+        if day != expected {
+            break;
+        }
+        streak += 1;
+        match expected.pred_opt() {
+            Some(previous) => expected = previous,
+            None => break,
+        }
+    }
+    streak
+}
+
+/// Longest run of consecutive days with poems. `dates` must be sorted ascending.
+pub fn longest_streak(dates: &[String]) -> i64 {
+    let days = parse_dates(dates);
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for day in days {
+        run = match previous {
+            Some(prev) if prev.succ_opt() == Some(day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+    longest
+}
+
+fn parse_dates(dates: &[String]) -> Vec<NaiveDate> {
+    dates
+        .iter()
+        .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(list: &[&str]) -> Vec<String> {
+        list.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_current_streak() {
+        let history = dates(&["2026-01-01", "2026-01-03", "2026-01-04", "2026-01-05"]);
+        assert_eq!(current_streak(&history, "2026-01-05"), 3);
+        // Today's poem not generated yet: the streak through yesterday still counts
+        assert_eq!(current_streak(&history, "2026-01-06"), 3);
+        assert_eq!(current_streak(&history, "2026-01-07"), 0);
+        assert_eq!(current_streak(&[], "2026-01-07"), 0);
+    }
+
+    #[test]
+    fn test_longest_streak() {
+        let history = dates(&[
+            "2026-01-01", "2026-01-02", "2026-01-03", "2026-01-05", "2026-01-06",
+        ]);
+        assert_eq!(longest_streak(&history), 3);
+        assert_eq!(longest_streak(&[]), 0);
+    }
+
+    #[test]
+    fn test_announcement() {
+        assert_eq!(announcement("poems", 1), "Chain Verse wrote its first poem");
+        assert_eq!(
+            announcement("poems", 100),
+            "Chain Verse has written 100 poems"
+        );
+        assert_eq!(
+            announcement(MilestoneKind::Streak.as_str(), 30),
+            "Chain Verse has written a poem 30 days in a row"
+        );
+    }
+}
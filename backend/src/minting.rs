@@ -0,0 +1,240 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::blockchain::SolanaClient;
+use crate::database::{StoredKeyword, StoredPoem};
+
+/// Symbol shown by wallets for minted poems
+const POEM_SYMBOL: &str = "VERSE";
+
+/// Metaplex JSON metadata for a poem, served at /api/poems/{date}/metadata.json
+pub fn poem_metadata(poem: &StoredPoem, keywords: &[StoredKeyword], base_url: &str) -> serde_json::Value {
+    let words: Vec<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+    // Curator-added keywords carry negative placeholder slots, not real ones
+    let slots = || keywords.iter().map(|k| k.slot).filter(|&slot| slot >= 0);
+    let first_slot = slots().min();
+    let last_slot = slots().max();
+
+    let mut metadata = json!({
+        "name": poem_name(&poem.date),
+        "symbol": POEM_SYMBOL,
+        "description": poem.content,
+        "external_url": format!("{}/api/poems/{}", base_url.trim_end_matches('/'), poem.date),
+        "attributes": [
+            { "trait_type": "date", "value": poem.date },
+            { "trait_type": "keywords", "value": words.join(", ") },
+            { "trait_type": "keyword_count", "value": keywords.len() },
+            { "trait_type": "first_slot", "value": first_slot },
+            { "trait_type": "last_slot", "value": last_slot },
+        ],
+        "properties": {
+            "category": "text",
+            "keywords": keywords.iter().map(|k| json!({
+                "word": k.word,
+                "slot": k.slot,
+                "blockhash": k.blockhash,
+                "source": k.source,
+                "theme": k.theme,
+            })).collect::<Vec<_>>(),
+        },
+    });
+
+    if let (Some(title), Some(attributes)) = (&poem.title, metadata["attributes"].as_array_mut()) {
+        attributes.push(json!({ "trait_type": "title", "value": title }));
+    }
+
+    metadata
+}
+
+/// On-chain name for a poem (Metaplex caps names at 32 bytes)
+fn poem_name(date: &str) -> String {
+    format!("Chain Verse {}", date)
+}
+
+/// Minting configuration, read from the environment
+///
+/// MINT_KEYPAIR_PATH  - payer and tree authority keypair (enables minting)
+/// MINT_MERKLE_TREE   - Bubblegum merkle tree address
+/// MINT_OWNER         - recipient of minted poems (defaults to the payer)
+/// PUBLIC_BASE_URL    - public API origin used for metadata URIs
+#[derive(Debug, Clone)]
+pub struct MintConfig {
+    pub keypair_path: String,
+    pub merkle_tree: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub public_base_url: String,
+}
+
+impl MintConfig {
+    /// Read the config; returns None when minting is not configured
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(keypair_path) = std::env::var("MINT_KEYPAIR_PATH") else {
+            return Ok(None);
+        };
+
+        let merkle_tree = std::env::var("MINT_MERKLE_TREE")
+            .context("MINT_MERKLE_TREE must be set when MINT_KEYPAIR_PATH is set")?;
+        let owner = std::env::var("MINT_OWNER")
+            .ok()
+            .map(|owner| Pubkey::from_str(&owner).context("Invalid MINT_OWNER"))
+            .transpose()?;
+        let public_base_url = std::env::var("PUBLIC_BASE_URL")
+            .context("PUBLIC_BASE_URL must be set so minted poems can link their metadata")?;
+
+        Ok(Some(Self {
+            keypair_path,
+            merkle_tree: Pubkey::from_str(&merkle_tree).context("Invalid MINT_MERKLE_TREE")?,
+            owner,
+            public_base_url,
+        }))
+    }
+}
+
+/// Mints generated poems as compressed NFTs (Metaplex Bubblegum)
+pub struct PoemMinter {
+    solana_client: SolanaClient,
+    payer: Arc<Keypair>,
+    config: MintConfig,
+}
+
+impl PoemMinter {
+    pub fn new(solana_client: SolanaClient, config: MintConfig) -> Result<Self> {
+        if !cfg!(feature = "nft-minting") {
+            anyhow::bail!("MINT_KEYPAIR_PATH is set but this build lacks the nft-minting feature");
+        }
+
+        let payer = read_keypair_file(&config.keypair_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", config.keypair_path, e))?;
+
+        Ok(Self {
+            solana_client,
+            payer: Arc::new(payer),
+            config,
+        })
+    }
+
+    /// Merkle tree poems are minted into
+    pub fn merkle_tree(&self) -> Pubkey {
+        self.config.merkle_tree
+    }
+
+    /// Mint a poem and return the transaction signature
+    pub async fn mint_poem(&self, poem: &StoredPoem) -> Result<String> {
+        let uri = format!(
+            "{}/api/poems/{}/metadata.json",
+            self.config.public_base_url.trim_end_matches('/'),
+            poem.date
+        );
+        let owner = self.config.owner.unwrap_or_else(|| self.payer.pubkey());
+        let instruction = self.mint_instruction(&poem.date, &uri, owner)?;
+
+        let signature = self
+            .solana_client
+            .send_instructions(vec![instruction], Arc::clone(&self.payer))
+            .await?;
+
+        info!(date = %poem.date, %signature, %owner, "Minted poem as compressed NFT");
+        Ok(signature)
+    }
+
+    #[cfg(feature = "nft-minting")]
+    fn mint_instruction(
+        &self,
+        date: &str,
+        uri: &str,
+        owner: Pubkey,
+    ) -> Result<solana_sdk::instruction::Instruction> {
+        use mpl_bubblegum::accounts::TreeConfig;
+        use mpl_bubblegum::instructions::MintV1Builder;
+        use mpl_bubblegum::types::{MetadataArgs, TokenProgramVersion, TokenStandard};
+
+        let (tree_config, _) = TreeConfig::find_pda(&self.config.merkle_tree);
+
+        let metadata = MetadataArgs {
+            name: poem_name(date),
+            symbol: POEM_SYMBOL.to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![],
+        };
+
+        Ok(MintV1Builder::new()
+            .tree_config(tree_config)
+            .leaf_owner(owner)
+            .leaf_delegate(owner)
+            .merkle_tree(self.config.merkle_tree)
+            .payer(self.payer.pubkey())
+            .tree_creator_or_delegate(self.payer.pubkey())
+            .metadata(metadata)
+            .instruction())
+    }
+
+    #[cfg(not(feature = "nft-minting"))]
+    fn mint_instruction(
+        &self,
+        _date: &str,
+        _uri: &str,
+        _owner: Pubkey,
+    ) -> Result<solana_sdk::instruction::Instruction> {
+        anyhow::bail!("Built without the nft-minting feature")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poem_metadata() {
+        let poem = StoredPoem {
+            id: 1,
+            date: "2026-01-05".to_string(),
+            title: None,
+            content: "moon over water".to_string(),
+            keyword_ids: vec![1, 2],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: "2026-01-05 23:00:00".to_string(),
+        };
+        let keyword = |id: i64, word: &str, slot: i64| StoredKeyword {
+            id,
+            word: word.to_string(),
+            slot,
+            blockhash: format!("hash{}", id),
+            previous_blockhash: None,
+            block_time: None,
+            word_index: 0,
+            source: "blockhash".to_string(),
+            theme: None,
+            rerolls: 0,
+            tag: None,
+            epoch: None,
+            orphaned: false,
+            created_at: "2026-01-05 12:00:00".to_string(),
+        };
+        let keywords = vec![keyword(1, "moon", 200), keyword(2, "water", 100)];
+
+        let metadata = poem_metadata(&poem, &keywords, "https://verse.example/");
+
+        assert_eq!(metadata["name"], "Chain Verse 2026-01-05");
+        assert!(metadata["name"].as_str().unwrap().len() <= 32);
+        assert_eq!(metadata["description"], "moon over water");
+        assert_eq!(metadata["external_url"], "https://verse.example/api/poems/2026-01-05");
+        assert_eq!(metadata["attributes"][1]["value"], "moon, water");
+        assert_eq!(metadata["attributes"][3]["value"], 100);
+        assert_eq!(metadata["attributes"][4]["value"], 200);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::blockchain::BlockInfo;
+use crate::consts::{
+    MOOD_BUSY_TRANSACTIONS_PER_BLOCK, MOOD_HEAVY_FEE_LAMPORTS, MOOD_QUIET_TRANSACTIONS_PER_BLOCK,
+};
+
+/// Totals over the blocks a day's keywords were derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockActivity {
+    pub blocks: i64,
+    /// Every transaction, votes included
+    pub transactions: i64,
+    pub non_vote_transactions: i64,
+    /// Lamports paid in fees
+    pub fees: i64,
+}
+
+impl BlockActivity {
+    pub fn add(&mut self, block: &BlockInfo) {
+        self.blocks += 1;
+        self.transactions +=
+            (block.vote_transaction_count + block.non_vote_transaction_count) as i64;
+        self.non_vote_transactions += block.non_vote_transaction_count as i64;
+        self.fees += block.total_fees as i64;
+    }
+
+    pub fn of<'a>(blocks: impl IntoIterator<Item = &'a BlockInfo>) -> Self {
+        let mut activity = Self::default();
+        for block in blocks {
+            activity.add(block);
+        }
+        activity
+    }
+}
+
+/// Tone of a chain day, from how busy its blocks were and what they paid
+///
+/// Derived only from `BlockActivity`, so the same blocks always give the
+/// same mood: busy (MOOD_BUSY_TRANSACTIONS_PER_BLOCK non-vote transactions
+/// per block or more), quiet (under MOOD_QUIET_TRANSACTIONS_PER_BLOCK) or in
+/// between, each with light or heavy fees (MOOD_HEAVY_FEE_LAMPORTS per
+/// transaction or more).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mood {
+    /// Busy, heavy fees
+    Frenetic,
+    /// Busy, light fees
+    Bustling,
+    /// In between, heavy fees
+    Heavy,
+    /// In between, light fees
+    Steady,
+    /// Quiet, heavy fees
+    Brooding,
+    /// Quiet, light fees
+    Tranquil,
+}
+
+impl Mood {
+    /// The mood of `activity`; None without any blocks to judge by
+    pub fn of(activity: &BlockActivity) -> Option<Self> {
+        if activity.blocks <= 0 {
+            return None;
+        }
+        let per_block = activity.non_vote_transactions.max(0) as u64 / activity.blocks as u64;
+        let heavy = activity.transactions > 0
+            && activity.fees.max(0) as u64 / activity.transactions as u64
+                >= MOOD_HEAVY_FEE_LAMPORTS;
+
+        Some(match (per_block, heavy) {
+            (n, true) if n >= MOOD_BUSY_TRANSACTIONS_PER_BLOCK => Mood::Frenetic,
+            (n, false) if n >= MOOD_BUSY_TRANSACTIONS_PER_BLOCK => Mood::Bustling,
+            (n, true) if n < MOOD_QUIET_TRANSACTIONS_PER_BLOCK => Mood::Brooding,
+            (n, false) if n < MOOD_QUIET_TRANSACTIONS_PER_BLOCK => Mood::Tranquil,
+            (_, true) => Mood::Heavy,
+            (_, false) => Mood::Steady,
+        })
+    }
+
+    /// Stored and API name of the mood
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mood::Frenetic => "frenetic",
+            Mood::Bustling => "bustling",
+            Mood::Heavy => "heavy",
+            Mood::Steady => "steady",
+            Mood::Brooding => "brooding",
+            Mood::Tranquil => "tranquil",
+        }
+    }
+
+    /// How the mood reads in the prompt's `{mood}` placeholder
+    pub fn description(&self) -> &'static str {
+        match self {
+            Mood::Frenetic => {
+                "frenetic: the chain was crowded and fees ran high, so let the poem feel restless and urgent"
+            }
+            Mood::Bustling => {
+                "bustling: the chain was busy but cheap, so let the poem feel lively and full of motion"
+            }
+            Mood::Heavy => {
+                "heavy: ordinary traffic paid steep fees, so let the poem feel weighted and deliberate"
+            }
+            Mood::Steady => {
+                "steady: an ordinary day on the chain, so let the poem keep an even, unhurried pace"
+            }
+            Mood::Brooding => {
+                "brooding: few transactions, but costly ones, so let the poem feel sparse and tense"
+            }
+            Mood::Tranquil => {
+                "tranquil: the chain was quiet and cheap, so let the poem feel calm and spacious"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Mood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(non_votes_per_block: i64, fee_per_transaction: i64) -> BlockActivity {
+        let blocks = 4;
+        let transactions = blocks * (non_votes_per_block + 1000);
+        BlockActivity {
+            blocks,
+            transactions,
+            non_vote_transactions: blocks * non_votes_per_block,
+            fees: transactions * fee_per_transaction,
+        }
+    }
+
+    #[test]
+    fn test_mood_of_activity() {
+        assert_eq!(Mood::of(&activity(900, 20_000)), Some(Mood::Frenetic));
+        assert_eq!(Mood::of(&activity(800, 5_000)), Some(Mood::Bustling));
+        assert_eq!(Mood::of(&activity(500, 15_000)), Some(Mood::Heavy));
+        assert_eq!(Mood::of(&activity(300, 14_999)), Some(Mood::Steady));
+        assert_eq!(Mood::of(&activity(100, 30_000)), Some(Mood::Brooding));
+        assert_eq!(Mood::of(&activity(0, 0)), Some(Mood::Tranquil));
+        assert_eq!(Mood::of(&BlockActivity::default()), None);
+    }
+}
@@ -0,0 +1,254 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::milestones;
+
+const NOTIFY_TIMEOUT_SECS: u64 = 10;
+
+/// Discord rejects webhook messages longer than this
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// Telegram rejects messages longer than this
+const TELEGRAM_MAX_CHARS: usize = 4096;
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// A channel new poems are pushed to
+#[derive(Clone, PartialEq, Eq)]
+pub enum NotifyTarget {
+    /// A Discord channel's incoming webhook
+    Discord { webhook_url: String },
+    /// A Telegram chat, group, or channel the bot has been added to
+    Telegram { bot_token: String, chat_id: String },
+}
+
+impl NotifyTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotifyTarget::Discord { .. } => "discord",
+            NotifyTarget::Telegram { .. } => "telegram",
+        }
+    }
+
+    /// URL and JSON body that deliver `text`, cut to the service's limit
+    fn request(&self, text: &str) -> (String, Value) {
+        match self {
+            NotifyTarget::Discord { webhook_url } => (
+                webhook_url.clone(),
+                json!({
+                    "content": truncate(text, DISCORD_MAX_CHARS),
+                    // A poem that happens to say @everyone shouldn't ping anyone
+                    "allowed_mentions": { "parse": [] },
+                }),
+            ),
+            NotifyTarget::Telegram { bot_token, chat_id } => (
+                format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, bot_token),
+                json!({
+                    "chat_id": chat_id,
+                    "text": truncate(text, TELEGRAM_MAX_CHARS),
+                    "disable_web_page_preview": true,
+                }),
+            ),
+        }
+    }
+}
+
+/// Something worth telling the channels about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification<'a> {
+    /// A day's poem was generated and stored
+    Poem {
+        date: &'a str,
+        title: Option<&'a str>,
+        content: &'a str,
+        keywords: &'a [String],
+    },
+    /// A day's poem failed to generate and was queued for another attempt
+    PoemFailed {
+        date: &'a str,
+        attempts: i64,
+        next_attempt_at: &'a str,
+        error: &'a str,
+    },
+    /// A poem, keyword, or streak milestone was reached on `date`
+    Milestone {
+        kind: &'a str,
+        value: i64,
+        date: &'a str,
+    },
+}
+
+impl Notification<'_> {
+    /// The message as plain text, the same for every service
+    pub fn text(&self) -> String {
+        match self {
+            Notification::Poem {
+                date,
+                title,
+                content,
+                keywords,
+            } => {
+                let heading = match title {
+                    Some(title) => format!("{}\nChain Verse, {}", title, date),
+                    None => format!("Chain Verse, {}", date),
+                };
+                format!(
+                    "{}\n\n{}\n\nKeywords: {}",
+                    heading,
+                    content.trim(),
+                    keywords.join(", ")
+                )
+            }
+            Notification::PoemFailed {
+                date,
+                attempts,
+                next_attempt_at,
+                error,
+            } => format!(
+                "Chain Verse: the poem for {} failed to generate (attempt {}). \
+                 Retrying at {} UTC.\n\n{}",
+                date, attempts, next_attempt_at, error
+            ),
+            Notification::Milestone { kind, value, date } => {
+                format!("{} ({}).", milestones::announcement(kind, *value), date)
+            }
+        }
+    }
+}
+
+/// Posts poems, milestones, and optionally failures, to Discord webhooks and
+/// Telegram chats
+///
+/// Delivery is best effort: a target that fails is logged and skipped, and
+/// never holds up the collector.
+pub struct Notifier {
+    client: reqwest::Client,
+    targets: Vec<NotifyTarget>,
+    failures: bool,
+}
+
+impl Notifier {
+    pub fn new(targets: Vec<NotifyTarget>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(NOTIFY_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            targets,
+            failures: true,
+        }
+    }
+
+    /// Whether to report poems that failed to generate (on by default)
+    pub fn with_failures(mut self, failures: bool) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    pub fn targets(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.targets.iter().map(NotifyTarget::name)
+    }
+
+    /// Send `notification` to every target
+    pub async fn send(&self, notification: &Notification<'_>) {
+        if matches!(notification, Notification::PoemFailed { .. }) && !self.failures {
+            return;
+        }
+        let text = notification.text();
+        for target in &self.targets {
+            match self.post(target, &text).await {
+                Ok(()) => info!(target = target.name(), "Notification sent"),
+                Err(e) => warn!(target = target.name(), error = %e, "Failed to send notification"),
+            }
+        }
+    }
+
+    async fn post(&self, target: &NotifyTarget, text: &str) -> Result<()> {
+        let (url, body) = target.request(text);
+        // The URL carries the webhook or bot secret, so keep it out of errors
+        self.client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.without_url())
+            .with_context(|| format!("Failed to reach {}", target.name()))?
+            .error_for_status()
+            .map_err(|e| e.without_url())
+            .with_context(|| format!("{} rejected the notification", target.name()))?;
+        Ok(())
+    }
+}
+
+/// `text` cut to at most `max` characters, ending in an ellipsis when cut
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max - 1).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poem_text() {
+        let keywords = vec!["ember".to_string(), "tide".to_string()];
+        let poem = Notification::Poem {
+            date: "2026-01-05",
+            title: Some("Ledger of Tides"),
+            content: "Embers in the rain\n",
+            keywords: &keywords,
+        };
+        assert_eq!(
+            poem.text(),
+            "Ledger of Tides\nChain Verse, 2026-01-05\n\nEmbers in the rain\n\nKeywords: ember, tide"
+        );
+
+        let failed = Notification::PoemFailed {
+            date: "2026-01-05",
+            attempts: 2,
+            next_attempt_at: "2026-01-05 14:00:00",
+            error: "OpenRouter returned 503",
+        };
+        assert!(failed.text().contains("attempt 2"));
+        assert!(failed.text().ends_with("OpenRouter returned 503"));
+
+        let milestone = Notification::Milestone {
+            kind: "poems",
+            value: 100,
+            date: "2026-01-05",
+        };
+        assert_eq!(
+            milestone.text(),
+            "Chain Verse has written 100 poems (2026-01-05)."
+        );
+    }
+
+    #[test]
+    fn test_requests() {
+        let discord = NotifyTarget::Discord {
+            webhook_url: "https://discord.com/api/webhooks/1/abc".to_string(),
+        };
+        let (url, body) = discord.request(&"é".repeat(3000));
+        assert_eq!(url, "https://discord.com/api/webhooks/1/abc");
+        let content = body["content"].as_str().unwrap();
+        assert_eq!(content.chars().count(), DISCORD_MAX_CHARS);
+        assert!(content.ends_with('…'));
+        assert_eq!(body["allowed_mentions"]["parse"], json!([]));
+
+        let telegram = NotifyTarget::Telegram {
+            bot_token: "123:secret".to_string(),
+            chat_id: "@chain_verse".to_string(),
+        };
+        let (url, body) = telegram.request("a poem");
+        assert_eq!(url, "https://api.telegram.org/bot123:secret/sendMessage");
+        assert_eq!(body["chat_id"], "@chain_verse");
+        assert_eq!(body["text"], "a poem");
+    }
+}
@@ -0,0 +1,1525 @@
+use serde_json::{json, Map, Value};
+
+use crate::auth::Role;
+use crate::consts::{
+    DEFAULT_API_USAGE_LIMIT, DEFAULT_COOCCURRENCE_LIMIT, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SIMILAR_LIMIT, DEFAULT_STATS_TOP_WORDS, MAX_ANNOTATION_CHARS, MAX_API_USAGE_LIMIT,
+    MAX_COOCCURRENCE_LIMIT, MAX_SEARCH_LIMIT, MAX_SIMILAR_LIMIT, MAX_STATS_TOP_WORDS,
+    POEM_REACTIONS,
+};
+
+/// Swagger UI assets, loaded by the /api/docs page
+const SWAGGER_UI_URL: &str = "https://unpkg.com/swagger-ui-dist@5";
+
+/// What an operation answers with on success
+enum Body {
+    /// JSON matching a schema (see `schema`)
+    Json(&'static str),
+    /// JSON for something the request created, with 201
+    Created(&'static str),
+    /// JSON acknowledging work queued for later, with 202
+    Accepted(&'static str),
+    /// Anything else, by media type
+    Media(&'static str),
+}
+
+#[derive(Clone, Copy)]
+enum In {
+    Path,
+    Query,
+}
+
+struct Param {
+    name: &'static str,
+    location: In,
+    /// `string` or `integer`
+    kind: &'static str,
+    required: bool,
+    description: &'static str,
+    /// Default and maximum of a limit parameter
+    bounds: Option<(i64, i64)>,
+}
+
+const fn path(name: &'static str, kind: &'static str, description: &'static str) -> Param {
+    Param {
+        name,
+        location: In::Path,
+        kind,
+        required: true,
+        description,
+        bounds: None,
+    }
+}
+
+const fn query(name: &'static str, kind: &'static str, description: &'static str) -> Param {
+    Param {
+        name,
+        location: In::Query,
+        kind,
+        required: false,
+        description,
+        bounds: None,
+    }
+}
+
+const fn required_query(name: &'static str, description: &'static str) -> Param {
+    Param {
+        required: true,
+        ..query(name, "string", description)
+    }
+}
+
+const fn limit(name: &'static str, description: &'static str, default: i64, max: i64) -> Param {
+    Param {
+        bounds: Some((default, max)),
+        ..query(name, "integer", description)
+    }
+}
+
+const DATE: Param = path("date", "string", "Day in UTC, `YYYY-MM-DD`");
+
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    params: &'static [Param],
+    /// Schema of the JSON request body, if any
+    request: Option<&'static str>,
+    response: Body,
+    /// Error statuses worth documenting besides the generic 500
+    errors: &'static [u16],
+    /// API key role required (see `auth`); None for public routes
+    role: Option<Role>,
+}
+
+/// Every REST route, in README order
+///
+/// Keep this in step with `api::create_router`; the tests check it against
+/// the router and the README's endpoint list.
+const OPERATIONS: &[Operation] = &[
+    Operation {
+        method: "get",
+        path: "/api/poems/today",
+        tag: "poems",
+        summary: "Today's poem status, with keywords collected against the daily target",
+        params: &[],
+        request: None,
+        response: Body::Json("TodayStatus"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems",
+        tag: "poems",
+        summary: "All poems, latest first",
+        params: &[],
+        request: None,
+        response: Body::Json("Poem[]"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/random",
+        tag: "poems",
+        summary: "One poem chosen at random",
+        params: &[],
+        request: None,
+        response: Body::Json("Poem"),
+        errors: &[404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/on-this-day",
+        tag: "poems",
+        summary: "Poems from earlier years on the same month and day, newest first",
+        params: &[query(
+            "date",
+            "string",
+            "Day to look back from (default today)",
+        )],
+        request: None,
+        response: Body::Json("Poem[]"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/search",
+        tag: "poems",
+        summary: "Full-text search over poem titles and text, best match first",
+        params: &[
+            required_query("q", "Words that must all appear; words are stemmed"),
+            limit(
+                "limit",
+                "Poems to return",
+                DEFAULT_SEARCH_LIMIT,
+                MAX_SEARCH_LIMIT,
+            ),
+        ],
+        request: None,
+        response: Body::Json("PoemSearchResult"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/archive",
+        tag: "poems",
+        summary: "Poem dates grouped by month, for calendar navigation",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}",
+        tag: "poems",
+        summary: "The poem for a date, with its anchor once anchored on-chain",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("Poem"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}.txt",
+        tag: "poems",
+        summary: "The poem as plain text, with its date and keywords as a footer",
+        params: &[DATE],
+        request: None,
+        response: Body::Media("text/plain"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}.md",
+        tag: "poems",
+        summary: "The poem as Markdown, with its date and keywords as a footer",
+        params: &[DATE],
+        request: None,
+        response: Body::Media("text/markdown"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}.png",
+        tag: "poems",
+        summary: "The poem as a 1080px-wide image",
+        params: &[DATE],
+        request: None,
+        response: Body::Media("image/png"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}/metadata.json",
+        tag: "poems",
+        summary: "Metaplex NFT metadata for the poem",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}/provenance",
+        tag: "poems",
+        summary: "Reproducibility manifest recorded when the poem was generated, with curator notes on its keywords",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}/similar",
+        tag: "poems",
+        summary: "Poems closest in theme to the day's poem, by embedding similarity",
+        params: &[
+            DATE,
+            limit(
+                "limit",
+                "Poems to return",
+                DEFAULT_SIMILAR_LIMIT,
+                MAX_SIMILAR_LIMIT,
+            ),
+        ],
+        request: None,
+        response: Body::Json("SimilarPoem[]"),
+        errors: &[404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/{date}/audio",
+        tag: "poems",
+        summary: "The poem read aloud, as MP3 or Ogg Opus (404 until it has been narrated)",
+        params: &[DATE],
+        request: None,
+        response: Body::Media("audio/*"),
+        errors: &[404],
+        role: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/poems/{date}/react",
+        tag: "poems",
+        summary: "Leave an emoji reaction on the poem (rate limited per IP)",
+        params: &[DATE],
+        request: Some("ReactionRequest"),
+        response: Body::Json("PoemEngagement"),
+        errors: &[400, 404, 503],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/week/{week}",
+        tag: "poems",
+        summary: "The daily poems of an ISO week, oldest first",
+        params: &[path("week", "string", "ISO week, e.g. `2026-W02`")],
+        request: None,
+        response: Body::Json("WeekPoems"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/epoch/{epoch}",
+        tag: "poems",
+        summary: "The daily poems using keywords from a Solana epoch, oldest first",
+        params: &[path("epoch", "integer", "Solana epoch number")],
+        request: None,
+        response: Body::Json("EpochPoems"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/hash/{hash}",
+        tag: "poems",
+        summary: "The poem with a content hash, including earlier versions of regenerated poems",
+        params: &[path("hash", "string", "A poem's 64-digit `poem_hash`")],
+        request: None,
+        response: Body::Json("Poem"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/keywords/today",
+        tag: "keywords",
+        summary: "Keywords collected today",
+        params: &[],
+        request: None,
+        response: Body::Json("Keyword[]"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/keywords/{date}",
+        tag: "keywords",
+        summary: "Keywords collected on a date",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("Keyword[]"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/keywords/search",
+        tag: "keywords",
+        summary: "Every occurrence of a word, with the poems it appeared in",
+        params: &[required_query("word", "Word to look up")],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[400],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/blocks/{slot}",
+        tag: "keywords",
+        summary:
+            "A block refetched from Solana RPC, the keywords derived from it, and explorer links",
+        params: &[path("slot", "integer", "Solana slot")],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[404, 502, 503],
+        role: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/derive",
+        tag: "keywords",
+        summary: "The word any blockhash or transaction signature derives, with every step",
+        params: &[],
+        request: Some("DeriveRequest"),
+        response: Body::Json("DerivationTrace"),
+        errors: &[400, 503],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/export/keywords.csv",
+        tag: "keywords",
+        summary: "Every keyword with its slot, blockhash, source, and date, streamed as CSV",
+        params: &[],
+        request: None,
+        response: Body::Media("text/csv"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/events",
+        tag: "collector",
+        summary: "Server-Sent Events stream of collector progress",
+        params: &[],
+        request: None,
+        response: Body::Media("text/event-stream"),
+        errors: &[503],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/stats",
+        tag: "stats",
+        summary: "Corpus analytics: totals, streaks, frequent words, sources, and LLM usage",
+        params: &[limit(
+            "top",
+            "Most frequent words to return",
+            DEFAULT_STATS_TOP_WORDS,
+            MAX_STATS_TOP_WORDS,
+        )],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/stats/cooccurrence",
+        tag: "stats",
+        summary: "Word pairs written into the same poem, with poem counts",
+        params: &[
+            query(
+                "min_count",
+                "integer",
+                "Fewest days a pair must share (default 1)",
+            ),
+            limit(
+                "limit",
+                "Pairs to return",
+                DEFAULT_COOCCURRENCE_LIMIT,
+                MAX_COOCCURRENCE_LIMIT,
+            ),
+        ],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/collections",
+        tag: "poems",
+        summary: "Weekly and monthly meta-poems, latest period first",
+        params: &[],
+        request: None,
+        response: Body::Json("Collection[]"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/collections/{period}",
+        tag: "poems",
+        summary: "The meta-poem for a week or month",
+        params: &[path(
+            "period",
+            "string",
+            "`2026-W02` for a week, `2026-01` for a month",
+        )],
+        request: None,
+        response: Body::Json("Collection"),
+        errors: &[400, 404],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/stats/milestones",
+        tag: "stats",
+        summary: "Current and longest daily-poem streaks, totals, and milestones",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/poems/custom",
+        tag: "previews",
+        summary: "Unofficial poem for a slot or timestamp range of up to one week",
+        params: &[],
+        request: Some("CustomRangeRequest"),
+        response: Body::Json("object"),
+        errors: &[400, 409, 429, 502, 503],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/poems/custom/{id}",
+        tag: "previews",
+        summary: "A custom poem stored with `persist: true`",
+        params: &[path("id", "integer", "Custom poem id")],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[404],
+        role: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/preview/{date}",
+        tag: "previews",
+        summary: "Unofficial preview poem for a date before the project started",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[400, 409, 429, 502, 503],
+        role: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/dictionary/reload",
+        tag: "admin",
+        summary: "Re-read the word dictionary and themes from disk",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[422, 503],
+        role: Some(Role::Admin),
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/collect-now",
+        tag: "admin",
+        summary: "Run a keyword collection now, off the schedule (even while paused)",
+        params: &[],
+        request: None,
+        response: Body::Accepted("CollectorCommand"),
+        errors: &[503],
+        role: Some(Role::Admin),
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/pause",
+        tag: "admin",
+        summary: "Skip scheduled and event keyword collections until resumed",
+        params: &[],
+        request: None,
+        response: Body::Accepted("CollectorCommand"),
+        errors: &[503],
+        role: Some(Role::Admin),
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/resume",
+        tag: "admin",
+        summary: "Collect keywords on schedule again after a pause",
+        params: &[],
+        request: None,
+        response: Body::Accepted("CollectorCommand"),
+        errors: &[503],
+        role: Some(Role::Admin),
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/keywords",
+        tag: "admin",
+        summary: "Add a word to a date before its poem is generated",
+        params: &[],
+        request: Some("AddKeywordRequest"),
+        response: Body::Created("Keyword"),
+        errors: &[400, 409, 503],
+        role: Some(Role::Curator),
+    },
+    Operation {
+        method: "delete",
+        path: "/api/admin/keywords/{id}",
+        tag: "admin",
+        summary: "Pull a keyword before its day's poem is generated",
+        params: &[
+            path("id", "integer", "Keyword id"),
+            query("reason", "string", "Recorded in the audit log"),
+        ],
+        request: None,
+        response: Body::Json("Keyword"),
+        errors: &[404, 409, 503],
+        role: Some(Role::Curator),
+    },
+    Operation {
+        method: "post",
+        path: "/api/admin/keywords/{id}/annotations",
+        tag: "admin",
+        summary: "Attach a note to a keyword, shown with its poem's provenance",
+        params: &[path("id", "integer", "Keyword id")],
+        request: Some("AnnotationRequest"),
+        response: Body::Created("KeywordAnnotation"),
+        errors: &[400, 404, 503],
+        role: Some(Role::Curator),
+    },
+    Operation {
+        method: "delete",
+        path: "/api/admin/annotations/{id}",
+        tag: "admin",
+        summary: "Remove a note from a keyword",
+        params: &[path("id", "integer", "Annotation id")],
+        request: None,
+        response: Body::Json("KeywordAnnotation"),
+        errors: &[404, 503],
+        role: Some(Role::Curator),
+    },
+    Operation {
+        method: "get",
+        path: "/api/admin/audit/{date}",
+        tag: "admin",
+        summary: "Curator keyword additions and removals for a date, oldest first",
+        params: &[DATE],
+        request: None,
+        response: Body::Json("AuditEntry[]"),
+        errors: &[400],
+        role: Some(Role::Reader),
+    },
+    Operation {
+        method: "get",
+        path: "/api/admin/usage",
+        tag: "admin",
+        summary: "Recent requests made with API keys, newest first",
+        params: &[
+            query("key", "string", "Only this key's requests, by name"),
+            limit(
+                "limit",
+                "Requests to return",
+                DEFAULT_API_USAGE_LIMIT,
+                MAX_API_USAGE_LIMIT,
+            ),
+        ],
+        request: None,
+        response: Body::Json("ApiKeyUsage[]"),
+        errors: &[],
+        role: Some(Role::Admin),
+    },
+    Operation {
+        method: "get",
+        path: "/api/openapi.json",
+        tag: "meta",
+        summary: "This document",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/docs",
+        tag: "meta",
+        summary: "Swagger UI for this document",
+        params: &[],
+        request: None,
+        response: Body::Media("text/html"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/health",
+        tag: "meta",
+        summary: "Static liveness probe",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/health/deep",
+        tag: "meta",
+        summary: "Collector liveness and dependency health",
+        params: &[],
+        request: None,
+        response: Body::Json("object"),
+        errors: &[503],
+        role: None,
+    },
+    Operation {
+        method: "get",
+        path: "/metrics",
+        tag: "meta",
+        summary: "Prometheus metrics",
+        params: &[],
+        request: None,
+        response: Body::Media("text/plain"),
+        errors: &[],
+        role: None,
+    },
+];
+
+/// OpenAPI 3 description of the REST API, served at /api/openapi.json
+pub fn document() -> Value {
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let item = paths
+            .entry(operation.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path items are objects");
+        item.insert(operation.method.to_string(), operation_object(operation));
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Chain Verse API",
+            "description": "Daily poems written from words derived from Solana blocks. \
+                Errors come back as `{\"error\": \"...\"}`.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "An API key with the reader, curator, or admin role",
+                },
+            },
+        },
+    })
+}
+
+fn operation_object(operation: &Operation) -> Value {
+    let mut responses = Map::new();
+    let (status, description, content) = match operation.response {
+        Body::Json(name) => (
+            "200",
+            "OK",
+            json!({ "application/json": { "schema": schema(name) } }),
+        ),
+        Body::Created(name) => (
+            "201",
+            "Created",
+            json!({ "application/json": { "schema": schema(name) } }),
+        ),
+        Body::Accepted(name) => (
+            "202",
+            "Accepted",
+            json!({ "application/json": { "schema": schema(name) } }),
+        ),
+        Body::Media(media_type) => ("200", "OK", json!({ media_type: {} })),
+    };
+    responses.insert(
+        status.to_string(),
+        json!({ "description": description, "content": content }),
+    );
+
+    let mut errors = operation.errors.to_vec();
+    if operation.role.is_some() {
+        errors.extend([401, 403]);
+    }
+    if operation.path.starts_with("/api/") {
+        errors.push(429);
+    }
+    errors.push(500);
+    errors.sort_unstable();
+    errors.dedup();
+    for status in errors {
+        responses.insert(
+            status.to_string(),
+            json!({
+                "description": error_description(status),
+                "content": { "application/json": { "schema": schema("Error") } },
+            }),
+        );
+    }
+
+    let mut object = json!({
+        "tags": [operation.tag],
+        "summary": operation.summary,
+        "parameters": operation.params.iter().map(parameter).collect::<Vec<_>>(),
+        "responses": responses,
+    });
+    if let Some(request) = operation.request {
+        object["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema(request) } },
+        });
+    }
+    if let Some(role) = operation.role {
+        object["security"] = json!([{ "apiKey": [] }]);
+        object["description"] = json!(format!("Requires an API key with the {} role", role));
+    }
+    object
+}
+
+fn parameter(param: &Param) -> Value {
+    let location = match param.location {
+        In::Path => "path",
+        In::Query => "query",
+    };
+    let mut schema = json!({ "type": param.kind });
+    if let Some((default, max)) = param.bounds {
+        schema["default"] = json!(default);
+        schema["minimum"] = json!(1);
+        schema["maximum"] = json!(max);
+    }
+    json!({
+        "name": param.name,
+        "in": location,
+        "required": param.required,
+        "description": param.description,
+        "schema": schema,
+    })
+}
+
+fn error_description(status: u16) -> &'static str {
+    match status {
+        400 => "Invalid request",
+        401 => "Missing or invalid API key",
+        403 => "The API key's role is too low, or no API keys are configured",
+        404 => "Not found",
+        409 => "Conflicts with the official archive, e.g. the day's poem already exists",
+        422 => "The dictionary could not be loaded",
+        429 => "Rate limited; see Retry-After",
+        502 => "Solana RPC or OpenRouter failed",
+        503 => "Unavailable: circuit breaker open, database busy, or feature not configured",
+        _ => "Internal error",
+    }
+}
+
+/// A `$ref` to a component schema; `Name[]` is an array of them, `object` any JSON object
+fn schema(name: &str) -> Value {
+    if name == "object" {
+        return json!({ "type": "object" });
+    }
+    match name.strip_suffix("[]") {
+        Some(item) => json!({ "type": "array", "items": schema(item) }),
+        None => json!({ "$ref": format!("#/components/schemas/{}", name) }),
+    }
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn nullable(kind: &str) -> Value {
+    json!({ "type": kind, "nullable": true })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "format": "int64" })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": string() })
+}
+
+/// Reaction counts keyed by emoji
+fn reactions() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": integer(),
+        "description": "Count per emoji; emoji nobody picked are left out",
+    })
+}
+
+fn object(required: &[&str], properties: Value) -> Value {
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn schemas() -> Value {
+    json!({
+        "Error": object(&["error"], json!({ "error": string() })),
+        "Poem": object(
+            &["id", "date", "content", "keyword_ids", "created_at", "views", "reactions"],
+            json!({
+                "id": integer(),
+                "date": string(),
+                "title": nullable("string"),
+                "content": string(),
+                "keyword_ids": { "type": "array", "items": integer() },
+                "anchor_hash": {
+                    "type": "string",
+                    "nullable": true,
+                    "description": "SHA-256 of the poem and its keywords, when anchored on-chain",
+                },
+                "anchor_signature": {
+                    "type": "string",
+                    "nullable": true,
+                    "description": "Memo transaction that recorded anchor_hash",
+                },
+                "poem_hash": {
+                    "type": "string",
+                    "nullable": true,
+                    "description": "SHA-256 of the date, keyword slots and text; stable across retitling",
+                },
+                "created_at": string(),
+                "views": { "type": "integer", "format": "int64", "description": "Reads of the day's poem" },
+                "reactions": reactions(),
+            }),
+        ),
+        "PoemEngagement": object(
+            &["views", "reactions"],
+            json!({ "views": integer(), "reactions": reactions() }),
+        ),
+        "ReactionRequest": object(
+            &["emoji"],
+            json!({ "emoji": { "type": "string", "enum": POEM_REACTIONS } }),
+        ),
+        "Keyword": object(
+            &["id", "word", "slot", "blockhash", "word_index", "source", "rerolls", "orphaned", "created_at"],
+            json!({
+                "id": integer(),
+                "word": string(),
+                "slot": { "type": "integer", "format": "int64", "description": "Negative for manual keywords" },
+                "blockhash": string(),
+                "previous_blockhash": nullable("string"),
+                "block_time": { "type": "integer", "format": "int64", "nullable": true },
+                "word_index": integer(),
+                "source": { "type": "string", "description": "Entropy the word was derived from, or `manual`" },
+                "theme": nullable("string"),
+                "rerolls": integer(),
+                "tag": { "type": "string", "nullable": true, "description": "`event` for keywords collected off-schedule" },
+                "epoch": { "type": "integer", "format": "int64", "nullable": true, "description": "Solana epoch of the slot, when recorded" },
+                "orphaned": { "type": "boolean", "description": "The block was abandoned by a reorg; left out of the day's poem" },
+                "created_at": string(),
+                "explorer": {
+                    "allOf": [schema("ExplorerLinks")],
+                    "nullable": true,
+                    "description": "Null for manual keywords",
+                },
+                "definition": {
+                    "allOf": [schema("WordDefinition")],
+                    "nullable": true,
+                },
+                "rarity": {
+                    "allOf": [schema("KeywordRarity")],
+                    "nullable": true,
+                    "description": "Null in admin responses",
+                },
+            }),
+        ),
+        "ExplorerLinks": object(
+            &["solscan", "solana_explorer"],
+            json!({ "solscan": string(), "solana_explorer": string() }),
+        ),
+        "KeywordRarity": object(
+            &["previous_occurrences", "score", "rare", "summary"],
+            json!({
+                "previous_occurrences": integer(),
+                "last_seen": nullable("string"),
+                "days_since_last": { "type": "integer", "format": "int64", "nullable": true },
+                "score": { "type": "number", "description": "1 for a first appearance, 0.5 after a 30-day gap" },
+                "rare": { "type": "boolean" },
+                "summary": { "type": "string", "example": "First appearance in 212 days" },
+            }),
+        ),
+        "WordDefinition": object(
+            &["definition", "source"],
+            json!({
+                "definition": string(),
+                "part_of_speech": nullable("string"),
+                "origin": nullable("string"),
+                "source": string(),
+            }),
+        ),
+        "TodayStatus": object(
+            &[
+                "date",
+                "timezone",
+                "keywords_collected",
+                "keywords_needed",
+                "poem_ready",
+                "interval_minutes",
+                "progress_percent",
+                "keywords",
+            ],
+            json!({
+                "date": string(),
+                "timezone": string(),
+                "keywords_collected": integer(),
+                "keywords_needed": integer(),
+                "poem_ready": { "type": "boolean" },
+                "interval_minutes": integer(),
+                "next_collection_secs": {
+                    "type": "integer",
+                    "nullable": true,
+                    "description": "Estimated; null once today's keywords are complete",
+                },
+                "poem_expected_secs": {
+                    "type": "integer",
+                    "nullable": true,
+                    "description": "Estimated; null once the poem is ready",
+                },
+                "progress_percent": { "type": "integer", "minimum": 0, "maximum": 100 },
+                "keywords": schema("Keyword[]"),
+                "poem": { "allOf": [schema("Poem")], "nullable": true },
+            }),
+        ),
+        "PoemSearchResult": object(
+            &["query", "matches", "poems"],
+            json!({
+                "query": string(),
+                "matches": integer(),
+                "poems": {
+                    "type": "array",
+                    "items": object(
+                        &["date", "snippet"],
+                        json!({
+                            "date": string(),
+                            "title": nullable("string"),
+                            "snippet": { "type": "string", "description": "Escaped HTML with matches in <mark>" },
+                        }),
+                    ),
+                },
+            }),
+        ),
+        "WeekPoems": object(
+            &["week", "start_date", "end_date", "poems"],
+            json!({
+                "week": string(),
+                "start_date": string(),
+                "end_date": string(),
+                "poems": { "type": "array", "items": schema("Poem") },
+            }),
+        ),
+        "EpochPoems": object(
+            &["epoch", "poems"],
+            json!({
+                "epoch": integer(),
+                "poems": { "type": "array", "items": schema("Poem") },
+            }),
+        ),
+        "SimilarPoem": object(
+            &["date", "similarity"],
+            json!({
+                "date": string(),
+                "title": nullable("string"),
+                "similarity": { "type": "number", "description": "Cosine similarity from -1 to 1" },
+            }),
+        ),
+        "Collection": object(
+            &["id", "period", "kind", "start_date", "end_date", "content", "poem_dates", "created_at"],
+            json!({
+                "id": integer(),
+                "period": string(),
+                "kind": { "type": "string", "enum": ["week", "month"] },
+                "start_date": string(),
+                "end_date": string(),
+                "title": nullable("string"),
+                "content": string(),
+                "poem_dates": strings(),
+                "created_at": string(),
+            }),
+        ),
+        "CustomRangeRequest": object(
+            &[],
+            json!({
+                "start_slot": integer(),
+                "end_slot": integer(),
+                "start_time": { "type": "integer", "format": "int64", "description": "Unix seconds" },
+                "end_time": { "type": "integer", "format": "int64", "description": "Unix seconds" },
+                "persist": { "type": "boolean", "default": false },
+            }),
+        ),
+        "DeriveRequest": object(
+            &["input"],
+            json!({
+                "input": { "type": "string", "description": "A blockhash, signature, or any string" },
+            }),
+        ),
+        "DerivationTrace": object(
+            &[
+                "input",
+                "sha256",
+                "seed",
+                "dictionary_size",
+                "dictionary_sha256",
+                "language",
+                "version",
+                "steps",
+                "word",
+                "word_index",
+                "rerolls",
+            ],
+            json!({
+                "input": string(),
+                "sha256": string(),
+                "seed": { "type": "string", "description": "u64 as a decimal string" },
+                "dictionary_size": integer(),
+                "dictionary_sha256": string(),
+                "language": string(),
+                "theme": nullable("string"),
+                "version": integer(),
+                "steps": {
+                    "type": "array",
+                    "items": object(
+                        &["seed", "word_index", "word", "blocked"],
+                        json!({
+                            "seed": string(),
+                            "word_index": integer(),
+                            "word": string(),
+                            "blocked": { "type": "boolean" },
+                        }),
+                    ),
+                },
+                "word": string(),
+                "word_index": integer(),
+                "rerolls": integer(),
+            }),
+        ),
+        "AddKeywordRequest": object(
+            &["date", "word"],
+            json!({ "date": string(), "word": string(), "reason": nullable("string") }),
+        ),
+        "AnnotationRequest": object(
+            &["note"],
+            json!({ "note": { "type": "string", "maxLength": MAX_ANNOTATION_CHARS } }),
+        ),
+        "AuditEntry": object(
+            &["id", "action", "keyword_id", "date", "word", "curator", "created_at"],
+            json!({
+                "id": integer(),
+                "action": { "type": "string", "enum": ["add", "remove"] },
+                "keyword_id": integer(),
+                "date": string(),
+                "word": string(),
+                "curator": string(),
+                "reason": nullable("string"),
+                "created_at": string(),
+            }),
+        ),
+        "KeywordAnnotation": object(
+            &["id", "keyword_id", "word", "slot", "note", "curator", "created_at"],
+            json!({
+                "id": integer(),
+                "keyword_id": integer(),
+                "word": string(),
+                "slot": integer(),
+                "note": string(),
+                "curator": string(),
+                "created_at": string(),
+            }),
+        ),
+        "CollectorCommand": object(
+            &["status", "command"],
+            json!({
+                "status": { "type": "string", "enum": ["accepted"] },
+                "command": { "type": "string", "enum": ["collect_now", "pause", "resume"] },
+            }),
+        ),
+        "ApiKeyUsage": object(
+            &["id", "key_name", "role", "method", "path", "status", "used_at"],
+            json!({
+                "id": integer(),
+                "key_name": string(),
+                "role": { "type": "string", "enum": ["reader", "curator", "admin"] },
+                "method": string(),
+                "path": string(),
+                "status": integer(),
+                "used_at": string(),
+            }),
+        ),
+    })
+}
+
+/// Swagger UI page over /api/openapi.json
+pub fn swagger_ui() -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Chain Verse API</title>
+  <link rel="stylesheet" href="{url}/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{url}/swagger-ui-bundle.js"></script>
+  <script>SwaggerUIBundle({{ url: "/api/openapi.json", dom_id: "#swagger-ui" }});</script>
+</body>
+</html>
+"##,
+        url = SWAGGER_UI_URL
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{
+        KeywordAnnotation, PoemEngagement, StoredCollection, StoredKeyword, StoredPoem,
+    };
+    use crate::definitions::WordDefinition;
+    use crate::derivation::KeywordDerivation;
+    use crate::engagement::EngagedPoem;
+    use crate::explorer::LinkedKeyword;
+    use crate::mood::Mood;
+    use crate::poem_form::PoemLength;
+    use crate::poem_generator::SamplingOptions;
+    use crate::provenance::{
+        AnnotatedProvenance, DerivationProvenance, KeywordProvenance, LlmProvenance, PoemProvenance,
+    };
+    use crate::rarity::KeywordRarity;
+    use crate::words::WordDictionary;
+    use chain_verse_types as types;
+    use chrono::NaiveDate;
+    use std::collections::BTreeSet;
+
+    /// `(method, path)` for every endpoint the README lists
+    fn readme_endpoints() -> BTreeSet<(String, String)> {
+        include_str!("../../README.md")
+            .lines()
+            .filter_map(|line| line.strip_prefix("- `"))
+            .filter_map(|line| {
+                let (method, rest) = line.split_once(' ')?;
+                if !["GET", "POST", "DELETE"].contains(&method) {
+                    return None;
+                }
+                let path = rest.split(['`', '?']).next()?;
+                Some((method.to_lowercase(), path.to_string()))
+            })
+            // GraphQL has its own schema and only exists with the graphql feature
+            .filter(|(_, path)| path != "/graphql")
+            .collect()
+    }
+
+    #[test]
+    fn test_document_covers_readme_endpoints() {
+        let documented = documented_operations();
+
+        let readme = readme_endpoints();
+        let missing: Vec<_> = readme.difference(&documented).collect();
+        assert!(missing.is_empty(), "undocumented endpoints: {:?}", missing);
+
+        // The README folds the .md and .png exports into the .txt entry
+        let unlisted: Vec<_> = documented
+            .difference(&readme)
+            .filter(|(_, path)| !path.ends_with(".md") && !path.ends_with(".png"))
+            .collect();
+        assert!(unlisted.is_empty(), "not in the README: {:?}", unlisted);
+    }
+
+    /// `(method, path)` for every operation in the document
+    fn documented_operations() -> BTreeSet<(String, String)> {
+        document()["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect()
+    }
+
+    /// Paths `api::create_router` registers, read from its source
+    fn router_paths() -> BTreeSet<String> {
+        include_str!("api.rs")
+            .split(".route(")
+            .skip(1)
+            .filter_map(|call| {
+                let path = call.trim_start().strip_prefix('"')?;
+                Some(path.split('"').next()?.to_string())
+            })
+            // The frontend's catch-all for unknown API paths
+            .filter(|path| path != "/api/{*path}")
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_document_matches_router() {
+        use axum::body::Body;
+        use axum::http::{header, Method, Request, StatusCode};
+        use tower::ServiceExt;
+
+        let (db_path, url) = crate::database::temp_database_url("openapi");
+        let db = crate::database::Database::new(&url).await.unwrap();
+        let mut state = crate::api::AppState::new(db.clone());
+        let key = "probe:admin:secret".parse().unwrap();
+        state.api_keys = std::sync::Arc::new(crate::auth::ApiKeys::new(vec![key]));
+        let router = crate::api::create_router(state);
+
+        // A method no route takes gets 405 and the methods the path does take,
+        // without running any handler (the admin routes' guard lets the key through)
+        for (method, path) in documented_operations() {
+            let uri = path
+                .split('/')
+                .map(|segment| match segment.strip_prefix('{') {
+                    Some(rest) => format!("1{}", rest.split_once('}').unwrap().1),
+                    None => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let request = Request::builder()
+                .method(Method::TRACE)
+                .uri(&uri)
+                .header(header::AUTHORIZATION, "Bearer secret")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(
+                response.status(),
+                StatusCode::METHOD_NOT_ALLOWED,
+                "{} is not routed",
+                path
+            );
+            let allowed = response.headers()[header::ALLOW].to_str().unwrap();
+            assert!(
+                allowed
+                    .split(',')
+                    .any(|m| m.trim().eq_ignore_ascii_case(&method)),
+                "{} {} is routed for {} only",
+                method,
+                path,
+                allowed
+            );
+        }
+
+        // The export formats share the poem route
+        let documented: BTreeSet<String> = documented_operations()
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect();
+        let undocumented: Vec<_> = router_paths().difference(&documented).cloned().collect();
+        assert!(
+            undocumented.is_empty(),
+            "undocumented routes: {:?}",
+            undocumented
+        );
+
+        db.close().await;
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[test]
+    fn test_schema_refs_resolve() {
+        let document = document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let text = document.to_string();
+        for reference in text.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "missing schema {}", name);
+        }
+    }
+
+    /// Property names of a component schema
+    fn properties(name: &str) -> BTreeSet<String> {
+        document()["components"]["schemas"][name]["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn fields(value: impl serde::Serialize) -> BTreeSet<String> {
+        serde_json::to_value(value)
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn sample_keyword() -> StoredKeyword {
+        StoredKeyword {
+            id: 1,
+            word: "ember".to_string(),
+            slot: 250_000_000,
+            blockhash: "hash".to_string(),
+            previous_blockhash: None,
+            block_time: None,
+            word_index: 7,
+            source: "blockhash".to_string(),
+            theme: None,
+            rerolls: 0,
+            tag: None,
+            epoch: None,
+            orphaned: false,
+            created_at: "2026-01-05 09:00:00".to_string(),
+        }
+    }
+
+    fn sample_poem() -> StoredPoem {
+        StoredPoem {
+            id: 1,
+            date: "2026-01-05".to_string(),
+            title: None,
+            content: "A poem".to_string(),
+            keyword_ids: vec![1],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: "2026-01-05 23:59:00".to_string(),
+        }
+    }
+
+    fn sample_annotation() -> KeywordAnnotation {
+        KeywordAnnotation {
+            id: 1,
+            keyword_id: 1,
+            word: "ember".to_string(),
+            slot: 250_000_000,
+            note: "Collected during the outage".to_string(),
+            curator: "alice".to_string(),
+            created_at: "2026-01-06 10:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_schemas_match_responses() {
+        let keyword = LinkedKeyword::from(sample_keyword());
+        assert_eq!(fields(keyword), properties("Keyword"));
+
+        let engagement = PoemEngagement::default();
+        assert_eq!(fields(&engagement), properties("PoemEngagement"));
+        let poem = EngagedPoem {
+            poem: sample_poem(),
+            engagement,
+        };
+        assert_eq!(fields(poem), properties("Poem"));
+
+        let collection = StoredCollection {
+            id: 1,
+            period: "2026-W02".to_string(),
+            kind: "week".to_string(),
+            start_date: "2026-01-05".to_string(),
+            end_date: "2026-01-11".to_string(),
+            title: None,
+            content: "A week".to_string(),
+            poem_dates: vec![],
+            created_at: "2026-01-12 00:00:00".to_string(),
+        };
+        assert_eq!(fields(collection), properties("Collection"));
+
+        assert_eq!(fields(sample_annotation()), properties("KeywordAnnotation"));
+
+        let trace = KeywordDerivation::new(WordDictionary::load().unwrap())
+            .trace("hash")
+            .unwrap();
+        assert_eq!(fields(trace), properties("DerivationTrace"));
+    }
+
+    /// Serialize `value` as the server does, read it as the client crate's
+    /// `T`, and check nothing was lost on the way
+    fn round_trip<T>(value: impl serde::Serialize) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let json = serde_json::to_value(value).unwrap();
+        let typed: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&typed).unwrap(), json);
+        typed
+    }
+
+    #[test]
+    fn test_types_crate_reads_responses() {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let rarity = KeywordRarity::new(2, NaiveDate::from_ymd_opt(2025, 6, 7), day);
+        let mut keyword = LinkedKeyword::from(sample_keyword()).with_rarity(Some(rarity));
+        keyword.definition = Some(WordDefinition {
+            definition: "A glowing coal".to_string(),
+            part_of_speech: Some("noun".to_string()),
+            origin: None,
+            source: "test".to_string(),
+        });
+        let typed: types::LinkedKeyword = round_trip(&keyword);
+        assert!(typed.explorer.is_some() && typed.rarity.is_some());
+
+        let poem = EngagedPoem {
+            poem: sample_poem(),
+            engagement: PoemEngagement {
+                views: 12,
+                reactions: [("🔥".to_string(), 3)].into(),
+            },
+        };
+        let typed: types::EngagedPoem = round_trip(&poem);
+        assert_eq!(typed.engagement.reactions["🔥"], 3);
+
+        let provenance = AnnotatedProvenance {
+            provenance: PoemProvenance {
+                date: "2026-01-05".to_string(),
+                derivation: DerivationProvenance {
+                    version: 3,
+                    dictionary_sha256: "abc".to_string(),
+                    theme: Some("ocean".to_string()),
+                    language: Some("en".to_string()),
+                    blocklist_sha256: None,
+                },
+                keywords: vec![KeywordProvenance {
+                    word: "ember".to_string(),
+                    slot: 250_000_000,
+                    blockhash: "hash".to_string(),
+                    word_index: 7,
+                    source: "blockhash".to_string(),
+                    theme: None,
+                    rerolls: 1,
+                }],
+                llm: LlmProvenance {
+                    model: "model-a".to_string(),
+                    sampling: SamplingOptions {
+                        temperature: Some(0.7),
+                        seed: Some(42),
+                        ..Default::default()
+                    },
+                    form: "free_verse".to_string(),
+                    mood: Some(Mood::Brooding),
+                    length: Some(PoemLength::new(20, 30)),
+                    prompt_sha256: "def".to_string(),
+                    system_prompt_sha256: None,
+                },
+            },
+            annotations: vec![sample_annotation()],
+        };
+        let typed: types::AnnotatedProvenance = round_trip(&provenance);
+        assert_eq!(typed.provenance.llm.mood, Some(types::Mood::Brooding));
+
+        // The server's TodayStatus is private to the API; hold the crate's
+        // to the schema instead
+        let today = types::TodayStatus {
+            date: "2026-01-05".to_string(),
+            timezone: "UTC".to_string(),
+            keywords_collected: 1,
+            keywords_needed: 10,
+            poem_ready: true,
+            interval_minutes: 144,
+            next_collection_secs: Some(60),
+            poem_expected_secs: Some(600),
+            progress_percent: 10,
+            keywords: vec![round_trip(&keyword)],
+            poem: Some(round_trip(&poem)),
+        };
+        assert_eq!(fields(&today), properties("TodayStatus"));
+    }
+}
@@ -0,0 +1,457 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::consts::{
+    DEFAULT_POEM_MAX_LINES, DEFAULT_POEM_MIN_LINES, MOOD_BUSY_TRANSACTIONS_PER_BLOCK,
+    MOOD_QUIET_TRANSACTIONS_PER_BLOCK, POEM_LINE_TOLERANCE,
+};
+use crate::mood::BlockActivity;
+
+/// Words an acrostic can spell, one per line's first letter
+const ACROSTIC_WORDS: &[&str] = &[
+    "BLOCK",
+    "CHAIN",
+    "EPOCH",
+    "LEDGER",
+    "SLOT",
+    "TIDE",
+    "EMBER",
+    "SIGNAL",
+    "ORBIT",
+    "HARBOR",
+    "LANTERN",
+    "RIVER",
+    "ECHO",
+    "STONE",
+    "DAWN",
+    "MERIDIAN",
+    "WINTER",
+    "COMPASS",
+    "THRESHOLD",
+    "VERSE",
+];
+
+/// Shape of the poem requested from the LLM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoemForm {
+    /// Three lines, 5-7-5 syllables
+    Haiku,
+    /// Five lines, 5-7-5-7-7 syllables
+    Tanka,
+    /// Five lines, AABBA rhyme
+    Limerick,
+    /// Fourteen lines of iambic pentameter
+    Sonnet,
+    /// Unrhymed, 20-30 lines by default (the original Chain Verse form)
+    FreeVerse(PoemLength),
+    /// One line per letter, the first letters spelling the word
+    Acrostic(&'static str),
+}
+
+impl PoemForm {
+    /// Forms `auto` picks from, in the order used for blockhash derivation
+    ///
+    /// Acrostics are left out so days already generated keep their form.
+    pub fn all() -> &'static [PoemForm] {
+        &[
+            PoemForm::Haiku,
+            PoemForm::Tanka,
+            PoemForm::Limerick,
+            PoemForm::Sonnet,
+            PoemForm::FreeVerse(PoemLength::DEFAULT),
+        ]
+    }
+
+    /// Config / API name of the form
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoemForm::Haiku => "haiku",
+            PoemForm::Tanka => "tanka",
+            PoemForm::Limerick => "limerick",
+            PoemForm::Sonnet => "sonnet",
+            PoemForm::FreeVerse(_) => "free_verse",
+            PoemForm::Acrostic(_) => "acrostic",
+        }
+    }
+
+    /// Allowed number of non-empty lines
+    pub fn line_range(&self) -> (usize, usize) {
+        match self {
+            PoemForm::Haiku => (3, 3),
+            PoemForm::Tanka | PoemForm::Limerick => (5, 5),
+            PoemForm::Sonnet => (14, 14),
+            PoemForm::FreeVerse(length) => length.line_range(),
+            PoemForm::Acrostic(word) => (word.len(), word.len()),
+        }
+    }
+
+    /// What to ask for, completing "create ..."
+    pub fn description(&self) -> String {
+        match self {
+            PoemForm::Haiku => {
+                "a haiku: exactly 3 lines following a 5-7-5 syllable pattern".to_string()
+            }
+            PoemForm::Tanka => {
+                "a tanka: exactly 5 lines following a 5-7-5-7-7 syllable pattern".to_string()
+            }
+            PoemForm::Limerick => {
+                "a limerick: exactly 5 lines with an AABBA rhyme scheme and a bouncing rhythm"
+                    .to_string()
+            }
+            PoemForm::Sonnet => {
+                "a sonnet: exactly 14 lines in iambic pentameter, ending in a rhyming couplet"
+                    .to_string()
+            }
+            PoemForm::FreeVerse(length) => {
+                let (min, max) = length.line_range();
+                format!("a cohesive poem of {}-{} lines", min, max)
+            }
+            PoemForm::Acrostic(word) => format!(
+                "an acrostic: exactly {} lines whose first letters, read top to bottom, spell {} \
+                 (one line per letter, starting {})",
+                word.len(),
+                word,
+                word.chars()
+                    .map(|letter| format!("\"{}\"", letter))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// How the keywords should be used; short forms can't hold every word
+    pub fn keyword_guidance(&self) -> &'static str {
+        match self {
+            PoemForm::Haiku | PoemForm::Tanka | PoemForm::Limerick => {
+                "Choose the few keywords that resonate most; you need not use them all"
+            }
+            PoemForm::Sonnet | PoemForm::FreeVerse(_) => {
+                "Use all or most of these keywords naturally in the poem"
+            }
+            PoemForm::Acrostic(_) => {
+                "Choose the keywords that fit the lines' letters; you need not use them all"
+            }
+        }
+    }
+
+    /// Most keywords the form can be expected to use; None when it can hold them all
+    pub fn keyword_capacity(&self) -> Option<usize> {
+        match self {
+            PoemForm::Haiku => Some(2),
+            PoemForm::Tanka | PoemForm::Limerick | PoemForm::Acrostic(_) => Some(3),
+            PoemForm::Sonnet | PoemForm::FreeVerse(_) => None,
+        }
+    }
+
+    /// Free verse with `length` in place of its own; other forms keep their
+    /// fixed line counts
+    pub fn with_length(self, length: PoemLength) -> Self {
+        match self {
+            PoemForm::FreeVerse(_) => PoemForm::FreeVerse(length),
+            form => form,
+        }
+    }
+
+    /// How long free verse was asked to be; None for the fixed forms
+    pub fn length(&self) -> Option<PoemLength> {
+        match self {
+            PoemForm::FreeVerse(length) => Some(*length),
+            _ => None,
+        }
+    }
+
+    /// Deterministically pick a form from a blockhash
+    pub fn from_blockhash(blockhash: &str) -> PoemForm {
+        let forms = Self::all();
+        forms[blockhash_index(blockhash, forms.len())]
+    }
+
+    /// Deterministically pick the word an acrostic spells from a blockhash
+    pub fn acrostic_from_blockhash(blockhash: &str) -> PoemForm {
+        PoemForm::Acrostic(ACROSTIC_WORDS[blockhash_index(blockhash, ACROSTIC_WORDS.len())])
+    }
+
+    /// Check the generated poem has the right number of lines for the form
+    pub fn validate(&self, poem: &str) -> Result<()> {
+        let lines = poem.lines().filter(|line| !line.trim().is_empty()).count();
+        let (min, max) = self.line_range();
+        if lines < min || lines > max {
+            if min == max {
+                anyhow::bail!("A {} needs {} lines, got {}", self.name(), min, lines);
+            }
+            anyhow::bail!("A {} needs {}-{} lines, got {}", self.name(), min, max, lines);
+        }
+        Ok(())
+    }
+
+    /// Check an acrostic's lines start with the letters of its word, in order;
+    /// always passes for other forms
+    pub fn validate_acrostic(&self, poem: &str) -> Result<()> {
+        let PoemForm::Acrostic(word) = self else {
+            return Ok(());
+        };
+        let initials: String = poem
+            .lines()
+            .filter_map(|line| line.chars().find(|c| c.is_alphanumeric()))
+            .flat_map(char::to_uppercase)
+            .collect();
+        for (i, (expected, actual)) in word.chars().zip(initials.chars()).enumerate() {
+            if expected != actual {
+                anyhow::bail!(
+                    "The first letters must spell {}, but line {} starts with \"{}\" instead of \"{}\"",
+                    word,
+                    i + 1,
+                    actual,
+                    expected
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Line count asked of free verse: anywhere within the configured bounds,
+/// or close to a count scaled from the day's chain activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoemLength {
+    pub min_lines: usize,
+    pub max_lines: usize,
+    /// Non-vote transactions per block, averaged over the blocks the day's
+    /// keywords came from
+    pub transactions_per_block: Option<u64>,
+    /// Lines scaled from `transactions_per_block` between the bounds
+    pub lines: Option<usize>,
+}
+
+impl PoemLength {
+    /// The original 20-30 line range, unscaled
+    pub const DEFAULT: PoemLength = PoemLength::new(DEFAULT_POEM_MIN_LINES, DEFAULT_POEM_MAX_LINES);
+
+    pub const fn new(min_lines: usize, max_lines: usize) -> Self {
+        Self {
+            min_lines,
+            max_lines,
+            transactions_per_block: None,
+            lines: None,
+        }
+    }
+
+    /// Scale the line count to the day's activity: quiet days (under
+    /// MOOD_QUIET_TRANSACTIONS_PER_BLOCK non-vote transactions per block) get
+    /// min_lines, busy ones (MOOD_BUSY_TRANSACTIONS_PER_BLOCK or more)
+    /// max_lines, and days in between a count in proportion; unchanged
+    /// without any blocks to judge by
+    pub fn scaled(self, activity: &BlockActivity) -> Self {
+        if activity.blocks <= 0 {
+            return self;
+        }
+        let per_block = activity.non_vote_transactions.max(0) as u64 / activity.blocks as u64;
+        let busyness = per_block.clamp(
+            MOOD_QUIET_TRANSACTIONS_PER_BLOCK,
+            MOOD_BUSY_TRANSACTIONS_PER_BLOCK,
+        ) - MOOD_QUIET_TRANSACTIONS_PER_BLOCK;
+        let span = self.max_lines.saturating_sub(self.min_lines) as u64;
+        let extra = busyness * span
+            / (MOOD_BUSY_TRANSACTIONS_PER_BLOCK - MOOD_QUIET_TRANSACTIONS_PER_BLOCK);
+
+        Self {
+            transactions_per_block: Some(per_block),
+            lines: Some(self.min_lines + extra as usize),
+            ..self
+        }
+    }
+
+    /// Non-empty lines a poem may have: within POEM_LINE_TOLERANCE of the
+    /// scaled count when there is one, never outside the bounds
+    pub fn line_range(&self) -> (usize, usize) {
+        match self.lines {
+            Some(lines) => (
+                lines
+                    .saturating_sub(POEM_LINE_TOLERANCE)
+                    .max(self.min_lines),
+                (lines + POEM_LINE_TOLERANCE).min(self.max_lines),
+            ),
+            None => (self.min_lines, self.max_lines),
+        }
+    }
+}
+
+impl Default for PoemLength {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Index below `len` derived from a blockhash, the same for every caller
+fn blockhash_index(blockhash: &str, len: usize) -> usize {
+    let digest = Sha256::digest(blockhash.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[0..8]);
+    (u64::from_le_bytes(bytes) % len as u64) as usize
+}
+
+impl fmt::Display for PoemForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PoemForm {
+    type Err = anyhow::Error;
+
+    /// An acrostic parses with its first word; `FormSelection::resolve` picks
+    /// the day's word from the blockhash
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase().replace('-', "_");
+        if normalized == "acrostic" {
+            return Ok(PoemForm::Acrostic(ACROSTIC_WORDS[0]));
+        }
+        Self::all()
+            .iter()
+            .copied()
+            .find(|form| form.name() == normalized || (normalized == "freeverse" && matches!(form, PoemForm::FreeVerse(_))))
+            .ok_or_else(|| anyhow::anyhow!("Unknown poem form `{}`", s))
+    }
+}
+
+/// How the form for a day is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormSelection {
+    /// Always use this form
+    Fixed(PoemForm),
+    /// Derive the form from the day's first blockhash
+    FromBlockhash,
+}
+
+impl FormSelection {
+    /// Resolve the form for a day given its first keyword's blockhash
+    pub fn resolve(&self, first_blockhash: Option<&str>) -> PoemForm {
+        match (self, first_blockhash) {
+            (FormSelection::Fixed(PoemForm::Acrostic(_)), Some(hash)) => {
+                PoemForm::acrostic_from_blockhash(hash)
+            }
+            (FormSelection::Fixed(form), _) => *form,
+            (FormSelection::FromBlockhash, Some(hash)) => PoemForm::from_blockhash(hash),
+            (FormSelection::FromBlockhash, None) => PoemForm::FreeVerse(PoemLength::DEFAULT),
+        }
+    }
+}
+
+impl Default for FormSelection {
+    fn default() -> Self {
+        FormSelection::Fixed(PoemForm::FreeVerse(PoemLength::DEFAULT))
+    }
+}
+
+impl FromStr for FormSelection {
+    type Err = anyhow::Error;
+
+    /// Accepts a form name or `auto` for blockhash derivation
+    fn from_str(s: &str) -> Result<Self> {
+        if s.trim().eq_ignore_ascii_case("auto") {
+            Ok(FormSelection::FromBlockhash)
+        } else {
+            s.parse().map(FormSelection::Fixed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forms() {
+        let free_verse = PoemForm::FreeVerse(PoemLength::DEFAULT);
+        assert_eq!("Haiku".parse::<PoemForm>().unwrap(), PoemForm::Haiku);
+        assert_eq!("free-verse".parse::<PoemForm>().unwrap(), free_verse);
+        assert_eq!("freeverse".parse::<PoemForm>().unwrap(), free_verse);
+        assert!("ode".parse::<PoemForm>().is_err());
+        assert_eq!("acrostic".parse::<PoemForm>().unwrap().name(), "acrostic");
+        assert_eq!("auto".parse::<FormSelection>().unwrap(), FormSelection::FromBlockhash);
+        assert_eq!(
+            "sonnet".parse::<FormSelection>().unwrap(),
+            FormSelection::Fixed(PoemForm::Sonnet)
+        );
+    }
+
+    #[test]
+    fn test_from_blockhash_is_deterministic() {
+        let form = PoemForm::from_blockhash("5xYzabc");
+        assert_eq!(form, PoemForm::from_blockhash("5xYzabc"));
+        assert_eq!(FormSelection::FromBlockhash.resolve(Some("5xYzabc")), form);
+        assert_eq!(
+            FormSelection::FromBlockhash.resolve(None),
+            PoemForm::FreeVerse(PoemLength::DEFAULT)
+        );
+    }
+
+    #[test]
+    fn test_acrostic_word_from_blockhash() {
+        let selection = "acrostic".parse::<FormSelection>().unwrap();
+        let form = selection.resolve(Some("5xYzabc"));
+        assert_eq!(form, PoemForm::acrostic_from_blockhash("5xYzabc"));
+        assert_eq!(selection.resolve(Some("5xYzabc")), form);
+
+        assert!((0..50).any(|i| selection.resolve(Some(&format!("hash{}", i))) != form));
+    }
+
+    #[test]
+    fn test_validate_acrostic() {
+        let form = PoemForm::Acrostic("TIDE");
+        assert_eq!(form.line_range(), (4, 4));
+        assert!(form.description().contains("spell TIDE"));
+
+        let poem =
+            "Tallies of the night\n\n**into** the ledger\ndawn settles its debts\nEmbers cool";
+        assert!(form.validate(poem).is_ok());
+        assert!(form.validate_acrostic(poem).is_ok());
+
+        let poem = "Tallies of the night\ninto the ledger\nnight settles its debts\nEmbers cool";
+        let error = form.validate_acrostic(poem).unwrap_err().to_string();
+        assert!(error.contains("line 3 starts with \"N\" instead of \"D\""));
+
+        assert!(PoemForm::Haiku.validate_acrostic(poem).is_ok());
+    }
+
+    #[test]
+    fn test_validate_line_counts() {
+        let haiku = "an old silent pond\n\na frog jumps into the pond\nsplash! silence again\n";
+        assert!(PoemForm::Haiku.validate(haiku).is_ok());
+        assert!(PoemForm::Tanka.validate(haiku).is_err());
+        let form = PoemForm::FreeVerse(PoemLength::DEFAULT);
+        assert!(form.validate(haiku).is_err());
+
+        let free_verse = vec!["line"; 24].join("\n");
+        assert!(form.validate(&free_verse).is_ok());
+    }
+
+    #[test]
+    fn test_length_scales_with_activity() {
+        let activity = |non_votes_per_block: i64| BlockActivity {
+            blocks: 4,
+            transactions: 4 * (non_votes_per_block + 1000),
+            non_vote_transactions: 4 * non_votes_per_block,
+            fees: 0,
+        };
+        let length = PoemLength::new(10, 30);
+        assert_eq!(length.scaled(&activity(100)).lines, Some(10));
+        assert_eq!(length.scaled(&activity(550)).lines, Some(20));
+        assert_eq!(length.scaled(&activity(5_000)).lines, Some(30));
+        assert_eq!(length.scaled(&BlockActivity::default()), length);
+
+        // Only free verse takes the day's length, within a couple of lines
+        let busy = length.scaled(&activity(550));
+        assert_eq!(busy.transactions_per_block, Some(550));
+        let form = PoemForm::FreeVerse(PoemLength::DEFAULT).with_length(busy);
+        assert_eq!(form.line_range(), (18, 22));
+        assert_eq!(form.description(), "a cohesive poem of 18-22 lines");
+        assert_eq!(form.length(), Some(busy));
+        assert!(form.validate(&vec!["line"; 24].join("\n")).is_err());
+        assert_eq!(PoemForm::Sonnet.with_length(busy), PoemForm::Sonnet);
+        assert_eq!(PoemForm::Sonnet.length(), None);
+    }
+}
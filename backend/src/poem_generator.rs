@@ -1,12 +1,96 @@
-use anyhow::{Context, Result};
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, instrument, warn};
+
+use crate::circuit_breaker::{is_circuit_open, CircuitBreaker};
+use crate::consts::{DEFAULT_LANGUAGE, LLM_STREAM_IDLE_TIMEOUT_SECS};
+use crate::error::{ChainVerseError, Result};
+use crate::events::{CollectorEvent, EventBus};
+use crate::metrics;
+use crate::mood::Mood;
+use crate::poem_form::PoemForm;
+use crate::poem_quality::{self, PoemValidator, QualityProblem};
+use crate::prompt::{PromptTemplate, PromptVars};
+use crate::provenance::LlmProvenance;
+use crate::retry::RetryPolicy;
 
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 
+/// Describes the API key; a cheap way to check OpenRouter is reachable and the key works
+const OPENROUTER_KEY_URL: &str = "https://openrouter.ai/api/v1/auth/key";
+
+/// Longer "titles" are assumed to be a line of the poem, not a title
+const TITLE_MAX_CHARS: usize = 80;
+
+/// A generated poem and the title the model gave it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPoem {
+    pub title: Option<String>,
+    pub content: String,
+    /// What the successful attempt cost; None if OpenRouter didn't report it
+    pub usage: Option<TokenUsage>,
+    /// The model in the fallback chain that wrote it
+    pub model: String,
+}
+
+impl GeneratedPoem {
+    /// The poem as the model wrote it, title line included
+    fn response(&self) -> String {
+        match &self.title {
+            Some(title) => format!("Title: {}\n\n{}", title, self.content),
+            None => self.content.clone(),
+        }
+    }
+}
+
+/// Token counts and spend OpenRouter reports at the end of a completion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+    /// OpenRouter's estimate in USD (credits), when it sends one
+    #[serde(default, rename = "cost")]
+    pub cost_usd: Option<f64>,
+}
+
+/// Sampling parameters sent with each completion; unset ones use the model's defaults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Caps the completion length, and so the spend per poem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Requests deterministic sampling from providers that support it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
 #[derive(Debug, Serialize)]
 struct OpenRouterRequest {
     model: String,
     messages: Vec<Message>,
+    stream: bool,
+    #[serde(flatten)]
+    sampling: SamplingOptions,
+    usage: UsageRequest,
+}
+
+/// Asks OpenRouter to append token counts and cost to the stream
+#[derive(Debug, Serialize)]
+struct UsageRequest {
+    include: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,129 +99,686 @@ struct Message {
     content: String,
 }
 
+/// One `data:` payload of a streamed chat completion
 #[derive(Debug, Deserialize)]
-struct OpenRouterResponse {
-    choices: Vec<Choice>,
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    /// OpenRouter reports failures after the stream has started in-band
+    error: Option<serde_json::Value>,
+    /// Only on the last chunk before [DONE]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Debug, Deserialize)]
-struct Choice {
-    message: Message,
+struct StreamChoice {
+    delta: Delta,
+}
+
+#[derive(Debug, Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+/// What a complete line of the response stream carried
+#[derive(Debug, PartialEq)]
+enum StreamEvent {
+    Token(String),
+    Usage(TokenUsage),
+    Done,
+}
+
+/// Incremental parser for OpenRouter's `text/event-stream` responses
+///
+/// Chunks can end mid-line (or mid-character), so bytes are buffered until a
+/// newline arrives. Comment lines (`: OPENROUTER PROCESSING`) are keep-alives.
+#[derive(Debug, Default)]
+struct StreamParser {
+    buffer: Vec<u8>,
+}
+
+impl StreamParser {
+    fn push(&mut self, chunk: &[u8]) -> anyhow::Result<Vec<StreamEvent>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim_end().strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim_start();
+            if data == "[DONE]" {
+                events.push(StreamEvent::Done);
+                continue;
+            }
+
+            let chunk: StreamChunk =
+                serde_json::from_str(data).context("Malformed OpenRouter stream chunk")?;
+            if let Some(error) = chunk.error {
+                anyhow::bail!("OpenRouter stream error: {}", error);
+            }
+            events.extend(
+                chunk
+                    .choices
+                    .into_iter()
+                    .filter_map(|choice| choice.delta.content)
+                    .filter(|token| !token.is_empty())
+                    .map(StreamEvent::Token),
+            );
+            events.extend(chunk.usage.map(StreamEvent::Usage));
+        }
+
+        Ok(events)
+    }
+}
+
+/// OpenRouter turned the request away with 429 Too Many Requests
+///
+/// Free models are often rate limited on their own, so this is not retried
+/// while another model in the chain could answer instead.
+#[derive(Debug)]
+struct RateLimited {
+    model: String,
+    message: String,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OpenRouter rate limited {}: {}",
+            self.model, self.message
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// OpenRouter took the request but one model couldn't answer it: an error
+/// status naming the model or its provider, or a stream that failed midway
+///
+/// Like `RateLimited`, it says nothing about OpenRouter itself, so it isn't
+/// counted against the shared circuit breaker.
+#[derive(Debug)]
+struct ModelFailed {
+    model: String,
+    message: String,
+}
+
+impl fmt::Display for ModelFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OpenRouter model {} failed: {}",
+            self.model, self.message
+        )
+    }
+}
+
+impl std::error::Error for ModelFailed {}
+
+/// Whether `error` is one model's failure rather than OpenRouter's
+fn is_model_failure(error: &anyhow::Error) -> bool {
+    error.is::<RateLimited>() || error.is::<ModelFailed>()
+}
+
+/// Statuses that fail every model alike: a rejected key, no credits left,
+/// or OpenRouter's own internal error
+fn is_provider_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 401 | 402 | 403 | 500)
+}
+
+/// A rejected response and why, sent back so the next attempt can fix it
+#[derive(Clone)]
+struct Correction {
+    rejected: String,
+    problems: Vec<QualityProblem>,
 }
 
 pub struct PoemGenerator {
     api_key: String,
-    model: String,
+    /// Tried in order; later models only write the poem when earlier ones fail
+    models: Vec<String>,
     client: reqwest::Client,
+    prompt: PromptTemplate,
+    sampling: SamplingOptions,
+    validator: PoemValidator,
+    events: Option<EventBus>,
+    breaker: CircuitBreaker,
+    retry: RetryPolicy,
+    /// Tag for the prompt's `{language}` placeholder
+    language: String,
 }
 
 impl PoemGenerator {
+    /// `model` is one OpenRouter model or a comma-separated priority list of them
     pub fn new(api_key: String, model: String) -> Self {
+        let mut models: Vec<String> = model
+            .split(',')
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+            .map(str::to_string)
+            .collect();
+        if models.is_empty() {
+            models.push(model);
+        }
         Self {
             api_key,
-            model,
+            models,
             client: reqwest::Client::new(),
+            prompt: PromptTemplate::default(),
+            sampling: SamplingOptions::default(),
+            validator: PoemValidator::default(),
+            events: None,
+            breaker: CircuitBreaker::new("openrouter"),
+            retry: RetryPolicy::llm(),
+            language: DEFAULT_LANGUAGE.to_string(),
         }
     }
 
-    /// Generate a poem from a list of keywords with retry logic
-    pub async fn generate_poem(&self, keywords: &[String]) -> Result<String> {
-        self.generate_poem_with_retry(keywords, 3).await
+    /// Publish each streamed token as a `poem_token` event while generating
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
     }
 
-    /// Generate a poem with configurable retry attempts
-    async fn generate_poem_with_retry(&self, keywords: &[String], max_retries: u32) -> Result<String> {
-        let mut last_error = None;
+    /// Fill the prompt's `{language}` placeholder with `language` instead of `en`
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = language;
+        self
+    }
 
-        for attempt in 0..max_retries {
-            if attempt > 0 {
-                let delay_secs = 2u64.pow(attempt); // Exponential backoff: 2, 4, 8 seconds
-                println!("⏳ Retry attempt {} after {} seconds...", attempt + 1, delay_secs);
-                tokio::time::sleep(tokio::time::Duration::from_secs(delay_secs)).await;
-            }
+    /// Use a custom prompt template and system prompt instead of the built-in one
+    pub fn with_prompt(mut self, prompt: PromptTemplate) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Send temperature, max_tokens, and the other sampling parameters with each request
+    pub fn with_sampling(mut self, sampling: SamplingOptions) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Attempts and backoff for each poem request; a rewrite after failed
+    /// quality checks counts as an attempt
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Reject poems using less than this fraction of their keywords (see `PoemValidator`)
+    pub fn with_min_keyword_fraction(mut self, fraction: f64) -> Self {
+        self.validator = PoemValidator::new(fraction);
+        self
+    }
+
+    /// Share a circuit breaker with other generators calling OpenRouter
+    ///
+    /// Failed requests and broken streams count against it; a poem rejected
+    /// for having the wrong shape does not.
+    pub fn with_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    pub fn breaker(&self) -> &CircuitBreaker {
+        &self.breaker
+    }
+
+    /// Check that OpenRouter is reachable and accepts the API key
+    pub async fn check_api(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(OPENROUTER_KEY_URL)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .map_err(|e| ChainVerseError::LlmProvider(e.into()))?;
+
+        if !response.status().is_success() {
+            return Err(ChainVerseError::LlmProvider(anyhow::anyhow!(
+                "OpenRouter returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Generate the poem for `date` in a specific form, in the day's `mood`
+    ///
+    /// A poem that fails the quality checks is sent back to the model with
+    /// what was wrong, and the rewrite counts as the next attempt.
+    pub async fn generate_poem_in_form(
+        &self,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+    ) -> Result<GeneratedPoem> {
+        let started = Instant::now();
+        let result = self
+            .generate_poem_with_retry(keywords, form, date, mood)
+            .await
+            .map_err(ChainVerseError::LlmProvider);
+        let elapsed = started.elapsed();
+        metrics::POEM_GENERATION_SECONDS.observe(elapsed.as_secs_f64());
+        if let Ok(poem) = &result {
+            info!(
+                model = %poem.model,
+                %form,
+                mood = mood.map(|mood| mood.name()),
+                duration_ms = elapsed.as_millis() as u64,
+                "Poem generated by LLM"
+            );
+        }
+        result
+    }
+
+    /// Generate a week's or month's meta-poem from a prompt built by `collections::prompt`
+    ///
+    /// Tokens stream as `poem_token` events under the period key.
+    #[instrument(skip(self, prompt), fields(models = %self.models.join(",")))]
+    pub async fn generate_collection_poem(
+        &self,
+        period: &str,
+        prompt: &str,
+    ) -> Result<GeneratedPoem> {
+        self.with_fallbacks(|model, attempt| {
+            let messages = vec![Message {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            }];
+            self.complete(model, messages, period, attempt)
+        })
+        .await
+        .map_err(ChainVerseError::LlmProvider)
+    }
+
+    /// Generate a poem, retrying as the generator's `RetryPolicy` allows
+    ///
+    /// A rejected poem's rewrite request carries over to the next model
+    /// when one takes over.
+    #[instrument(skip(self, keywords), fields(models = %self.models.join(","), keywords = keywords.len()))]
+    async fn generate_poem_with_retry(
+        &self,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+    ) -> anyhow::Result<GeneratedPoem> {
+        let correction = Mutex::new(None);
+        self.with_fallbacks(|model, attempt| {
+            self.try_generate_poem(model, keywords, form, date, mood, attempt, &correction)
+        })
+        .await
+    }
 
-            match self.try_generate_poem(keywords).await {
+    /// Run `attempt` against each model in priority order until one writes
+    /// the poem
+    ///
+    /// Each model gets the full `RetryPolicy`, except that a rate limit moves
+    /// on to the next model straight away. An open circuit breaker stops the
+    /// chain, since every model is behind the same OpenRouter endpoint.
+    async fn with_fallbacks<'a, F, Fut>(
+        &'a self,
+        mut attempt_once: F,
+    ) -> anyhow::Result<GeneratedPoem>
+    where
+        F: FnMut(&'a str, u32) -> Fut,
+        Fut: Future<Output = anyhow::Result<GeneratedPoem>>,
+    {
+        let (last, fallbacks) = self
+            .models
+            .split_last()
+            .expect("PoemGenerator::new keeps at least one model");
+        for (i, model) in fallbacks.iter().enumerate() {
+            match self
+                .with_retries(true, |attempt| attempt_once(model, attempt))
+                .await
+            {
                 Ok(poem) => return Ok(poem),
+                Err(e) if is_circuit_open(&e) => return Err(e),
                 Err(e) => {
-                    println!("⚠️  Attempt {} failed: {}", attempt + 1, e);
-                    last_error = Some(e);
+                    metrics::LLM_MODEL_FALLBACKS.inc();
+                    warn!(
+                        %model,
+                        next = %self.models[i + 1],
+                        error = %e,
+                        "Model failed; falling back to the next one"
+                    );
                 }
             }
         }
+        self.with_retries(false, |attempt| attempt_once(last, attempt))
+            .await
+    }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Failed after {} attempts", max_retries)))
+    /// Run `attempt` (numbered from 1) under the generator's `RetryPolicy`;
+    /// rate limits aren't retried when there is a `fallback` model to try
+    async fn with_retries<F, Fut>(
+        &self,
+        fallback: bool,
+        mut attempt_once: F,
+    ) -> anyhow::Result<GeneratedPoem>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = anyhow::Result<GeneratedPoem>>,
+    {
+        self.retry
+            .run(
+                "poem generation",
+                // Retrying can't help until the breaker lets a probe through,
+                // and a rate-limited model gives way to the next one
+                |e: &anyhow::Error| !is_circuit_open(e) && (!fallback || !e.is::<RateLimited>()),
+                |attempt| {
+                    if attempt > 1 {
+                        metrics::LLM_RETRIES.inc();
+                    }
+                    attempt_once(attempt)
+                },
+            )
+            .await
     }
 
-    /// Single attempt to generate a poem
-    async fn try_generate_poem(&self, keywords: &[String]) -> Result<String> {
-        let prompt = self.create_prompt(keywords);
+    /// Single attempt to generate a poem, streaming the response
+    ///
+    /// After a rejection, `correction` holds the rejected poem and its
+    /// problems; this attempt asks for a rewrite and replaces it if that is
+    /// rejected too.
+    #[allow(clippy::too_many_arguments)]
+    async fn try_generate_poem(
+        &self,
+        model: &str,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+        attempt: u32,
+        correction: &Mutex<Option<Correction>>,
+    ) -> anyhow::Result<GeneratedPoem> {
+        let mut messages = Vec::new();
+        if let Some(system) = self.create_system_prompt(keywords, form, date, mood) {
+            messages.push(Message {
+                role: "system".to_string(),
+                content: system,
+            });
+        }
+        messages.push(Message {
+            role: "user".to_string(),
+            content: self.create_prompt(keywords, form, date, mood),
+        });
+        let previous = correction.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(correction) = previous {
+            messages.push(Message {
+                role: "assistant".to_string(),
+                content: correction.rejected,
+            });
+            messages.push(Message {
+                role: "user".to_string(),
+                content: poem_quality::corrective_prompt(&correction.problems),
+            });
+        }
 
+        let poem = self.complete(model, messages, date, attempt).await?;
+        let problems = self.validator.check(&poem.content, keywords, form);
+        if problems.iter().any(|problem| !problem.is_hint()) {
+            metrics::POEMS_REJECTED.inc();
+            let reasons: Vec<String> = problems.iter().map(ToString::to_string).collect();
+            *correction.lock().unwrap_or_else(|e| e.into_inner()) = Some(Correction {
+                rejected: poem.response(),
+                problems,
+            });
+            anyhow::bail!("Poem rejected: {}", reasons.join("; "));
+        }
+        for problem in &problems {
+            info!(%date, %problem, "Keeping poem despite a problem");
+        }
+
+        Ok(poem)
+    }
+
+    /// Stream one completion through the breaker and split off its title
+    async fn complete(
+        &self,
+        model: &str,
+        messages: Vec<Message>,
+        date: &str,
+        attempt: u32,
+    ) -> anyhow::Result<GeneratedPoem> {
         let request = OpenRouterRequest {
-            model: self.model.clone(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt,
-            }],
+            model: model.to_string(),
+            messages,
+            stream: true,
+            sampling: self.sampling,
+            usage: UsageRequest { include: true },
         };
 
+        let (text, usage) = self
+            .call_openrouter(self.stream_completion(&request, date, attempt))
+            .await?;
+
+        if text.trim().is_empty() {
+            anyhow::bail!("OpenRouter returned an empty poem");
+        }
+        Ok(GeneratedPoem {
+            usage,
+            model: model.to_string(),
+            ..split_title(&text)
+        })
+    }
+
+    /// Run `call` unless the breaker is open, counting only OpenRouter's own
+    /// failures against it
+    ///
+    /// A model that is rate limited or failing shouldn't open the breaker and
+    /// so starve the fallback models behind the same endpoint.
+    async fn call_openrouter<T>(
+        &self,
+        call: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        self.breaker.call(call, |e| !is_model_failure(e)).await
+    }
+
+    /// Send a completion request and collect the streamed text and reported usage
+    ///
+    /// Once OpenRouter has answered, failures are the model's (`ModelFailed`),
+    /// except for statuses that would fail any model.
+    async fn stream_completion(
+        &self,
+        request: &OpenRouterRequest,
+        date: &str,
+        attempt: u32,
+    ) -> anyhow::Result<(String, Option<TokenUsage>)> {
         let response = self
             .client
             .post(OPENROUTER_API_URL)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(request)
             .send()
             .await?;
 
-        if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RateLimited {
+                model: request.model.clone(),
+                message: response.text().await?,
+            }
+            .into());
+        }
+        let status = response.status();
+        if !status.is_success() {
             let error_text = response.text().await?;
-            anyhow::bail!("OpenRouter API error: {}", error_text);
+            if is_provider_status(status) {
+                anyhow::bail!("OpenRouter API error: {}", error_text);
+            }
+            return Err(ModelFailed {
+                model: request.model.clone(),
+                message: format!("{}: {}", status, error_text),
+            }
+            .into());
         }
 
-        let response_data: OpenRouterResponse = response.json().await?;
+        self.read_stream(response, date, attempt)
+            .await
+            .map_err(|e| {
+                ModelFailed {
+                    model: request.model.clone(),
+                    message: format!("{:#}", e),
+                }
+                .into()
+            })
+    }
 
-        let poem = response_data
-            .choices
-            .first()
-            .context("No choices in response")?
-            .message
-            .content
-            .clone();
+    /// Collect a completion's streamed text and reported usage, publishing
+    /// each token
+    ///
+    /// Fails as soon as the stream goes quiet for
+    /// LLM_STREAM_IDLE_TIMEOUT_SECS, rather than after a full response wait.
+    async fn read_stream(
+        &self,
+        mut response: reqwest::Response,
+        date: &str,
+        attempt: u32,
+    ) -> anyhow::Result<(String, Option<TokenUsage>)> {
+        let idle_timeout = Duration::from_secs(LLM_STREAM_IDLE_TIMEOUT_SECS);
+        let mut parser = StreamParser::default();
+        let mut text = String::new();
+        let mut usage = None;
+        let mut done = false;
 
-        Ok(poem)
+        while !done {
+            let chunk = tokio::time::timeout(idle_timeout, response.chunk())
+                .await
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "OpenRouter stream stalled for {}s after {} chars",
+                        LLM_STREAM_IDLE_TIMEOUT_SECS,
+                        text.len()
+                    )
+                })??;
+            let Some(chunk) = chunk else {
+                anyhow::bail!("OpenRouter stream ended before [DONE]");
+            };
+
+            for event in parser.push(&chunk)? {
+                match event {
+                    StreamEvent::Token(token) => {
+                        if let Some(events) = &self.events {
+                            events.publish(CollectorEvent::PoemToken {
+                                date: date.to_string(),
+                                attempt,
+                                text: token.clone(),
+                            });
+                        }
+                        text.push_str(&token);
+                    }
+                    StreamEvent::Usage(reported) => usage = Some(reported),
+                    StreamEvent::Done => done = true,
+                }
+            }
+        }
+
+        Ok((text, usage))
     }
 
-    /// Create a prompt for poem generation
-    fn create_prompt(&self, keywords: &[String]) -> String {
-        let keywords_str = keywords.join(", ");
+    /// Render the user prompt for a day's keywords
+    pub fn create_prompt(
+        &self,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+    ) -> String {
+        self.prompt.render_user(&PromptVars {
+            keywords,
+            date,
+            form,
+            mood,
+            language: &self.language,
+        })
+    }
 
-        format!(
-            r#"You are a poetic AI that creates beautiful, evocative poems.
+    /// Render the system prompt, if one is configured
+    pub fn create_system_prompt(
+        &self,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+    ) -> Option<String> {
+        self.prompt.render_system(&PromptVars {
+            keywords,
+            date,
+            form,
+            mood,
+            language: &self.language,
+        })
+    }
 
-Using ONLY the following keywords derived from the Solana blockchain, create a cohesive poem of 20-30 lines.
+    /// Record the model that wrote a day's poem and hashes of the exact
+    /// prompts it was generated from
+    pub fn provenance(
+        &self,
+        model: &str,
+        keywords: &[String],
+        form: PoemForm,
+        date: &str,
+        mood: Option<Mood>,
+    ) -> LlmProvenance {
+        let sha256 = |text: String| format!("{:x}", Sha256::digest(text.as_bytes()));
+        LlmProvenance {
+            model: model.to_string(),
+            sampling: self.sampling,
+            form: form.to_string(),
+            mood,
+            length: form.length(),
+            prompt_sha256: sha256(self.create_prompt(keywords, form, date, mood)),
+            system_prompt_sha256: self
+                .create_system_prompt(keywords, form, date, mood)
+                .map(sha256),
+        }
+    }
+}
 
-Keywords: {}
+/// Split a leading `Title: ...` line off the model's response
+///
+/// Models sometimes wrap the line in markdown (`**Title:** ...`, `# Title: ...`)
+/// or quotes; those are stripped. A response without a title line is all poem.
+fn split_title(response: &str) -> GeneratedPoem {
+    let response = response.trim();
+    let (first, rest) = response.split_once('\n').unwrap_or((response, ""));
 
-Instructions:
-- Use all or most of these keywords naturally in the poem
-- Create a coherent narrative or emotional arc
-- The poem can be any mood - happy, sad, dark, light, mysterious, etc.
-- Let the words guide the tone naturally
-- Use vivid imagery and metaphor
-- Make it flow well and feel complete
-- Do NOT add a title
-- Do NOT explain or comment on the poem
-- ONLY output the poem itself
+    let decoration = |c: char| c == '*' || c == '#' || c == '_' || c == '"' || c.is_whitespace();
+    let first = first.trim_matches(decoration);
+    let title = first
+        .get(..6)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("title:"))
+        .map(|_| first[6..].trim_matches(decoration).to_string())
+        .filter(|title| !title.is_empty() && title.chars().count() <= TITLE_MAX_CHARS);
 
-Write the poem now:"#,
-            keywords_str
-        )
+    match title {
+        Some(title) => GeneratedPoem {
+            title: Some(title),
+            content: rest.trim().to_string(),
+            usage: None,
+            model: String::new(),
+        },
+        None => GeneratedPoem {
+            title: None,
+            content: response.to_string(),
+            usage: None,
+            model: String::new(),
+        },
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::poem_form::PoemLength;
 
     #[test]
     fn test_create_prompt() {
@@ -147,11 +788,172 @@ mod tests {
         );
 
         let keywords = vec!["moon".to_string(), "silence".to_string(), "journey".to_string()];
-        let prompt = generator.create_prompt(&keywords);
+        let form = PoemForm::FreeVerse(PoemLength::DEFAULT);
+        let prompt = generator.create_prompt(&keywords, form, "2026-01-05", None);
 
         assert!(prompt.contains("moon"));
         assert!(prompt.contains("silence"));
         assert!(prompt.contains("journey"));
         assert!(prompt.contains("20-30 lines"));
     }
+
+    #[test]
+    fn test_model_fallback_chain() {
+        let generator = PoemGenerator::new(
+            "test_key".to_string(),
+            " meta-llama/llama-3.2-3b-instruct:free, mistralai/mistral-7b-instruct:free,,"
+                .to_string(),
+        );
+        assert_eq!(
+            generator.models,
+            [
+                "meta-llama/llama-3.2-3b-instruct:free",
+                "mistralai/mistral-7b-instruct:free"
+            ]
+        );
+
+        let generator = PoemGenerator::new("test_key".to_string(), "test_model".to_string());
+        assert_eq!(generator.models, ["test_model"]);
+    }
+
+    #[tokio::test]
+    async fn test_failing_model_leaves_breaker_closed() {
+        let generator = PoemGenerator::new("key".to_string(), "model-a,model-b".to_string())
+            .with_retry(RetryPolicy::new(4, Duration::ZERO));
+        let fallback_attempts = std::sync::atomic::AtomicU32::new(0);
+
+        // model-a fails every attempt; model-b only succeeds on its last one
+        let poem = generator
+            .with_fallbacks(|model, attempt| {
+                if model == "model-b" {
+                    fallback_attempts.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+                generator.call_openrouter(async move {
+                    if model == "model-a" || attempt < 4 {
+                        return Err(ModelFailed {
+                            model: model.to_string(),
+                            message: "502 Bad Gateway".to_string(),
+                        }
+                        .into());
+                    }
+                    Ok(GeneratedPoem {
+                        model: model.to_string(),
+                        ..split_title("A poem")
+                    })
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(poem.model, "model-b");
+        assert_eq!(
+            fallback_attempts.load(std::sync::atomic::Ordering::Relaxed),
+            4
+        );
+        assert_eq!(generator.breaker().status().consecutive_failures, 0);
+
+        // OpenRouter's own failures still count
+        let unreachable = generator
+            .call_openrouter(async { Err::<(), _>(anyhow::anyhow!("connection refused")) })
+            .await;
+        assert!(unreachable.is_err());
+        assert_eq!(generator.breaker().status().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_create_prompt_for_form() {
+        let generator = PoemGenerator::new(
+            "test_key".to_string(),
+            "test_model".to_string(),
+        );
+
+        let keywords = vec!["moon".to_string()];
+        let prompt = generator.create_prompt(&keywords, PoemForm::Haiku, "2026-01-05", None);
+
+        assert!(prompt.contains("haiku"));
+        assert!(prompt.contains("5-7-5"));
+        assert!(!prompt.contains("20-30 lines"));
+    }
+
+    #[test]
+    fn test_sampling_options_serialized() {
+        let request = |sampling| {
+            serde_json::to_value(OpenRouterRequest {
+                model: "test_model".to_string(),
+                messages: Vec::new(),
+                stream: true,
+                sampling,
+                usage: UsageRequest { include: true },
+            })
+            .unwrap()
+        };
+
+        // Unset options are left to the model's defaults
+        let json = request(SamplingOptions::default());
+        assert!(json.get("temperature").is_none());
+        assert!(json.get("seed").is_none());
+
+        let json = request(SamplingOptions {
+            temperature: Some(0.5),
+            max_tokens: Some(600),
+            seed: Some(42),
+            ..SamplingOptions::default()
+        });
+        assert_eq!(json["temperature"], 0.5);
+        assert_eq!(json["max_tokens"], 600);
+        assert_eq!(json["seed"], 42);
+        assert!(json.get("top_p").is_none());
+        assert_eq!(json["usage"]["include"], true);
+    }
+
+    #[test]
+    fn test_stream_parser() {
+        let mut parser = StreamParser::default();
+        let events = parser
+            .push(b": OPENROUTER PROCESSING\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"moon \"}}]}\n\nda")
+            .unwrap();
+        assert_eq!(events, vec![StreamEvent::Token("moon ".to_string())]);
+
+        // A line split across chunks, a role-only delta, and the terminator
+        let events = parser
+            .push(b"ta: {\"choices\":[{\"delta\":{\"content\":\"over\"}}]}\r\n\ndata: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\ndata: [DONE]\n\n")
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![StreamEvent::Token("over".to_string()), StreamEvent::Done]
+        );
+
+        // Usage arrives on a final chunk with no content
+        let events = parser
+            .push(b"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":120,\"completion_tokens\":300,\"total_tokens\":420,\"cost\":0.0021}}\n")
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![StreamEvent::Usage(TokenUsage {
+                prompt_tokens: 120,
+                completion_tokens: 300,
+                cost_usd: Some(0.0021),
+            })]
+        );
+
+        let error = parser
+            .push(b"data: {\"error\":{\"message\":\"rate limited\"}}\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("rate limited"));
+    }
+
+    #[test]
+    fn test_split_title() {
+        let poem = split_title("Title: Ledger of Tides\n\nmoon over water\nsilence");
+        assert_eq!(poem.title.as_deref(), Some("Ledger of Tides"));
+        assert_eq!(poem.content, "moon over water\nsilence");
+
+        let poem = split_title("**Title:** \"Ledger of Tides\"\n\nmoon over water");
+        assert_eq!(poem.title.as_deref(), Some("Ledger of Tides"));
+        assert_eq!(poem.content, "moon over water");
+
+        let poem = split_title("moon over water\nsilence");
+        assert_eq!(poem.title, None);
+        assert_eq!(poem.content, "moon over water\nsilence");
+    }
 }
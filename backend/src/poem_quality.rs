@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::consts::DEFAULT_MIN_KEYWORD_FRACTION;
+use crate::poem_form::PoemForm;
+
+/// Endings a keyword may take and still count as used ("rain" in "raining")
+const INFLECTIONS: &[&str] = &["s", "es", "d", "ed", "ing", "er", "ers", "ly"];
+
+/// Lines opening like these are never part of a poem
+const META_LINES: &[&str] = &["as an ai", "note:", "(note", "keywords used", "```"];
+
+/// First or last lines opening like these introduce or sign off the poem;
+/// inside a poem they may be verse
+const META_FRAMING: &[&str] = &[
+    "here is",
+    "here's",
+    "sure!",
+    "sure,",
+    "certainly!",
+    "of course!",
+    "i hope you",
+    "i've written",
+    "i have written",
+    "this poem",
+];
+
+/// Why a generated poem was turned down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QualityProblem {
+    /// The form's line count wasn't met (message from `PoemForm::validate`)
+    LineCount(String),
+    /// Free verse missed the line count it was asked for; only passed on in a
+    /// rewrite, since its length is a guide rather than a rule
+    LineCountHint(String),
+    /// An acrostic's lines don't spell its word (message from `PoemForm::validate_acrostic`)
+    Acrostic(String),
+    /// Too few of the keywords made it into the poem
+    KeywordCoverage {
+        used: usize,
+        required: usize,
+        missing: Vec<String>,
+    },
+    /// A line of commentary around the poem
+    MetaCommentary(String),
+}
+
+impl fmt::Display for QualityProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QualityProblem::LineCount(message)
+            | QualityProblem::LineCountHint(message)
+            | QualityProblem::Acrostic(message) => f.write_str(message),
+            QualityProblem::KeywordCoverage {
+                used,
+                required,
+                missing,
+            } => write!(
+                f,
+                "It uses {} of the keywords but needs at least {}; unused: {}",
+                used,
+                required,
+                missing.join(", ")
+            ),
+            QualityProblem::MetaCommentary(line) => write!(
+                f,
+                "It contains commentary that is not part of the poem: \"{}\"",
+                line
+            ),
+        }
+    }
+}
+
+impl QualityProblem {
+    /// Whether the problem is worth fixing in a rewrite but not a reason to reject
+    pub fn is_hint(&self) -> bool {
+        matches!(self, QualityProblem::LineCountHint(_))
+    }
+}
+
+/// Checks a generated poem before it is stored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoemValidator {
+    /// Fraction of the keywords the poem must use, capped by what the form can hold
+    min_keyword_fraction: f64,
+}
+
+impl Default for PoemValidator {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_KEYWORD_FRACTION)
+    }
+}
+
+impl PoemValidator {
+    pub fn new(min_keyword_fraction: f64) -> Self {
+        Self {
+            min_keyword_fraction: min_keyword_fraction.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Every problem with `poem`; empty when it can be stored
+    pub fn check(&self, poem: &str, keywords: &[String], form: PoemForm) -> Vec<QualityProblem> {
+        let mut problems = Vec::new();
+
+        if let Err(e) = form.validate(poem) {
+            problems.push(match form {
+                PoemForm::FreeVerse(_) => QualityProblem::LineCountHint(e.to_string()),
+                _ => QualityProblem::LineCount(e.to_string()),
+            });
+        }
+        if let Err(e) = form.validate_acrostic(poem) {
+            problems.push(QualityProblem::Acrostic(e.to_string()));
+        }
+
+        let required = self.required_keywords(keywords.len(), form);
+        let missing = unused_keywords(poem, keywords);
+        let used = keywords.len() - missing.len();
+        if used < required {
+            problems.push(QualityProblem::KeywordCoverage {
+                used,
+                required,
+                missing,
+            });
+        }
+
+        let lines: Vec<&str> = poem
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let last = lines.len().saturating_sub(1);
+        problems.extend(
+            lines
+                .iter()
+                .enumerate()
+                .filter(|&(i, line)| is_meta_commentary(line, i == 0 || i == last))
+                .map(|(_, line)| QualityProblem::MetaCommentary(line.to_string())),
+        );
+        problems
+    }
+
+    /// Keywords the poem must use out of `count`
+    fn required_keywords(&self, count: usize, form: PoemForm) -> usize {
+        let required = (count as f64 * self.min_keyword_fraction).ceil() as usize;
+        match form.keyword_capacity() {
+            Some(capacity) => required.min(capacity),
+            None => required,
+        }
+    }
+}
+
+/// The follow-up asking the model to fix a rejected poem
+pub fn corrective_prompt(problems: &[QualityProblem]) -> String {
+    let list: Vec<String> = problems
+        .iter()
+        .map(|problem| format!("- {}", problem))
+        .collect();
+    format!(
+        "That poem can't be used:\n{}\n\nRewrite it to fix these problems. Reply with only the \
+         title line and the poem, with no commentary before or after it.",
+        list.join("\n")
+    )
+}
+
+/// Keywords that appear nowhere in the poem, even inflected, in keyword order
+fn unused_keywords(poem: &str, keywords: &[String]) -> Vec<String> {
+    let words: HashSet<String> = poem
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    keywords
+        .iter()
+        .filter(|keyword| {
+            let keyword = keyword.to_lowercase();
+            !words.iter().any(|word| {
+                word.strip_prefix(keyword.as_str())
+                    .is_some_and(|ending| ending.is_empty() || INFLECTIONS.contains(&ending))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether `line` is commentary; `framing` for the poem's first and last lines
+fn is_meta_commentary(line: &str, framing: bool) -> bool {
+    let line = line
+        .trim_start_matches(|c: char| c == '*' || c == '_' || c == '#' || c.is_whitespace())
+        .to_lowercase();
+    let opens = |openings: &[&str]| openings.iter().any(|opening| line.starts_with(opening));
+    opens(META_LINES) || (framing && opens(META_FRAMING))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poem_form::PoemLength;
+
+    fn keywords(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    const HAIKU: &str = "Embers in the rain\nthe river keeps its secrets\nwinter stars listen";
+
+    #[test]
+    fn test_accepts_good_poem() {
+        let validator = PoemValidator::new(0.5);
+        let words = keywords(&["ember", "rain", "river", "anchor"]);
+        assert!(validator.check(HAIKU, &words, PoemForm::Haiku).is_empty());
+    }
+
+    #[test]
+    fn test_keyword_coverage() {
+        let validator = PoemValidator::new(0.5);
+        let poem = vec!["the raining sky falls"; 20].join("\n");
+        let words = keywords(&["rain", "ember", "river", "anchor"]);
+
+        let free_verse = PoemForm::FreeVerse(PoemLength::DEFAULT);
+
+        let problems = validator.check(&poem, &words, free_verse);
+        assert_eq!(
+            problems,
+            vec![QualityProblem::KeywordCoverage {
+                used: 1,
+                required: 2,
+                missing: keywords(&["ember", "river", "anchor"]),
+            }]
+        );
+
+        // Short forms only need as many keywords as they can hold
+        let words = keywords(&["rain", "ember", "river", "anchor", "tide", "moss"]);
+        assert!(validator.check(HAIKU, &words, PoemForm::Haiku).is_empty());
+
+        // A keyword hidden inside a longer word doesn't count
+        assert_eq!(unused_keywords("a heart", &keywords(&["art"])), ["art"]);
+        assert!(PoemValidator::new(0.0)
+            .check(&poem, &words, free_verse)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_free_verse_length_is_a_hint() {
+        let validator = PoemValidator::new(0.0);
+        let poem = ["the raining sky falls"; 12].join("\n");
+
+        let problems = validator.check(&poem, &[], PoemForm::FreeVerse(PoemLength::DEFAULT));
+        assert_eq!(
+            problems,
+            vec![QualityProblem::LineCountHint(
+                "A free_verse needs 20-30 lines, got 12".to_string()
+            )]
+        );
+        assert!(problems[0].is_hint());
+
+        // The fixed forms' line counts are the form itself
+        let problems = validator.check(&poem, &[], PoemForm::Sonnet);
+        assert!(!problems[0].is_hint());
+    }
+
+    #[test]
+    fn test_acrostic() {
+        let validator = PoemValidator::new(0.0);
+        let form = PoemForm::Acrostic("TIDE");
+        let poem = "Tallies of the night\ninto the ledger\ndawn settles its debts\nembers cool";
+        assert!(validator.check(poem, &[], form).is_empty());
+
+        let problems = validator.check(HAIKU, &[], form);
+        assert!(matches!(problems[0], QualityProblem::LineCount(_)));
+        assert!(matches!(problems[1], QualityProblem::Acrostic(_)));
+    }
+
+    #[test]
+    fn test_meta_commentary() {
+        let validator = PoemValidator::new(0.0);
+        let poem = format!(
+            "Here is your poem:\n{}\n\n*Note: I used every word.*",
+            HAIKU
+        );
+
+        let problems = validator.check(&poem, &[], PoemForm::Haiku);
+        assert!(problems.contains(&QualityProblem::MetaCommentary(
+            "Here is your poem:".to_string()
+        )));
+        assert!(problems.contains(&QualityProblem::MetaCommentary(
+            "*Note: I used every word.*".to_string()
+        )));
+        assert!(matches!(problems[0], QualityProblem::LineCount(_)));
+
+        // Framing phrases inside the poem may be verse
+        let poem = "Embers in the rain\nhere is the river\nwinter stars listen";
+        assert!(validator.check(poem, &[], PoemForm::Haiku).is_empty());
+
+        let prompt = corrective_prompt(&problems);
+        assert!(prompt.contains("- A haiku needs 3 lines, got 5"));
+        assert!(prompt.contains("Here is your poem:"));
+    }
+}
@@ -0,0 +1,630 @@
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, instrument, warn};
+
+use crate::blockchain::SolanaClient;
+use crate::consts::{
+    CUSTOM_POEM_MAX_RANGE_SLOTS, ESTIMATED_SLOTS_PER_DAY, MIN_KEYWORDS_FOR_POEM, ONE_DAY,
+    ONE_HOUR, PREVIEW_BLOCKS, PREVIEW_CACHE_CAPACITY, PREVIEW_MAX_PER_HOUR,
+};
+use crate::database::Database;
+use crate::derivation::KeywordDerivation;
+use crate::error::ChainVerseError;
+use crate::poem_form::{FormSelection, PoemForm, PoemLength};
+use crate::poem_generator::PoemGenerator;
+
+/// Number of neighbouring slots to try when a sampled slot was skipped
+const NEARBY_SLOT_ATTEMPTS: u64 = 10;
+
+/// A keyword derived for a preview poem
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewKeyword {
+    pub word: String,
+    pub slot: u64,
+    pub blockhash: String,
+    pub previous_blockhash: String,
+    pub block_time: Option<i64>,
+    pub source: &'static str,
+}
+
+/// An unofficial poem generated on demand for a date before the project started
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewPoem {
+    pub date: String,
+    pub title: Option<String>,
+    pub content: String,
+    pub keywords: Vec<PreviewKeyword>,
+    /// Always true: previews are never part of the canonical archive
+    pub unofficial: bool,
+    pub generated_at: String,
+}
+
+/// Request body for an on-demand poem: either a slot range or a unix timestamp range
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CustomRangeRequest {
+    pub start_slot: Option<u64>,
+    pub end_slot: Option<u64>,
+    /// Unix timestamp in seconds, converted to an estimated slot
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    /// Store the poem so it can be fetched again by id
+    #[serde(default)]
+    pub persist: bool,
+}
+
+/// An unofficial poem generated on demand for a slot range
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomPoem {
+    /// Set when the poem was persisted
+    pub id: Option<i64>,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub title: Option<String>,
+    pub content: String,
+    pub keywords: Vec<PreviewKeyword>,
+    /// Always true: custom poems are never part of the canonical archive
+    pub unofficial: bool,
+    pub generated_at: String,
+}
+
+/// Why a preview could not be produced
+#[derive(Debug)]
+pub enum PreviewError {
+    /// The date is malformed or not in the past
+    InvalidDate(String),
+    /// The requested slot or time range is malformed, in the future, or too wide
+    InvalidRange(String),
+    /// The date is covered by the official archive, starting at the contained date
+    OfficialRange(String),
+    /// Too many previews were generated recently
+    RateLimited,
+    /// Fetching blocks or generating the poem failed
+    Failed(anyhow::Error),
+}
+
+impl fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreviewError::InvalidDate(reason) => write!(f, "Invalid date: {}", reason),
+            PreviewError::InvalidRange(reason) => write!(f, "Invalid range: {}", reason),
+            PreviewError::OfficialRange(start) => write!(
+                f,
+                "Chain Verse has official poems from {} onward; use GET /api/poems/{{date}}",
+                start
+            ),
+            PreviewError::RateLimited => {
+                write!(f, "Preview limit reached, try again later")
+            }
+            PreviewError::Failed(e) => write!(f, "Preview generation failed: {}", e),
+        }
+    }
+}
+
+impl From<anyhow::Error> for PreviewError {
+    fn from(e: anyhow::Error) -> Self {
+        PreviewError::Failed(e)
+    }
+}
+
+impl From<ChainVerseError> for PreviewError {
+    fn from(e: ChainVerseError) -> Self {
+        PreviewError::Failed(e.into())
+    }
+}
+
+/// Generates "what would the chain have said" poems for historical dates and
+/// user-chosen slot ranges. Date previews are cached in memory and never written
+/// to the database; custom range poems are stored only when asked to be
+pub struct PreviewService {
+    solana_client: SolanaClient,
+    derivation: KeywordDerivation,
+    poem_generator: PoemGenerator,
+    database: Database,
+    min_keywords_for_poem: usize,
+    form_selection: FormSelection,
+    poem_length: PoemLength,
+    cache: Mutex<HashMap<String, PreviewPoem>>,
+    recent_generations: Mutex<VecDeque<Instant>>,
+    /// Serializes generation so concurrent requests for one date don't both hit the LLM
+    generation: tokio::sync::Mutex<()>,
+}
+
+impl PreviewService {
+    pub fn new(
+        solana_client: SolanaClient,
+        derivation: KeywordDerivation,
+        poem_generator: PoemGenerator,
+        database: Database,
+    ) -> Self {
+        Self {
+            solana_client,
+            derivation,
+            poem_generator,
+            database,
+            min_keywords_for_poem: MIN_KEYWORDS_FOR_POEM,
+            form_selection: FormSelection::default(),
+            poem_length: PoemLength::DEFAULT,
+            cache: Mutex::new(HashMap::new()),
+            recent_generations: Mutex::new(VecDeque::new()),
+            generation: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Override how many sampled keywords a poem needs, as the collector does
+    pub fn with_min_keywords_for_poem(mut self, min_keywords: usize) -> Self {
+        self.min_keywords_for_poem = min_keywords;
+        self
+    }
+
+    /// Pick poem forms the same way the collector does
+    pub fn with_form_selection(mut self, form_selection: FormSelection) -> Self {
+        self.form_selection = form_selection;
+        self
+    }
+
+    /// Bound free verse's line count, as configured for daily poems
+    pub fn with_poem_length(mut self, poem_length: PoemLength) -> Self {
+        self.poem_length = poem_length;
+        self
+    }
+
+    /// The form for a poem written from `keywords`, chosen like the collector's
+    fn form_for(&self, keywords: &[PreviewKeyword]) -> PoemForm {
+        self.form_selection
+            .resolve(keywords.first().map(|k| k.blockhash.as_str()))
+            .with_length(self.poem_length)
+    }
+
+    /// Get the preview poem for a date, generating it if it isn't cached
+    #[instrument(skip(self))]
+    pub async fn preview(&self, date: &str) -> Result<PreviewPoem, PreviewError> {
+        let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| PreviewError::InvalidDate("expected YYYY-MM-DD".to_string()))?;
+
+        if day >= self.database.timezone().today() {
+            return Err(PreviewError::InvalidDate("date must be in the past".to_string()));
+        }
+
+        if let Some(start) = self.database.get_project_start_date().await? {
+            if date >= start.as_str() {
+                return Err(PreviewError::OfficialRange(start));
+            }
+        }
+
+        if let Some(cached) = self.cached(date) {
+            return Ok(cached);
+        }
+
+        let _guard = self.generation.lock().await;
+
+        // Another request may have generated it while we waited
+        if let Some(cached) = self.cached(date) {
+            return Ok(cached);
+        }
+
+        let reserved = self.reserve_generation_slot()?;
+        let preview = self.generate(day).await;
+        if preview.is_err() {
+            self.release_generation_slot(reserved);
+        }
+        let preview = preview?;
+        self.store(preview.clone());
+
+        Ok(preview)
+    }
+
+    /// Generate a poem from blocks sampled across a slot or timestamp range
+    #[instrument(skip(self))]
+    pub async fn custom(&self, request: &CustomRangeRequest) -> Result<CustomPoem, PreviewError> {
+        let current_slot = self.solana_client.get_current_slot().await?;
+        let (start_slot, end_slot) = resolve_slot_range(
+            request,
+            current_slot,
+            Utc::now().timestamp(),
+            self.min_keywords_for_poem,
+        )?;
+
+        let _guard = self.generation.lock().await;
+        let reserved = self.reserve_generation_slot()?;
+        let poem = self.generate_custom(request, start_slot, end_slot).await;
+        if poem.is_err() {
+            self.release_generation_slot(reserved);
+        }
+        poem
+    }
+
+    /// Sample the range and write its poem, storing it if asked to
+    async fn generate_custom(
+        &self,
+        request: &CustomRangeRequest,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> Result<CustomPoem, PreviewError> {
+        info!(start_slot, end_slot, "Generating custom range poem");
+        let keywords = self.sample_keywords(start_slot, end_slot).await?;
+        if keywords.len() < self.min_keywords_for_poem {
+            return Err(PreviewError::Failed(anyhow::anyhow!(
+                "Only {} blocks were available between slots {} and {} (need {})",
+                keywords.len(),
+                start_slot,
+                end_slot,
+                self.min_keywords_for_poem
+            )));
+        }
+
+        let words: Vec<String> = keywords.iter().map(|k| k.word.clone()).collect();
+        let today = self
+            .database
+            .timezone()
+            .today()
+            .format("%Y-%m-%d")
+            .to_string();
+        let poem = self
+            .poem_generator
+            .generate_poem_in_form(&words, self.form_for(&keywords), &today, None)
+            .await?;
+
+        let id = if request.persist {
+            Some(
+                self.database
+                    .insert_custom_poem(
+                        start_slot,
+                        end_slot,
+                        poem.title.as_deref(),
+                        &poem.content,
+                        &words,
+                    )
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(CustomPoem {
+            id,
+            start_slot,
+            end_slot,
+            title: poem.title,
+            content: poem.content,
+            keywords,
+            unofficial: true,
+            generated_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    fn cached(&self, date: &str) -> Option<PreviewPoem> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get(date).cloned()
+    }
+
+    fn store(&self, preview: PreviewPoem) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if cache.len() >= PREVIEW_CACHE_CAPACITY {
+            let oldest = cache
+                .values()
+                .min_by(|a, b| a.generated_at.cmp(&b.generated_at))
+                .map(|p| p.date.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(preview.date.clone(), preview);
+    }
+
+    /// Count this generation against the hourly budget, or refuse if it is spent;
+    /// returns the reservation to give back if the generation fails
+    fn reserve_generation_slot(&self) -> Result<Instant, PreviewError> {
+        let mut recent = self
+            .recent_generations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let window = Duration::from_secs(ONE_HOUR);
+
+        while recent.front().is_some_and(|t| t.elapsed() > window) {
+            recent.pop_front();
+        }
+
+        if recent.len() >= PREVIEW_MAX_PER_HOUR {
+            return Err(PreviewError::RateLimited);
+        }
+
+        let reserved = Instant::now();
+        recent.push_back(reserved);
+        Ok(reserved)
+    }
+
+    /// Refund a reservation, so failed generations don't use up the budget
+    fn release_generation_slot(&self, reserved: Instant) {
+        let mut recent = self
+            .recent_generations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(index) = recent.iter().position(|&t| t == reserved) {
+            recent.remove(index);
+        }
+    }
+
+    /// Sample blocks across the day, derive keywords, and write the poem
+    async fn generate(&self, day: NaiveDate) -> Result<PreviewPoem> {
+        let date = day.format("%Y-%m-%d").to_string();
+        info!(%date, "Generating preview poem");
+
+        let current_slot = self.solana_client.get_current_slot().await?;
+        let day_start = self.database.timezone().midnight(day);
+        let seconds_ago = (Utc::now() - day_start).num_seconds().max(0) as u64;
+        let first_slot = current_slot.saturating_sub(seconds_ago * ESTIMATED_SLOTS_PER_DAY / ONE_DAY);
+        let keywords = self
+            .sample_keywords(first_slot, first_slot + ESTIMATED_SLOTS_PER_DAY - 1)
+            .await?;
+
+        if keywords.len() < self.min_keywords_for_poem {
+            anyhow::bail!(
+                "Only {} historical blocks were available for {} (need {})",
+                keywords.len(),
+                date,
+                self.min_keywords_for_poem
+            );
+        }
+
+        let words: Vec<String> = keywords.iter().map(|k| k.word.clone()).collect();
+        let poem = self
+            .poem_generator
+            .generate_poem_in_form(&words, self.form_for(&keywords), &date, None)
+            .await?;
+
+        Ok(PreviewPoem {
+            date,
+            title: poem.title,
+            content: poem.content,
+            keywords,
+            unofficial: true,
+            generated_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Derive up to PREVIEW_BLOCKS keywords from blocks spread evenly across
+    /// `start_slot..=end_slot`, stepping past skipped slots within each stride
+    async fn sample_keywords(&self, start_slot: u64, end_slot: u64) -> Result<Vec<PreviewKeyword>> {
+        let mut keywords = Vec::new();
+        for target_slot in sample_slots(start_slot, end_slot, PREVIEW_BLOCKS as u64) {
+            for offset in 0..NEARBY_SLOT_ATTEMPTS {
+                if target_slot + offset > end_slot {
+                    break;
+                }
+                match self.solana_client.get_block(target_slot + offset).await {
+                    Ok(block) => {
+                        let keyword = self.derivation.derive_keyword(&block)?;
+                        keywords.push(PreviewKeyword {
+                            word: keyword.word,
+                            slot: keyword.slot,
+                            blockhash: keyword.blockhash,
+                            previous_blockhash: keyword.previous_blockhash,
+                            block_time: keyword.block_time,
+                            source: keyword.source.as_str(),
+                        });
+                        break;
+                    }
+                    Err(e) => warn!(slot = target_slot + offset, error = %e, "Preview slot unavailable"),
+                }
+            }
+        }
+
+        Ok(keywords)
+    }
+}
+
+/// Turn a custom poem request into an inclusive slot range, estimating slots
+/// for timestamps from the current slot and the observed slot rate
+fn resolve_slot_range(
+    request: &CustomRangeRequest,
+    current_slot: u64,
+    now: i64,
+    min_keywords: usize,
+) -> Result<(u64, u64), PreviewError> {
+    let invalid = |reason: &str| PreviewError::InvalidRange(reason.to_string());
+
+    let (start_slot, end_slot) = match request {
+        CustomRangeRequest {
+            start_slot: Some(start),
+            end_slot: Some(end),
+            start_time: None,
+            end_time: None,
+            ..
+        } => (*start, *end),
+        CustomRangeRequest {
+            start_slot: None,
+            end_slot: None,
+            start_time: Some(start),
+            end_time: Some(end),
+            ..
+        } => {
+            if *end > now {
+                return Err(invalid("end_time is in the future"));
+            }
+            let slot_at = |time: i64| {
+                let seconds_ago = (now - time).max(0) as u64;
+                current_slot.saturating_sub(seconds_ago * ESTIMATED_SLOTS_PER_DAY / ONE_DAY)
+            };
+            (slot_at(*start), slot_at(*end))
+        }
+        _ => {
+            return Err(invalid(
+                "give either start_slot and end_slot, or start_time and end_time",
+            ))
+        }
+    };
+
+    if start_slot >= end_slot {
+        return Err(invalid("start must be before end"));
+    }
+    if end_slot > current_slot {
+        return Err(invalid("end is past the current slot"));
+    }
+    if end_slot - start_slot + 1 < min_keywords as u64 {
+        return Err(invalid("range is too narrow to sample enough blocks"));
+    }
+    if end_slot - start_slot > CUSTOM_POEM_MAX_RANGE_SLOTS {
+        return Err(invalid("range is wider than one week"));
+    }
+
+    Ok((start_slot, end_slot))
+}
+
+/// Up to `count` slots evenly spaced across `start..=end`, each centred in its stride
+fn sample_slots(start: u64, end: u64, count: u64) -> Vec<u64> {
+    let span = end - start + 1;
+    let count = count.min(span);
+    let spacing = span / count;
+    (0..count).map(|i| start + i * spacing + spacing / 2).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::WordDictionary;
+
+    async fn test_service(name: &str) -> (PreviewService, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "chain_verse_preview_{}_{}.db",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let db = Database::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        let service = PreviewService::new(
+            SolanaClient::new(),
+            KeywordDerivation::new(WordDictionary::load().unwrap()),
+            PoemGenerator::new("key".to_string(), "model-a".to_string()),
+            db,
+        );
+        (service, path)
+    }
+
+    #[tokio::test]
+    async fn test_preview_cache_hit() {
+        let (service, path) = test_service("cache").await;
+        service.store(PreviewPoem {
+            date: "2020-03-16".to_string(),
+            title: Some("Genesis".to_string()),
+            content: "A cached poem".to_string(),
+            keywords: Vec::new(),
+            unofficial: true,
+            generated_at: Utc::now().to_rfc3339(),
+        });
+
+        // Served without reserving a generation or touching the RPC
+        let preview = service.preview("2020-03-16").await.unwrap();
+        assert_eq!(preview.content, "A cached poem");
+        assert!(service.recent_generations.lock().unwrap().is_empty());
+
+        service.database.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_generation_budget() {
+        let (service, path) = test_service("budget").await;
+        let reservations: Vec<Instant> = (0..PREVIEW_MAX_PER_HOUR)
+            .map(|_| service.reserve_generation_slot().unwrap())
+            .collect();
+
+        assert!(matches!(
+            service.preview("2020-03-16").await,
+            Err(PreviewError::RateLimited)
+        ));
+
+        // A failed generation hands its reservation back
+        service.release_generation_slot(reservations[0]);
+        assert!(service.reserve_generation_slot().is_ok());
+        assert!(matches!(
+            service.reserve_generation_slot(),
+            Err(PreviewError::RateLimited)
+        ));
+
+        service.database.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_sample_slots() {
+        // A full day keeps the original preview spacing
+        let slots = sample_slots(1_000, 1_000 + ESTIMATED_SLOTS_PER_DAY - 1, 12);
+        assert_eq!(slots.len(), 12);
+        assert_eq!(slots[0], 1_000 + 9_000);
+        assert_eq!(slots[1] - slots[0], 18_000);
+
+        // Narrow ranges sample every slot
+        assert_eq!(sample_slots(10, 14, 12), vec![10, 11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn test_resolve_slot_range() {
+        let current_slot = 300_000_000;
+        let now = 1_700_000_000;
+        let min = MIN_KEYWORDS_FOR_POEM;
+
+        let slots = CustomRangeRequest {
+            start_slot: Some(1_000),
+            end_slot: Some(2_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_slot_range(&slots, current_slot, now, min).unwrap(),
+            (1_000, 2_000)
+        );
+
+        let hour = CustomRangeRequest {
+            start_time: Some(now - 2 * ONE_HOUR as i64),
+            end_time: Some(now - ONE_HOUR as i64),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_slot_range(&hour, current_slot, now, min).unwrap(),
+            (current_slot - 18_000, current_slot - 9_000)
+        );
+
+        let mixed = CustomRangeRequest {
+            start_slot: Some(1_000),
+            end_time: Some(now),
+            ..Default::default()
+        };
+        assert!(resolve_slot_range(&mixed, current_slot, now, min).is_err());
+
+        let backwards = CustomRangeRequest {
+            start_slot: Some(2_000),
+            end_slot: Some(1_000),
+            ..Default::default()
+        };
+        assert!(resolve_slot_range(&backwards, current_slot, now, min).is_err());
+
+        let future = CustomRangeRequest {
+            start_slot: Some(current_slot),
+            end_slot: Some(current_slot + 100),
+            ..Default::default()
+        };
+        assert!(resolve_slot_range(&future, current_slot, now, min).is_err());
+
+        let too_wide = CustomRangeRequest {
+            start_slot: Some(1_000),
+            end_slot: Some(1_000 + CUSTOM_POEM_MAX_RANGE_SLOTS + 1),
+            ..Default::default()
+        };
+        assert!(resolve_slot_range(&too_wide, current_slot, now, min).is_err());
+
+        // The configured minimum, not the default, bounds how narrow a range can be
+        let narrow = CustomRangeRequest {
+            start_slot: Some(1_000),
+            end_slot: Some(1_000 + min as u64 * 2),
+            ..Default::default()
+        };
+        assert!(resolve_slot_range(&narrow, current_slot, now, min).is_ok());
+        assert!(resolve_slot_range(&narrow, current_slot, now, min * 4).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::mood::Mood;
+use crate::poem_form::PoemForm;
+
+/// Prompt compiled into the binary, used when no template file is configured
+const DEFAULT_TEMPLATE: &str = include_str!("../prompts/default.txt");
+
+/// Placeholders a template may use
+const PLACEHOLDERS: &[&str] = &[
+    "keywords",
+    "date",
+    "form",
+    "form_name",
+    "guidance",
+    "mood",
+    "language",
+];
+
+/// What `{mood}` says on days without recorded block activity
+const NO_MOOD: &str = "unrecorded, so let the keywords set the tone";
+
+/// Values substituted into a prompt template
+#[derive(Debug, Clone)]
+pub struct PromptVars<'a> {
+    pub keywords: &'a [String],
+    /// Day the poem is for (YYYY-MM-DD)
+    pub date: &'a str,
+    pub form: PoemForm,
+    /// Tone from the day's block activity; None when it wasn't recorded
+    pub mood: Option<Mood>,
+    /// Tag of the language keywords were derived in, like `en` or `ja`
+    pub language: &'a str,
+}
+
+impl PromptVars<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "keywords" => Some(self.keywords.join(", ")),
+            "date" => Some(self.date.to_string()),
+            "form" => Some(self.form.description()),
+            "form_name" => Some(self.form.name().to_string()),
+            "guidance" => Some(self.form.keyword_guidance().to_string()),
+            "mood" => Some(
+                self.mood
+                    .map_or(NO_MOOD, |mood| mood.description())
+                    .to_string(),
+            ),
+            "language" => Some(self.language.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// User prompt template and optional system prompt sent to the LLM
+///
+/// Templates are plain text with `{keywords}`, `{date}`, `{form}` (the form's
+/// description), `{form_name}`, `{guidance}`, `{mood}` (the day's mood and
+/// how it should color the poem), and `{language}` (the dictionary's language
+/// tag) placeholders; `{{` and `}}` produce literal braces. Unknown placeholders are rejected when loading.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    system: Option<String>,
+    user: String,
+}
+
+impl Default for PromptTemplate {
+    fn default() -> Self {
+        Self {
+            system: None,
+            user: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl PromptTemplate {
+    /// Build from template text, checking every placeholder is known
+    pub fn new(system: Option<String>, user: String) -> Result<Self> {
+        if let Some(system) = &system {
+            check(system).context("Invalid system prompt")?;
+        }
+        check(&user).context("Invalid prompt template")?;
+        Ok(Self { system, user })
+    }
+
+    /// Load from files; a missing `user_path` keeps the built-in prompt
+    pub fn load(system_path: Option<&Path>, user_path: Option<&Path>) -> Result<Self> {
+        let read = |path: &Path| {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read prompt file {}", path.display()))
+        };
+
+        let system = system_path.map(read).transpose()?;
+        let user = match user_path {
+            Some(path) => read(path)?,
+            None => DEFAULT_TEMPLATE.to_string(),
+        };
+        Self::new(system, user)
+    }
+
+    /// Render the system prompt, if any
+    pub fn render_system(&self, vars: &PromptVars) -> Option<String> {
+        self.system.as_deref().map(|system| render(system, vars))
+    }
+
+    /// Render the user prompt
+    pub fn render_user(&self, vars: &PromptVars) -> String {
+        render(&self.user, vars)
+    }
+}
+
+/// A piece of a parsed template
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into literal text and `{name}` placeholders
+fn parse(template: &str) -> Result<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        segments.push(Segment::Text(&rest[..index]));
+        let tail = &rest[index..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            segments.push(Segment::Text(&tail[..1]));
+            rest = &tail[2..];
+        } else if let Some(stripped) = tail.strip_prefix('{') {
+            let end = stripped
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed `{{` in template"))?;
+            segments.push(Segment::Placeholder(&stripped[..end]));
+            rest = &stripped[end + 1..];
+        } else {
+            anyhow::bail!("Unmatched `}}` in template (use `}}}}` for a literal brace)");
+        }
+    }
+    segments.push(Segment::Text(rest));
+
+    Ok(segments)
+}
+
+fn check(template: &str) -> Result<()> {
+    for segment in parse(template)? {
+        if let Segment::Placeholder(name) = segment {
+            if !PLACEHOLDERS.contains(&name) {
+                anyhow::bail!(
+                    "Unknown placeholder `{{{}}}` (expected one of {})",
+                    name,
+                    PLACEHOLDERS.join(", ")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Substitute placeholders; templates are checked when loaded, so parse errors can't occur
+fn render(template: &str, vars: &PromptVars) -> String {
+    let mut output = String::with_capacity(template.len());
+    for segment in parse(template).unwrap_or_default() {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Placeholder(name) => output.push_str(&vars.get(name).unwrap_or_default()),
+        }
+    }
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(keywords: &[String]) -> PromptVars<'_> {
+        PromptVars {
+            keywords,
+            date: "2026-01-05",
+            form: PoemForm::Haiku,
+            mood: None,
+            language: "en",
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let keywords = vec!["moon".to_string(), "tide".to_string()];
+        let template = PromptTemplate::new(
+            Some("You write {form_name} for {date}.".to_string()),
+            "Write {form} from {keywords}. {{literal}}\n".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render_system(&vars(&keywords)).as_deref(),
+            Some("You write haiku for 2026-01-05.")
+        );
+        assert_eq!(
+            template.render_user(&vars(&keywords)),
+            format!("Write {} from moon, tide. {{literal}}", PoemForm::Haiku.description())
+        );
+    }
+
+    #[test]
+    fn test_mood_placeholder() {
+        let keywords = vec!["moon".to_string()];
+        let template = PromptTemplate::new(None, "Mood: {mood}".to_string()).unwrap();
+        assert_eq!(
+            template.render_user(&vars(&keywords)),
+            format!("Mood: {}", NO_MOOD)
+        );
+        let tranquil = PromptVars {
+            mood: Some(Mood::Tranquil),
+            ..vars(&keywords)
+        };
+        assert_eq!(
+            template.render_user(&tranquil),
+            format!("Mood: {}", Mood::Tranquil.description())
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates_rejected() {
+        assert!(PromptTemplate::new(None, "Use {words}".to_string()).is_err());
+        assert!(PromptTemplate::new(None, "Use {keywords".to_string()).is_err());
+        assert!(PromptTemplate::new(None, "Use keywords}".to_string()).is_err());
+        assert!(PromptTemplate::new(Some("{nope}".to_string()), "{keywords}".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_default_template_is_valid() {
+        let keywords = vec!["moon".to_string()];
+        let template = PromptTemplate::default();
+        assert!(check(&template.user).is_ok());
+        assert!(template.render_system(&vars(&keywords)).is_none());
+        assert!(template.render_user(&vars(&keywords)).ends_with("Write the poem now:"));
+    }
+}
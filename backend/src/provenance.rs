@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::database::{KeywordAnnotation, StoredKeyword};
+use crate::mood::Mood;
+use crate::poem_form::PoemLength;
+use crate::poem_generator::SamplingOptions;
+
+/// Everything needed to reproduce a poem, served at /api/poems/{date}/provenance
+///
+/// Recorded when the poem is generated. Re-deriving each keyword needs the
+/// block (by slot), its entropy source, and a dictionary matching
+/// `derivation.dictionary_sha256`; the LLM call itself is only reproducible
+/// as far as the model is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoemProvenance {
+    pub date: String,
+    pub derivation: DerivationProvenance,
+    pub keywords: Vec<KeywordProvenance>,
+    pub llm: LlmProvenance,
+}
+
+/// A poem's provenance as /api/poems/{date}/provenance serves it, with
+/// curators' notes on its keywords
+///
+/// Notes are kept apart from the recorded manifest since they can be added
+/// long after the poem was generated.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotatedProvenance {
+    #[serde(flatten)]
+    pub provenance: PoemProvenance,
+    /// Notes on the day's keywords, oldest first; match them to `keywords`
+    /// by word and slot
+    pub annotations: Vec<KeywordAnnotation>,
+}
+
+/// The derivation algorithm and word lists in effect when the poem was generated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivationProvenance {
+    /// DERIVATION_VERSION of the binary that generated the poem
+    pub version: u32,
+    /// SHA-256 of the active dictionary's words, one per line, in index order
+    pub dictionary_sha256: String,
+    /// Active dictionary theme; None for the base dictionary
+    pub theme: Option<String>,
+    /// Language tag of the active dictionary; None for manifests recorded
+    /// before dictionaries declared one
+    #[serde(default)]
+    pub language: Option<String>,
+    /// SHA-256 of the sorted blocklist, one word per line; None without a blocklist
+    pub blocklist_sha256: Option<String>,
+}
+
+/// Where one of the poem's keywords came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordProvenance {
+    pub word: String,
+    /// Negative for words a curator added by hand
+    pub slot: i64,
+    pub blockhash: String,
+    pub word_index: i64,
+    /// Entropy the word was derived from (see BlockDataSource), or `manual`
+    pub source: String,
+    pub theme: Option<String>,
+    /// Re-hashes past blocked or recently used words: the word is at
+    /// `sha256("{seed}:{rerolls}")` rather than the seed itself when non-zero
+    #[serde(default)]
+    pub rerolls: i64,
+}
+
+/// The model and exact prompts the poem was generated from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProvenance {
+    pub model: String,
+    /// Only the parameters that were set; the rest were the model's defaults
+    #[serde(default)]
+    pub sampling: SamplingOptions,
+    pub form: String,
+    /// Day's mood from its block activity; None when it wasn't recorded
+    #[serde(default)]
+    pub mood: Option<Mood>,
+    /// Free verse's line bounds and the count scaled from the day's activity;
+    /// None for the fixed forms
+    #[serde(default)]
+    pub length: Option<PoemLength>,
+    pub prompt_sha256: String,
+    pub system_prompt_sha256: Option<String>,
+}
+
+impl PoemProvenance {
+    pub fn new(
+        date: &str,
+        derivation: DerivationProvenance,
+        keywords: &[StoredKeyword],
+        llm: LlmProvenance,
+    ) -> Self {
+        Self {
+            date: date.to_string(),
+            derivation,
+            keywords: keywords
+                .iter()
+                .map(|k| KeywordProvenance {
+                    word: k.word.clone(),
+                    slot: k.slot,
+                    blockhash: k.blockhash.clone(),
+                    word_index: k.word_index,
+                    source: k.source.clone(),
+                    theme: k.theme.clone(),
+                    rerolls: k.rerolls,
+                })
+                .collect(),
+            llm,
+        }
+    }
+}
@@ -0,0 +1,677 @@
+use anyhow::{Context, Result};
+use base64ct::{Base64, Encoding};
+use chrono::{SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha1::Sha1;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::database::{StoredKeyword, StoredPoem};
+use crate::export::ExportFormat;
+
+const PUBLISH_TIMEOUT_SECS: u64 = 30;
+
+const X_API_URL: &str = "https://api.x.com/2";
+
+/// X's post limit; most characters count as one toward it
+const X_MAX_CHARS: usize = 280;
+
+/// Bluesky's post limit, in graphemes (never more than characters)
+const BLUESKY_MAX_CHARS: usize = 300;
+
+/// Bluesky's limit on an image's alt text
+const BLUESKY_MAX_ALT_CHARS: usize = 2000;
+
+/// How each day's poem is posted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PublishFormat {
+    /// The poem's text, split across a thread when it doesn't fit one post
+    #[default]
+    Thread,
+    /// One post with the rendered PNG (as `/api/poems/{date}.png`)
+    Image,
+}
+
+impl fmt::Display for PublishFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PublishFormat::Thread => "thread",
+            PublishFormat::Image => "image",
+        })
+    }
+}
+
+impl FromStr for PublishFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "thread" => Ok(PublishFormat::Thread),
+            "image" | "png" => Ok(PublishFormat::Image),
+            _ => anyhow::bail!("Unknown publish format `{}`", s),
+        }
+    }
+}
+
+/// OAuth 1.0a credentials of the X app and the account it posts as
+#[derive(Clone, PartialEq, Eq)]
+pub struct XCredentials {
+    pub api_key: String,
+    pub api_secret: String,
+    pub access_token: String,
+    pub access_token_secret: String,
+}
+
+/// An account the daily poem is posted to
+#[derive(Clone, PartialEq, Eq)]
+pub enum PublishTarget {
+    X(XCredentials),
+    /// A Bluesky account on `service` (a PDS URL), logged into with an app password
+    Bluesky {
+        service: String,
+        handle: String,
+        app_password: String,
+    },
+}
+
+impl PublishTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PublishTarget::X(_) => "x",
+            PublishTarget::Bluesky { .. } => "bluesky",
+        }
+    }
+
+    fn max_chars(&self) -> usize {
+        match self {
+            PublishTarget::X(_) => X_MAX_CHARS,
+            PublishTarget::Bluesky { .. } => BLUESKY_MAX_CHARS,
+        }
+    }
+}
+
+/// Posts each day's poem to X and Bluesky once it is stored
+///
+/// Posts carry the poem's keywords and the slots they came from. A target
+/// that fails is logged and skipped; nothing is retried.
+pub struct PoemPublisher {
+    client: reqwest::Client,
+    targets: Vec<PublishTarget>,
+    format: PublishFormat,
+}
+
+/// Reference to a Bluesky record, as used in replies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StrongRef {
+    uri: String,
+    cid: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlueskySession {
+    access_jwt: String,
+    did: String,
+}
+
+#[derive(Deserialize)]
+struct BlueskyBlob {
+    blob: Value,
+}
+
+#[derive(Deserialize)]
+struct XResponse {
+    data: XObject,
+}
+
+#[derive(Deserialize)]
+struct XObject {
+    id: String,
+}
+
+impl PoemPublisher {
+    pub fn new(targets: Vec<PublishTarget>, format: PublishFormat) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(PUBLISH_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            targets,
+            format,
+        }
+    }
+
+    pub fn targets(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.targets.iter().map(PublishTarget::name)
+    }
+
+    pub fn format(&self) -> PublishFormat {
+        self.format
+    }
+
+    /// Post `text` on its own to every target, cut to fit one post
+    pub async fn announce(&self, text: &str) {
+        for target in &self.targets {
+            let post = [truncate(text, target.max_chars())];
+            let result = match target {
+                PublishTarget::X(credentials) => self.post_to_x(credentials, &post, None).await,
+                PublishTarget::Bluesky {
+                    service,
+                    handle,
+                    app_password,
+                } => {
+                    self.post_to_bluesky(service, handle, app_password, &post, None, "")
+                        .await
+                }
+            };
+            match result {
+                Ok(url) => info!(target = target.name(), %url, "Announcement published"),
+                Err(e) => error!(
+                    target = target.name(),
+                    error = %e,
+                    "Failed to publish announcement"
+                ),
+            }
+        }
+    }
+
+    /// Post `poem`, written from `keywords`, to every target
+    pub async fn publish(&self, poem: &StoredPoem, keywords: &[StoredKeyword]) {
+        let image = match self.format {
+            PublishFormat::Thread => None,
+            PublishFormat::Image => match ExportFormat::Png
+                .render_blocking(poem.clone(), keywords.to_vec())
+                .await
+            {
+                Ok(png) => Some(png),
+                Err(e) => {
+                    error!(date = %poem.date, error = %e, "Failed to render poem for publishing");
+                    return;
+                }
+            },
+        };
+        let alt = truncate(
+            &format!("{}\n\n{}", heading(poem), poem.content.trim()),
+            BLUESKY_MAX_ALT_CHARS,
+        );
+
+        for target in &self.targets {
+            let posts = match self.format {
+                PublishFormat::Thread => thread_posts(poem, keywords, target.max_chars()),
+                PublishFormat::Image => vec![image_post(poem, keywords, target.max_chars())],
+            };
+            let result = match target {
+                PublishTarget::X(credentials) => {
+                    self.post_to_x(credentials, &posts, image.as_deref()).await
+                }
+                PublishTarget::Bluesky {
+                    service,
+                    handle,
+                    app_password,
+                } => {
+                    self.post_to_bluesky(
+                        service,
+                        handle,
+                        app_password,
+                        &posts,
+                        image.as_deref(),
+                        &alt,
+                    )
+                    .await
+                }
+            };
+            match result {
+                Ok(url) => info!(
+                    date = %poem.date,
+                    target = target.name(),
+                    posts = posts.len(),
+                    %url,
+                    "Poem published"
+                ),
+                Err(e) => error!(
+                    date = %poem.date,
+                    target = target.name(),
+                    error = %e,
+                    "Failed to publish poem"
+                ),
+            }
+        }
+    }
+
+    /// Post `posts` as a thread, the image on the first; returns the first post's URL
+    async fn post_to_x(
+        &self,
+        credentials: &XCredentials,
+        posts: &[String],
+        image: Option<&[u8]>,
+    ) -> Result<String> {
+        let media_id = match image {
+            Some(png) => {
+                let body = json!({
+                    "media": Base64::encode_string(png),
+                    "media_category": "tweet_image",
+                    "media_type": "image/png",
+                });
+                let uploaded = self
+                    .x_request(credentials, &format!("{}/media/upload", X_API_URL), &body)
+                    .await
+                    .context("Failed to upload image to X")?;
+                Some(uploaded.data.id)
+            }
+            None => None,
+        };
+
+        let mut first = None;
+        let mut parent: Option<String> = None;
+        for (i, text) in posts.iter().enumerate() {
+            let mut body = json!({ "text": text });
+            if let (0, Some(media_id)) = (i, &media_id) {
+                body["media"] = json!({ "media_ids": [media_id] });
+            }
+            if let Some(parent) = &parent {
+                body["reply"] = json!({ "in_reply_to_tweet_id": parent });
+            }
+            let posted = self
+                .x_request(credentials, &format!("{}/tweets", X_API_URL), &body)
+                .await
+                .context("Failed to post to X")?;
+            first.get_or_insert_with(|| posted.data.id.clone());
+            parent = Some(posted.data.id);
+        }
+
+        Ok(format!(
+            "https://x.com/i/status/{}",
+            first.unwrap_or_default()
+        ))
+    }
+
+    async fn x_request(
+        &self,
+        credentials: &XCredentials,
+        url: &str,
+        body: &Value,
+    ) -> Result<XResponse> {
+        let nonce: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        let authorization = oauth_header("POST", url, credentials, &nonce, Utc::now().timestamp());
+
+        Ok(self
+            .client
+            .post(url)
+            .header(AUTHORIZATION, authorization)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Post `posts` as a thread, the image on the first; returns the first post's URL
+    async fn post_to_bluesky(
+        &self,
+        service: &str,
+        handle: &str,
+        app_password: &str,
+        posts: &[String],
+        image: Option<&[u8]>,
+        alt: &str,
+    ) -> Result<String> {
+        let service = service.trim_end_matches('/');
+        let session: BlueskySession = self
+            .client
+            .post(format!("{}/xrpc/com.atproto.server.createSession", service))
+            .json(&json!({ "identifier": handle, "password": app_password }))
+            .send()
+            .await?
+            .error_for_status()
+            .context("Bluesky rejected the login")?
+            .json()
+            .await?;
+
+        let blob = match image {
+            Some(png) => {
+                let uploaded: BlueskyBlob = self
+                    .client
+                    .post(format!("{}/xrpc/com.atproto.repo.uploadBlob", service))
+                    .bearer_auth(&session.access_jwt)
+                    .header(CONTENT_TYPE, "image/png")
+                    .body(png.to_vec())
+                    .send()
+                    .await?
+                    .error_for_status()
+                    .context("Failed to upload image to Bluesky")?
+                    .json()
+                    .await?;
+                Some(uploaded.blob)
+            }
+            None => None,
+        };
+
+        let mut root: Option<StrongRef> = None;
+        let mut parent: Option<StrongRef> = None;
+        for (i, text) in posts.iter().enumerate() {
+            let mut record = json!({
+                "$type": "app.bsky.feed.post",
+                "text": text,
+                "createdAt": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            });
+            if let (0, Some(blob)) = (i, &blob) {
+                record["embed"] = json!({
+                    "$type": "app.bsky.embed.images",
+                    "images": [{ "alt": alt, "image": blob }],
+                });
+            }
+            if let (Some(root), Some(parent)) = (&root, &parent) {
+                record["reply"] = json!({ "root": root, "parent": parent });
+            }
+            let created: StrongRef = self
+                .client
+                .post(format!("{}/xrpc/com.atproto.repo.createRecord", service))
+                .bearer_auth(&session.access_jwt)
+                .json(&json!({
+                    "repo": session.did,
+                    "collection": "app.bsky.feed.post",
+                    "record": record,
+                }))
+                .send()
+                .await?
+                .error_for_status()
+                .context("Failed to post to Bluesky")?
+                .json()
+                .await?;
+            root.get_or_insert_with(|| created.clone());
+            parent = Some(created);
+        }
+
+        let rkey = root
+            .as_ref()
+            .and_then(|root| root.uri.rsplit('/').next())
+            .unwrap_or_default();
+        Ok(format!(
+            "https://bsky.app/profile/{}/post/{}",
+            session.did, rkey
+        ))
+    }
+}
+
+/// `Authorization` header signing a request with OAuth 1.0a (HMAC-SHA1)
+///
+/// Only the OAuth parameters are signed: X's v2 endpoints take JSON bodies,
+/// which are left out of the signature.
+fn oauth_header(
+    method: &str,
+    url: &str,
+    credentials: &XCredentials,
+    nonce: &str,
+    timestamp: i64,
+) -> String {
+    let timestamp = timestamp.to_string();
+    // Sorted by name, as the signature base string requires
+    let params = [
+        ("oauth_consumer_key", credentials.api_key.as_str()),
+        ("oauth_nonce", nonce),
+        ("oauth_signature_method", "HMAC-SHA1"),
+        ("oauth_timestamp", timestamp.as_str()),
+        ("oauth_token", credentials.access_token.as_str()),
+        ("oauth_version", "1.0"),
+    ];
+
+    let query: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value)))
+        .collect();
+    let base = format!(
+        "{}&{}&{}",
+        method.to_ascii_uppercase(),
+        percent_encode(url),
+        percent_encode(&query.join("&"))
+    );
+    let key = format!(
+        "{}&{}",
+        percent_encode(&credentials.api_secret),
+        percent_encode(&credentials.access_token_secret)
+    );
+    let mut mac =
+        Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(base.as_bytes());
+    let signature = Base64::encode_string(&mac.finalize().into_bytes());
+
+    let fields: Vec<String> = params
+        .iter()
+        .copied()
+        .chain([("oauth_signature", signature.as_str())])
+        .map(|(name, value)| format!("{}=\"{}\"", name, percent_encode(value)))
+        .collect();
+    format!("OAuth {}", fields.join(", "))
+}
+
+/// RFC 3986 percent-encoding, leaving only unreserved characters as they are
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn heading(poem: &StoredPoem) -> String {
+    poem.title
+        .clone()
+        .unwrap_or_else(|| format!("Chain Verse, {}", poem.date))
+}
+
+/// The keyword list and the range of slots the keywords came from
+fn footer(keywords: &[StoredKeyword]) -> String {
+    let words: Vec<&str> = keywords.iter().map(|k| k.word.as_str()).collect();
+    let first = keywords.iter().map(|k| k.slot).min();
+    let last = keywords.iter().map(|k| k.slot).max();
+    let slots = match (first, last) {
+        (Some(first), Some(last)) if first == last => format!("\nSlot {}", first),
+        (Some(first), Some(last)) => format!("\nSlots {}–{}", first, last),
+        _ => String::new(),
+    };
+    format!("Keywords: {}{}", words.join(", "), slots)
+}
+
+/// Posts of at most `limit` characters carrying the whole poem, broken
+/// between lines, with the keywords and slots at the end
+fn thread_posts(poem: &StoredPoem, keywords: &[StoredKeyword], limit: usize) -> Vec<String> {
+    let mut posts = Vec::new();
+    // The trailing newline leaves a blank line between heading and poem
+    let mut current = format!("{}\n", truncate(&heading(poem), limit - 1));
+
+    for line in poem.content.trim().lines() {
+        for piece in split_line(line.trim_end(), limit) {
+            if !current.is_empty() && chars(&current) + 1 + chars(&piece) > limit {
+                posts.push(current.trim_end().to_string());
+                current.clear();
+            }
+            if current.is_empty() {
+                // A stanza break that lands between posts is dropped
+                current = piece;
+            } else {
+                current.push('\n');
+                current.push_str(&piece);
+            }
+        }
+    }
+
+    let footer = footer(keywords);
+    if chars(&current) + 2 + chars(&footer) <= limit {
+        current = format!("{}\n\n{}", current.trim_end(), footer);
+    } else {
+        posts.push(current.trim_end().to_string());
+        current = truncate(&footer, limit);
+    }
+    posts.push(current);
+    posts.retain(|post| !post.is_empty());
+    posts
+}
+
+/// The single post accompanying the rendered image
+fn image_post(poem: &StoredPoem, keywords: &[StoredKeyword], limit: usize) -> String {
+    truncate(&format!("{}\n\n{}", heading(poem), footer(keywords)), limit)
+}
+
+/// `line`, or its words regrouped into pieces of at most `limit` characters
+fn split_line(line: &str, limit: usize) -> Vec<String> {
+    if chars(line) <= limit {
+        return vec![line.to_string()];
+    }
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+    for word in line.split_whitespace() {
+        let letters: Vec<char> = word.chars().collect();
+        for part in letters.chunks(limit) {
+            let part: String = part.iter().collect();
+            if !piece.is_empty() && chars(&piece) + 1 + chars(&part) > limit {
+                pieces.push(std::mem::take(&mut piece));
+            }
+            if !piece.is_empty() {
+                piece.push(' ');
+            }
+            piece.push_str(&part);
+        }
+    }
+    if !piece.is_empty() {
+        pieces.push(piece);
+    }
+    pieces
+}
+
+fn chars(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// `text` cut to at most `max` characters, ending in an ellipsis when cut
+fn truncate(text: &str, max: usize) -> String {
+    if chars(text) <= max {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max - 1).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poem(content: &str) -> StoredPoem {
+        StoredPoem {
+            id: 1,
+            date: "2026-01-05".to_string(),
+            title: Some("Ledger of Tides".to_string()),
+            content: content.to_string(),
+            keyword_ids: vec![1, 2],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: "2026-01-05 12:00:00".to_string(),
+        }
+    }
+
+    fn keyword(word: &str, slot: i64) -> StoredKeyword {
+        StoredKeyword {
+            id: slot,
+            word: word.to_string(),
+            slot,
+            blockhash: "hash".to_string(),
+            previous_blockhash: None,
+            block_time: None,
+            word_index: 0,
+            source: "blockhash".to_string(),
+            theme: None,
+            rerolls: 0,
+            tag: None,
+            epoch: None,
+            orphaned: false,
+            created_at: "2026-01-05 12:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_short_poem_fits_one_post() {
+        let keywords = [keyword("ember", 300), keyword("tide", 120)];
+        let posts = thread_posts(
+            &poem("Embers in the rain\n\nTides of the ledger"),
+            &keywords,
+            X_MAX_CHARS,
+        );
+        assert_eq!(
+            posts,
+            vec![
+                "Ledger of Tides\n\nEmbers in the rain\n\nTides of the ledger\n\nKeywords: ember, tide\nSlots 120–300"
+            ]
+        );
+        assert_eq!(
+            image_post(&poem(""), &keywords[..1], BLUESKY_MAX_CHARS),
+            "Ledger of Tides\n\nKeywords: ember\nSlot 300"
+        );
+    }
+
+    #[test]
+    fn test_long_poem_becomes_a_thread() {
+        let line = "the ledger hums beneath a tide of embers ".repeat(2);
+        let content = [line.trim(); 12].join("\n");
+        let long_line = "stone ".repeat(100);
+        let content = format!("{}\n\n{}", content, long_line.trim());
+        let keywords = [keyword("ember", 1)];
+
+        for limit in [X_MAX_CHARS, BLUESKY_MAX_CHARS] {
+            let posts = thread_posts(&poem(&content), &keywords, limit);
+            assert!(posts.len() > 1);
+            assert!(posts.iter().all(|post| chars(post) <= limit));
+            assert!(posts[0].starts_with("Ledger of Tides\n\nthe ledger hums"));
+            assert!(posts.last().unwrap().ends_with("Keywords: ember\nSlot 1"));
+            // Every word of the poem makes it into the thread
+            let words: usize = posts.iter().map(|post| post.matches("stone").count()).sum();
+            assert_eq!(words, 100);
+        }
+    }
+
+    #[test]
+    fn test_oauth_header() {
+        let credentials = XCredentials {
+            api_key: "xvz1evFS4wEEPTGEFPHBog".to_string(),
+            api_secret: "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw".to_string(),
+            access_token: "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb".to_string(),
+            access_token_secret: "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE".to_string(),
+        };
+        let header = oauth_header(
+            "post",
+            "https://api.x.com/2/tweets",
+            &credentials,
+            "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg",
+            1318622958,
+        );
+        assert!(header.starts_with(
+            "OAuth oauth_consumer_key=\"xvz1evFS4wEEPTGEFPHBog\", oauth_nonce=\"kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg\""
+        ));
+        assert!(
+            header.ends_with("oauth_signature=\"lr%2BtV%2FDKclEvXKVjG6tgaSSLV0k%3D\""),
+            "{}",
+            header
+        );
+        assert_eq!(
+            percent_encode("Ladies + Gentlemen ~!"),
+            "Ladies%20%2B%20Gentlemen%20~%21"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::consts::RARE_KEYWORD_GAP_DAYS;
+
+/// How unusual a keyword's word is, judged by its earlier appearances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordRarity {
+    /// Keywords with the same word collected before this one
+    pub previous_occurrences: i64,
+    /// Day the word last appeared before this keyword; None on its first appearance
+    pub last_seen: Option<String>,
+    /// Days between `last_seen` and this keyword's day
+    pub days_since_last: Option<i64>,
+    /// 1.0 for a first appearance, otherwise rising from 0 with the gap since
+    /// the last one (0.5 at RARE_KEYWORD_GAP_DAYS)
+    pub score: f64,
+    /// Worth celebrating: a first appearance or a return after a long gap
+    pub rare: bool,
+    /// The rarity in words, e.g. "First appearance in 212 days"
+    pub summary: String,
+}
+
+impl KeywordRarity {
+    /// Rarity of a keyword collected on `day`, after `previous_occurrences`
+    /// earlier ones of which the latest was on `last_seen`
+    pub fn new(previous_occurrences: i64, last_seen: Option<NaiveDate>, day: NaiveDate) -> Self {
+        let days_since_last = last_seen.map(|last| (day - last).num_days().max(0));
+        let score = match days_since_last {
+            None => 1.0,
+            Some(days) => days as f64 / (days + RARE_KEYWORD_GAP_DAYS) as f64,
+        };
+        let summary = match days_since_last {
+            None => "First appearance".to_string(),
+            Some(0) => "Also collected earlier the same day".to_string(),
+            Some(1) => "Last seen the day before".to_string(),
+            Some(days) => format!("First appearance in {} days", days),
+        };
+
+        Self {
+            previous_occurrences,
+            last_seen: last_seen.map(|last| last.format("%Y-%m-%d").to_string()),
+            days_since_last,
+            score,
+            rare: score >= 0.5,
+            summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_keyword_rarity() {
+        let first = KeywordRarity::new(0, None, day("2026-01-05"));
+        assert_eq!(first.score, 1.0);
+        assert!(first.rare);
+        assert_eq!(first.summary, "First appearance");
+
+        let returning = KeywordRarity::new(3, Some(day("2025-06-07")), day("2026-01-05"));
+        assert_eq!(returning.last_seen.as_deref(), Some("2025-06-07"));
+        assert_eq!(returning.days_since_last, Some(212));
+        assert!(returning.rare);
+        assert_eq!(returning.summary, "First appearance in 212 days");
+
+        let at_threshold = KeywordRarity::new(1, Some(day("2025-12-06")), day("2026-01-05"));
+        assert_eq!(at_threshold.score, 0.5);
+        assert!(at_threshold.rare);
+
+        let common = KeywordRarity::new(9, Some(day("2026-01-04")), day("2026-01-05"));
+        assert!(!common.rare);
+        assert_eq!(common.summary, "Last seen the day before");
+        assert_eq!(
+            KeywordRarity::new(1, Some(day("2026-01-05")), day("2026-01-05")).score,
+            0.0
+        );
+    }
+}
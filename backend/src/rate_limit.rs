@@ -0,0 +1,267 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::auth::ApiKeys;
+use crate::consts::RATE_LIMIT_MAX_TRACKED_CLIENTS;
+
+/// Sustained request rate and burst size for one client
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    pub per_second: f64,
+    pub burst: u32,
+}
+
+/// Who a request is charged to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ClientKey {
+    Ip(IpAddr),
+    /// Requests bearing a configured API key, by key name, with the admin quota
+    ApiKey(String),
+    /// No peer address was available (e.g. the router is driven without a socket)
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    pub(crate) fn full(quota: Quota, now: Instant) -> Self {
+        Self {
+            tokens: quota.burst as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, quota: Quota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * quota.per_second).min(quota.burst as f64);
+        self.updated = now;
+    }
+
+    /// Spend one token, or return how long until one is available
+    pub(crate) fn try_take(&mut self, quota: Quota, now: Instant) -> Result<(), Duration> {
+        self.refill(quota, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / quota.per_second))
+        }
+    }
+}
+
+/// Per-client token-bucket rate limiter for the public API
+///
+/// Clients are keyed by IP address; requests carrying a configured API key
+/// draw from that key's own, larger quota. Buckets live in memory and reset on restart.
+pub struct RateLimiter {
+    public: Quota,
+    admin: Quota,
+    api_keys: Arc<ApiKeys>,
+    trust_forwarded_for: bool,
+    buckets: Mutex<HashMap<ClientKey, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(public: Quota, admin: Quota, api_keys: Arc<ApiKeys>) -> Self {
+        Self {
+            public,
+            admin,
+            api_keys,
+            trust_forwarded_for: false,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Key clients by the first X-Forwarded-For address (only behind a trusted proxy)
+    pub fn with_trust_forwarded_for(mut self, trust: bool) -> Self {
+        self.trust_forwarded_for = trust;
+        self
+    }
+
+    fn client_key(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> ClientKey {
+        if let Some(key) = self.api_keys.authenticate(headers) {
+            return ClientKey::ApiKey(key.name.clone());
+        }
+
+        if self.trust_forwarded_for {
+            let forwarded = headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .and_then(|ip| ip.trim().parse().ok());
+            if let Some(ip) = forwarded {
+                return ClientKey::Ip(ip);
+            }
+        }
+
+        peer.map(ClientKey::Ip).unwrap_or(ClientKey::Unknown)
+    }
+
+    /// The quota a client draws from
+    fn quota(&self, key: &ClientKey) -> Quota {
+        if matches!(key, ClientKey::ApiKey(_)) {
+            self.admin
+        } else {
+            self.public
+        }
+    }
+
+    fn check(&self, key: ClientKey, now: Instant) -> Result<(), Duration> {
+        let quota = self.quota(&key);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Forget clients whose buckets have refilled so the map can't grow without bound
+        if buckets.len() >= RATE_LIMIT_MAX_TRACKED_CLIENTS && !buckets.contains_key(&key) {
+            buckets.retain(|client, bucket| {
+                let quota = self.quota(client);
+                bucket.refill(quota, now);
+                bucket.tokens < quota.burst as f64
+            });
+        }
+
+        buckets
+            .entry(key)
+            .or_insert_with(|| Bucket::full(quota, now))
+            .try_take(quota, now)
+    }
+}
+
+/// Middleware: reject requests over quota with 429 and a Retry-After header
+pub async fn limit(State(limiter): State<Arc<RateLimiter>>, request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let key = limiter.client_key(request.headers(), peer);
+
+    match limiter.check(key.clone(), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            debug!(client = ?key, path = %request.uri().path(), "Rate limited request");
+            let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(serde_json::json!({
+                    "error": format!("Rate limit exceeded, retry in {}s", seconds)
+                })),
+            )
+                .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC: Quota = Quota {
+        per_second: 1.0,
+        burst: 3,
+    };
+    const ADMIN: Quota = Quota {
+        per_second: 10.0,
+        burst: 30,
+    };
+
+    #[test]
+    fn test_bucket_burst_then_refill() {
+        let start = Instant::now();
+        let mut bucket = Bucket::full(PUBLIC, start);
+
+        for _ in 0..3 {
+            assert!(bucket.try_take(PUBLIC, start).is_ok());
+        }
+        let wait = bucket.try_take(PUBLIC, start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(1));
+
+        assert!(bucket.try_take(PUBLIC, start + Duration::from_secs(1)).is_ok());
+        assert!(bucket.try_take(PUBLIC, start + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_clients_are_limited_separately() {
+        let limiter = RateLimiter::new(PUBLIC, ADMIN, Arc::default());
+        let now = Instant::now();
+        let a = ClientKey::Ip("10.0.0.1".parse().unwrap());
+        let b = ClientKey::Ip("10.0.0.2".parse().unwrap());
+
+        for _ in 0..3 {
+            assert!(limiter.check(a.clone(), now).is_ok());
+        }
+        assert!(limiter.check(a, now).is_err());
+        assert!(limiter.check(b, now).is_ok());
+    }
+
+    #[test]
+    fn test_prune_uses_each_clients_quota() {
+        let limiter = RateLimiter::new(PUBLIC, ADMIN, Arc::default());
+        let now = Instant::now();
+        let key = ClientKey::ApiKey("ops".to_string());
+
+        // Still well short of the admin burst, though above the public one
+        for _ in 0..20 {
+            assert!(limiter.check(key.clone(), now).is_ok());
+        }
+        for i in 1..RATE_LIMIT_MAX_TRACKED_CLIENTS {
+            let ip = IpAddr::from((i as u32).to_be_bytes());
+            assert!(limiter.check(ClientKey::Ip(ip), now).is_ok());
+        }
+
+        // The next new client prunes the idle public buckets but not the key's
+        let later = now + Duration::from_secs(1);
+        assert!(limiter
+            .check(ClientKey::Ip("10.0.0.1".parse().unwrap()), later)
+            .is_ok());
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[&key].tokens, 20.0);
+    }
+
+    #[test]
+    fn test_client_key() {
+        let keys = ApiKeys::new(vec!["ops:reader:secret".parse().unwrap()]);
+        let limiter = RateLimiter::new(PUBLIC, ADMIN, Arc::new(keys));
+        let peer: IpAddr = "192.168.1.5".parse().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7, 10.0.0.1"));
+        assert_eq!(limiter.client_key(&headers, Some(peer)), ClientKey::Ip(peer));
+
+        let limiter = limiter.with_trust_forwarded_for(true);
+        assert_eq!(
+            limiter.client_key(&headers, Some(peer)),
+            ClientKey::Ip("203.0.113.7".parse().unwrap())
+        );
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        assert_eq!(
+            limiter.client_key(&headers, Some(peer)),
+            ClientKey::ApiKey("ops".to_string())
+        );
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer wrong"));
+        assert!(!matches!(
+            limiter.client_key(&headers, Some(peer)),
+            ClientKey::ApiKey(_)
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use dashmap::DashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::database::{StoredKeyword, StoredPoem};
+use crate::metrics;
+
+/// Results kept per query; past this the query's entries are dropped wholesale
+const MAX_ENTRIES_PER_QUERY: usize = 32;
+
+/// In-memory copies of the reads the API serves most: the poem list and a
+/// day's keywords and poem
+///
+/// Every write to `keywords` or `poems` through a `Database` holding the cache
+/// invalidates it, so a process sees its own writes at once. Writes from
+/// another process (a separate collector daemon) show up within `ttl`.
+#[derive(Debug)]
+pub struct ReadCache {
+    ttl: Duration,
+    /// Bumped by every invalidation; results read before one are never stored
+    generation: AtomicU64,
+    all_poems: Query<(), Vec<StoredPoem>>,
+    keywords_by_date: Query<String, Vec<StoredKeyword>>,
+    poem_by_date: Query<String, Option<StoredPoem>>,
+}
+
+impl ReadCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            generation: AtomicU64::new(0),
+            all_poems: Query::default(),
+            keywords_by_date: Query::default(),
+            poem_by_date: Query::default(),
+        }
+    }
+
+    /// Current generation, to pass back when storing a result read after this call
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Drop everything; called after any write to keywords or poems
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.all_poems.clear();
+        self.keywords_by_date.clear();
+        self.poem_by_date.clear();
+    }
+
+    pub fn all_poems(&self) -> Option<Vec<StoredPoem>> {
+        self.all_poems.get(&(), self.ttl, self.generation())
+    }
+
+    pub fn store_all_poems(&self, generation: u64, poems: &[StoredPoem]) {
+        self.store(&self.all_poems, (), poems.to_vec(), generation);
+    }
+
+    pub fn keywords_for_date(&self, date: &str) -> Option<Vec<StoredKeyword>> {
+        self.keywords_by_date.get(date, self.ttl, self.generation())
+    }
+
+    pub fn store_keywords_for_date(&self, generation: u64, date: &str, keywords: &[StoredKeyword]) {
+        self.store(
+            &self.keywords_by_date,
+            date.to_string(),
+            keywords.to_vec(),
+            generation,
+        );
+    }
+
+    pub fn poem_by_date(&self, date: &str) -> Option<Option<StoredPoem>> {
+        self.poem_by_date.get(date, self.ttl, self.generation())
+    }
+
+    pub fn store_poem_by_date(&self, generation: u64, date: &str, poem: &Option<StoredPoem>) {
+        self.store(
+            &self.poem_by_date,
+            date.to_string(),
+            poem.clone(),
+            generation,
+        );
+    }
+
+    fn store<K: Eq + Hash, T>(&self, query: &Query<K, T>, key: K, value: T, generation: u64) {
+        // A write since the read began would make this result stale
+        if generation == self.generation() {
+            query.insert(key, value, generation);
+        }
+    }
+}
+
+/// Cached results of one query, by its arguments
+#[derive(Debug)]
+struct Query<K: Eq + Hash, T> {
+    entries: DashMap<K, Entry<T>>,
+}
+
+impl<K: Eq + Hash, T> Default for Query<K, T> {
+    fn default() -> Self {
+        Self {
+            entries: DashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    value: T,
+    generation: u64,
+    stored_at: Instant,
+}
+
+impl<K: Eq + Hash, T> Query<K, T> {
+    fn insert(&self, key: K, value: T, generation: u64) {
+        if self.entries.len() >= MAX_ENTRIES_PER_QUERY {
+            self.entries.clear();
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                generation,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    fn clear(&self) {
+        self.entries.clear();
+    }
+}
+
+impl<K: Eq + Hash, T: Clone> Query<K, T> {
+    fn get<Q>(&self, key: &Q, ttl: Duration, generation: u64) -> Option<T>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let hit = self.entries.get(key).and_then(|entry| {
+            (entry.generation == generation && entry.stored_at.elapsed() < ttl)
+                .then(|| entry.value.clone())
+        });
+        match hit {
+            Some(_) => metrics::READ_CACHE_HITS.inc(),
+            None => metrics::READ_CACHE_MISSES.inc(),
+        }
+        hit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poem(date: &str) -> StoredPoem {
+        StoredPoem {
+            id: 1,
+            date: date.to_string(),
+            title: None,
+            content: "Embers in the rain".to_string(),
+            keyword_ids: vec![1, 2],
+            anchor_hash: None,
+            anchor_signature: None,
+            poem_hash: None,
+            created_at: format!("{} 12:00:00", date),
+        }
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let cache = ReadCache::new(Duration::from_secs(60));
+        assert_eq!(cache.all_poems(), None);
+
+        let generation = cache.generation();
+        cache.store_all_poems(generation, &[poem("2026-01-05")]);
+        cache.store_poem_by_date(generation, "2026-01-06", &None);
+        assert_eq!(cache.all_poems(), Some(vec![poem("2026-01-05")]));
+        // A day without a poem is cached too
+        assert_eq!(cache.poem_by_date("2026-01-06"), Some(None));
+        assert_eq!(cache.poem_by_date("2026-01-07"), None);
+
+        cache.invalidate();
+        assert_eq!(cache.all_poems(), None);
+        assert_eq!(cache.poem_by_date("2026-01-06"), None);
+
+        // A read that started before the write isn't stored
+        cache.store_all_poems(generation, &[poem("2026-01-05")]);
+        assert_eq!(cache.all_poems(), None);
+    }
+
+    #[test]
+    fn test_expiry_and_bounds() {
+        let cache = ReadCache::new(Duration::ZERO);
+        cache.store_keywords_for_date(cache.generation(), "2026-01-05", &[]);
+        assert_eq!(cache.keywords_for_date("2026-01-05"), None);
+
+        let cache = ReadCache::new(Duration::from_secs(60));
+        for day in 0..MAX_ENTRIES_PER_QUERY + 1 {
+            cache.store_keywords_for_date(cache.generation(), &format!("day {}", day), &[]);
+        }
+        assert!(cache.keywords_by_date.entries.len() <= MAX_ENTRIES_PER_QUERY);
+        assert_eq!(
+            cache.keywords_for_date(&format!("day {}", MAX_ENTRIES_PER_QUERY)),
+            Some(vec![])
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use rand::Rng;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::consts::{
+    DEFAULT_LLM_RETRY_ATTEMPTS, DEFAULT_LLM_RETRY_BASE_DELAY_MS, DEFAULT_RETRY_JITTER,
+    DEFAULT_RETRY_MAX_DELAY_MS, DEFAULT_RPC_RETRY_ATTEMPTS, DEFAULT_RPC_RETRY_BASE_DELAY_MS,
+};
+
+/// How many times to try a call and how long to wait between tries
+///
+/// Delays start at `base_delay` and double per retry up to `max_delay`, then
+/// get up to `jitter` of themselves added at random so clients that failed
+/// together don't retry together. Which errors are worth retrying is up to
+/// the caller (see `run`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Try up to `max_attempts` times, counting the first (at least once)
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay: Duration::from_millis(DEFAULT_RETRY_MAX_DELAY_MS),
+            jitter: 0.0,
+        }
+    }
+
+    /// Poem and meta-poem requests to OpenRouter
+    pub fn llm() -> Self {
+        Self::new(
+            DEFAULT_LLM_RETRY_ATTEMPTS,
+            Duration::from_millis(DEFAULT_LLM_RETRY_BASE_DELAY_MS),
+        )
+        .with_jitter(DEFAULT_RETRY_JITTER)
+    }
+
+    /// Passes over every Solana RPC endpoint
+    pub fn rpc() -> Self {
+        Self::new(
+            DEFAULT_RPC_RETRY_ATTEMPTS,
+            Duration::from_millis(DEFAULT_RPC_RETRY_BASE_DELAY_MS),
+        )
+        .with_jitter(DEFAULT_RETRY_JITTER)
+    }
+
+    /// Cap each delay, before jitter
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Add up to this fraction of each delay at random (0 waits exactly)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.max(0.0);
+        self
+    }
+
+    /// Delay before attempt `attempt + 1`, without jitter
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Delay before attempt `attempt + 1`
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        if self.jitter <= 0.0 {
+            return backoff;
+        }
+        backoff.mul_f64(1.0 + rand::thread_rng().gen_range(0.0..=self.jitter))
+    }
+
+    /// Run `attempt` (numbered from 1) until it succeeds, fails with an
+    /// error `retry_on` rejects, or runs out of attempts
+    ///
+    /// The last error is returned as is.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        what: &str,
+        retry_on: impl Fn(&E) -> bool,
+        mut attempt: F,
+    ) -> Result<T, E>
+    where
+        E: fmt::Display,
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut number = 1;
+        loop {
+            match attempt(number).await {
+                Ok(value) => return Ok(value),
+                Err(e) if number < self.max_attempts && retry_on(&e) => {
+                    let delay = self.delay(number);
+                    warn!(
+                        what,
+                        attempt = number,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Attempt failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    number += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_delays() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300));
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(300));
+        assert_eq!(policy.delay(40), Duration::from_millis(300));
+        assert_eq!(RetryPolicy::new(0, Duration::ZERO).max_attempts, 1);
+
+        let jittered = policy.with_jitter(0.5);
+        for _ in 0..20 {
+            let delay = jittered.delay(2);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(300));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+        let calls = AtomicU32::new(0);
+
+        // Succeeds on the third attempt
+        let result: Result<u32, String> = policy
+            .run(
+                "test",
+                |_| true,
+                |attempt| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    async move {
+                        if attempt < 3 {
+                            Err(format!("attempt {}", attempt))
+                        } else {
+                            Ok(attempt)
+                        }
+                    }
+                },
+            )
+            .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.swap(0, Ordering::Relaxed), 3);
+
+        // Out of attempts: the last error comes back
+        let result: Result<u32, String> = policy
+            .run(
+                "test",
+                |_| true,
+                |attempt| async move { Err(format!("attempt {}", attempt)) },
+            )
+            .await;
+        assert_eq!(result, Err("attempt 3".to_string()));
+
+        // Errors the predicate rejects aren't retried
+        let result: Result<u32, String> = policy
+            .run(
+                "test",
+                |e: &String| e != "fatal",
+                |_| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    async { Err("fatal".to_string()) }
+                },
+            )
+            .await;
+        assert_eq!(result, Err("fatal".to_string()));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
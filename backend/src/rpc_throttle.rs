@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::consts::{
+    BLOCK_LOOKUP_BURST, BLOCK_LOOKUP_REQUESTS_PER_SECOND, DEFAULT_RPC_BURST,
+    DEFAULT_RPC_REQUESTS_PER_SECOND, RPC_THROTTLE_BACKOFF_MS, RPC_THROTTLE_MAX_PAUSE_SECS,
+};
+use crate::metrics;
+use crate::rate_limit::{Bucket, Quota};
+
+#[derive(Debug)]
+struct EndpointThrottle {
+    bucket: Option<Bucket>,
+    /// No calls until then, after the endpoint answered 429
+    paused_until: Option<Instant>,
+    /// 429s in a row, for the backoff when the endpoint gives no Retry-After
+    consecutive_throttles: u32,
+}
+
+/// Paces Solana RPC calls per endpoint URL
+///
+/// Each endpoint gets a token bucket, and an endpoint that answers 429 is
+/// paused for everyone: for as long as its Retry-After asked, or with an
+/// exponential backoff when it gave none. Clones share state, so every
+/// client of the same endpoints draws from one budget.
+#[derive(Debug, Clone)]
+pub struct RpcThrottle {
+    /// None leaves calls unpaced; 429 pauses still apply
+    quota: Option<Quota>,
+    endpoints: Arc<Mutex<HashMap<String, EndpointThrottle>>>,
+}
+
+impl Default for RpcThrottle {
+    fn default() -> Self {
+        Self::new(Some(Quota {
+            per_second: DEFAULT_RPC_REQUESTS_PER_SECOND,
+            burst: DEFAULT_RPC_BURST,
+        }))
+    }
+}
+
+impl RpcThrottle {
+    pub fn new(quota: Option<Quota>) -> Self {
+        Self {
+            quota,
+            endpoints: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Pacing for public block lookups, kept apart from the collector's
+    pub fn block_lookups() -> Self {
+        Self::new(Some(Quota {
+            per_second: BLOCK_LOOKUP_REQUESTS_PER_SECOND,
+            burst: BLOCK_LOOKUP_BURST,
+        }))
+    }
+
+    /// Wait until a call to `url` is allowed, then spend a token for it
+    pub async fn acquire(&self, url: &str) {
+        while let Err(wait) = self.try_acquire(url, Instant::now()) {
+            debug!(%url, wait_ms = wait.as_millis() as u64, "Pacing RPC call");
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Spend a token for `url`, or return how long until a call is allowed
+    fn try_acquire(&self, url: &str, now: Instant) -> Result<(), Duration> {
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        let endpoint = self.endpoint(&mut endpoints, url, now);
+
+        if let Some(until) = endpoint.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            endpoint.paused_until = None;
+        }
+        match (self.quota, endpoint.bucket.as_mut()) {
+            (Some(quota), Some(bucket)) => bucket.try_take(quota, now),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pause calls to `url` after it answered 429; returns the pause
+    ///
+    /// `retry_after` is how long the endpoint asked callers to wait, if it said.
+    pub fn record_throttled(&self, url: &str, retry_after: Option<Duration>) -> Duration {
+        self.record_throttled_at(url, retry_after, Instant::now())
+    }
+
+    fn record_throttled_at(
+        &self,
+        url: &str,
+        retry_after: Option<Duration>,
+        now: Instant,
+    ) -> Duration {
+        metrics::RPC_THROTTLED.inc();
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        let endpoint = self.endpoint(&mut endpoints, url, now);
+
+        endpoint.consecutive_throttles += 1;
+        let pause = retry_after
+            .unwrap_or_else(|| {
+                let doublings = (endpoint.consecutive_throttles - 1).min(16);
+                Duration::from_millis(RPC_THROTTLE_BACKOFF_MS.saturating_mul(1 << doublings))
+            })
+            .min(Duration::from_secs(RPC_THROTTLE_MAX_PAUSE_SECS));
+
+        // Concurrent 429s keep the longest pause rather than shortening it
+        let until = now + pause;
+        if endpoint.paused_until.is_none_or(|current| current < until) {
+            endpoint.paused_until = Some(until);
+        }
+        warn!(%url, pause_ms = pause.as_millis() as u64, "RPC endpoint rate limited, pausing calls");
+        pause
+    }
+
+    /// Reset the backoff once `url` serves a call without throttling it
+    pub fn record_success(&self, url: &str) {
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(endpoint) = endpoints.get_mut(url) {
+            endpoint.consecutive_throttles = 0;
+        }
+    }
+
+    fn endpoint<'a>(
+        &self,
+        endpoints: &'a mut HashMap<String, EndpointThrottle>,
+        url: &str,
+        now: Instant,
+    ) -> &'a mut EndpointThrottle {
+        endpoints
+            .entry(url.to_string())
+            .or_insert_with(|| EndpointThrottle {
+                bucket: self.quota.map(|quota| Bucket::full(quota, now)),
+                paused_until: None,
+                consecutive_throttles: 0,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUOTA: Quota = Quota {
+        per_second: 2.0,
+        burst: 2,
+    };
+
+    #[test]
+    fn test_endpoints_paced_separately() {
+        let throttle = RpcThrottle::new(Some(QUOTA));
+        let now = Instant::now();
+
+        assert!(throttle.try_acquire("https://a.example", now).is_ok());
+        assert!(throttle.try_acquire("https://a.example", now).is_ok());
+        assert_eq!(
+            throttle.try_acquire("https://a.example", now),
+            Err(Duration::from_millis(500))
+        );
+        assert!(throttle.try_acquire("https://b.example", now).is_ok());
+
+        // Clones draw from the same buckets
+        let shared = throttle.clone();
+        assert!(shared.try_acquire("https://a.example", now).is_err());
+        assert!(shared
+            .try_acquire("https://a.example", now + Duration::from_millis(500))
+            .is_ok());
+
+        let unpaced = RpcThrottle::new(None);
+        for _ in 0..100 {
+            assert!(unpaced.try_acquire("https://a.example", now).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_throttled_endpoint_pauses() {
+        let throttle = RpcThrottle::new(None);
+        let url = "https://a.example";
+        let now = Instant::now();
+
+        // Retry-After wins when the endpoint sends one
+        let pause = throttle.record_throttled_at(url, Some(Duration::from_secs(3)), now);
+        assert_eq!(pause, Duration::from_secs(3));
+        assert_eq!(throttle.try_acquire(url, now), Err(Duration::from_secs(3)));
+        assert!(throttle.try_acquire("https://b.example", now).is_ok());
+        assert!(throttle.try_acquire(url, now + pause).is_ok());
+
+        // Otherwise the pause doubles with each 429 in a row
+        let later = now + pause;
+        let base = Duration::from_millis(RPC_THROTTLE_BACKOFF_MS);
+        assert_eq!(throttle.record_throttled_at(url, None, later), base * 2);
+        assert_eq!(throttle.record_throttled_at(url, None, later), base * 4);
+
+        // A shorter pause doesn't cut a longer one short
+        throttle.record_throttled_at(url, Some(Duration::from_millis(1)), later);
+        assert_eq!(throttle.try_acquire(url, later), Err(base * 4));
+
+        throttle.record_success(url);
+        assert_eq!(throttle.record_throttled_at(url, None, later), base);
+
+        // Retry-After is capped
+        let pause = throttle.record_throttled_at(url, Some(Duration::from_secs(3600)), later);
+        assert_eq!(pause, Duration::from_secs(RPC_THROTTLE_MAX_PAUSE_SECS));
+    }
+}
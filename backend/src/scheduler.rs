@@ -1,53 +1,578 @@
 use anyhow::Result;
-use std::time::Duration;
+use chrono::{DateTime, NaiveDate, Utc, Weekday};
+use rand::Rng;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
 use tokio::time;
+use tracing::{debug, error, info, instrument, warn};
 
-use crate::blockchain::SolanaClient;
-use crate::consts::MIN_KEYWORDS_FOR_POEM;
-use crate::database::Database;
-use crate::derivation::KeywordDerivation;
-use crate::poem_generator::PoemGenerator;
-use crate::words::WordDictionary;
+use crate::anchor::PoemAnchor;
+use crate::backfill::Backfill;
+use crate::backup::DatabaseBackup;
+use crate::blockchain::{self, BlockInfo, ChainEvent, EventWatcher, SolanaClient};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::collections::{self, Period};
+use crate::consts::{
+    BlockDataSource, DEFAULT_COLLECTION_INTERVAL_MINUTES, DEFAULT_KEYWORDS_PER_DAY,
+    DEFINITIONS_PER_TICK, EMBEDDINGS_PER_TICK, EVENT_WATCH_INTERVAL_SECS, KEYWORD_TAG_EVENT,
+    MANUAL_KEYWORD_SOURCE, MIN_KEYWORDS_FOR_POEM, MIN_POEMS_FOR_COLLECTION, NARRATIONS_PER_TICK,
+    ONE_DAY, POEM_LOCK_TTL_SECS, POEM_RETRY_BASE_SECS, POEM_RETRY_CHECK_SECS, POEM_RETRY_MAX_SECS,
+    REORG_CHECKS_PER_TICK, REORG_CHECK_DELAY_SECS, REORG_CHECK_INTERVAL_SECS,
+    REORG_CHECK_WINDOW_SECS,
+};
+use crate::control::CollectorCommand;
+use crate::database::{Database, FailedKeyword, StoredKeyword};
+use crate::definitions::DefinitionClient;
+use crate::derivation::{DerivedKeyword, KeywordDerivation, SourceWeights};
+use crate::embeddings::{self, EmbeddingClient};
+use crate::error::ChainVerseError;
+use crate::events::{CollectorEvent, EventBus};
+use crate::metrics;
+use crate::milestones;
+use crate::minting::PoemMinter;
+use crate::mood::{BlockActivity, Mood};
+use crate::notifications::{Notification, Notifier};
+use crate::poem_form::{FormSelection, PoemForm, PoemLength};
+use crate::poem_generator::{GeneratedPoem, PoemGenerator, SamplingOptions};
+use crate::prompt::PromptTemplate;
+use crate::provenance::PoemProvenance;
+use crate::publisher::PoemPublisher;
+use crate::retry::RetryPolicy;
+use crate::rpc_throttle::RpcThrottle;
+use crate::timezone::{self, Timezone};
+use crate::tts::TtsClient;
+use crate::words::{Blocklist, DictionaryHandle, ThemeSelection};
+
+/// Repair work discovered by the startup recovery scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepairJob {
+    /// A past day has enough keywords but never got its poem
+    GeneratePoem { date: String },
+    /// Today is behind schedule because ticks were missed while the daemon was down
+    CollectKeywords { date: String, missing: usize },
+    /// A past day has too few keywords to generate a poem and needs a historical backfill
+    Backfill { date: String, keywords: usize },
+}
+
+/// The collector's cadence, for estimating today's progress from outside it
+/// (see /api/poems/today)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectionSchedule {
+    pub interval_minutes: u64,
+    /// Timezone whose midnight aligned collections count from, None when unaligned
+    pub aligned: Option<Timezone>,
+    pub keywords_per_tick: usize,
+    pub keywords_per_day: usize,
+    pub min_keywords_for_poem: usize,
+}
+
+/// When today's next keywords and poem should arrive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TodayEstimate {
+    /// None once today's keywords are complete, or unaligned before the first
+    /// collection gives the cadence a starting point
+    pub next_collection: Option<DateTime<Utc>>,
+    /// The collection that should bring the poem; None once it is ready
+    pub poem_expected: Option<DateTime<Utc>>,
+    /// Keywords toward the poem, 0-100; only 100 once the poem is stored
+    pub progress_percent: u8,
+}
+
+impl Default for CollectionSchedule {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_COLLECTION_INTERVAL_MINUTES,
+            aligned: None,
+            keywords_per_tick: 1,
+            keywords_per_day: DEFAULT_KEYWORDS_PER_DAY,
+            min_keywords_for_poem: MIN_KEYWORDS_FOR_POEM,
+        }
+    }
+}
+
+impl CollectionSchedule {
+    /// Estimate today's progress from its stored keywords
+    ///
+    /// Unaligned collections are assumed to keep the cadence of the latest
+    /// scheduled one; event and manual keywords don't count toward it.
+    pub fn estimate(
+        &self,
+        keywords: &[StoredKeyword],
+        poem_ready: bool,
+        now: DateTime<Utc>,
+    ) -> TodayEstimate {
+        let collected = keywords.len();
+        let progress_percent = if poem_ready {
+            100
+        } else {
+            (collected * 100 / self.min_keywords_for_poem.max(1)).min(99) as u8
+        };
+
+        let next_collection = match self.aligned {
+            Some(timezone) => Some(next_collection(
+                None,
+                now,
+                self.interval_minutes,
+                Some(timezone),
+            )),
+            None => keywords
+                .iter()
+                .filter(|k| k.tag.is_none() && k.source != MANUAL_KEYWORD_SOURCE)
+                .filter_map(|k| timezone::parse_timestamp(&k.created_at))
+                .max()
+                .map(|last| next_collection(Some(last), now, self.interval_minutes, None)),
+        }
+        .filter(|_| collected < self.keywords_per_day);
+
+        // The collection that brings the last keyword needed also writes the poem
+        let poem_expected = next_collection.filter(|_| !poem_ready).map(|next| {
+            let ticks = self
+                .min_keywords_for_poem
+                .saturating_sub(collected)
+                .div_ceil(self.keywords_per_tick.max(1))
+                .max(1);
+            next + chrono::Duration::minutes(((ticks - 1) as u64 * self.interval_minutes) as i64)
+        });
+
+        TodayEstimate {
+            next_collection,
+            poem_expected,
+            progress_percent,
+        }
+    }
+}
 
 pub struct KeywordCollector {
     solana_client: SolanaClient,
     derivation: KeywordDerivation,
     database: Database,
     poem_generator: PoemGenerator,
+    minter: Option<PoemMinter>,
+    anchor: Option<PoemAnchor>,
+    definitions: Option<DefinitionClient>,
+    embeddings: Option<EmbeddingClient>,
+    tts: Option<TtsClient>,
+    notifier: Option<Notifier>,
+    publisher: Option<PoemPublisher>,
+    backup: Option<DatabaseBackup>,
+    backfill: Option<Backfill>,
+    self_backfill_days: usize,
     interval_minutes: u64,
+    align_to_interval: bool,
+    jitter_secs: u64,
+    min_keywords_for_poem: usize,
+    keywords_per_day: usize,
+    blocks_per_tick: usize,
+    keywords_per_block: usize,
+    source_weights: SourceWeights,
+    dedup_days: u32,
+    event_keywords_per_day: usize,
+    form_selection: FormSelection,
+    poem_length: PoemLength,
+    dictionary: DictionaryHandle,
+    theme_selection: Option<ThemeSelection>,
+    events: EventBus,
+    /// Names this collector's leases on poem generation (see `Database::try_acquire_poem_lock`)
+    instance_id: String,
+    /// Taken by `start`; see `with_control`
+    commands: Mutex<Option<mpsc::Receiver<CollectorCommand>>>,
 }
 
 impl KeywordCollector {
     pub fn new(
-        dictionary: WordDictionary,
+        dictionary: DictionaryHandle,
         database: Database,
+        rpc_urls: &[String],
         api_key: String,
         model: String,
         interval_minutes: u64,
     ) -> Self {
         Self {
-            solana_client: SolanaClient::new(),
-            derivation: KeywordDerivation::new(dictionary),
+            solana_client: SolanaClient::with_urls(rpc_urls),
+            derivation: KeywordDerivation::with_handle(dictionary.clone()),
             database,
             poem_generator: PoemGenerator::new(api_key, model),
+            minter: None,
+            anchor: None,
+            definitions: None,
+            embeddings: None,
+            tts: None,
+            notifier: None,
+            publisher: None,
+            backup: None,
+            backfill: None,
+            self_backfill_days: 0,
             interval_minutes,
+            align_to_interval: false,
+            jitter_secs: 0,
+            min_keywords_for_poem: MIN_KEYWORDS_FOR_POEM,
+            keywords_per_day: DEFAULT_KEYWORDS_PER_DAY,
+            blocks_per_tick: 1,
+            keywords_per_block: 1,
+            source_weights: SourceWeights::default(),
+            dedup_days: 0,
+            event_keywords_per_day: 0,
+            form_selection: FormSelection::default(),
+            poem_length: PoemLength::DEFAULT,
+            dictionary,
+            theme_selection: None,
+            events: EventBus::new(),
+            instance_id: lease_holder(),
+            commands: Mutex::new(None),
         }
     }
 
+    /// Draw keywords from a dictionary theme chosen per week (needs a themes directory)
+    pub fn with_theme_selection(mut self, theme_selection: ThemeSelection) -> Self {
+        self.theme_selection = Some(theme_selection);
+        self
+    }
+
+    /// Re-hash derived words that are on `blocklist`
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.derivation = self.derivation.with_blocklist(blocklist);
+        self
+    }
+
+    /// Sample only transactions touching `program` (see `SolanaClient::with_program_filter`)
+    pub fn with_program_filter(mut self, program: Pubkey) -> Self {
+        self.solana_client = self.solana_client.with_program_filter(program);
+        self
+    }
+
+    /// Keep `size` transaction signatures per block (see `SolanaClient::with_sample_size`)
+    pub fn with_sample_size(mut self, size: usize) -> Self {
+        self.solana_client = self.solana_client.with_sample_size(size);
+        self
+    }
+
+    /// Fetch parsed blocks for the enhanced entropy sources (see
+    /// `SolanaClient::with_enhanced_rpc`)
+    pub fn with_enhanced_rpc(mut self) -> Self {
+        self.solana_client = self.solana_client.with_enhanced_rpc();
+        self
+    }
+
+    /// Choose how each day's poem form is picked
+    pub fn with_form_selection(mut self, form_selection: FormSelection) -> Self {
+        self.form_selection = form_selection;
+        self
+    }
+
+    /// Bound free verse's line count, which scales with each day's activity
+    pub fn with_poem_length(mut self, poem_length: PoemLength) -> Self {
+        self.poem_length = poem_length;
+        self
+    }
+
+    /// Override how many keywords a day needs before its poem is generated
+    pub fn with_min_keywords_for_poem(mut self, min_keywords: usize) -> Self {
+        self.min_keywords_for_poem = min_keywords;
+        self
+    }
+
+    /// Override how many keywords are collected per day before collection pauses
+    pub fn with_keywords_per_day(mut self, keywords_per_day: usize) -> Self {
+        self.keywords_per_day = keywords_per_day;
+        self
+    }
+
+    /// Fetch this many recent blocks per collection instead of only the latest
+    pub fn with_blocks_per_tick(mut self, blocks_per_tick: usize) -> Self {
+        self.blocks_per_tick = blocks_per_tick.max(1);
+        self
+    }
+
+    /// Keep this many words from each block, each from a different data
+    /// source (see `KeywordDerivation::derive_weighted`)
+    pub fn with_keywords_per_block(mut self, keywords_per_block: usize) -> Self {
+        self.keywords_per_block = keywords_per_block.max(1);
+        self
+    }
+
+    /// Choose which sources each block's words come from by their weights
+    /// (see `SourceWeights::compose`)
+    pub fn with_source_weights(mut self, source_weights: SourceWeights) -> Self {
+        self.source_weights = source_weights;
+        self
+    }
+
+    /// Collect on multiples of the interval since local midnight rather than
+    /// counting from startup (see `next_collection`)
+    pub fn with_alignment(mut self, align_to_interval: bool) -> Self {
+        self.align_to_interval = align_to_interval;
+        self
+    }
+
+    /// Delay each collection by a random 0..=`jitter_secs` seconds
+    pub fn with_jitter(mut self, jitter_secs: u64) -> Self {
+        self.jitter_secs = jitter_secs;
+        self
+    }
+
+    /// Re-roll words already collected today or in the previous `days` days (0 disables)
+    pub fn with_dedup_days(mut self, days: u32) -> Self {
+        self.dedup_days = days;
+        self
+    }
+
+    /// Share circuit breakers with the other Solana RPC and OpenRouter clients
+    pub fn with_circuit_breakers(mut self, rpc: CircuitBreaker, llm: CircuitBreaker) -> Self {
+        self.solana_client = self.solana_client.with_breaker(rpc);
+        self.poem_generator = self.poem_generator.with_breaker(llm);
+        self
+    }
+
+    /// Retry failed RPC calls and poem requests as these policies allow
+    pub fn with_retry_policies(mut self, rpc: RetryPolicy, llm: RetryPolicy) -> Self {
+        self.solana_client = self.solana_client.with_retry(rpc);
+        self.poem_generator = self.poem_generator.with_retry(llm);
+        self
+    }
+
+    /// Share Solana RPC pacing with the other clients of the same endpoints
+    pub fn with_rpc_throttle(mut self, throttle: RpcThrottle) -> Self {
+        self.solana_client = self.solana_client.with_throttle(throttle);
+        self
+    }
+
+    /// Watch for notable blocks and collect up to `extra` more keywords a day from them
+    /// (see `EventWatcher`); 0 disables the watcher
+    pub fn with_event_keywords_per_day(mut self, extra: usize) -> Self {
+        self.event_keywords_per_day = extra;
+        self
+    }
+
+    /// Generate poems from a custom prompt template and system prompt
+    pub fn with_prompt(mut self, prompt: PromptTemplate) -> Self {
+        self.poem_generator = self.poem_generator.with_prompt(prompt);
+        self
+    }
+
+    /// Send these sampling parameters with each poem request
+    pub fn with_sampling(mut self, sampling: SamplingOptions) -> Self {
+        self.poem_generator = self.poem_generator.with_sampling(sampling);
+        self
+    }
+
+    /// Reject poems using less than this fraction of the day's keywords
+    pub fn with_min_keyword_fraction(mut self, fraction: f64) -> Self {
+        self.poem_generator = self.poem_generator.with_min_keyword_fraction(fraction);
+        self
+    }
+
+    /// Mint each new poem as a compressed NFT after it is stored
+    pub fn with_minter(mut self, minter: PoemMinter) -> Self {
+        self.minter = Some(minter);
+        self
+    }
+
+    /// Take operator commands from the admin API while running
+    /// (see `CollectorControl::channel`)
+    pub fn with_control(self, commands: mpsc::Receiver<CollectorCommand>) -> Self {
+        *self.commands.lock().unwrap_or_else(|e| e.into_inner()) = Some(commands);
+        self
+    }
+
+    /// Publish lifecycle events on `events` (see GET /api/events)
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.poem_generator = self.poem_generator.with_events(events.clone());
+        self.events = events;
+        self
+    }
+
+    /// Anchor a hash of each new poem on-chain in a memo transaction
+    pub fn with_anchor(mut self, anchor: PoemAnchor) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Look up new words in the dictionary after each collection
+    pub fn with_definitions(mut self, definitions: DefinitionClient) -> Self {
+        self.definitions = Some(definitions);
+        self
+    }
+
+    /// Embed new poems, and any from before embeddings were enabled, for
+    /// /api/poems/{date}/similar
+    pub fn with_embeddings(mut self, embeddings: EmbeddingClient) -> Self {
+        self.embeddings = Some(embeddings);
+        self
+    }
+
+    /// Read new poems aloud, and any from before audio was enabled, for
+    /// /api/poems/{date}/audio
+    pub fn with_tts(mut self, tts: TtsClient) -> Self {
+        self.tts = Some(tts);
+        self
+    }
+
+    /// Push each new poem, and failed generations, to Discord and Telegram
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Post each new poem to X and Bluesky after it is stored
+    pub fn with_publisher(mut self, publisher: PoemPublisher) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Snapshot the database into a directory on a schedule, keeping the newest few
+    pub fn with_backup(mut self, backup: DatabaseBackup) -> Self {
+        self.backup = Some(backup);
+        self
+    }
+
+    /// On start, backfill the newest `max_days` past days that never got a
+    /// poem, oldest first, alongside the normal collections
+    pub fn with_self_backfill(mut self, backfill: Backfill, max_days: usize) -> Self {
+        self.backfill = Some(backfill);
+        self.self_backfill_days = max_days;
+        self
+    }
+
     /// Start the keyword collection loop
-    pub async fn start(&self) -> Result<()> {
-        println!("🚀 Starting keyword collector...");
-        println!("   Collecting keywords every {} minutes\n", self.interval_minutes);
+    ///
+    /// Runs until `shutdown` flips to true. A tick that is already running
+    /// (block fetch, keyword insert, poem generation) finishes before the loop
+    /// exits. The database pool is left open for the caller, which may share it
+    /// with the API.
+    ///
+    /// Days whose poem failed to generate are retried from the `pending_poems`
+    /// queue with exponential backoff until one attempt succeeds. Keywords that
+    /// failed to store are replayed from `failed_keywords` on the same schedule.
+    ///
+    /// With a backup configured, the database is snapshotted on start and then
+    /// every backup interval.
+    ///
+    /// With self-backfill configured, past days without a poem are backfilled
+    /// on start, before the first collection.
+    ///
+    /// Collections resume the cadence of the last one recorded in
+    /// `scheduler_state`, after the recovery scan has caught up on any ticks
+    /// missed while the daemon was down.
+    ///
+    /// With a control channel, operators can collect off the schedule or pause
+    /// collection. A paused collector skips scheduled and event collections
+    /// but keeps generating poems; pausing isn't remembered across restarts.
+    ///
+    /// Collectors sharing a database take a lease on each day's poem before
+    /// generating it, so only one of them writes it.
+    ///
+    /// Keywords are re-checked against the finalized chain an hour after they
+    /// were collected; those whose block a reorg abandoned are flagged
+    /// orphaned and left out of their day's poem.
+    pub async fn start(&self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        info!(
+            interval_minutes = self.interval_minutes,
+            aligned = self.align_to_interval,
+            jitter_secs = self.jitter_secs,
+            instance = %self.instance_id,
+            "Starting keyword collector"
+        );
 
-        let mut interval = time::interval(Duration::from_secs(self.interval_minutes * 60));
+        // Heal anything a previous shutdown left behind before resuming the normal cadence
+        self.replay_failed_keywords().await;
+        match self.recovery_scan().await {
+            Ok(jobs) => self.run_repair_jobs(&jobs).await,
+            Err(e) => error!(error = %e, "Recovery scan failed"),
+        }
+        self.maybe_generate_collections().await;
+
+        // Old gaps fill in alongside collection rather than holding up the first one
+        let self_backfill = self.self_backfill(shutdown.clone());
+        tokio::pin!(self_backfill);
+        let mut backfilling = self.backfill.is_some();
+
+        let last_collection = self.last_collection().await;
+        let mut scheduled = next_collection(
+            last_collection,
+            Utc::now(),
+            self.interval_minutes,
+            self.alignment(),
+        );
+        let mut due = self.collection_deadline(scheduled);
+        let mut retries = time::interval(Duration::from_secs(POEM_RETRY_CHECK_SECS));
+        let mut event_checks = time::interval(Duration::from_secs(EVENT_WATCH_INTERVAL_SECS));
+        let mut reorg_checks = time::interval(Duration::from_secs(REORG_CHECK_INTERVAL_SECS));
+        let mut backups = time::interval(
+            self.backup
+                .as_ref()
+                .map_or(Duration::from_secs(ONE_DAY), DatabaseBackup::interval),
+        );
+        let mut watcher = EventWatcher::new();
+        let mut commands = self
+            .commands
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        let mut paused = false;
 
         loop {
-            interval.tick().await;
+            // Whether an operator asked for this collection
+            let requested = tokio::select! {
+                _ = time::sleep_until(due) => {
+                    scheduled = next_collection(
+                        Some(scheduled),
+                        Utc::now(),
+                        self.interval_minutes,
+                        self.alignment(),
+                    );
+                    due = self.collection_deadline(scheduled);
+                    false
+                }
+                command = next_command(&mut commands) => {
+                    info!(command = command.name(), "Received collector command");
+                    match command {
+                        CollectorCommand::CollectNow => true,
+                        CollectorCommand::Pause => {
+                            paused = true;
+                            continue;
+                        }
+                        CollectorCommand::Resume => {
+                            paused = false;
+                            continue;
+                        }
+                    }
+                }
+                _ = retries.tick() => {
+                    self.replay_failed_keywords().await;
+                    self.retry_pending_poems().await;
+                    continue;
+                }
+                _ = event_checks.tick(), if self.event_keywords_per_day > 0 && !paused => {
+                    self.watch_for_events(&mut watcher).await;
+                    continue;
+                }
+                _ = reorg_checks.tick() => {
+                    self.verify_finality().await;
+                    continue;
+                }
+                _ = backups.tick(), if self.backup.is_some() => {
+                    self.back_up_database().await;
+                    continue;
+                }
+                () = &mut self_backfill, if backfilling => {
+                    backfilling = false;
+                    continue;
+                }
+                // The watch::Ref from wait_for isn't Send, so don't let it reach select!'s output
+                _ = async { shutdown.wait_for(|&stop| stop).await.is_ok() } => break,
+            };
 
-            match self.collect_keyword().await {
-                Ok(()) => {}
-                Err(e) => {
-                    eprintln!("❌ Error collecting keyword: {}", e);
+            if paused && !requested {
+                info!("Collection is paused, skipping this tick");
+            } else {
+                match self.run_collection().await {
+                    Ok(()) => {}
+                    Err(e) => {
+                        error!(error = %e, "Error collecting keyword");
+                    }
                 }
             }
 
@@ -55,93 +580,1897 @@ impl KeywordCollector {
             match self.maybe_generate_daily_poem().await {
                 Ok(()) => {}
                 Err(e) => {
-                    eprintln!("❌ Error generating daily poem: {}", e);
+                    error!(error = %e, "Error generating daily poem");
                 }
             }
+
+            // A week or month that just ended gets its meta-poem
+            self.maybe_generate_collections().await;
+
+            self.define_new_words().await;
+            self.embed_new_poems().await;
+            self.narrate_new_poems().await;
         }
+
+        info!("Keyword collector stopping");
+        Ok(())
     }
 
-    /// Collect a single keyword from the blockchain
-    async fn collect_keyword(&self) -> Result<()> {
-        println!("🔗 Fetching latest block from Solana...");
+    /// Timezone whose midnight aligned collections count from, None when unaligned
+    fn alignment(&self) -> Option<Timezone> {
+        self.align_to_interval.then(|| self.database.timezone())
+    }
 
-        // Fetch block with retry
-        let block = match self.solana_client.get_latest_block().await {
-            Ok(b) => b,
+    /// When to wake up for the collection scheduled at `scheduled`, jitter included
+    fn collection_deadline(&self, scheduled: DateTime<Utc>) -> time::Instant {
+        let jitter = if self.jitter_secs > 0 {
+            rand::thread_rng().gen_range(0..=self.jitter_secs)
+        } else {
+            0
+        };
+        debug!(%scheduled, jitter_secs = jitter, "Next keyword collection");
+        let wait = (scheduled - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+        time::Instant::now() + wait + Duration::from_secs(jitter)
+    }
+
+    /// When the last collection finished, from `scheduler_state`; None if
+    /// there never was one or it can't be read
+    async fn last_collection(&self) -> Option<DateTime<Utc>> {
+        match self.database.get_scheduler_state().await {
+            Ok(state) => state
+                .last_collection_at
+                .as_deref()
+                .and_then(timezone::parse_timestamp),
+            Err(e) => {
+                warn!(error = %e, "Failed to read scheduler state");
+                None
+            }
+        }
+    }
+
+    /// Collect keywords and record the collection in `scheduler_state`
+    async fn run_collection(&self) -> Result<()> {
+        self.collect_keywords().await?;
+        if let Err(e) = self.database.record_collection_run().await {
+            warn!(error = %e, "Failed to record collection time");
+        }
+        Ok(())
+    }
+
+    /// Keywords a single collection can add
+    fn keywords_per_tick(&self) -> usize {
+        self.blocks_per_tick * self.keywords_per_block
+    }
+
+    /// Collect keywords from the latest blocks (`blocks_per_tick` of them,
+    /// `keywords_per_block` words each) until today has `keywords_per_day`
+    #[instrument(skip(self))]
+    async fn collect_keywords(&self) -> Result<()> {
+        let stored = self
+            .database
+            .get_keywords_for_date(&self.database.today())
+            .await?;
+        let scheduled = scheduled_keywords(&stored);
+        let collected = scheduled.len();
+        if collected >= self.keywords_per_day {
+            info!(collected, target = self.keywords_per_day, "Today's keywords are complete");
+            return Ok(());
+        }
+
+        info!(
+            blocks = self.blocks_per_tick,
+            "Fetching latest blocks from Solana"
+        );
+        let started = Instant::now();
+
+        // Fetch blocks with retry
+        let fetched = if self.blocks_per_tick > 1 {
+            self.solana_client
+                .get_recent_blocks(self.blocks_per_tick)
+                .await
+        } else {
+            self.solana_client.get_latest_block().await.map(|b| vec![b])
+        };
+        let blocks = match fetched {
+            Ok(blocks) if !blocks.is_empty() => blocks,
+            Ok(_) => anyhow::bail!("Solana RPC returned none of the recent blocks"),
             Err(e) => {
-                eprintln!("❌ Failed to fetch block from Solana: {}", e);
-                eprintln!("   Will retry on next interval");
+                error!(
+                    error = %e,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Failed to fetch block from Solana, will retry on next interval"
+                );
+                self.events.publish(CollectorEvent::error("block_fetch", &e));
                 anyhow::bail!("Solana RPC error: {}", e);
             }
         };
 
-        // Derive keyword (this should not fail unless word dictionary is corrupted)
-        let keyword = self.derivation.derive_keyword(&block)?;
+        for block in &blocks {
+            self.events.publish(CollectorEvent::BlockFetched {
+                slot: block.slot,
+                blockhash: block.blockhash.clone(),
+            });
+        }
+
+        if let Some(selection) = &self.theme_selection {
+            if let Err(e) = apply_weekly_theme(
+                &self.database,
+                &self.dictionary,
+                selection,
+                self.database.timezone().today(),
+                &blocks[0].blockhash,
+            )
+            .await
+            {
+                warn!(error = %e, "Failed to select dictionary theme, keeping the current one");
+            }
+        }
+
+        // Derive keywords (this should not fail unless word dictionary is corrupted)
+        let mut recent = recent_words(
+            &self.database,
+            self.database.timezone().today(),
+            self.dedup_days,
+        )
+        .await?;
+        // A block already collected from would only give re-rolled copies of its words
+        let stored_slots: HashSet<u64> = stored.iter().map(|k| k.slot as u64).collect();
+        let mut taken = source_counts(scheduled.iter().copied());
+        let mut keywords = Vec::new();
+        for block in blocks.iter().filter(|b| !stored_slots.contains(&b.slot)) {
+            let room =
+                (self.keywords_per_day - collected - keywords.len()).min(self.keywords_per_block);
+            if room == 0 {
+                break;
+            }
+            for keyword in self.derivation.derive_weighted(
+                block,
+                room,
+                &recent,
+                &self.source_weights,
+                &mut taken,
+            )? {
+                // Words from this tick's other blocks are avoided too, for variety
+                recent.insert(keyword.word.clone());
+                keywords.push(keyword);
+            }
+        }
+        if keywords.is_empty() {
+            info!(
+                slot = blocks[0].slot,
+                "Latest blocks were already collected from"
+            );
+            return Ok(());
+        }
+        let composition: BTreeMap<&str, usize> = taken
+            .iter()
+            .map(|(source, count)| (source.as_str(), *count))
+            .collect();
+        // This collection's keywords included, before they are stored
+        info!(composition = ?composition, "Today's keyword composition");
+        assign_epochs(&self.solana_client, &mut keywords).await;
+
+        let mut last_error = None;
+        let mut stored_from = HashSet::new();
+        for keyword in &keywords {
+            info!(
+                word = %keyword.word,
+                slot = keyword.slot,
+                source = keyword.source.as_str(),
+                rerolls = keyword.rerolls,
+                duration_ms = started.elapsed().as_millis() as u64,
+                "Derived keyword"
+            );
+
+            // Store in database with error handling
+            match self.database.insert_keyword(keyword).await {
+                Ok(Some(_)) => {
+                    stored_from.insert(keyword.slot);
+                    metrics::KEYWORDS_COLLECTED.inc();
+                    metrics::LAST_KEYWORD_TIMESTAMP.set(Utc::now().timestamp());
+                    info!(word = %keyword.word, slot = keyword.slot, "Keyword stored");
+                    self.events.publish(CollectorEvent::KeywordDerived {
+                        date: self.database.today(),
+                        word: keyword.word.clone(),
+                        slot: keyword.slot,
+                    });
+                }
+                // Another instance sharing the database got to this block first
+                Ok(None) => {
+                    info!(word = %keyword.word, slot = keyword.slot, "Keyword already stored");
+                }
+                Err(e) => {
+                    error!(
+                        error = %e,
+                        word = %keyword.word,
+                        slot = keyword.slot,
+                        "Failed to store keyword in database"
+                    );
+                    self.events
+                        .publish(CollectorEvent::error("keyword_store", &e));
+                    self.dead_letter(keyword, None, &e).await;
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        // The day's mood comes from the blocks its keywords were derived from
+        let activity = BlockActivity::of(
+            blocks
+                .iter()
+                .filter(|block| stored_from.contains(&block.slot)),
+        );
+        if activity.blocks > 0 {
+            match self
+                .database
+                .record_day_activity(&self.database.today(), &activity)
+                .await
+            {
+                Ok(mood) => debug!(
+                    mood = mood.map(|mood| mood.name()),
+                    "Recorded block activity"
+                ),
+                Err(e) => warn!(error = %e, "Failed to record block activity"),
+            }
+        }
+
+        self.celebrate_milestones(&self.database.today()).await;
+        match last_error {
+            Some(e) => anyhow::bail!("Database error: {}", e),
+            None => Ok(()),
+        }
+    }
+
+    /// Feed the latest block to `watcher`, collecting a keyword from it if it is notable
+    async fn watch_for_events(&self, watcher: &mut EventWatcher) {
+        let block = match self.solana_client.get_latest_block().await {
+            Ok(block) => block,
+            Err(e) => {
+                debug!(error = %e, "Event watcher could not fetch the latest block");
+                return;
+            }
+        };
+        let Some(event) = watcher.observe(&block) else {
+            return;
+        };
+
+        info!(?event, "Notable on-chain event");
+        if let Err(e) = self.collect_event_keyword(&block, &event).await {
+            warn!(error = %e, "Failed to collect event keyword");
+            self.events.publish(CollectorEvent::error("keyword_store", &e));
+        }
+    }
+
+    /// Re-read the slots of keywords collected between REORG_CHECK_DELAY_SECS
+    /// and REORG_CHECK_WINDOW_SECS ago at finalized commitment, flagging those
+    /// whose block is no longer on the chain as orphaned
+    ///
+    /// A slot that can't be read is left unverified and tried again next check.
+    async fn verify_finality(&self) {
+        let now = Utc::now();
+        let format = |secs: u64| {
+            (now - chrono::Duration::seconds(secs as i64))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        };
+        let keywords = match self
+            .database
+            .get_unverified_keywords(
+                &format(REORG_CHECK_WINDOW_SECS),
+                &format(REORG_CHECK_DELAY_SECS),
+                REORG_CHECKS_PER_TICK,
+            )
+            .await
+        {
+            Ok(keywords) => keywords,
+            Err(e) => {
+                error!(error = %e, "Failed to load keywords to verify");
+                return;
+            }
+        };
 
-        println!("   Derived keyword: \"{}\" from slot {}", keyword.word, keyword.slot);
+        // Several keywords share a block; read each slot once
+        let mut finalized: HashMap<i64, Option<String>> = HashMap::new();
+        for keyword in keywords {
+            let blockhash = match finalized.get(&keyword.slot) {
+                Some(blockhash) => blockhash.clone(),
+                None => match self
+                    .solana_client
+                    .get_finalized_blockhash(keyword.slot as u64)
+                    .await
+                {
+                    Ok(blockhash) => {
+                        finalized.insert(keyword.slot, blockhash.clone());
+                        blockhash
+                    }
+                    Err(e) => {
+                        debug!(slot = keyword.slot, error = %e, "Could not read finalized block");
+                        continue;
+                    }
+                },
+            };
 
-        // Store in database with error handling
-        match self.database.insert_keyword(&keyword).await {
-            Ok(_) => {
-                println!("   ✅ Keyword stored\n");
-                Ok(())
+            let orphaned = blockhash.as_deref() != Some(keyword.blockhash.as_str());
+            if orphaned {
+                warn!(
+                    word = %keyword.word,
+                    slot = keyword.slot,
+                    finalized = ?blockhash,
+                    "Keyword's block was abandoned by a reorg, flagging it orphaned"
+                );
+                metrics::KEYWORDS_ORPHANED.inc();
+            }
+            if let Err(e) = self
+                .database
+                .record_keyword_verification(keyword.id, orphaned)
+                .await
+            {
+                error!(id = keyword.id, error = %e, "Failed to record keyword verification");
+            }
+        }
+    }
+
+    /// Derive an extra keyword tagged `event` from a notable block, within today's allowance
+    async fn collect_event_keyword(&self, block: &BlockInfo, event: &ChainEvent) -> Result<()> {
+        let today = self.database.today();
+        // Once the poem is written, more keywords wouldn't reach it
+        if self.database.get_poem_by_date(&today).await?.is_some() {
+            debug!(?event, "Today's poem already exists, skipping event keyword");
+            return Ok(());
+        }
+        let stored = self.database.get_keywords_for_date(&today).await?;
+        let collected = stored
+            .iter()
+            .filter(|k| k.tag.as_deref() == Some(KEYWORD_TAG_EVENT))
+            .count();
+        if collected >= self.event_keywords_per_day {
+            debug!(collected, "Today's event keywords are used up");
+            return Ok(());
+        }
+        if stored.iter().any(|k| k.slot as u64 == block.slot) {
+            debug!(
+                ?event,
+                slot = block.slot,
+                "Block was already collected from"
+            );
+            return Ok(());
+        }
+
+        let recent = recent_words(
+            &self.database,
+            self.database.timezone().today(),
+            self.dedup_days,
+        )
+        .await?;
+        let mut keyword = self.derivation.derive_keyword_avoiding(block, &recent)?;
+        assign_epochs(&self.solana_client, std::slice::from_mut(&mut keyword)).await;
+        match self
+            .database
+            .insert_tagged_keyword(&keyword, Some(KEYWORD_TAG_EVENT))
+            .await
+        {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                debug!(word = %keyword.word, slot = keyword.slot, "Event keyword already stored");
+                return Ok(());
             }
             Err(e) => {
-                eprintln!("❌ Failed to store keyword in database: {}", e);
-                eprintln!("   Keyword: {} (slot: {})", keyword.word, keyword.slot);
-                anyhow::bail!("Database error: {}", e);
+                self.dead_letter(&keyword, Some(KEYWORD_TAG_EVENT), &e)
+                    .await;
+                return Err(e.into());
             }
         }
+
+        metrics::KEYWORDS_COLLECTED.inc();
+        metrics::LAST_KEYWORD_TIMESTAMP.set(Utc::now().timestamp());
+        info!(word = %keyword.word, slot = keyword.slot, ?event, "Event keyword stored");
+        self.events.publish(CollectorEvent::KeywordDerived {
+            date: today,
+            word: keyword.word,
+            slot: keyword.slot,
+        });
+        Ok(())
+    }
+
+    /// Queue a keyword that failed to store in `failed_keywords` for replay
+    async fn dead_letter(
+        &self,
+        keyword: &DerivedKeyword,
+        tag: Option<&str>,
+        error: &ChainVerseError,
+    ) {
+        let collected_at = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        match self
+            .database
+            .dead_letter_keyword(keyword, tag, &collected_at, &error.to_string())
+            .await
+        {
+            Ok(()) => {
+                info!(word = %keyword.word, slot = keyword.slot, "Keyword queued for replay");
+                // A keyword already queued is only updated, so count rather than increment
+                match self.database.count_failed_keywords().await {
+                    Ok(count) => metrics::FAILED_KEYWORDS.set(count),
+                    Err(e) => warn!(error = %e, "Failed to count keywords awaiting replay"),
+                }
+            }
+            // Log everything needed to re-insert it by hand
+            Err(e) => error!(
+                error = %e,
+                ?keyword,
+                %collected_at,
+                "Failed to queue keyword for replay, it is lost"
+            ),
+        }
+    }
+
+    /// Replay keywords that failed to store, then generate any past day's poem
+    /// they complete (today's is handled by the regular tick)
+    async fn replay_failed_keywords(&self) {
+        let failed = match self.database.get_failed_keywords().await {
+            Ok(failed) => failed,
+            Err(e) => {
+                error!(error = %e, "Failed to read keyword replay queue");
+                return;
+            }
+        };
+        if failed.is_empty() {
+            return;
+        }
+
+        let mut dates = BTreeSet::new();
+        for keyword in failed {
+            let date = self
+                .database
+                .timezone()
+                .date_of_timestamp(&keyword.collected_at)
+                .map(|date| date.format("%Y-%m-%d").to_string());
+            let replay = match &date {
+                Some(date) => self.replay_fits(&keyword, date).await,
+                None => Ok(true),
+            };
+            let replayed = match replay {
+                Ok(true) => self.database.replay_failed_keyword(keyword.id).await,
+                Ok(false) => {
+                    info!(
+                        word = %keyword.word,
+                        slot = keyword.slot,
+                        collected_at = %keyword.collected_at,
+                        "Dropped queued keyword, its day already has its keywords"
+                    );
+                    self.database
+                        .discard_failed_keyword(keyword.id)
+                        .await
+                        .map(|()| false)
+                }
+                Err(e) => Err(e),
+            };
+            match replayed {
+                Ok(true) => {
+                    metrics::KEYWORDS_COLLECTED.inc();
+                    info!(
+                        word = %keyword.word,
+                        slot = keyword.slot,
+                        collected_at = %keyword.collected_at,
+                        "Replayed keyword"
+                    );
+                    if let Some(date) = date {
+                        dates.insert(date);
+                    }
+                }
+                Ok(false) => debug!(slot = keyword.slot, "Replayed keyword was not stored"),
+                Err(e) => {
+                    warn!(slot = keyword.slot, error = %e, "Keyword replay failed");
+                    if let Err(e) = self
+                        .database
+                        .record_failed_keyword_error(keyword.id, &e.to_string())
+                        .await
+                    {
+                        warn!(error = %e, "Failed to record keyword replay error");
+                    }
+                    // The database is most likely still unavailable; try the rest next time
+                    break;
+                }
+            }
+        }
+
+        let today = self.database.today();
+        for date in dates.iter().filter(|date| **date < today) {
+            if let Err(e) = self.maybe_generate_poem_for_date(date).await {
+                error!(%date, error = %e, "Failed to generate poem after keyword replay");
+            }
+        }
+
+        match self.database.count_failed_keywords().await {
+            Ok(count) => metrics::FAILED_KEYWORDS.set(count),
+            Err(e) => warn!(error = %e, "Failed to count keywords awaiting replay"),
+        }
+    }
+
+    /// Whether `date` still has room for a queued keyword: while it waited,
+    /// collection may have filled the day (or its event allowance) without it
+    async fn replay_fits(
+        &self,
+        keyword: &FailedKeyword,
+        date: &str,
+    ) -> Result<bool, ChainVerseError> {
+        let stored = self.database.get_keywords_for_date(date).await?;
+        if keyword.tag.as_deref() == Some(KEYWORD_TAG_EVENT) {
+            let events = stored
+                .iter()
+                .filter(|k| k.tag.as_deref() == Some(KEYWORD_TAG_EVENT))
+                .count();
+            return Ok(events < self.event_keywords_per_day);
+        }
+        Ok(scheduled_keywords(&stored).len() < self.keywords_per_day)
     }
 
     /// Check if we should generate today's poem and do it if needed
     async fn maybe_generate_daily_poem(&self) -> Result<()> {
-        let today = Database::today();
+        let today = self.database.today();
+        self.maybe_generate_poem_for_date(&today).await.map(|_| ())
+    }
 
-        // Check if we already have a poem for today
-        if let Some(_) = self.database.get_poem_by_date(&today).await? {
-            return Ok(()); // Already have today's poem
+    /// Generate the poem for a date if it is missing and enough keywords exist
+    ///
+    /// Another collector sharing the database may hold the day's lease; this
+    /// one then leaves the poem to it and returns false.
+    #[instrument(skip(self))]
+    async fn maybe_generate_poem_for_date(&self, date: &str) -> Result<bool> {
+        // Check if we already have a poem for this date
+        if self.database.get_poem_by_date(date).await?.is_some() {
+            return Ok(true); // Already have the poem
         }
 
-        // Get today's keywords
-        let keywords = self.database.get_keywords_for_date(&today).await?;
+        // Get the day's keywords
+        let keywords = self.database.get_poem_keywords_for_date(date).await?;
 
         // Need minimum keywords to generate a poem
-        if keywords.len() < MIN_KEYWORDS_FOR_POEM {
-            return Ok(()); // Not enough keywords yet
+        if keywords.len() < self.min_keywords_for_poem {
+            return Ok(true); // Not enough keywords yet
         }
 
-        println!("🎨 Generating poem for {}...", today);
-        println!("   Using {} keywords", keywords.len());
+        if !self
+            .database
+            .try_acquire_poem_lock(date, &self.instance_id, POEM_LOCK_TTL_SECS)
+            .await?
+        {
+            info!("Another instance is generating this poem, leaving it to them");
+            return Ok(false);
+        }
+        let result = async {
+            // The holder we waited on may have just finished and released the lease
+            if self.database.get_poem_by_date(date).await?.is_some() {
+                return Ok(());
+            }
+            self.generate_poem_for_date(date, keywords).await
+        }
+        .await
+        .map(|()| true);
+        if let Err(e) = self
+            .database
+            .release_poem_lock(date, &self.instance_id)
+            .await
+        {
+            warn!(error = %e, "Failed to release poem lock");
+        }
+        result
+    }
+
+    /// Generate and store the poem for a date from `keywords`, queueing a
+    /// retry if the LLM fails; the caller holds the day's lease
+    async fn generate_poem_for_date(&self, date: &str, keywords: Vec<StoredKeyword>) -> Result<()> {
+        let activity = day_activity(&self.database, date).await;
+        let mood = Mood::of(&activity);
+        let form = self
+            .form_selection
+            .resolve(keywords.first().map(|k| k.blockhash.as_str()))
+            .with_length(self.poem_length.scaled(&activity));
+        info!(keywords = keywords.len(), %form, "Generating poem");
+        self.events.publish(CollectorEvent::PoemStarted {
+            date: date.to_string(),
+            keywords: keywords.len(),
+            form: form.to_string(),
+        });
+        let started = Instant::now();
 
         let keyword_strings: Vec<String> = keywords.iter().map(|k| k.word.clone()).collect();
 
-        match self.poem_generator.generate_poem(&keyword_strings).await {
+        match self
+            .poem_generator
+            .generate_poem_in_form(&keyword_strings, form, date, mood)
+            .await
+        {
             Ok(poem) => {
+                renew_poem_lock(&self.database, date, &self.instance_id).await?;
                 let keyword_ids: Vec<i64> = keywords.iter().map(|k| k.id).collect();
 
                 self.database
-                    .insert_poem(&today, None, &poem, &keyword_ids)
+                    .insert_poem(date, poem.title.as_deref(), &poem.content, &keyword_ids)
                     .await?;
+                if let Err(e) = self.database.record_poem_run().await {
+                    warn!(error = %e, "Failed to record poem time");
+                }
+                self.record_usage(date, &poem).await;
+                self.record_provenance(date, &poem, &keywords, &keyword_strings, form, mood)
+                    .await;
+
+                metrics::POEMS_GENERATED.inc();
+                info!(
+                    title = poem.title.as_deref().unwrap_or_default(),
+                    lines = poem.content.lines().count(),
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Poem generated and stored"
+                );
+                info!("Poem of the day {}:\n{}", date, poem.content);
+                self.events.publish(CollectorEvent::PoemFinished {
+                    date: date.to_string(),
+                    title: poem.title.clone(),
+                });
+                if let Err(e) = self.database.remove_pending_poem(date).await {
+                    warn!(error = %e, "Failed to clear poem retry");
+                }
+                if let Some(notifier) = &self.notifier {
+                    notifier
+                        .send(&Notification::Poem {
+                            date,
+                            title: poem.title.as_deref(),
+                            content: &poem.content,
+                            keywords: &keyword_strings,
+                        })
+                        .await;
+                }
+                self.mint_poem(date).await;
+                self.anchor_poem(date).await;
+                self.publish_poem(date).await;
+                self.celebrate_milestones(date).await;
+            }
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Failed to generate poem"
+                );
+                self.events.publish(CollectorEvent::error("poem_generation", &e));
+                self.queue_poem_retry(date, &e).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Put a failed date in the retry queue, backing off further on each failure
+    async fn queue_poem_retry(&self, date: &str, error: &ChainVerseError) -> Result<()> {
+        let attempts = self
+            .database
+            .get_pending_poem(date)
+            .await?
+            .map_or(0, |pending| pending.attempts)
+            + 1;
+        let delay = retry_delay(attempts as u32);
+        let next_attempt_at = (Utc::now() + chrono::Duration::from_std(delay)?)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let error = format!("{:#}", error);
+
+        self.database
+            .queue_pending_poem(date, attempts, &next_attempt_at, &error)
+            .await?;
+        metrics::PENDING_POEMS.set(self.database.count_pending_poems().await?);
+        info!(%date, attempts, %next_attempt_at, "Queued poem for retry");
+        if let Some(notifier) = &self.notifier {
+            notifier
+                .send(&Notification::PoemFailed {
+                    date,
+                    attempts,
+                    next_attempt_at: &next_attempt_at,
+                    error: &error,
+                })
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Retry every queued poem whose backoff has elapsed
+    async fn retry_pending_poems(&self) {
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let due = match self.database.get_due_pending_poems(&now).await {
+            Ok(due) => due,
+            Err(e) => {
+                error!(error = %e, "Failed to read poem retry queue");
+                return;
+            }
+        };
+
+        for pending in due {
+            info!(date = %pending.date, attempts = pending.attempts, "Retrying poem generation");
+            match self.maybe_generate_poem_for_date(&pending.date).await {
+                Ok(true) => {}
+                // Check again once the other instance's lease has run out
+                Ok(false) => self.postpone_poem_retry(&pending.date).await,
+                Err(e) => error!(date = %pending.date, error = %e, "Poem retry failed"),
+            }
+            // Also clears days whose poem appeared some other way (e.g. a backfill)
+            match self.database.get_poem_by_date(&pending.date).await {
+                Ok(Some(_)) => {
+                    if let Err(e) = self.database.remove_pending_poem(&pending.date).await {
+                        warn!(error = %e, "Failed to clear poem retry");
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!(error = %e, "Failed to check retried poem"),
+            }
+        }
+
+        match self.database.count_pending_poems().await {
+            Ok(count) => metrics::PENDING_POEMS.set(count),
+            Err(e) => warn!(error = %e, "Failed to count poem retries"),
+        }
+    }
+
+    /// Push a queued poem's next attempt past the day's lease, without counting
+    /// an attempt; failures are logged
+    async fn postpone_poem_retry(&self, date: &str) {
+        let next_attempt_at = (Utc::now() + chrono::Duration::seconds(POEM_LOCK_TTL_SECS as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        match self
+            .database
+            .postpone_pending_poem(date, &next_attempt_at)
+            .await
+        {
+            Ok(()) => info!(%date, %next_attempt_at, "Postponed poem retry"),
+            Err(e) => warn!(%date, error = %e, "Failed to postpone poem retry"),
+        }
+    }
+
+    /// Mint the stored poem for `date` if minting is configured; failures are logged
+    async fn mint_poem(&self, date: &str) {
+        let Some(minter) = &self.minter else {
+            return;
+        };
+
+        let result = async {
+            if self.database.get_poem_mint(date).await?.is_some() {
+                return Ok(());
+            }
+            let poem = self
+                .database
+                .get_poem_by_date(date)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Poem for {} not found", date))?;
+            let signature = minter.mint_poem(&poem).await?;
+            self.database
+                .insert_poem_mint(date, &signature, &minter.merkle_tree().to_string())
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!(%date, error = %e, "Failed to mint poem");
+        }
+    }
+
+    /// Anchor the stored poem for `date` if anchoring is configured; failures are logged
+    async fn anchor_poem(&self, date: &str) {
+        let Some(anchor) = &self.anchor else {
+            return;
+        };
+
+        let result = async {
+            let poem = self
+                .database
+                .get_poem_by_date(date)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Poem for {} not found", date))?;
+            if poem.anchor_signature.is_some() {
+                return Ok(());
+            }
+            let mut keywords = self.database.get_keywords_for_date(date).await?;
+            keywords.retain(|k| poem.keyword_ids.contains(&k.id));
+            let (hash, signature) = anchor.anchor_poem(&poem, &keywords).await?;
+            self.database
+                .set_poem_anchor(date, &hash, &signature)
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!(%date, error = %e, "Failed to anchor poem");
+        }
+    }
+
+    /// Post the stored poem for `date` if publishing is configured; failures are logged
+    async fn publish_poem(&self, date: &str) {
+        let Some(publisher) = &self.publisher else {
+            return;
+        };
+
+        let result = async {
+            let poem = self
+                .database
+                .get_poem_by_date(date)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Poem for {} not found", date))?;
+            let mut keywords = self.database.get_keywords_for_date(date).await?;
+            keywords.retain(|k| poem.keyword_ids.contains(&k.id));
+            publisher.publish(&poem, &keywords).await;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!(%date, error = %e, "Failed to publish poem");
+        }
+    }
+
+    /// Store the manifest needed to reproduce a just-generated poem; failures are logged
+    async fn record_provenance(
+        &self,
+        date: &str,
+        poem: &GeneratedPoem,
+        keywords: &[StoredKeyword],
+        words: &[String],
+        form: PoemForm,
+        mood: Option<Mood>,
+    ) {
+        let provenance = PoemProvenance::new(
+            date,
+            self.derivation.provenance(),
+            keywords,
+            self.poem_generator
+                .provenance(&poem.model, words, form, date, mood),
+        );
+        if let Err(e) = self.database.insert_poem_provenance(&provenance).await {
+            warn!(%date, error = %e, "Failed to store poem provenance");
+        }
+    }
+
+    /// Store the token usage OpenRouter reported for a just-generated poem; failures are logged
+    async fn record_usage(&self, date: &str, poem: &GeneratedPoem) {
+        let Some(usage) = &poem.usage else {
+            return;
+        };
+        info!(
+            prompt_tokens = usage.prompt_tokens,
+            completion_tokens = usage.completion_tokens,
+            cost_usd = usage.cost_usd,
+            "LLM usage"
+        );
+        if let Err(e) = self.database.set_poem_usage(date, usage).await {
+            warn!(%date, error = %e, "Failed to store poem token usage");
+        }
+    }
 
-                println!("   ✅ Poem generated and stored!");
-                println!("\n✨ POEM OF THE DAY: {} ✨", today);
-                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-                println!("{}", poem);
-                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+    /// Write the meta-poems for every ended week and month that lacks one
+    ///
+    /// Failures are logged; the next collection tick tries again.
+    async fn maybe_generate_collections(&self) {
+        let periods = async {
+            let Some(first) = self.database.get_project_start_date().await? else {
+                return Ok(Vec::new());
+            };
+            let first = NaiveDate::parse_from_str(&first, "%Y-%m-%d")?;
+            let stored: HashSet<String> = self
+                .database
+                .get_poem_collections()
+                .await?
+                .into_iter()
+                .map(|collection| collection.period)
+                .collect();
+            anyhow::Ok(
+                Period::ended_since(first, self.database.timezone().today())
+                    .into_iter()
+                    .filter(|period| !stored.contains(&period.key()))
+                    .collect::<Vec<_>>(),
+            )
+        }
+        .await;
+        let periods = match periods {
+            Ok(periods) => periods,
+            Err(e) => {
+                error!(error = %e, "Failed to list periods missing a meta-poem");
+                return;
             }
+        };
+
+        for period in periods {
+            if let Err(e) = self.maybe_generate_collection(&period).await {
+                error!(period = %period.key(), error = %e, "Error generating meta-poem");
+            }
+        }
+    }
+
+    /// Generate a period's meta-poem once every day in it has settled
+    ///
+    /// Waits while any of the period's poems is queued for retry, and skips
+    /// periods with fewer than MIN_POEMS_FOR_COLLECTION poems.
+    async fn maybe_generate_collection(&self, period: &Period) -> Result<()> {
+        let key = period.key();
+        if self.database.get_poem_collection(&key).await?.is_some() {
+            return Ok(());
+        }
+
+        let (start, end) = (period.start_date(), period.end_date());
+        if self
+            .database
+            .count_pending_poems_between(&start, &end)
+            .await?
+            > 0
+        {
+            return Ok(());
+        }
+        let poems = self.database.get_poems_between(&start, &end).await?;
+        if poems.len() < MIN_POEMS_FOR_COLLECTION {
+            return Ok(());
+        }
+
+        let mut keywords: Vec<String> = self
+            .database
+            .get_words_between(&start, &end)
+            .await?
+            .into_iter()
+            .collect();
+        keywords.sort();
+
+        info!(period = %key, poems = poems.len(), "Generating meta-poem");
+        let prompt = collections::prompt(period, &poems, &keywords);
+        let poem = self
+            .poem_generator
+            .generate_collection_poem(&key, &prompt)
+            .await?;
+
+        let poem_dates: Vec<String> = poems.into_iter().map(|poem| poem.date).collect();
+        self.database
+            .insert_poem_collection(period, poem.title.as_deref(), &poem.content, &poem_dates)
+            .await?;
+        info!(
+            period = %key,
+            title = poem.title.as_deref().unwrap_or_default(),
+            lines = poem.content.lines().count(),
+            "Meta-poem generated and stored"
+        );
+        Ok(())
+    }
+
+    /// Look up definitions for up to DEFINITIONS_PER_TICK words that don't have one yet
+    ///
+    /// Stops at the first failed lookup so the remaining words wait for the
+    /// next tick instead of hammering an unreachable API.
+    async fn define_new_words(&self) {
+        let Some(definitions) = &self.definitions else {
+            return;
+        };
+        let words = match self
+            .database
+            .get_undefined_words(DEFINITIONS_PER_TICK)
+            .await
+        {
+            Ok(words) => words,
             Err(e) => {
-                eprintln!("   ⚠️  Failed to generate poem: {}", e);
+                warn!(error = %e, "Failed to list words missing definitions");
+                return;
+            }
+        };
+
+        for word in words {
+            let definition = match definitions.lookup(&word).await {
+                Ok(definition) => definition,
+                Err(e) => {
+                    warn!(%word, error = %format!("{:#}", e), "Dictionary lookup failed");
+                    return;
+                }
+            };
+            debug!(%word, found = definition.is_some(), "Looked up definition");
+            if let Err(e) = self
+                .database
+                .store_definition(&word, definition.as_ref())
+                .await
+            {
+                warn!(%word, error = %e, "Failed to store definition");
+                return;
+            }
+        }
+    }
+
+    /// Snapshot the database and drop old snapshots, if backups are configured
+    async fn back_up_database(&self) {
+        let Some(backup) = &self.backup else {
+            return;
+        };
+        match backup.run(&self.database).await {
+            Ok(path) => {
+                metrics::LAST_BACKUP_TIMESTAMP.set(Utc::now().timestamp());
+                info!(path = %path.display(), "Database backed up");
+            }
+            Err(e) => error!(error = %e, "Database backup failed"),
+        }
+    }
+
+    /// Embed up to EMBEDDINGS_PER_TICK poems that have no embedding from the
+    /// configured model, newest first; stops at the first failure like `define_new_words`
+    async fn embed_new_poems(&self) {
+        let Some(embedder) = &self.embeddings else {
+            return;
+        };
+        let poems = match self
+            .database
+            .get_unembedded_poems(embedder.model(), EMBEDDINGS_PER_TICK)
+            .await
+        {
+            Ok(poems) => poems,
+            Err(e) => {
+                warn!(error = %e, "Failed to list poems missing embeddings");
+                return;
+            }
+        };
+
+        for poem in poems {
+            let text = embeddings::poem_text(poem.title.as_deref(), &poem.content);
+            let vector = match embedder.embed(&text).await {
+                Ok(vector) => vector,
+                Err(e) => {
+                    warn!(date = %poem.date, error = %format!("{:#}", e), "Embedding failed");
+                    return;
+                }
+            };
+            debug!(date = %poem.date, dimensions = vector.len(), "Embedded poem");
+            if let Err(e) = self
+                .database
+                .store_poem_embedding(poem.id, embedder.model(), &vector)
+                .await
+            {
+                warn!(date = %poem.date, error = %e, "Failed to store embedding");
+                return;
+            }
+        }
+    }
+
+    /// Read aloud up to NARRATIONS_PER_TICK poems without a recording in the
+    /// configured voice and format, newest first; stops at the first failure
+    /// like `embed_new_poems`
+    async fn narrate_new_poems(&self) {
+        let Some(tts) = &self.tts else {
+            return;
+        };
+        let format = tts.format().to_string();
+        let poems = match self
+            .database
+            .get_unnarrated_poems(&format, tts.voice(), NARRATIONS_PER_TICK)
+            .await
+        {
+            Ok(poems) => poems,
+            Err(e) => {
+                warn!(error = %e, "Failed to list poems missing audio");
+                return;
+            }
+        };
+
+        for poem in poems {
+            let path = match tts
+                .narrate(&poem.date, poem.title.as_deref(), &poem.content)
+                .await
+            {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!(date = %poem.date, error = %format!("{:#}", e), "Narration failed");
+                    return;
+                }
+            };
+            debug!(date = %poem.date, path = %path.display(), "Narrated poem");
+            if let Err(e) = self
+                .database
+                .store_poem_audio(poem.id, &path.to_string_lossy(), &format, tts.voice())
+                .await
+            {
+                warn!(date = %poem.date, error = %e, "Failed to store audio");
+                return;
+            }
+        }
+    }
+
+    /// Record milestones reached as of `date` and announce them on the event
+    /// stream, the notifier, and the publisher; failures never interrupt collection
+    async fn celebrate_milestones(&self, date: &str) {
+        match milestones::check_milestones(&self.database, date).await {
+            Ok(reached) => {
+                for milestone in reached {
+                    let notification = Notification::Milestone {
+                        kind: &milestone.kind,
+                        value: milestone.value,
+                        date: &milestone.date,
+                    };
+                    if let Some(notifier) = &self.notifier {
+                        notifier.send(&notification).await;
+                    }
+                    if let Some(publisher) = &self.publisher {
+                        publisher.announce(&notification.text()).await;
+                    }
+                    self.events.publish(CollectorEvent::MilestoneReached {
+                        kind: milestone.kind,
+                        value: milestone.value,
+                        date: milestone.date,
+                    });
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to check milestones"),
+        }
+    }
+
+    /// Backfill the most recent `self_backfill_days` days between the first
+    /// one collected and yesterday that have no poem
+    ///
+    /// Each run of consecutive days is one backfill range, so an interrupted
+    /// one resumes from `backfill_state` on the next start. Days still missing
+    /// once the limit is reached (or whose blocks the RPC no longer serves)
+    /// are tried again on the next start.
+    async fn self_backfill(&self, shutdown: watch::Receiver<bool>) {
+        let Some(backfill) = &self.backfill else {
+            return;
+        };
+
+        let ranges = match self.poem_gaps().await {
+            Ok(ranges) => ranges,
+            Err(e) => {
+                error!(error = %e, "Failed to look for days without a poem");
+                return;
+            }
+        };
+        for (start, end) in ranges {
+            if *shutdown.borrow() {
+                return;
+            }
+            info!(%start, %end, "Backfilling days without a poem");
+            match backfill.run(start, end, shutdown.clone()).await {
+                Ok(summary) => info!(
+                    days = summary.days_processed,
+                    keywords = summary.keywords_collected,
+                    poems = summary.poems_generated,
+                    "Self-backfill finished"
+                ),
+                Err(e) => warn!(%start, %end, error = %e, "Self-backfill failed"),
+            }
+        }
+    }
+
+    /// Ranges of past days with no poem, limited to the newest `self_backfill_days`
+    async fn poem_gaps(&self) -> Result<Vec<(NaiveDate, NaiveDate)>> {
+        let Some(first) = self.database.get_project_start_date().await? else {
+            return Ok(Vec::new());
+        };
+        let first = NaiveDate::parse_from_str(&first, "%Y-%m-%d")?;
+        let yesterday = self.database.timezone().today() - chrono::Duration::days(1);
+        let poems: HashSet<NaiveDate> = self
+            .database
+            .get_poem_dates()
+            .await?
+            .iter()
+            .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .collect();
+
+        Ok(missing_day_ranges(
+            first,
+            yesterday,
+            &poems,
+            self.self_backfill_days,
+        ))
+    }
+
+    /// Inspect the database for holes left by downtime and return the repairs needed
+    pub async fn recovery_scan(&self) -> Result<Vec<RepairJob>> {
+        info!("Running startup recovery scan");
+
+        let mut jobs = Vec::new();
+        let now = Utc::now();
+        let timezone = self.database.timezone();
+        let today = timezone.date_of(now);
+        let yesterday = (today - chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let today = today.format("%Y-%m-%d").to_string();
+
+        // Yesterday's poem: generate it if we can, otherwise flag the day for backfill
+        if self.database.get_poem_by_date(&yesterday).await?.is_none() {
+            let keywords = self
+                .database
+                .get_poem_keywords_for_date(&yesterday)
+                .await?
+                .len();
+            if keywords >= self.min_keywords_for_poem {
+                jobs.push(RepairJob::GeneratePoem { date: yesterday });
+            } else {
+                jobs.push(RepairJob::Backfill { date: yesterday, keywords });
+            }
+        }
+
+        // Today's collection: the ticks missed since the last recorded collection,
+        // or without one, what the interval should have produced so far
+        if self.database.get_poem_by_date(&today).await?.is_none() {
+            let collected = self.database.get_keywords_for_date(&today).await?.len();
+            let missing = match self.last_collection().await {
+                Some(last) => {
+                    let missed = missed_collections(
+                        last,
+                        now,
+                        timezone.midnight(timezone.date_of(now)),
+                        self.interval_minutes,
+                        self.align_to_interval,
+                    );
+                    info!(%last, missed, "Last collection before this start");
+                    missed as usize * self.keywords_per_tick()
+                }
+                None => expected_keywords_by(
+                    timezone.minutes_into_day(now),
+                    self.interval_minutes,
+                    self.keywords_per_tick(),
+                )
+                .saturating_sub(collected),
             }
+            .min(self.keywords_per_day.saturating_sub(collected));
+            if missing > 0 {
+                jobs.push(RepairJob::CollectKeywords {
+                    date: today,
+                    missing,
+                });
+            }
+        }
+
+        if jobs.is_empty() {
+            info!("Nothing to repair");
+        } else {
+            info!(jobs = jobs.len(), "Found repair jobs");
         }
 
+        Ok(jobs)
+    }
+
+    /// Execute repair jobs from the recovery scan; failures are logged, not fatal
+    pub async fn run_repair_jobs(&self, jobs: &[RepairJob]) {
+        for job in jobs {
+            match job {
+                RepairJob::GeneratePoem { date } => {
+                    info!(%date, "Generating missing poem");
+                    if let Err(e) = self.maybe_generate_poem_for_date(date).await {
+                        error!(%date, error = %e, "Failed to repair poem");
+                    }
+                }
+                RepairJob::CollectKeywords { date, missing } => {
+                    info!(%date, missing, "Catching up missed keywords");
+                    for i in 0..missing.div_ceil(self.keywords_per_tick()) {
+                        if i > 0 {
+                            // Space requests out so each lands on a different confirmed slot
+                            time::sleep(Duration::from_secs(CATCH_UP_DELAY_SECS)).await;
+                        }
+                        if let Err(e) = self.run_collection().await {
+                            error!(error = %e, "Catch-up collection failed");
+                            break;
+                        }
+                    }
+                }
+                // Left to the self-backfill, which runs next
+                RepairJob::Backfill { .. } if self.backfill.is_some() => {}
+                RepairJob::Backfill { date, keywords } => {
+                    warn!(
+                        %date,
+                        keywords,
+                        needed = self.min_keywords_for_poem,
+                        "Day has no poem and too few keywords; run `chain_verse backfill {}`",
+                        date
+                    );
+                }
+            }
+        }
+    }
+
+    /// Walk the collection path without storing anything or calling the LLM:
+    /// derive a keyword from the latest block and print it with the exact
+    /// prompt today's poem would be generated from
+    pub async fn dry_run(&self) -> Result<()> {
+        let block = self.solana_client.get_latest_block().await?;
+        if let Some(selection) = &self.theme_selection {
+            apply_weekly_theme(
+                &self.database,
+                &self.dictionary,
+                selection,
+                self.database.timezone().today(),
+                &block.blockhash,
+            )
+            .await?;
+        }
+        let recent = recent_words(
+            &self.database,
+            self.database.timezone().today(),
+            self.dedup_days,
+        )
+        .await?;
+        let today = self.database.today();
+        let stored = self.database.get_poem_keywords_for_date(&today).await?;
+        let mut taken = source_counts(scheduled_keywords(&stored));
+        let Some(keyword) = self
+            .derivation
+            .derive_weighted(&block, 1, &recent, &self.source_weights, &mut taken)?
+            .into_iter()
+            .next()
+        else {
+            anyhow::bail!("No keyword derived from slot {}", block.slot);
+        };
+        let first_blockhash = stored
+            .first()
+            .map_or(keyword.blockhash.as_str(), |k| k.blockhash.as_str());
+        let activity = day_activity(&self.database, &today).await;
+        let mood = Mood::of(&activity);
+        let form = self
+            .form_selection
+            .resolve(Some(first_blockhash))
+            .with_length(self.poem_length.scaled(&activity));
+        let mut words: Vec<String> = stored.iter().map(|k| k.word.clone()).collect();
+        words.push(keyword.word.clone());
+
+        println!(
+            "Derived keyword: {} (slot {}, source {}, theme {}, rerolls {})",
+            keyword.word,
+            keyword.slot,
+            keyword.source.as_str(),
+            keyword.theme.as_deref().unwrap_or("none"),
+            keyword.rerolls
+        );
+        println!(
+            "Keywords for {} ({} stored + this one; {} needed, {} collected per day): {}",
+            today,
+            stored.len(),
+            self.min_keywords_for_poem,
+            self.keywords_per_day,
+            words.join(", ")
+        );
+        println!("Form: {}", form);
+        if let Some((min, max)) = form.length().map(|length| length.line_range()) {
+            println!("Lines: {}-{}", min, max);
+        }
+        println!("Mood: {}", mood.map_or("unrecorded", |mood| mood.name()));
+        if let Some(system) = self
+            .poem_generator
+            .create_system_prompt(&words, form, &today, mood)
+        {
+            println!("\n--- System prompt ---\n{}", system);
+        }
+        println!(
+            "\n--- Prompt ---\n{}",
+            self.poem_generator
+                .create_prompt(&words, form, &today, mood)
+        );
         Ok(())
     }
 
     /// Run once to collect a keyword immediately (for testing)
     pub async fn run_once(&self) -> Result<()> {
-        self.collect_keyword().await?;
+        self.run_collection().await?;
         self.maybe_generate_daily_poem().await?;
         Ok(())
     }
+
+    /// Generate a day's poem from its stored keywords and print it
+    ///
+    /// An existing poem is kept (and printed) unless `force` is set. Unlike
+    /// the collector, this runs below `min_keywords_for_poem`, with a warning.
+    pub async fn regenerate_poem(&self, date: &str, force: bool) -> Result<()> {
+        if let Some(existing) = self.database.get_poem_by_date(date).await? {
+            if !force {
+                info!(%date, "Poem already exists; pass --force to replace it");
+                println!("{}", existing.content);
+                return Ok(());
+            }
+        }
+
+        let keywords = self.database.get_poem_keywords_for_date(date).await?;
+        if keywords.is_empty() {
+            anyhow::bail!("No keywords stored for {}; backfill the day first", date);
+        }
+        if keywords.len() < self.min_keywords_for_poem {
+            warn!(
+                keywords = keywords.len(),
+                recommended = self.min_keywords_for_poem,
+                "Few keywords; the poem may be short or less coherent"
+            );
+        }
+
+        let activity = day_activity(&self.database, date).await;
+        let mood = Mood::of(&activity);
+        let form = self
+            .form_selection
+            .resolve(keywords.first().map(|k| k.blockhash.as_str()))
+            .with_length(self.poem_length.scaled(&activity));
+        let words: Vec<String> = keywords.iter().map(|k| k.word.clone()).collect();
+        info!(
+            %date,
+            %form,
+            mood = mood.map(|mood| mood.name()),
+            keywords = %words.join(", "),
+            "Generating poem"
+        );
+
+        let poem = self
+            .poem_generator
+            .generate_poem_in_form(&words, form, date, mood)
+            .await?;
+        let keyword_ids: Vec<i64> = keywords.iter().map(|k| k.id).collect();
+        self.database
+            .insert_poem(date, poem.title.as_deref(), &poem.content, &keyword_ids)
+            .await?;
+        self.record_usage(date, &poem).await;
+        self.record_provenance(date, &poem, &keywords, &words, form, mood)
+            .await;
+        self.database.remove_pending_poem(date).await?;
+
+        if let Some(title) = &poem.title {
+            println!("{}\n", title);
+        }
+        println!("{}", poem.content);
+        info!(%date, "Poem saved");
+        Ok(())
+    }
+}
+
+/// The keywords among a day's `stored` that count toward `keywords_per_day`:
+/// event keywords come on top of the daily target, and orphaned ones are replaced
+pub(crate) fn scheduled_keywords(stored: &[StoredKeyword]) -> Vec<&StoredKeyword> {
+    stored
+        .iter()
+        .filter(|k| k.tag.as_deref() != Some(KEYWORD_TAG_EVENT) && !k.orphaned)
+        .collect()
+}
+
+/// Keywords per entropy source among `stored`, the `taken` counts
+/// `SourceWeights::compose` starts a day from; manual keywords have no
+/// entropy source and take no source's share
+pub(crate) fn source_counts<'a>(
+    stored: impl IntoIterator<Item = &'a StoredKeyword>,
+) -> HashMap<BlockDataSource, usize> {
+    let mut taken = HashMap::new();
+    for source in stored
+        .into_iter()
+        .filter_map(|k| BlockDataSource::from_name(&k.source))
+    {
+        *taken.entry(source).or_default() += 1;
+    }
+    taken
+}
+
+/// A name for this process's leases on poem generation, unique across
+/// instances sharing the database
+pub(crate) fn lease_holder() -> String {
+    format!(
+        "{}-{:08x}",
+        std::process::id(),
+        rand::thread_rng().gen::<u32>()
+    )
+}
+
+/// Renew `holder`'s lease on `date`'s poem just before storing it
+///
+/// Generation can outlast the lease, and storing a poem overwrites the day's,
+/// so a poem is only stored while the lease is still held (or free to take
+/// back) and no other instance stored one in the meantime.
+pub(crate) async fn renew_poem_lock(database: &Database, date: &str, holder: &str) -> Result<()> {
+    if !database
+        .try_acquire_poem_lock(date, holder, POEM_LOCK_TTL_SECS)
+        .await?
+    {
+        anyhow::bail!("Lost the lease on {}'s poem to another instance", date);
+    }
+    if database.get_poem_by_date(date).await?.is_some() {
+        anyhow::bail!("Another instance stored {}'s poem first", date);
+    }
+    Ok(())
+}
+
+/// The block activity recorded for a day, which sets its poem's mood and
+/// length; empty (with a warning) when it can't be read, so the poem is still written
+pub(crate) async fn day_activity(database: &Database, date: &str) -> BlockActivity {
+    database.get_day_activity(date).await.unwrap_or_else(|e| {
+        warn!(%date, error = %e, "Failed to read the day's block activity");
+        BlockActivity::default()
+    })
+}
+
+/// Activate the dictionary theme for the week (Monday to Sunday) containing `day`
+///
+/// Automatic selection hashes the first blockhash stored that week, or
+/// `fallback_blockhash` when the week has none yet (that block will be its first).
+pub(crate) async fn apply_weekly_theme(
+    database: &Database,
+    dictionary: &DictionaryHandle,
+    selection: &ThemeSelection,
+    day: NaiveDate,
+    fallback_blockhash: &str,
+) -> Result<Option<String>> {
+    let week_blockhash = match selection {
+        ThemeSelection::Fixed(_) => None,
+        ThemeSelection::FromBlockhash => {
+            let monday = day.week(Weekday::Mon).first_day();
+            database
+                .get_first_blockhash_between(
+                    &monday.format("%Y-%m-%d").to_string(),
+                    &(monday + chrono::Duration::days(7)).format("%Y-%m-%d").to_string(),
+                )
+                .await?
+        }
+    };
+
+    let previous = dictionary.active_theme();
+    let theme = dictionary.select_theme(
+        selection,
+        week_blockhash.as_deref().unwrap_or(fallback_blockhash),
+    )?;
+    if theme != previous {
+        info!(theme = theme.as_deref().unwrap_or("base"), "Dictionary theme changed");
+    }
+    Ok(theme)
+}
+
+/// Record the Solana epoch of each keyword's slot, from one getEpochInfo call
+///
+/// The epoch is only for browsing, so keywords are stored without one if the
+/// lookup fails.
+pub(crate) async fn assign_epochs(solana_client: &SolanaClient, keywords: &mut [DerivedKeyword]) {
+    match solana_client.get_epoch_info().await {
+        Ok(info) => {
+            for keyword in keywords {
+                keyword.epoch = Some(blockchain::epoch_of_slot(&info, keyword.slot));
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to get epoch info, storing keywords without one"),
+    }
+}
+
+/// Words collected on `day` or in the `days` days before it, for derivation to
+/// re-roll; empty when `days` is 0
+pub(crate) async fn recent_words(
+    database: &Database,
+    day: NaiveDate,
+    days: u32,
+) -> Result<HashSet<String>, ChainVerseError> {
+    if days == 0 {
+        return Ok(HashSet::new());
+    }
+    let from = day - chrono::Duration::days(days as i64);
+    database
+        .get_words_between(
+            &from.format("%Y-%m-%d").to_string(),
+            &day.format("%Y-%m-%d").to_string(),
+        )
+        .await
+}
+
+/// Delay between catch-up collections so consecutive fetches hit different slots
+const CATCH_UP_DELAY_SECS: u64 = 5;
+
+/// The next command from the admin API; never resolves without a control
+/// channel, or once every sender is gone
+async fn next_command(commands: &mut Option<mpsc::Receiver<CollectorCommand>>) -> CollectorCommand {
+    if let Some(receiver) = commands {
+        if let Some(command) = receiver.recv().await {
+            return command;
+        }
+        *commands = None;
+    }
+    std::future::pending().await
+}
+
+/// Backoff before retry number `attempts + 1`: doubles from POEM_RETRY_BASE_SECS,
+/// capped at POEM_RETRY_MAX_SECS
+fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    Duration::from_secs((POEM_RETRY_BASE_SECS << exponent).min(POEM_RETRY_MAX_SECS))
+}
+
+/// Wall-clock time of the collection after `previous` (None: the first one)
+///
+/// Unaligned, the first collection is immediate and later ones follow every
+/// `interval_minutes` from it, skipping any the collector was too busy to make.
+/// Scheduling from the previous time rather than from when it actually ran
+/// keeps jitter and slow ticks from drifting the cadence. Aligned to a
+/// timezone, collections fall on multiples of `interval_minutes` since its
+/// midnight, starting with the next one.
+fn next_collection(
+    previous: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    interval_minutes: u64,
+    aligned: Option<Timezone>,
+) -> DateTime<Utc> {
+    let interval = chrono::Duration::minutes(interval_minutes.max(1) as i64);
+    if let Some(timezone) = aligned {
+        // Never before `previous`, in case the timer fired a moment early by the wall clock
+        let from = previous.map_or(now, |previous| previous.max(now));
+        let midnight = timezone.midnight(timezone.date_of(from));
+        let periods = (from - midnight).num_seconds() / interval.num_seconds() + 1;
+        // A day that isn't a whole number of intervals restarts at midnight
+        return (midnight + interval * periods as i32).min(midnight + chrono::Duration::days(1));
+    }
+
+    let Some(previous) = previous else {
+        return now;
+    };
+    let mut next = previous + interval;
+    while next <= now {
+        next += interval;
+    }
+    next
+}
+
+/// Collections scheduled after `last` and due by `now`, counting only those
+/// at or after `midnight` (earlier days are the backfill's business)
+///
+/// Unaligned ticks fall every interval after `last`; aligned ones on
+/// multiples of the interval since midnight.
+fn missed_collections(
+    last: DateTime<Utc>,
+    now: DateTime<Utc>,
+    midnight: DateTime<Utc>,
+    interval_minutes: u64,
+    aligned: bool,
+) -> u64 {
+    let interval = interval_minutes.max(1) as i64 * 60;
+    let anchor = if aligned { midnight } else { last };
+    // Ticks in (from, now]; a tick exactly at midnight counts as today's
+    let from = last.max(midnight - chrono::Duration::seconds(1));
+    let ticks_by = |at: DateTime<Utc>| (at - anchor).num_seconds().div_euclid(interval);
+    (ticks_by(now) - ticks_by(from)).max(0) as u64
+}
+
+/// Runs of consecutive days in `first..=last` not in `present`, oldest first,
+/// keeping only the `limit` newest missing days
+fn missing_day_ranges(
+    first: NaiveDate,
+    last: NaiveDate,
+    present: &HashSet<NaiveDate>,
+    limit: usize,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut ranges: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+    let mut remaining = limit;
+    let mut day = last;
+    while day >= first && remaining > 0 {
+        if !present.contains(&day) {
+            remaining -= 1;
+            match ranges.last_mut() {
+                Some((start, _)) if start.pred_opt() == Some(day) => *start = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+        let Some(previous) = day.pred_opt() else {
+            break;
+        };
+        day = previous;
+    }
+    ranges.reverse();
+    ranges
+}
+
+/// Number of keywords a collector running all day would have stored after
+/// `minutes_elapsed` minutes, counting the tick that fires immediately on
+/// start, when each tick stores `per_tick`
+fn expected_keywords_by(minutes_elapsed: u64, interval_minutes: u64, per_tick: usize) -> usize {
+    if interval_minutes == 0 {
+        return 0;
+    }
+    (minutes_elapsed / interval_minutes + 1) as usize * per_tick
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_keywords_by() {
+        assert_eq!(expected_keywords_by(0, 90, 1), 1);
+        assert_eq!(expected_keywords_by(89, 90, 1), 1);
+        assert_eq!(expected_keywords_by(90, 90, 1), 2);
+        assert_eq!(expected_keywords_by(23 * 60 + 59, 90, 1), 16);
+        assert_eq!(expected_keywords_by(600, 0, 1), 0);
+        assert_eq!(expected_keywords_by(90, 90, 3), 6);
+    }
+
+    #[test]
+    fn test_missed_collections() {
+        let at = |d, h, m| {
+            NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let midnight = at(2, 0, 0);
+        let missed = |last, now, aligned| missed_collections(last, now, midnight, 30, aligned);
+
+        // Unaligned: every interval after the last collection
+        assert_eq!(missed(at(2, 9, 0), at(2, 10, 35), false), 3);
+        assert_eq!(missed(at(2, 9, 0), at(2, 9, 20), false), 0);
+        // Only today's ticks count
+        assert_eq!(missed(at(1, 23, 50), at(2, 0, 45), false), 1);
+
+        // Aligned: multiples of the interval since midnight, midnight included
+        assert_eq!(missed(at(2, 9, 10), at(2, 10, 35), true), 3);
+        assert_eq!(missed(at(1, 20, 0), at(2, 1, 0), true), 3);
+    }
+
+    #[test]
+    fn test_missing_day_ranges() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let present: HashSet<NaiveDate> = [2, 5, 6, 9].into_iter().map(day).collect();
+
+        assert_eq!(
+            missing_day_ranges(day(1), day(10), &present, 10),
+            vec![
+                (day(1), day(1)),
+                (day(3), day(4)),
+                (day(7), day(8)),
+                (day(10), day(10))
+            ]
+        );
+        // Only the newest gaps are kept, still oldest first
+        assert_eq!(
+            missing_day_ranges(day(1), day(10), &present, 4),
+            vec![(day(4), day(4)), (day(7), day(8)), (day(10), day(10))]
+        );
+        assert!(missing_day_ranges(day(1), day(10), &present, 0).is_empty());
+        assert!(missing_day_ranges(day(5), day(4), &present, 10).is_empty());
+    }
+
+    #[test]
+    fn test_next_collection() {
+        let at = |h, m, s| {
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(h, m, s)
+                .unwrap()
+                .and_utc()
+        };
+        let utc = Some(Timezone::default());
+
+        // Unaligned: now, then every interval from the previous schedule
+        assert_eq!(
+            next_collection(None, at(10, 7, 13), 30, None),
+            at(10, 7, 13)
+        );
+        assert_eq!(
+            next_collection(Some(at(10, 7, 13)), at(10, 7, 40), 30, None),
+            at(10, 37, 13)
+        );
+        // Missed collections are skipped, not burst through
+        assert_eq!(
+            next_collection(Some(at(10, 7, 13)), at(11, 20, 0), 30, None),
+            at(11, 37, 13)
+        );
+
+        // Aligned: the next :00 or :30
+        assert_eq!(
+            next_collection(None, at(10, 7, 13), 30, utc),
+            at(10, 30, 0)
+        );
+        assert_eq!(next_collection(None, at(10, 30, 0), 30, utc), at(11, 0, 0));
+        assert_eq!(
+            next_collection(Some(at(10, 30, 0)), at(10, 29, 59), 30, utc),
+            at(11, 0, 0)
+        );
+        assert_eq!(
+            next_collection(None, at(23, 50, 0), 30, utc),
+            NaiveDate::from_ymd_opt(2026, 3, 2)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        );
+        // 7 hours doesn't divide a day: 21:00 is followed by midnight
+        assert_eq!(
+            next_collection(None, at(22, 0, 0), 7 * 60, utc),
+            next_collection(None, at(23, 50, 0), 30, utc)
+        );
+    }
+
+    #[test]
+    fn test_today_estimate() {
+        let at = |h, m| {
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let keyword = |created_at: &str, source: &str, tag: Option<&str>| StoredKeyword {
+            id: 1,
+            word: "ember".to_string(),
+            slot: 1,
+            blockhash: "hash".to_string(),
+            previous_blockhash: None,
+            block_time: None,
+            word_index: 0,
+            source: source.to_string(),
+            theme: None,
+            rerolls: 0,
+            tag: tag.map(str::to_string),
+            epoch: None,
+            orphaned: false,
+            created_at: created_at.to_string(),
+        };
+        let schedule = CollectionSchedule {
+            interval_minutes: 30,
+            aligned: None,
+            keywords_per_tick: 2,
+            keywords_per_day: 8,
+            min_keywords_for_poem: 6,
+        };
+
+        // Unaligned with nothing collected yet: no cadence to estimate from
+        let estimate = schedule.estimate(&[], false, at(10, 0));
+        assert_eq!(estimate.next_collection, None);
+        assert_eq!(estimate.poem_expected, None);
+        assert_eq!(estimate.progress_percent, 0);
+
+        // Event and manual keywords don't set the cadence
+        let keywords = vec![
+            keyword("2026-03-01 09:50:00", "blockhash", None),
+            keyword("2026-03-01 09:50:00", "blockhash", None),
+            keyword("2026-03-01 09:55:00", "blockhash", Some("event")),
+            keyword("2026-03-01 09:58:00", MANUAL_KEYWORD_SOURCE, None),
+        ];
+        let estimate = schedule.estimate(&keywords, false, at(10, 0));
+        assert_eq!(estimate.next_collection, Some(at(10, 20)));
+        // Two more keywords needed: one more collection
+        assert_eq!(estimate.poem_expected, Some(at(10, 20)));
+        assert_eq!(estimate.progress_percent, 66);
+
+        // Enough keywords but no poem yet stays below 100
+        let estimate = schedule.estimate(&[&keywords[..2]; 3].concat(), false, at(10, 0));
+        assert_eq!(estimate.progress_percent, 99);
+        let estimate = schedule.estimate(&[&keywords[..2]; 3].concat(), true, at(10, 0));
+        assert_eq!(estimate.poem_expected, None);
+        assert_eq!(estimate.progress_percent, 100);
+
+        // Aligned collections count from midnight; a full day has none left
+        let aligned = CollectionSchedule {
+            aligned: Some(Timezone::default()),
+            ..schedule
+        };
+        let estimate = aligned.estimate(&keywords[..1], false, at(10, 5));
+        assert_eq!(estimate.next_collection, Some(at(10, 30)));
+        assert_eq!(estimate.poem_expected, Some(at(11, 30)));
+        assert_eq!(
+            aligned.estimate(&[&keywords[..2]; 4].concat(), true, at(10, 5)),
+            TodayEstimate {
+                next_collection: None,
+                poem_expected: None,
+                progress_percent: 100,
+            }
+        );
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5 * 60));
+        assert_eq!(retry_delay(2), Duration::from_secs(10 * 60));
+        assert_eq!(retry_delay(4), Duration::from_secs(40 * 60));
+        assert_eq!(retry_delay(10), Duration::from_secs(6 * 60 * 60));
+        assert_eq!(retry_delay(u32::MAX), Duration::from_secs(6 * 60 * 60));
+    }
+
+    #[tokio::test]
+    async fn test_renew_poem_lock() {
+        let (path, url) = crate::database::temp_database_url("lease");
+        let db = Database::new(&url).await.unwrap();
+        let date = "2026-01-05";
+
+        assert!(db.try_acquire_poem_lock(date, "a", 60).await.unwrap());
+        renew_poem_lock(&db, date, "a").await.unwrap();
+        // Another holder's running lease wins
+        assert!(renew_poem_lock(&db, date, "b").await.is_err());
+
+        // Once a poem is stored, even a free lease doesn't allow another
+        db.release_poem_lock(date, "a").await.unwrap();
+        db.insert_poem(date, None, "poem", &[]).await.unwrap();
+        assert!(renew_poem_lock(&db, date, "b").await.is_err());
+
+        db.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::database::{Database, MonthlyUsage, SourceCount, WordCount};
+use crate::milestones;
+
+/// Response for GET /api/stats
+#[derive(Debug, Clone, Serialize)]
+pub struct CorpusStats {
+    pub total_poems: i64,
+    pub total_keywords: i64,
+    pub first_date: Option<String>,
+    pub longest_streak: i64,
+    /// Mean keywords per poem; None before the first poem
+    pub average_keywords_per_poem: Option<f64>,
+    pub top_words: Vec<WordCount>,
+    /// Keywords per entropy source (see BlockDataSource)
+    pub sources: Vec<SourceCount>,
+    /// LLM tokens and estimated spend per month, oldest first
+    pub llm_usage: Vec<MonthlyUsage>,
+}
+
+/// One month of GET /api/archive
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchiveMonth {
+    pub count: usize,
+    /// Days with a poem, oldest first
+    pub dates: Vec<String>,
+}
+
+/// Gather corpus-wide analytics, listing up to `top_words` most frequent words
+pub async fn corpus_stats(db: &Database, top_words: i64) -> Result<CorpusStats> {
+    let dates = db.get_poem_dates().await?;
+
+    Ok(CorpusStats {
+        total_poems: db.count_poems().await?,
+        total_keywords: db.count_keywords().await?,
+        first_date: db.get_project_start_date().await?,
+        longest_streak: milestones::longest_streak(&dates),
+        average_keywords_per_poem: db
+            .get_average_keywords_per_poem()
+            .await?
+            .map(|average| (average * 100.0).round() / 100.0),
+        top_words: db.get_top_words(top_words).await?,
+        sources: db.get_source_counts().await?,
+        llm_usage: db.get_monthly_usage().await?,
+    })
+}
+
+/// Month-by-month index of poem dates, keyed `YYYY-MM`, for archive navigation
+pub async fn archive(db: &Database) -> Result<BTreeMap<String, ArchiveMonth>> {
+    Ok(archive_index(&db.get_poem_dates().await?))
+}
+
+/// Group `YYYY-MM-DD` dates by month, keeping their order within each month
+fn archive_index(dates: &[String]) -> BTreeMap<String, ArchiveMonth> {
+    let mut months: BTreeMap<String, ArchiveMonth> = BTreeMap::new();
+    for date in dates {
+        let Some(month) = date.get(..7) else {
+            continue;
+        };
+        let entry = months.entry(month.to_string()).or_insert_with(|| ArchiveMonth {
+            count: 0,
+            dates: Vec::new(),
+        });
+        entry.count += 1;
+        entry.dates.push(date.clone());
+    }
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_index() {
+        let dates: Vec<String> = ["2025-12-31", "2026-01-01", "2026-01-02", "2026-02-14"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        let archive = archive_index(&dates);
+
+        assert_eq!(
+            archive.keys().collect::<Vec<_>>(),
+            vec!["2025-12", "2026-01", "2026-02"]
+        );
+        assert_eq!(archive["2026-01"].count, 2);
+        assert_eq!(archive["2026-01"].dates, vec!["2026-01-01", "2026-01-02"]);
+        assert!(archive_index(&[]).is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Offset, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+/// Format of stored timestamps (`CURRENT_TIMESTAMP`, always UTC)
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The instance's timezone, which decides when one day's keywords and poem
+/// end and the next day's begin
+///
+/// A fixed offset from UTC; an instance in a zone with daylight saving time
+/// picks one of its offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timezone(FixedOffset);
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Timezone(Utc.fix())
+    }
+}
+
+impl Timezone {
+    /// Local date at `at`
+    pub fn date_of(&self, at: DateTime<Utc>) -> NaiveDate {
+        at.with_timezone(&self.0).date_naive()
+    }
+
+    /// Local date now
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+
+    /// Local date of a stored UTC timestamp (`YYYY-MM-DD HH:MM:SS`)
+    pub fn date_of_timestamp(&self, timestamp: &str) -> Option<NaiveDate> {
+        parse_timestamp(timestamp).map(|at| self.date_of(at))
+    }
+
+    /// When `day` begins, local midnight, in UTC
+    pub fn midnight(&self, day: NaiveDate) -> DateTime<Utc> {
+        let midnight = day.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        (midnight - self.0).and_utc()
+    }
+
+    /// Local noon on `day` as a stored UTC timestamp, for rows dated after the fact
+    pub fn noon_timestamp(&self, day: NaiveDate) -> String {
+        (self.midnight(day) + chrono::Duration::hours(12))
+            .format(TIMESTAMP_FORMAT)
+            .to_string()
+    }
+
+    /// SQLite date modifier shifting a UTC timestamp to local time, as in
+    /// `DATE(created_at, ?)`
+    pub fn sqlite_modifier(&self) -> String {
+        format!("{:+} seconds", self.0.local_minus_utc())
+    }
+
+    /// Minutes since local midnight at `at`
+    pub fn minutes_into_day(&self, at: DateTime<Utc>) -> u64 {
+        ((at - self.midnight(self.date_of(at))).num_seconds() / 60) as u64
+    }
+}
+
+/// A stored UTC timestamp (`YYYY-MM-DD HH:MM:SS`)
+pub fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|at| at.and_utc())
+}
+
+/// `UTC`, or an offset like `+09:00`, `-05:00`, or `+0530`
+impl FromStr for Timezone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("utc") || s == "Z" {
+            return Ok(Timezone::default());
+        }
+        let invalid = || format!("`{}` is not UTC or an offset like +09:00", s);
+
+        let (sign, rest) = match s.as_bytes().first() {
+            Some(b'+') => (1, &s[1..]),
+            Some(b'-') => (-1, &s[1..]),
+            _ => return Err(invalid()),
+        };
+        let digits = rest.replacen(':', "", 1);
+        if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+        let minutes: i32 = digits[2..].parse().map_err(|_| invalid())?;
+        if hours > 14 || minutes >= 60 {
+            return Err(invalid());
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .map(Timezone)
+            .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.local_minus_utc() == 0 {
+            f.write_str("UTC")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, TIMESTAMP_FORMAT)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!("UTC".parse::<Timezone>(), Ok(Timezone::default()));
+        assert_eq!("+00:00".parse::<Timezone>(), Ok(Timezone::default()));
+        assert_eq!("+09:00".parse::<Timezone>().unwrap().to_string(), "+09:00");
+        assert_eq!("-0530".parse::<Timezone>().unwrap().to_string(), "-05:30");
+        assert_eq!(Timezone::default().to_string(), "UTC");
+
+        for invalid in ["America/New_York", "+9", "+15:00", "+09:60", "09:00", ""] {
+            assert!(invalid.parse::<Timezone>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_day_boundaries() {
+        let tokyo: Timezone = "+09:00".parse().unwrap();
+        let new_york: Timezone = "-05:00".parse().unwrap();
+
+        // 20:00 UTC is already tomorrow in Tokyo and still afternoon in New York
+        let at = utc("2026-01-05 20:00:00");
+        assert_eq!(tokyo.date_of(at), day("2026-01-06"));
+        assert_eq!(new_york.date_of(at), day("2026-01-05"));
+        assert_eq!(
+            new_york.date_of_timestamp("2026-01-06 03:00:00"),
+            Some(day("2026-01-05"))
+        );
+
+        assert_eq!(
+            tokyo.midnight(day("2026-01-06")),
+            utc("2026-01-05 15:00:00")
+        );
+        assert_eq!(
+            new_york.noon_timestamp(day("2026-01-05")),
+            "2026-01-05 17:00:00"
+        );
+        assert_eq!(tokyo.minutes_into_day(at), 5 * 60);
+
+        assert_eq!(tokyo.sqlite_modifier(), "+32400 seconds");
+        assert_eq!(new_york.sqlite_modifier(), "-18000 seconds");
+    }
+}
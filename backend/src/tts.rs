@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// OpenAI's speech endpoint; local engines with an OpenAI-compatible API
+/// (Kokoro-FastAPI, openedai-speech) work via `tts.url`
+pub const OPENAI_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+
+const TTS_TIMEOUT_SECS: u64 = 120;
+
+/// Container the narration is stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioFormat {
+    #[default]
+    Mp3,
+    /// Opus in an Ogg container
+    Ogg,
+}
+
+impl AudioFormat {
+    /// `response_format` asked of the speech endpoint
+    fn response_format(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Ogg => "opus",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Ogg => "ogg",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Ogg => "audio/ogg",
+        }
+    }
+}
+
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for AudioFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mp3" => Ok(AudioFormat::Mp3),
+            "ogg" | "opus" => Ok(AudioFormat::Ogg),
+            _ => anyhow::bail!("Unknown audio format `{}`", s),
+        }
+    }
+}
+
+/// Reads poems aloud through an OpenAI-compatible text-to-speech endpoint and
+/// keeps the recordings as `<date>.<ext>` files in one directory
+pub struct TtsClient {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+    model: String,
+    voice: String,
+    format: AudioFormat,
+    dir: PathBuf,
+}
+
+#[derive(Serialize)]
+struct SpeechRequest<'a> {
+    model: &'a str,
+    voice: &'a str,
+    input: &'a str,
+    response_format: &'static str,
+}
+
+impl TtsClient {
+    pub fn new(url: String, model: String, voice: String, dir: PathBuf) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(TTS_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            url,
+            api_key: None,
+            model,
+            voice,
+            format: AudioFormat::default(),
+            dir,
+        }
+    }
+
+    /// Send `Authorization: Bearer <key>`; local engines usually need none
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    pub fn with_format(mut self, format: AudioFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    pub fn voice(&self) -> &str {
+        &self.voice
+    }
+
+    /// Speak `text`, returning the encoded audio
+    pub async fn synthesize(&self, text: &str) -> Result<Vec<u8>> {
+        let mut request = self.client.post(&self.url).json(&SpeechRequest {
+            model: &self.model,
+            voice: &self.voice,
+            input: text,
+            response_format: self.format.response_format(),
+        });
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let audio = request
+            .send()
+            .await
+            .context("Failed to reach the TTS provider")?
+            .error_for_status()
+            .context("The TTS provider rejected the request")?
+            .bytes()
+            .await
+            .context("Failed to read TTS audio")?;
+        if audio.is_empty() {
+            anyhow::bail!("The TTS provider returned no audio");
+        }
+        Ok(audio.to_vec())
+    }
+
+    /// Narrate a day's poem and write it to the audio directory, replacing any
+    /// earlier recording; returns the file's path
+    pub async fn narrate(&self, date: &str, title: Option<&str>, content: &str) -> Result<PathBuf> {
+        let audio = self.synthesize(&narration(title, content)).await?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .with_context(|| format!("Failed to create audio directory {}", self.dir.display()))?;
+        let path = audio_path(&self.dir, date, self.format);
+        // Written aside and renamed, so the API never serves half a file
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, &audio)
+            .await
+            .with_context(|| format!("Failed to write {}", partial.display()))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// What is read aloud: the title, a pause, then the poem
+pub fn narration(title: Option<&str>, content: &str) -> String {
+    match title {
+        Some(title) => format!("{}.\n\n{}", title.trim_end_matches('.'), content.trim()),
+        None => content.trim().to_string(),
+    }
+}
+
+/// Where a day's recording is kept
+pub fn audio_path(dir: &Path, date: &str, format: AudioFormat) -> PathBuf {
+    dir.join(format!("{}.{}", date, format.extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_format() {
+        assert_eq!("MP3".parse::<AudioFormat>().unwrap(), AudioFormat::Mp3);
+        assert_eq!("opus".parse::<AudioFormat>().unwrap(), AudioFormat::Ogg);
+        assert!("wav".parse::<AudioFormat>().is_err());
+        assert_eq!(AudioFormat::Ogg.response_format(), "opus");
+        assert_eq!(
+            audio_path(Path::new("audio"), "2026-01-05", AudioFormat::Ogg),
+            Path::new("audio/2026-01-05.ogg")
+        );
+    }
+
+    #[test]
+    fn test_narration() {
+        assert_eq!(
+            narration(Some("Tide."), "rain\nfalls\n"),
+            "Tide.\n\nrain\nfalls"
+        );
+        assert_eq!(narration(None, " rain "), "rain");
+    }
+}
@@ -1,25 +1,79 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use crate::consts::DEFAULT_LANGUAGE;
+
+/// Default dictionary compiled into the binary (BIP-39 English wordlist)
+#[cfg(feature = "embedded-dictionary")]
+const DEFAULT_DICTIONARY: &str = include_str!("../words.json");
+
+/// Default dictionary read at runtime when it isn't compiled in
+#[cfg(not(feature = "embedded-dictionary"))]
+const DEFAULT_DICTIONARY_PATH: &str = "words.json";
+
+/// Word list used for derivation
+///
+/// Accepts either a flat `words` list (the BIP-39 format) or categorized
+/// `nouns`/`verbs`/`adjectives` lists; all present lists are concatenated.
+/// `language` is a lowercase tag like `en` or `ja`, `en` when omitted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordDictionary {
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default)]
+    pub words: Vec<String>,
+    #[serde(default)]
     pub nouns: Vec<String>,
+    #[serde(default)]
     pub verbs: Vec<String>,
+    #[serde(default)]
     pub adjectives: Vec<String>,
 }
 
 impl WordDictionary {
-    /// Load the word dictionary from the JSON file
+    /// Load the default dictionary embedded in the binary
+    #[cfg(feature = "embedded-dictionary")]
     pub fn load() -> Result<Self> {
-        let content = fs::read_to_string("words.json")?;
-        let dict: WordDictionary = serde_json::from_str(&content)?;
+        Self::from_json(DEFAULT_DICTIONARY)
+    }
+
+    /// Load the default dictionary from `words.json` in the working directory
+    /// (built without the `embedded-dictionary` feature)
+    #[cfg(not(feature = "embedded-dictionary"))]
+    pub fn load() -> Result<Self> {
+        Self::load_from(Path::new(DEFAULT_DICTIONARY_PATH))
+    }
+
+    /// Load a dictionary from a JSON file on disk
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read dictionary {}", path.display()))?;
+        Self::from_json(&content)
+            .with_context(|| format!("Invalid dictionary {}", path.display()))
+    }
+
+    /// Parse a dictionary from JSON, rejecting empty word lists and
+    /// malformed language tags
+    pub fn from_json(content: &str) -> Result<Self> {
+        let mut dict: WordDictionary = serde_json::from_str(content)?;
+        if dict.total_count() == 0 {
+            anyhow::bail!("Dictionary contains no words");
+        }
+        dict.language = parse_language(&dict.language)?;
         Ok(dict)
     }
 
     /// Get all words as a single flat list
     pub fn all_words(&self) -> Vec<String> {
         let mut words = Vec::new();
+        words.extend(self.words.clone());
         words.extend(self.nouns.clone());
         words.extend(self.verbs.clone());
         words.extend(self.adjectives.clone());
@@ -28,14 +82,477 @@ impl WordDictionary {
 
     /// Get total word count
     pub fn total_count(&self) -> usize {
-        self.nouns.len() + self.verbs.len() + self.adjectives.len()
+        self.words.len() + self.nouns.len() + self.verbs.len() + self.adjectives.len()
     }
 
     /// Get a word by index
+    #[cfg(test)]
     pub fn get_word(&self, index: usize) -> Option<String> {
         let all = self.all_words();
         all.get(index).cloned()
     }
+
+    /// Hex SHA-256 of the words in index order, one per line
+    pub fn sha256(&self) -> String {
+        let mut hasher = Sha256::new();
+        for word in self.all_words() {
+            hasher.update(word.as_bytes());
+            hasher.update(b"\n");
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
+/// Normalize a language tag like `ja` or `pt-BR` to lowercase, rejecting
+/// anything but letters, digits, and hyphens
+pub fn parse_language(tag: &str) -> Result<String> {
+    let tag = tag.trim();
+    if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        anyhow::bail!("Invalid language tag `{}`", tag);
+    }
+    Ok(tag.to_ascii_lowercase())
+}
+
+/// Words derivation must never produce, matched case-insensitively
+///
+/// The file lists one word per line; blank lines and lines starting with `#`
+/// are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    words: Arc<HashSet<String>>,
+}
+
+impl Blocklist {
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read blocklist {}", path.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let words = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+        Self {
+            words: Arc::new(words),
+        }
+    }
+
+    pub fn is_blocked(&self, word: &str) -> bool {
+        !self.words.is_empty() && self.words.contains(&word.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Hex SHA-256 of the blocked words, sorted, one per line; None when empty
+    pub fn sha256(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut words: Vec<&String> = self.words.iter().collect();
+        words.sort();
+        let mut hasher = Sha256::new();
+        for word in words {
+            hasher.update(word.as_bytes());
+            hasher.update(b"\n");
+        }
+        Some(format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// Load every `<theme>.json` dictionary in `dir`, keyed by file stem
+pub fn load_themes(dir: &Path) -> Result<BTreeMap<String, WordDictionary>> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read themes directory {}", dir.display()))?;
+
+    let mut themes = BTreeMap::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        themes.insert(name.to_lowercase(), WordDictionary::load_from(&path)?);
+    }
+
+    if themes.is_empty() {
+        anyhow::bail!("No theme dictionaries (*.json) found in {}", dir.display());
+    }
+    Ok(themes)
+}
+
+/// Load every `*.json` dictionary in `dir`, keyed by its language tag
+pub fn load_languages(dir: &Path) -> Result<BTreeMap<String, WordDictionary>> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read languages directory {}", dir.display()))?;
+
+    let mut languages = BTreeMap::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let dictionary = WordDictionary::load_from(&path)?;
+        let language = dictionary.language.clone();
+        if languages.insert(language.clone(), dictionary).is_some() {
+            anyhow::bail!(
+                "More than one `{}` dictionary in {}",
+                language,
+                dir.display()
+            );
+        }
+    }
+
+    if languages.is_empty() {
+        anyhow::bail!(
+            "No language dictionaries (*.json) found in {}",
+            dir.display()
+        );
+    }
+    Ok(languages)
+}
+
+/// How the active theme is chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeSelection {
+    /// Always use this theme
+    Fixed(String),
+    /// Derive the theme from the week's first blockhash
+    FromBlockhash,
+}
+
+impl ThemeSelection {
+    /// Pick a theme from `names` (sorted) given the week's first blockhash
+    pub fn resolve(&self, names: &[String], week_blockhash: &str) -> Option<String> {
+        match self {
+            ThemeSelection::Fixed(name) => names.iter().find(|n| *n == name).cloned(),
+            ThemeSelection::FromBlockhash => {
+                if names.is_empty() {
+                    return None;
+                }
+                let digest = Sha256::digest(week_blockhash.as_bytes());
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&digest[0..8]);
+                let index = (u64::from_le_bytes(bytes) % names.len() as u64) as usize;
+                Some(names[index].clone())
+            }
+        }
+    }
+}
+
+impl FromStr for ThemeSelection {
+    type Err = anyhow::Error;
+
+    /// Accepts a theme name or `auto` for blockhash derivation
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            anyhow::bail!("Theme name must not be empty");
+        }
+        if s.eq_ignore_ascii_case("auto") {
+            Ok(ThemeSelection::FromBlockhash)
+        } else {
+            Ok(ThemeSelection::Fixed(s.to_lowercase()))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Dictionaries {
+    base: WordDictionary,
+    languages: BTreeMap<String, WordDictionary>,
+    /// Language from `languages` replacing the base dictionary; None keeps the base
+    language: Option<String>,
+    themes: BTreeMap<String, WordDictionary>,
+    active_theme: Option<String>,
+}
+
+impl Dictionaries {
+    /// The dictionary for the selected language, used when no theme is active
+    fn language_base(&self) -> &WordDictionary {
+        self.language
+            .as_ref()
+            .and_then(|language| self.languages.get(language))
+            .unwrap_or(&self.base)
+    }
+
+    fn active(&self) -> &WordDictionary {
+        self.active_theme
+            .as_ref()
+            .and_then(|name| self.themes.get(name))
+            .unwrap_or_else(|| self.language_base())
+    }
+
+    /// Which of `languages` (or the base) serves `language`; Err when none does
+    fn resolve_language(
+        base: &WordDictionary,
+        languages: &BTreeMap<String, WordDictionary>,
+        language: &str,
+    ) -> Result<Option<String>> {
+        if base.language == language {
+            Ok(None)
+        } else if languages.contains_key(language) {
+            Ok(Some(language.to_string()))
+        } else {
+            anyhow::bail!("No `{}` dictionary is loaded", language)
+        }
+    }
+}
+
+/// Read guard over whichever dictionary is currently active
+pub struct DictionaryGuard<'a>(RwLockReadGuard<'a, Dictionaries>);
+
+impl DictionaryGuard<'_> {
+    /// Name of the active theme; None means the base dictionary
+    pub fn theme(&self) -> Option<&str> {
+        self.0.active_theme.as_deref()
+    }
+}
+
+impl Deref for DictionaryGuard<'_> {
+    type Target = WordDictionary;
+
+    fn deref(&self) -> &WordDictionary {
+        self.0.active()
+    }
+}
+
+/// Shared, reloadable dictionary
+///
+/// Cloning the handle shares the underlying dictionary, so a reload is seen
+/// by every derivation using it (collector, previews, API).
+///
+/// When a languages directory is configured, `set_language` can swap one of its
+/// dictionaries in for the base one. When a themes directory is configured, one
+/// of its dictionaries in that language can be made active with `set_theme`.
+#[derive(Debug, Clone)]
+pub struct DictionaryHandle {
+    path: Option<PathBuf>,
+    languages_dir: Option<PathBuf>,
+    themes_dir: Option<PathBuf>,
+    current: Arc<RwLock<Dictionaries>>,
+}
+
+impl DictionaryHandle {
+    /// Load from `path` if given, otherwise use the default (see `WordDictionary::load`)
+    pub fn load(path: Option<PathBuf>) -> Result<Self> {
+        let dictionary = match &path {
+            Some(path) => WordDictionary::load_from(path)?,
+            None => WordDictionary::load()?,
+        };
+        let mut handle = Self::from_dictionary(dictionary);
+        handle.path = path;
+        Ok(handle)
+    }
+
+    /// Wrap an already loaded dictionary (reloads keep it unchanged)
+    pub fn from_dictionary(dictionary: WordDictionary) -> Self {
+        Self {
+            path: None,
+            languages_dir: None,
+            themes_dir: None,
+            current: Arc::new(RwLock::new(Dictionaries {
+                base: dictionary,
+                languages: BTreeMap::new(),
+                language: None,
+                themes: BTreeMap::new(),
+                active_theme: None,
+            })),
+        }
+    }
+
+    /// Load a dictionary per language from `dir` (reloads re-read it too)
+    pub fn with_languages_dir(mut self, dir: PathBuf) -> Result<Self> {
+        let languages = load_languages(&dir)?;
+        self.write().languages = languages;
+        self.languages_dir = Some(dir);
+        Ok(self)
+    }
+
+    /// Load theme dictionaries from `dir` (reloads re-read it too)
+    pub fn with_themes_dir(mut self, dir: PathBuf) -> Result<Self> {
+        let themes = load_themes(&dir)?;
+        self.write().themes = themes;
+        self.themes_dir = Some(dir);
+        Ok(self)
+    }
+
+    /// Path the dictionary is reloaded from, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Read access to the active dictionary
+    pub fn read(&self) -> DictionaryGuard<'_> {
+        DictionaryGuard(self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Dictionaries> {
+        self.current.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Tags of every loaded language, the base dictionary's included, sorted
+    pub fn languages(&self) -> Vec<String> {
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        let mut languages: Vec<String> = current.languages.keys().cloned().collect();
+        if !languages.contains(&current.base.language) {
+            languages.push(current.base.language.clone());
+            languages.sort();
+        }
+        languages
+    }
+
+    /// Language keywords are currently derived in
+    pub fn language(&self) -> String {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .language_base()
+            .language
+            .clone()
+    }
+
+    /// Derive in `language` from now on; a theme in another language is
+    /// deactivated. Errors when no dictionary for it is loaded
+    pub fn set_language(&self, language: &str) -> Result<()> {
+        let language = parse_language(language)?;
+        let mut current = self.write();
+        current.language =
+            Dictionaries::resolve_language(&current.base, &current.languages, &language)?;
+        if current
+            .active_theme
+            .as_ref()
+            .and_then(|name| current.themes.get(name))
+            .is_some_and(|theme| theme.language != language)
+        {
+            current.active_theme = None;
+        }
+        Ok(())
+    }
+
+    /// Names of the loaded themes in the current language, sorted
+    pub fn theme_names(&self) -> Vec<String> {
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        let language = &current.language_base().language;
+        current
+            .themes
+            .iter()
+            .filter(|(_, theme)| &theme.language == language)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Words of theme `name`, or of the current language's dictionary for
+    /// None, whichever theme is active; None when that theme isn't loaded
+    pub fn words_for(&self, theme: Option<&str>) -> Option<Vec<String>> {
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        match theme {
+            Some(name) => current.themes.get(name).map(WordDictionary::all_words),
+            None => Some(current.language_base().all_words()),
+        }
+    }
+
+    /// Theme currently used for derivation; None means the base dictionary
+    pub fn active_theme(&self) -> Option<String> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .active_theme
+            .clone()
+    }
+
+    /// Make `name` the active theme, or return to the base dictionary with None
+    pub fn set_theme(&self, name: Option<&str>) -> Result<()> {
+        let mut current = self.write();
+        if let Some(name) = name {
+            let Some(theme) = current.themes.get(name) else {
+                anyhow::bail!("Unknown dictionary theme `{}`", name);
+            };
+            let language = &current.language_base().language;
+            if &theme.language != language {
+                anyhow::bail!(
+                    "Dictionary theme `{}` is in `{}`, not `{}`",
+                    name,
+                    theme.language,
+                    language
+                );
+            }
+        }
+        current.active_theme = name.map(str::to_string);
+        Ok(())
+    }
+
+    /// Resolve the theme for a week from its first blockhash and activate it;
+    /// a no-op without themes. Returns the active theme.
+    pub fn select_theme(
+        &self,
+        selection: &ThemeSelection,
+        week_blockhash: &str,
+    ) -> Result<Option<String>> {
+        let names = self.theme_names();
+        if names.is_empty() {
+            return Ok(None);
+        }
+        let theme = selection
+            .resolve(&names, week_blockhash)
+            .ok_or_else(|| anyhow::anyhow!("Theme {:?} is not loaded", selection))?;
+        self.set_theme(Some(&theme))?;
+        Ok(Some(theme))
+    }
+
+    /// Re-read the dictionary, languages, and themes from their source and swap
+    /// them in; returns the new word count in the current language. On error,
+    /// including that language's dictionary disappearing, the previous
+    /// dictionaries stay active
+    pub fn reload(&self) -> Result<usize> {
+        let dictionary = match &self.path {
+            Some(path) => WordDictionary::load_from(path)?,
+            None => WordDictionary::load()?,
+        };
+        let languages = match &self.languages_dir {
+            Some(dir) => load_languages(dir)?,
+            None => BTreeMap::new(),
+        };
+        let themes = match &self.themes_dir {
+            Some(dir) => Some(load_themes(dir)?),
+            None => None,
+        };
+
+        let mut current = self.write();
+        let language = current.language_base().language.clone();
+        let selected = Dictionaries::resolve_language(&dictionary, &languages, &language)?;
+        current.base = dictionary;
+        current.languages = languages;
+        current.language = selected;
+        if let Some(themes) = themes {
+            // A theme removed from disk, or no longer in the language, falls
+            // back to the language's dictionary
+            if current.active_theme.as_ref().is_some_and(|name| {
+                themes
+                    .get(name)
+                    .is_none_or(|theme| theme.language != language)
+            }) {
+                current.active_theme = None;
+            }
+            current.themes = themes;
+        }
+        Ok(current.language_base().total_count())
+    }
 }
 
 #[cfg(test)]
@@ -45,9 +562,134 @@ mod tests {
     #[test]
     fn test_load_dictionary() {
         let dict = WordDictionary::load().unwrap();
-        assert!(dict.total_count() > 0);
-        assert!(!dict.nouns.is_empty());
-        assert!(!dict.verbs.is_empty());
-        assert!(!dict.adjectives.is_empty());
+        assert_eq!(dict.total_count(), 2048);
+        assert_eq!(dict.get_word(0).as_deref(), Some("abandon"));
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let blocklist = Blocklist::parse("# offensive\n\n  Crude \nrude\n");
+        assert_eq!(blocklist.len(), 2);
+        assert!(blocklist.is_blocked("crude"));
+        assert!(blocklist.is_blocked("RUDE"));
+        assert!(!blocklist.is_blocked("offensive"));
+        assert!(!Blocklist::default().is_blocked("rude"));
+    }
+
+    #[test]
+    fn test_categorized_dictionary() {
+        let dict = WordDictionary::from_json(
+            r#"{"nouns": ["moon"], "verbs": ["drift"], "adjectives": ["pale"]}"#,
+        )
+        .unwrap();
+        assert_eq!(dict.all_words(), vec!["moon", "drift", "pale"]);
+    }
+
+    #[test]
+    fn test_empty_dictionary_rejected() {
+        assert!(WordDictionary::from_json(r#"{"words": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_reload_from_path() {
+        let path = std::env::temp_dir().join("chain_verse_reload_test.json");
+        fs::write(&path, r#"{"words": ["one", "two"]}"#).unwrap();
+        let handle = DictionaryHandle::load(Some(path.clone())).unwrap();
+        assert_eq!(handle.read().total_count(), 2);
+
+        fs::write(&path, r#"{"words": ["one", "two", "three"]}"#).unwrap();
+        assert_eq!(handle.reload().unwrap(), 3);
+        assert_eq!(handle.read().total_count(), 3);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(handle.reload().is_err());
+        assert_eq!(handle.read().total_count(), 3);
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_themes() {
+        let dir = std::env::temp_dir().join(format!("chain_verse_themes_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ocean.json"), r#"{"nouns": ["tide", "reef"]}"#).unwrap();
+        fs::write(dir.join("Space.json"), r#"{"nouns": ["nebula"]}"#).unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let handle = DictionaryHandle::from_dictionary(WordDictionary::load().unwrap())
+            .with_themes_dir(dir.clone())
+            .unwrap();
+        assert_eq!(handle.theme_names(), vec!["ocean", "space"]);
+        assert_eq!(handle.read().total_count(), 2048);
+
+        handle.set_theme(Some("space")).unwrap();
+        assert_eq!(handle.read().all_words(), vec!["nebula"]);
+        assert!(handle.set_theme(Some("autumn")).is_err());
+
+        let fixed: ThemeSelection = "Ocean".parse().unwrap();
+        assert_eq!(handle.select_theme(&fixed, "5xYz").unwrap().as_deref(), Some("ocean"));
+        assert_eq!(handle.read().total_count(), 2);
+
+        let auto: ThemeSelection = "auto".parse().unwrap();
+        let names = handle.theme_names();
+        assert_eq!(auto.resolve(&names, "5xYz"), auto.resolve(&names, "5xYz"));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_languages() {
+        let root =
+            std::env::temp_dir().join(format!("chain_verse_languages_{}", std::process::id()));
+        let (languages, themes) = (root.join("languages"), root.join("themes"));
+        fs::create_dir_all(&languages).unwrap();
+        fs::create_dir_all(&themes).unwrap();
+        fs::write(
+            languages.join("japanese.json"),
+            r#"{"language": "JA", "words": ["月", "潮"]}"#,
+        )
+        .unwrap();
+        fs::write(
+            languages.join("spanish.json"),
+            r#"{"language": "es", "words": ["luna"]}"#,
+        )
+        .unwrap();
+        fs::write(themes.join("ocean.json"), r#"{"nouns": ["tide", "reef"]}"#).unwrap();
+        fs::write(
+            themes.join("umi.json"),
+            r#"{"language": "ja", "nouns": ["波"]}"#,
+        )
+        .unwrap();
+
+        let handle = DictionaryHandle::from_dictionary(WordDictionary::load().unwrap())
+            .with_languages_dir(languages.clone())
+            .unwrap()
+            .with_themes_dir(themes)
+            .unwrap();
+        assert_eq!(handle.languages(), vec!["en", "es", "ja"]);
+        assert_eq!(handle.language(), "en");
+        assert_eq!(handle.theme_names(), vec!["ocean"]);
+
+        // Switching language drops a theme in another one
+        handle.set_theme(Some("ocean")).unwrap();
+        handle.set_language("ja").unwrap();
+        assert_eq!(handle.read().theme(), None);
+        assert_eq!(handle.read().all_words(), vec!["月", "潮"]);
+        assert_eq!(handle.words_for(None).unwrap(), vec!["月", "潮"]);
+        assert_eq!(handle.theme_names(), vec!["umi"]);
+        assert!(handle.set_theme(Some("ocean")).is_err());
+        assert!(handle.set_language("fr").is_err());
+        assert_eq!(handle.language(), "ja");
+
+        // A reload that loses the current language keeps the old dictionaries
+        fs::remove_file(languages.join("japanese.json")).unwrap();
+        assert!(handle.reload().is_err());
+        assert_eq!(handle.read().all_words(), vec!["月", "潮"]);
+
+        handle.set_language("en").unwrap();
+        assert_eq!(handle.read().total_count(), 2048);
+        assert!(WordDictionary::from_json(r#"{"language": "e n", "words": ["a"]}"#).is_err());
+
+        fs::remove_dir_all(root).ok();
     }
 }
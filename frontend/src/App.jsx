@@ -1,6 +1,17 @@
 import { useState, useEffect } from 'react'
 import './App.css'
 
+// The parsed JSON at `url`, or null when it isn't there (a static host
+// answers API paths with 404 or its index.html)
+async function fetchJson(url) {
+  const res = await fetch(url)
+  const type = res.headers.get('content-type') || ''
+  if (!res.ok || !type.includes('application/json')) {
+    return null
+  }
+  return res.json()
+}
+
 function App() {
   const [todayData, setTodayData] = useState(null)
   const [allPoems, setAllPoems] = useState([])
@@ -14,29 +25,43 @@ function App() {
 
   const fetchData = async () => {
     try {
-      // Fetch today's poem from static JSON
-      const todayRes = await fetch('/data/today.json')
-      if (todayRes.ok) {
-        const data = await todayRes.json()
-        setTodayData({
-          date: data.date,
-          poem: data.poem,
-          keywords: data.keywords,
-          poem_ready: data.poemReady,
-          keywords_collected: data.keywordsCollected,
-          keywords_needed: data.keywordsNeeded
-        })
+      // Served by the backend (single container), read its API; on a static
+      // host, read the JSON the daily script writes
+      const today = await fetchJson('/api/poems/today')
+      if (today) {
+        setTodayData(today)
+      } else {
+        const data = await fetchJson('/data/today.json')
+        if (data) {
+          setTodayData({
+            date: data.date,
+            poem: data.poem,
+            keywords: data.keywords,
+            poem_ready: data.poemReady,
+            keywords_collected: data.keywordsCollected,
+            keywords_needed: data.keywordsNeeded
+          })
+        }
       }
 
-      // Fetch archive from static JSON
-      const archiveRes = await fetch('/data/archive.json')
-      if (archiveRes.ok) {
-        const data = await archiveRes.json()
-        setAllPoems(data.map(p => ({
+      const poems = await fetchJson('/api/poems')
+      if (poems) {
+        setAllPoems(poems.map(p => ({
           id: p.date,
           date: p.date,
-          content: p.poem?.content || ''
+          title: p.title,
+          content: p.content
         })))
+      } else {
+        const data = await fetchJson('/data/archive.json')
+        if (data) {
+          setAllPoems(data.map(p => ({
+            id: p.date,
+            date: p.date,
+            title: p.poem?.title || null,
+            content: p.poem?.content || ''
+          })))
+        }
       }
 
       setError(null)
@@ -123,6 +148,7 @@ function TodayView({ data }) {
 
       {data.poem_ready && data.poem ? (
         <div className="poem-container">
+          {data.poem.title && <h3 className="poem-title">{data.poem.title}</h3>}
           <div className="poem">
             {data.poem.content}
           </div>
@@ -167,6 +193,7 @@ function ArchiveView({ poems }) {
       {poems.map((poem) => (
         <div key={poem.id} className="poem-card">
           <div className="poem-date">{poem.date}</div>
+          {poem.title && <h3 className="poem-title">{poem.title}</h3>}
           <div className="poem-content">
             {poem.content}
           </div>